@@ -1,7 +1,8 @@
 use sunscreen::{
     fhe_program,
     types::{bfv::Rational, Cipher},
-    Ciphertext, CompiledFheProgram, Compiler, Error, FheRuntime, Params, PrivateKey, PublicKey,
+    Ciphertext, CompiledFheProgram, Compiler, Error, EvaluationKeys, FheRuntime, PrivateKey,
+    PublicKey,
 };
 
 #[fhe_program(scheme = "bfv")]
@@ -38,11 +39,13 @@ impl Miner {
     pub fn run_contract(
         &self,
         nu_tokens_to_trade: Ciphertext,
-        public_key: &PublicKey,
+        evaluation_keys: &EvaluationKeys,
     ) -> Result<Ciphertext, Error> {
-        let results =
-            self.runtime
-                .run(&self.compiled_swap_nu, vec![nu_tokens_to_trade], public_key)?;
+        let results = self.runtime.run(
+            &self.compiled_swap_nu,
+            vec![nu_tokens_to_trade],
+            evaluation_keys,
+        )?;
 
         Ok(results[0].clone())
     }
@@ -53,6 +56,9 @@ struct Alice {
     /// Alice's public key
     pub public_key: PublicKey,
 
+    /// The keys a server needs to run FHE programs on Alice's data
+    pub evaluation_keys: EvaluationKeys,
+
     /// Alice's private key
     private_key: PrivateKey,
 
@@ -61,13 +67,15 @@ struct Alice {
 }
 
 impl Alice {
-    pub fn setup(params: &Params) -> Result<Alice, Error> {
-        let runtime = FheRuntime::new(params)?;
+    pub fn setup(fhe_program: &CompiledFheProgram) -> Result<Alice, Error> {
+        let runtime = FheRuntime::new(&fhe_program.metadata.params)?;
 
         let (public_key, private_key) = runtime.generate_keys()?;
+        let evaluation_keys = runtime.generate_evaluation_keys(&private_key, [fhe_program])?;
 
         Ok(Alice {
             public_key,
+            evaluation_keys,
             private_key,
             runtime,
         })
@@ -94,13 +102,13 @@ fn main() -> Result<(), Error> {
     // Set up the miner with some NU and ETH tokens.
     let miner = Miner::setup()?;
 
-    // Alice sets herself up. The FHE scheme parameters are public to the
-    // protocol, so Alice has them.
-    let alice = Alice::setup(&miner.compiled_swap_nu.metadata.params)?;
+    // Alice sets herself up. The compiled FHE program (and hence its scheme
+    // parameters) is public to the protocol, so Alice has it.
+    let alice = Alice::setup(&miner.compiled_swap_nu)?;
 
     let transaction = alice.create_transaction(20.0)?;
 
-    let encrypted_received_eth = miner.run_contract(transaction, &alice.public_key)?;
+    let encrypted_received_eth = miner.run_contract(transaction, &alice.evaluation_keys)?;
 
     alice.check_received_eth(encrypted_received_eth)?;
 
@@ -53,6 +53,8 @@ fn main() -> Result<(), Error> {
      * sending the public key to bob, who then runs a computation.
      */
     let (public_key, private_key) = runtime.generate_keys()?;
+    let evaluation_keys =
+        runtime.generate_evaluation_keys(&private_key, [app.get_fhe_program(mul).unwrap()])?;
 
     /*
      * We can create `Unsigned256` values from `u64` literals.
@@ -70,7 +72,11 @@ fn main() -> Result<(), Error> {
      * Now, we run the FHE program with our arguments. This produces a results
      * `Vec` containing the encrypted outputs of the FHE program.
      */
-    let results = runtime.run(app.get_fhe_program(mul).unwrap(), vec![a, b], &public_key)?;
+    let results = runtime.run(
+        app.get_fhe_program(mul).unwrap(),
+        vec![a, b],
+        &evaluation_keys,
+    )?;
 
     /*
      * Finally, we decrypt our program's output so we can check it. Our FHE
@@ -6,8 +6,8 @@ use std::thread::{self, JoinHandle};
 use sunscreen::{
     fhe_program,
     types::{bfv::Fractional, Cipher},
-    Ciphertext, Compiler, FheApplication, FheRuntime, Params, PlainModulusConstraint, PublicKey,
-    RuntimeError,
+    Ciphertext, Compiler, EvaluationKeys, FheApplication, FheRuntime, PlainModulusConstraint,
+    PublicKey, RuntimeError,
 };
 
 fn help() {
@@ -131,9 +131,9 @@ fn encrypt_term(runtime: &FheRuntime, public_key: &PublicKey, input: Term) -> Te
 }
 
 fn alice(
-    send_pub: Sender<PublicKey>,
+    send_pub: Sender<(PublicKey, EvaluationKeys)>,
     send_calc: Sender<Expression>,
-    recv_params: Receiver<Params>,
+    recv_app: Receiver<FheApplication>,
     recv_res: Receiver<Ciphertext>,
 ) -> JoinHandle<()> {
     thread::spawn(move || {
@@ -142,15 +142,22 @@ fn alice(
 
         println!("Bob's private calculator. Type `help` for help.");
 
-        // Bob needs to send us the scheme parameters compatible with his FHE programs.
-        let params = recv_params.recv().unwrap();
+        // Bob needs to send us his FHE programs so we can use compatible scheme parameters.
+        let app = recv_app.recv().unwrap();
 
-        let runtime = FheRuntime::new(&params).unwrap();
+        let runtime = FheRuntime::new(app.params()).unwrap();
 
         let (public_key, private_key) = runtime.generate_keys().unwrap();
 
+        // Bob needs evaluation keys to run his FHE programs on our data.
+        let evaluation_keys = runtime
+            .generate_evaluation_keys(&private_key, app.get_fhe_programs().map(|(_, p)| p))
+            .unwrap();
+
         // Send Bob a copy of our public keys.
-        send_pub.send(public_key.clone()).unwrap();
+        send_pub
+            .send((public_key.clone(), evaluation_keys))
+            .unwrap();
 
         loop {
             print!(">> ");
@@ -235,17 +242,17 @@ fn compile_fhe_programs() -> FheApplication {
 }
 
 fn bob(
-    recv_pub: Receiver<PublicKey>,
+    recv_pub: Receiver<(PublicKey, EvaluationKeys)>,
     recv_calc: Receiver<Expression>,
-    send_params: Sender<Params>,
+    send_app: Sender<FheApplication>,
     send_res: Sender<Ciphertext>,
 ) -> JoinHandle<()> {
     thread::spawn(move || {
         let app = compile_fhe_programs();
 
-        send_params.send(app.params().clone()).unwrap();
+        send_app.send(app.clone()).unwrap();
 
-        let public_key = recv_pub.recv().unwrap();
+        let (public_key, evaluation_keys) = recv_pub.recv().unwrap();
 
         let runtime = FheRuntime::new(app.params()).unwrap();
 
@@ -273,21 +280,21 @@ fn bob(
                     .run(
                         app.get_fhe_program("add").unwrap(),
                         vec![left, right],
-                        &public_key,
+                        &evaluation_keys,
                     )
                     .unwrap(),
                 Operand::Sub => runtime
                     .run(
                         app.get_fhe_program("sub").unwrap(),
                         vec![left, right],
-                        &public_key,
+                        &evaluation_keys,
                     )
                     .unwrap(),
                 Operand::Mul => runtime
                     .run(
                         app.get_fhe_program("mul").unwrap(),
                         vec![left, right],
-                        &public_key,
+                        &evaluation_keys,
                     )
                     .unwrap(),
                 // To do division, Alice must send us 1 / b and we
@@ -296,7 +303,7 @@ fn bob(
                     .run(
                         app.get_fhe_program("mul").unwrap(),
                         vec![left, right],
-                        &public_key,
+                        &evaluation_keys,
                     )
                     .unwrap(),
             };
@@ -312,13 +319,14 @@ fn bob(
 
 fn main() {
     // A channel for Alice to send her public keys to Bob.
-    let (send_alice_pub, receive_alice_pub) = std::sync::mpsc::channel::<PublicKey>();
+    let (send_alice_pub, receive_alice_pub) =
+        std::sync::mpsc::channel::<(PublicKey, EvaluationKeys)>();
 
     // A channel for Alice to send calculation requests to Bob.
     let (send_alice_calc, receive_alice_calc) = std::sync::mpsc::channel::<Expression>();
 
-    // A channel for Bob to send scheme params to Alice
-    let (send_bob_params, receive_bob_params) = std::sync::mpsc::channel::<Params>();
+    // A channel for Bob to send his FHE programs to Alice
+    let (send_bob_app, receive_bob_app) = std::sync::mpsc::channel::<FheApplication>();
 
     // A channel for Bob to send calculation results to Alice.
     let (send_bob_result, receive_bob_result) = std::sync::mpsc::channel::<Ciphertext>();
@@ -329,13 +337,13 @@ fn main() {
     let a = alice(
         send_alice_pub,
         send_alice_calc,
-        receive_bob_params,
+        receive_bob_app,
         receive_bob_result,
     );
     let b = bob(
         receive_alice_pub,
         receive_alice_calc,
-        send_bob_params,
+        send_bob_app,
         send_bob_result,
     );
 
@@ -189,6 +189,8 @@ where
 
     let start = Instant::now();
     let (public_key, private_key) = runtime.generate_keys()?;
+    let evaluation_keys =
+        runtime.generate_evaluation_keys(&private_key, [app.get_fhe_program(c).unwrap()])?;
     let elapsed = start.elapsed().as_secs_f64();
 
     println!("\t\tKeygen time {elapsed}s");
@@ -204,7 +206,7 @@ where
     let start = Instant::now();
     let args: Vec<FheProgramInput> = vec![n_0_enc.into(), n_1_enc.into(), n_2_enc.into()];
 
-    let result = runtime.run(app.get_fhe_program(c).unwrap(), args, &public_key)?;
+    let result = runtime.run(app.get_fhe_program(c).unwrap(), args, &evaluation_keys)?;
     let elapsed = start.elapsed().as_secs_f64();
 
     println!("\t\tRun time {elapsed}s");
@@ -167,13 +167,19 @@ fn main() -> Result<(), Error> {
     let runtime = FheRuntime::new(app.params())?;
 
     let (public_key, private_key) = runtime.generate_keys()?;
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(dot_product).unwrap()])?;
     let a_enc = runtime.encrypt(a_batched, &public_key)?;
 
     let args: Vec<FheProgramInput> = vec![a_enc.clone().into(), a_enc.into()];
 
     // Run our dot product homomorphically, decrypt and verify the result.
     let start = Instant::now();
-    let results = runtime.run(app.get_fhe_program(dot_product).unwrap(), args, &public_key)?;
+    let results = runtime.run(
+        app.get_fhe_program(dot_product).unwrap(),
+        args,
+        &evaluation_keys,
+    )?;
     let end = start.elapsed();
 
     let fhe_dot: Batched<VECLENDIV2> = runtime.decrypt(&results[0], &private_key)?;
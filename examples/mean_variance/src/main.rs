@@ -3,7 +3,7 @@ use std::ops::{Add, Div, Mul, Sub};
 use sunscreen::{
     fhe_program,
     types::{bfv::Fractional, Cipher},
-    Ciphertext, CompiledFheProgram, Compiler, Error as SunscreenError, FheRuntime, Params,
+    Ciphertext, CompiledFheProgram, Compiler, Error as SunscreenError, EvaluationKeys, FheRuntime,
     PrivateKey, PublicKey, RuntimeError,
 };
 
@@ -63,7 +63,6 @@ impl From<BincodeError> for Error {
 }
 
 pub struct Bob {
-    params: Params,
     mean_fhe: CompiledFheProgram,
     variance_fhe: CompiledFheProgram,
     runtime: FheRuntime,
@@ -92,7 +91,6 @@ impl Bob {
         let runtime = FheRuntime::new(app.params())?;
 
         Ok(Self {
-            params: app.params().to_owned(),
             mean_fhe: mean_program.to_owned(),
             variance_fhe: variance_program.to_owned(),
             runtime,
@@ -102,18 +100,18 @@ impl Bob {
     pub fn compute_and_serialize_mean_variance(
         &self,
         serialized_ciphertext: &[u8],
-        serialized_public_key: &[u8],
+        serialized_evaluation_keys: &[u8],
     ) -> Result<(Vec<u8>, Vec<u8>), Error> {
         let data: Ciphertext = bincode::deserialize(serialized_ciphertext)?;
-        let public_key = bincode::deserialize(serialized_public_key)?;
+        let evaluation_keys = bincode::deserialize(serialized_evaluation_keys)?;
 
         let mean_result = self
             .runtime
-            .run(&self.mean_fhe, vec![data.clone()], &public_key)?;
+            .run(&self.mean_fhe, vec![data.clone()], &evaluation_keys)?;
 
         let variance_result = self
             .runtime
-            .run(&self.variance_fhe, vec![data], &public_key)?;
+            .run(&self.variance_fhe, vec![data], &evaluation_keys)?;
 
         Ok((
             bincode::serialize(&mean_result[0])?,
@@ -121,8 +119,8 @@ impl Bob {
         ))
     }
 
-    pub fn serialized_scheme_params(&self) -> Result<Vec<u8>, Error> {
-        Ok(bincode::serialize(&self.params)?)
+    pub fn serialized_fhe_programs(&self) -> Result<Vec<u8>, Error> {
+        Ok(bincode::serialize(&[&self.mean_fhe, &self.variance_fhe])?)
     }
 }
 
@@ -130,19 +128,22 @@ pub struct Alice {
     runtime: FheRuntime,
     public_key: PublicKey,
     private_key: PrivateKey,
+    evaluation_keys: EvaluationKeys,
 }
 
 impl Alice {
-    pub fn new(serialized_params: &[u8]) -> Result<Self, Error> {
-        let params = bincode::deserialize(serialized_params)?;
-        let runtime = FheRuntime::new(&params)?;
+    pub fn new(serialized_fhe_programs: &[u8]) -> Result<Self, Error> {
+        let fhe_programs: Vec<CompiledFheProgram> = bincode::deserialize(serialized_fhe_programs)?;
+        let runtime = FheRuntime::new(&fhe_programs[0].metadata.params)?;
 
         let (public_key, private_key) = runtime.generate_keys()?;
+        let evaluation_keys = runtime.generate_evaluation_keys(&private_key, &fhe_programs)?;
 
         Ok(Self {
             runtime,
             public_key,
             private_key,
+            evaluation_keys,
         })
     }
 
@@ -162,8 +163,8 @@ impl Alice {
         Ok(bincode::serialize(&ciphertext)?)
     }
 
-    pub fn serialized_public_key(&self) -> Result<Vec<u8>, Error> {
-        Ok(bincode::serialize(&self.public_key)?)
+    pub fn serialized_evaluation_keys(&self) -> Result<Vec<u8>, Error> {
+        Ok(bincode::serialize(&self.evaluation_keys)?)
     }
 
     pub fn deserialize_decrypt_and_print_results(
@@ -191,11 +192,13 @@ fn main() -> Result<(), Error> {
     // going over a network.
     let bob = Bob::new()?;
 
-    let alice = Alice::new(&bob.serialized_scheme_params()?)?;
+    let alice = Alice::new(&bob.serialized_fhe_programs()?)?;
     let serialized_input = alice.encrypt_and_serialize_input()?;
 
-    let (serialized_mean, serialized_variance) = bob
-        .compute_and_serialize_mean_variance(&serialized_input, &alice.serialized_public_key()?)?;
+    let (serialized_mean, serialized_variance) = bob.compute_and_serialize_mean_variance(
+        &serialized_input,
+        &alice.serialized_evaluation_keys()?,
+    )?;
 
     alice.deserialize_decrypt_and_print_results(&serialized_mean, &serialized_variance)?;
 
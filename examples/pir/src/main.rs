@@ -3,7 +3,7 @@
 use sunscreen::{
     fhe_program,
     types::{bfv::Signed, Cipher},
-    Ciphertext, CompiledFheProgram, Compiler, Error, FheProgramInput, FheRuntime, Params,
+    Ciphertext, CompiledFheProgram, Compiler, Error, EvaluationKeys, FheProgramInput, FheRuntime,
     PrivateKey, PublicKey,
 };
 
@@ -69,7 +69,7 @@ impl Server {
         &self,
         col_query: Ciphertext,
         row_query: Ciphertext,
-        public_key: &PublicKey,
+        evaluation_keys: &EvaluationKeys,
     ) -> Result<Ciphertext, Error> {
         // Our database will consist of values between 400 and 500.
         let mut database = [[Signed::from(0); SQRT_DATABASE_SIZE]; SQRT_DATABASE_SIZE];
@@ -84,7 +84,9 @@ impl Server {
 
         let args: Vec<FheProgramInput> = vec![col_query.into(), row_query.into(), database.into()];
 
-        let results = self.runtime.run(&self.compiled_lookup, args, public_key)?;
+        let results = self
+            .runtime
+            .run(&self.compiled_lookup, args, evaluation_keys)?;
 
         Ok(results[0].clone())
     }
@@ -96,6 +98,9 @@ struct Alice {
     /// Alice's public key
     pub public_key: PublicKey,
 
+    /// The keys a server needs to run FHE programs on Alice's data
+    pub evaluation_keys: EvaluationKeys,
+
     /// Alice's private key
     private_key: PrivateKey,
 
@@ -104,13 +109,15 @@ struct Alice {
 }
 
 impl Alice {
-    pub fn setup(params: &Params) -> Result<Alice, Error> {
-        let runtime = FheRuntime::new(params)?;
+    pub fn setup(fhe_program: &CompiledFheProgram) -> Result<Alice, Error> {
+        let runtime = FheRuntime::new(&fhe_program.metadata.params)?;
 
         let (public_key, private_key) = runtime.generate_keys()?;
+        let evaluation_keys = runtime.generate_evaluation_keys(&private_key, [fhe_program])?;
 
         Ok(Alice {
             public_key,
+            evaluation_keys,
             private_key,
             runtime,
         })
@@ -147,13 +154,13 @@ fn main() -> Result<(), Error> {
     // Set up the database
     let server = Server::setup()?;
 
-    // Alice sets herself up. The FHE scheme parameters are public to the
-    // protocol, so Alice has them.
-    let alice = Alice::setup(&server.compiled_lookup.metadata.params)?;
+    // Alice sets herself up. The compiled FHE program (and hence its scheme
+    // parameters) is public to the protocol, so Alice has it.
+    let alice = Alice::setup(&server.compiled_lookup)?;
 
     let (col_query, row_query) = alice.create_query(94)?;
 
-    let response = server.run_query(col_query, row_query, &alice.public_key)?;
+    let response = server.run_query(col_query, row_query, &alice.evaluation_keys)?;
 
     alice.check_response(response)?;
 
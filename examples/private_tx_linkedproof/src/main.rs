@@ -18,7 +18,8 @@ use sunscreen::{
         Cipher,
     },
     zkp_program, zkp_var, Ciphertext, CompiledFheProgram, CompiledZkpProgram, Compiler,
-    FheProgramInput, FheZkpApplication, FheZkpRuntime, Params, PrivateKey, PublicKey, Result,
+    EvaluationKeys, FheProgramInput, FheZkpApplication, FheZkpRuntime, Params, PrivateKey,
+    PublicKey, Result,
 };
 
 /// Subtract the transaction amount from the sender's balance.
@@ -212,10 +213,21 @@ impl User {
             .public_input(BulletproofsField::from(initial_deposit))
             .build()?;
 
+        // The chain needs evaluation keys to run its FHE programs on our balance
+        let evaluation_keys = self.runtime.generate_evaluation_keys(
+            &self.private_key,
+            [
+                self.app.get_transfer_from_fhe(),
+                self.app.get_transfer_to_fhe(),
+                self.app.get_deposit_to_fhe(),
+            ],
+        )?;
+
         Ok(Register {
             proof,
             encrypted_amount: amount_enc,
             public_key: self.public_key.clone(),
+            evaluation_keys,
             deposit: self.create_deposit(initial_deposit),
         })
     }
@@ -229,6 +241,7 @@ impl User {
 pub struct Register {
     proof: LinkedProof,
     public_key: PublicKey,
+    evaluation_keys: EvaluationKeys,
     encrypted_amount: Ciphertext,
     deposit: Deposit,
 }
@@ -288,6 +301,8 @@ pub struct Chain {
     balances: HashMap<Username, Ciphertext>,
     /// The user's public keys
     keys: HashMap<Username, PublicKey>,
+    /// The user's evaluation keys
+    evaluation_keys: HashMap<Username, EvaluationKeys>,
     /// Ledger of transactions
     ledger: Vec<Transaction>,
     /// App holding FHE and ZKP programs
@@ -303,6 +318,7 @@ impl Chain {
         Ok(Self {
             balances: HashMap::new(),
             keys: HashMap::new(),
+            evaluation_keys: HashMap::new(),
             ledger: Vec::new(),
             runtime,
             app,
@@ -315,6 +331,7 @@ impl Chain {
             proof,
             encrypted_amount,
             public_key,
+            evaluation_keys,
             deposit,
         } = register;
 
@@ -327,8 +344,10 @@ impl Chain {
             .public_input(BulletproofsField::from(deposit.public_amount))
             .verify()?;
 
-        // Register the user's public key
+        // Register the user's public and evaluation keys
         self.keys.insert(deposit.name.clone(), public_key);
+        self.evaluation_keys
+            .insert(deposit.name.clone(), evaluation_keys);
 
         // Set the initial encrypted balance
         self.balances.insert(deposit.name, encrypted_amount);
@@ -343,14 +362,14 @@ impl Chain {
         } = deposit;
 
         // Deposit into the user's balance
-        let pk = self.keys.get(&name).unwrap();
+        let evaluation_keys = self.evaluation_keys.get(&name).unwrap();
         let curr_bal = self.balances.get_mut(&name).unwrap();
         *curr_bal = self
             .runtime
             .run::<FheProgramInput>(
                 self.app.get_deposit_to_fhe(),
                 vec![curr_bal.clone().into(), Signed::from(public_amount).into()],
-                pk,
+                evaluation_keys,
             )?
             .remove(0);
         Ok(())
@@ -384,26 +403,26 @@ impl Chain {
             .verify()?;
 
         // Update the sender's balance:
-        let sender_pk = self.keys.get(&sender).unwrap();
+        let sender_keys = self.evaluation_keys.get(&sender).unwrap();
         let sender_balance = self.balances.get_mut(&sender).unwrap();
         *sender_balance = self
             .runtime
             .run(
                 self.app.get_transfer_from_fhe(),
                 vec![sender_balance.clone(), encrypted_amount_sender],
-                sender_pk,
+                sender_keys,
             )?
             .remove(0);
 
         // Update receiver's balance
-        let receiver_pk = self.keys.get(&receiver).unwrap();
+        let receiver_keys = self.evaluation_keys.get(&receiver).unwrap();
         let receiver_balance = self.balances.get_mut(&receiver).unwrap();
         *receiver_balance = self
             .runtime
             .run(
                 self.app.get_transfer_to_fhe(),
                 vec![receiver_balance.clone(), encrypted_amount_receiver],
-                receiver_pk,
+                receiver_keys,
             )?
             .remove(0);
         Ok(())
@@ -53,6 +53,10 @@ fn main() -> Result<(), Error> {
      * sending the public key to bob, who then runs a computation.
      */
     let (public_key, private_key) = runtime.generate_keys()?;
+    let evaluation_keys = runtime.generate_evaluation_keys(
+        &private_key,
+        [app.get_fhe_program(simple_multiply).unwrap()],
+    )?;
 
     let a = runtime.encrypt(Signed::from(15), &public_key)?;
     let b = runtime.encrypt(Signed::from(5), &public_key)?;
@@ -64,7 +68,7 @@ fn main() -> Result<(), Error> {
     let results = runtime.run(
         app.get_fhe_program(simple_multiply).unwrap(),
         vec![a, b],
-        &public_key,
+        &evaluation_keys,
     )?;
 
     /*
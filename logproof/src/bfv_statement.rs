@@ -715,11 +715,11 @@ impl<T: Zero + Clone + Debug + PartialEq> MatrixSet<T> for Matrix<T> {
     }
 }
 
-fn calculate_ciphertext_modulus(qs: Vec<u64>) -> Uint<4> {
+fn calculate_ciphertext_modulus<const N: usize>(qs: Vec<u64>) -> Uint<N> {
     // Calculate the data coefficient modulus, which for fields with more
     // that one modulus in the coefficient modulus set is equal to the
     // product of all but the last moduli in the set.
-    let mut data_modulus = Uint::<4>::from_u8(1);
+    let mut data_modulus = Uint::<N>::from_u8(1);
     if qs.len() == 1 {
         data_modulus = data_modulus
             .checked_mul(&Uint::<1>::from_u64(qs[0]))
@@ -733,12 +733,10 @@ fn calculate_ciphertext_modulus(qs: Vec<u64>) -> Uint<4> {
 }
 
 fn calculate_delta<const N: usize, B: BarrettConfig<N>>(p: u64, qs: Vec<u64>) -> Z<N, B> {
-    let q_bigint = calculate_ciphertext_modulus(qs);
+    let q_bigint = calculate_ciphertext_modulus::<N>(qs);
     let p_bigint = NonZero::new(Uint::from(p)).unwrap();
     let delta = q_bigint.div_rem(&p_bigint).0;
-    let limbs = delta.as_limbs().map(|l| l.into());
-    let delta_uint = Uint::<N>::from_words(limbs[0..N].try_into().unwrap());
-    Zq::try_from(delta_uint).unwrap()
+    Zq::try_from(delta).unwrap()
 }
 
 fn strip_trailing_value<T>(mut v: Vec<T>, trim_value: T) -> Vec<T>
@@ -762,7 +760,7 @@ mod tests {
     };
 
     use crate::{
-        rings::{ZqSeal128_1024, ZqSeal128_4096},
+        rings::{SealQ128_16384, ZqSeal128_1024, ZqSeal128_16384, ZqSeal128_4096},
         InnerProductVerifierKnowledge, LogProof, LogProofGenerators, ProofError,
     };
 
@@ -790,6 +788,10 @@ mod tests {
         // ignores last moduli 11
         let delta: ZqSeal128_4096 = calculate_delta(4, vec![53, 53, 11]);
         assert_eq!(delta.val.as_words(), &[53 * 53 / 4, 0]);
+
+        // Wider than the 4 limbs of the proof field
+        let delta: ZqSeal128_16384 = calculate_delta(1 << 10, SealQ128_16384::Q.to_vec());
+        assert_eq!(delta.val, SealQ128_16384::MODULUS.shr_vartime(10));
     }
 
     #[test]
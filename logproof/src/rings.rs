@@ -27,6 +27,88 @@ pub struct RistrettoConfig {}
  */
 pub type ZqRistretto = Zq<4, BarrettBackend<4, RistrettoConfig>>;
 
+/**
+ * The configuration type for q modulus SEAL BFV uses with 128-bit security
+ * an lattice dimension 32768.
+ *
+ * # Remarks
+ *
+ * SEAL uses Q =
+ * 0x7fffffffe90001 * 0x7fffffffbf0001 * 0x7fffffffbd0001 * 0x7fffffffba0001 *
+ * 0x7fffffffaa0001 * 0x7fffffffa50001 * 0x7fffffff9f0001 * 0x7fffffff7e0001 *
+ * 0x7fffffff770001 * 0x7fffffff380001 * 0x7fffffff330001 * 0x7fffffff2d0001 *
+ * 0x7fffffff170001 * 0x7fffffff150001 * 0x7ffffffef00001 * 0xfffffffff70001
+ *
+ * This can be derived by running
+ * `CoefficientModulus::bfv_default(32768, SecurityLevel::TC128)`
+ * or by running the underlying SEAL function
+ * `CoeffModulus::BFVDefault(32768, sec_level_type::tc128)`
+ */
+#[derive(BarrettConfig)]
+#[barrett_config(
+    modulus = "223741435999467160568560227190177879546801083834671483735047619201430833835054747611197644281314796184979470480968360268025870958760423282306130331966438774560212659384853658020055067910066538256152151486195650895231514702971773677064350737875992577",
+    num_limbs = 13
+)]
+pub struct SealQ128_32768 {}
+impl SealQ128_32768 {
+    /// The SEAL modulus chain
+    pub const Q: &'static [u64] = &[
+        0x7fffffffe90001,
+        0x7fffffffbf0001,
+        0x7fffffffbd0001,
+        0x7fffffffba0001,
+        0x7fffffffaa0001,
+        0x7fffffffa50001,
+        0x7fffffff9f0001,
+        0x7fffffff7e0001,
+        0x7fffffff770001,
+        0x7fffffff380001,
+        0x7fffffff330001,
+        0x7fffffff2d0001,
+        0x7fffffff170001,
+        0x7fffffff150001,
+        0x7ffffffef00001,
+        0xfffffffff70001,
+    ];
+}
+
+/**
+ * The configuration type for q modulus SEAL BFV uses with 128-bit security
+ * an lattice dimension 16384.
+ *
+ * # Remarks
+ *
+ * SEAL uses Q =
+ * 0xfffffffd8001 * 0xfffffffa0001 * 0xfffffff00001 * 0x1fffffff68001 *
+ * 0x1fffffff50001 * 0x1ffffffee8001 * 0x1ffffffea0001 * 0x1ffffffe88001 *
+ * 0x1ffffffe48001
+ *
+ * This can be derived by running
+ * `CoefficientModulus::bfv_default(16384, SecurityLevel::TC128)`
+ * or by running the underlying SEAL function
+ * `CoeffModulus::BFVDefault(16384, sec_level_type::tc128)`
+ */
+#[derive(BarrettConfig)]
+#[barrett_config(
+    modulus = "1260864178835779834920553696788645403855582773375888727150779106181446108524381258964564013757204357435827521269792769",
+    num_limbs = 7
+)]
+pub struct SealQ128_16384 {}
+impl SealQ128_16384 {
+    /// The SEAL modulus chain
+    pub const Q: &'static [u64] = &[
+        0xfffffffd8001,
+        0xfffffffa0001,
+        0xfffffff00001,
+        0x1fffffff68001,
+        0x1fffffff50001,
+        0x1ffffffee8001,
+        0x1ffffffea0001,
+        0x1ffffffe88001,
+        0x1ffffffe48001,
+    ];
+}
+
 /**
  * The configuration type for q modulus SEAL BFV uses with 128-bit security
  * an lattice dimension 8192.
@@ -124,6 +206,20 @@ impl SealQ128_1024 {
     pub const Q: &'static [u64] = &[0x7e00001];
 }
 
+#[allow(unused)]
+/**
+ * The field SEAL's BFV scheme uses with 128-bit security and a poly degree
+ * of 32768.
+ */
+pub type ZqSeal128_32768 = Zq<13, BarrettBackend<13, SealQ128_32768>>;
+
+#[allow(unused)]
+/**
+ * The field SEAL's BFV scheme uses with 128-bit security and a poly degree
+ * of 16384.
+ */
+pub type ZqSeal128_16384 = Zq<7, BarrettBackend<7, SealQ128_16384>>;
+
 #[allow(unused)]
 /**
  * The field SEAL's BFV scheme uses with 128-bit security and a poly degree
@@ -243,6 +339,7 @@ where
 
 #[cfg(test)]
 mod tests {
+    use crypto_bigint::CheckedMul;
     use sunscreen_math::{poly::Polynomial, ring::RingModulus};
 
     use crate::math::Log2;
@@ -345,6 +442,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn seal_moduli_match_modulus_chain() {
+        fn assert_matches_chain<const N: usize>(modulus: Uint<N>, q: &[u64]) {
+            // The last modulus in the chain is SEAL's special prime.
+            let data_modulus = q[..q.len() - 1].iter().fold(Uint::<N>::ONE, |acc, x| {
+                acc.checked_mul(&Uint::<1>::from_u64(*x)).unwrap()
+            });
+
+            assert_eq!(modulus, data_modulus);
+        }
+
+        assert_matches_chain(ZqSeal128_4096::field_modulus(), SealQ128_4096::Q);
+        assert_matches_chain(ZqSeal128_8192::field_modulus(), SealQ128_8192::Q);
+        assert_matches_chain(ZqSeal128_16384::field_modulus(), SealQ128_16384::Q);
+        assert_matches_chain(ZqSeal128_32768::field_modulus(), SealQ128_32768::Q);
+    }
+
     #[test]
     fn can_log_2_modulus() {
         let modulus: Uint<4> = ZqSeal128_8192::field_modulus();
@@ -25,3 +25,4 @@
 //#include "seal/c/utilities.h"
 #include "seal/c/valcheck.h"
 #include "seal/c/rns.h"
+#include "native/extensions.h"
@@ -27,6 +27,22 @@ fn setup_macos_cross_compile(config: &mut Config) {
     }
 }
 
+/// Builds the functions in `native/` that extend SEAL's C API. These call into
+/// SEAL, so this must run before we emit SEAL's link directives.
+fn compile_extensions(seal_dst: &Path) {
+    cc::Build::new()
+        .cpp(true)
+        .flag_if_supported("-std=c++17")
+        .flag_if_supported("/std:c++17")
+        .include(seal_dst.join("include/SEAL-4.0"))
+        .include("SEAL/native/src")
+        .include("native")
+        .file("native/extensions.cpp")
+        .compile("seal_extensions");
+
+    println!("cargo:rerun-if-changed=native");
+}
+
 fn compile_native(profile: &str, out_path: &Path) {
     let hexl = if std::env::var("CARGO_FEATURE_HEXL").is_ok() {
         "ON"
@@ -69,6 +85,8 @@ fn compile_native(profile: &str, out_path: &Path) {
 
     let dst = builder.build();
 
+    compile_extensions(&dst);
+
     let out_path_suffix = if std::env::var("CARGO_CFG_WINDOWS").is_ok() {
         profile
     } else {
@@ -105,6 +123,8 @@ fn compile_wasm(profile: &str, _out_path: &Path) {
         .define("SEAL_USE_ZSTD", "ON")
         .build();
 
+    compile_extensions(&dst);
+
     let lib_path = format!("{}/lib/{}", dst.display(), "");
 
     println!("cargo:rustc-link-search=native={}", lib_path);
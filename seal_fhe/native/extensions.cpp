@@ -0,0 +1,532 @@
+// STD
+#include <algorithm>
+#include <new>
+#include <stdexcept>
+#include <vector>
+
+// SEAL
+#include "seal/ciphertext.h"
+#include "seal/context.h"
+#include "seal/evaluator.h"
+#include "seal/galoiskeys.h"
+#include "seal/kswitchkeys.h"
+#include "seal/publickey.h"
+#include "seal/randomgen.h"
+#include "seal/secretkey.h"
+#include "seal/util/galois.h"
+#include "seal/util/ntt.h"
+#include "seal/util/polyarithsmallmod.h"
+#include "seal/util/rlwe.h"
+#include "seal/util/uintarithsmallmod.h"
+
+#include "extensions.h"
+
+using namespace std;
+using namespace seal;
+using namespace seal::util;
+
+namespace
+{
+    // Runs f, translating SEAL's exceptions to the HRESULTs the C API uses.
+    template <class F>
+    HRESULT guard(F f)
+    {
+        try
+        {
+            return f();
+        }
+        catch (const invalid_argument &)
+        {
+            return E_INVALIDARG;
+        }
+        catch (const logic_error &)
+        {
+            return COR_E_INVALIDOPERATION;
+        }
+        catch (const bad_alloc &)
+        {
+            return E_OUTOFMEMORY;
+        }
+        catch (...)
+        {
+            return E_UNEXPECTED;
+        }
+    }
+
+    // Copies len values to data, or only reports len when data is null.
+    HRESULT export_data(const uint64_t *src, size_t len, uint64_t *count, uint64_t *data)
+    {
+        if (!data)
+        {
+            *count = len;
+            return S_OK;
+        }
+
+        if (*count != len)
+        {
+            return E_INVALIDARG;
+        }
+
+        copy_n(src, len, data);
+
+        return S_OK;
+    }
+
+    // The number of values in a polynomial under the key level modulus.
+    size_t key_poly_len(const SEALContext &context)
+    {
+        auto &parms = context.key_context_data()->parms();
+
+        return parms.poly_modulus_degree() * parms.coeff_modulus().size();
+    }
+
+    HRESULT export_ciphertext(const Ciphertext &cipher, uint64_t *count, uint64_t *data)
+    {
+        size_t len = cipher.size() * cipher.poly_modulus_degree() * cipher.coeff_modulus_size();
+
+        return export_data(cipher.data(), len, count, data);
+    }
+
+    void import_public_key(const SEALContext &context, const uint64_t *data, PublicKey &key)
+    {
+        auto parms_id = context.key_parms_id();
+
+        key.data().resize(context, parms_id, 2);
+        key.data().is_ntt_form() = true;
+        copy_n(data, 2 * key_poly_len(context), key.data().data());
+        key.parms_id() = parms_id;
+    }
+
+    // Rotates the rows of a BFV ciphertext by each Galois element in elts.
+    // Every rotation key switches the same polynomial c1 up to an
+    // automorphism, and the automorphism commutes with the RNS decomposition,
+    // so c1 is decomposed (and NTT'd) once and each rotation only permutes the
+    // decomposition before taking its inner product with the Galois key. The
+    // rest follows SEAL's Evaluator::switch_key_inplace.
+    //
+    // SEAL decomposes the rotated c1, so its j-th digit is
+    // [sigma(c1)]_{q_j} with coefficients in [0, q_j). We rotate the digits
+    // [c1]_{q_j} instead. The automorphism sigma maps x^i to +-x^(i * elt), so
+    // sigma([c1]_{q_j}) has coefficients in (-q_j, q_j): a different
+    // representative of the same residue mod q_j. Key switching only needs
+    //   sum_j digit_j * (Q / q_j) * [(Q / q_j)^-1]_{q_j} = sigma(c1) mod Q
+    // and that each digit is the same integer polynomial under every modulus
+    // (including the special prime), which holds since each limb is the
+    // same permutation and negation of one integer polynomial. The digits'
+    // magnitudes, and so the added noise, are bounded as in SEAL. The
+    // resulting ciphertexts differ from SEAL's but decrypt to the same
+    // plaintext.
+    void rotate_hoisted(
+        const SEALContext &context, const Ciphertext &encrypted, const vector<uint32_t> &elts,
+        const GaloisKeys &galois_keys, const vector<Ciphertext *> &destinations)
+    {
+        auto &context_data = *context.get_context_data(encrypted.parms_id());
+        auto &key_context_data = *context.key_context_data();
+        auto &coeff_modulus = context_data.parms().coeff_modulus();
+        auto &key_modulus = key_context_data.parms().coeff_modulus();
+        auto ntt_tables = key_context_data.small_ntt_tables();
+        auto galois_tool = key_context_data.galois_tool();
+
+        size_t n = context_data.parms().poly_modulus_degree();
+        size_t decomp_size = coeff_modulus.size();
+
+        // The ciphertext's moduli followed by the special prime.
+        size_t rns_size = decomp_size + 1;
+        size_t special_index = key_modulus.size() - 1;
+        auto &special = key_modulus[special_index];
+        auto key_index = [&](size_t i) { return i == decomp_size ? special_index : i; };
+
+        const uint64_t *c0 = encrypted.data(0);
+        const uint64_t *c1 = encrypted.data(1);
+
+        // The j-th RNS limb of c1, reduced by the i-th modulus and in NTT form.
+        vector<uint64_t> digits(decomp_size * rns_size * n);
+        auto digit = [&](size_t j, size_t i) { return digits.data() + (j * rns_size + i) * n; };
+
+        for (size_t j = 0; j < decomp_size; j++)
+        {
+            for (size_t i = 0; i < rns_size; i++)
+            {
+                modulo_poly_coeffs(c1 + j * n, n, key_modulus[key_index(i)], digit(j, i));
+                ntt_negacyclic_harvey(digit(j, i), ntt_tables[key_index(i)]);
+            }
+        }
+
+        vector<uint64_t> products(2 * rns_size * n);
+        vector<uint64_t> permuted(n);
+        vector<uint64_t> temp(n);
+
+        for (size_t e = 0; e < elts.size(); e++)
+        {
+            auto &key_vector = galois_keys.key(elts[e]);
+            auto &destination = *destinations[e];
+
+            // Products of the permuted decomposition with the key, for each of
+            // the key's 2 polynomials.
+            fill(products.begin(), products.end(), 0);
+            auto product = [&](size_t k, size_t i) { return products.data() + (k * rns_size + i) * n; };
+
+            for (size_t i = 0; i < rns_size; i++)
+            {
+                auto &modulus = key_modulus[key_index(i)];
+
+                for (size_t j = 0; j < decomp_size; j++)
+                {
+                    galois_tool->apply_galois_ntt(digit(j, i), elts[e], permuted.data());
+
+                    for (size_t k = 0; k < 2; k++)
+                    {
+                        auto key_poly = key_vector[j].data().data(k) + key_index(i) * n;
+
+                        dyadic_product_coeffmod(permuted.data(), key_poly, n, modulus, temp.data());
+                        add_poly_coeffmod(product(k, i), temp.data(), n, modulus, product(k, i));
+                    }
+                }
+            }
+
+            destination = encrypted;
+
+            // Divide by the special prime, rounding.
+            uint64_t half = special.value() >> 1;
+
+            for (size_t k = 0; k < 2; k++)
+            {
+                auto last = product(k, decomp_size);
+
+                inverse_ntt_negacyclic_harvey(last, ntt_tables[special_index]);
+
+                for (size_t l = 0; l < n; l++)
+                {
+                    last[l] = barrett_reduce_64(last[l] + half, special);
+                }
+
+                for (size_t i = 0; i < decomp_size; i++)
+                {
+                    auto &modulus = coeff_modulus[i];
+                    auto result = destination.data(k) + i * n;
+                    uint64_t half_mod = barrett_reduce_64(half, modulus);
+                    uint64_t inv_special;
+
+                    if (!try_invert_uint_mod(barrett_reduce_64(special.value(), modulus), modulus, inv_special))
+                    {
+                        throw logic_error("special prime is not invertible");
+                    }
+
+                    modulo_poly_coeffs(last, n, modulus, temp.data());
+
+                    for (size_t l = 0; l < n; l++)
+                    {
+                        temp[l] = sub_uint_mod(temp[l], half_mod, modulus);
+                    }
+
+                    inverse_ntt_negacyclic_harvey(product(k, i), ntt_tables[i]);
+                    sub_poly_coeffmod(product(k, i), temp.data(), n, modulus, temp.data());
+                    multiply_poly_scalar_coeffmod(temp.data(), n, inv_special, modulus, result);
+                }
+            }
+
+            // Add the rotated c0 to the switched c1.
+            for (size_t i = 0; i < decomp_size; i++)
+            {
+                auto result = destination.data(0) + i * n;
+
+                galois_tool->apply_galois(c0 + i * n, elts[e], coeff_modulus[i], temp.data());
+                add_poly_coeffmod(result, temp.data(), n, coeff_modulus[i], result);
+            }
+        }
+    }
+} // namespace
+
+SEAL_C_FUNC Ciphertext_GetRnsData(void *thisptr, uint64_t *count, uint64_t *data)
+{
+    if (!thisptr || !count)
+    {
+        return E_POINTER;
+    }
+
+    return guard([&]() -> HRESULT {
+        return export_ciphertext(*static_cast<Ciphertext *>(thisptr), count, data);
+    });
+}
+
+SEAL_C_FUNC Ciphertext_SetRnsData(void *thisptr, void *context, uint64_t size, uint64_t count, const uint64_t *data)
+{
+    if (!thisptr || !context || !data)
+    {
+        return E_POINTER;
+    }
+
+    return guard([&]() -> HRESULT {
+        auto cipher = static_cast<Ciphertext *>(thisptr);
+        auto ctx = static_cast<SEALContext *>(context);
+        auto context_data = ctx->get_context_data(cipher->parms_id());
+
+        if (!context_data)
+        {
+            return E_INVALIDARG;
+        }
+
+        auto &parms = context_data->parms();
+
+        if (count != size * parms.poly_modulus_degree() * parms.coeff_modulus().size())
+        {
+            return E_INVALIDARG;
+        }
+
+        cipher->resize(*ctx, cipher->parms_id(), size);
+        copy_n(data, count, cipher->data());
+
+        return S_OK;
+    });
+}
+
+SEAL_C_FUNC SecretKey_GetRnsData(void *thisptr, uint64_t *count, uint64_t *data)
+{
+    if (!thisptr || !count)
+    {
+        return E_POINTER;
+    }
+
+    return guard([&]() -> HRESULT {
+        auto &plain = static_cast<SecretKey *>(thisptr)->data();
+
+        return export_data(plain.data(), plain.coeff_count(), count, data);
+    });
+}
+
+SEAL_C_FUNC SecretKey_SetRnsData(void *thisptr, void *context, uint64_t count, const uint64_t *data)
+{
+    if (!thisptr || !context || !data)
+    {
+        return E_POINTER;
+    }
+
+    return guard([&]() -> HRESULT {
+        auto key = static_cast<SecretKey *>(thisptr);
+        auto ctx = static_cast<SEALContext *>(context);
+
+        if (count != key_poly_len(*ctx))
+        {
+            return E_INVALIDARG;
+        }
+
+        // Plaintexts in NTT form can't be resized, so reset the parms_id first
+        // as SEAL's key generator does.
+        key->parms_id() = parms_id_zero;
+        key->data().resize(count);
+        copy_n(data, count, key->data().data());
+        key->parms_id() = ctx->key_parms_id();
+
+        return S_OK;
+    });
+}
+
+SEAL_C_FUNC PublicKey_GetRnsData(void *thisptr, uint64_t *count, uint64_t *data)
+{
+    if (!thisptr || !count)
+    {
+        return E_POINTER;
+    }
+
+    return guard([&]() -> HRESULT {
+        return export_ciphertext(static_cast<PublicKey *>(thisptr)->data(), count, data);
+    });
+}
+
+SEAL_C_FUNC PublicKey_SetRnsData(void *thisptr, void *context, uint64_t count, const uint64_t *data)
+{
+    if (!thisptr || !context || !data)
+    {
+        return E_POINTER;
+    }
+
+    return guard([&]() -> HRESULT {
+        auto ctx = static_cast<SEALContext *>(context);
+
+        if (count != 2 * key_poly_len(*ctx))
+        {
+            return E_INVALIDARG;
+        }
+
+        import_public_key(*ctx, data, *static_cast<PublicKey *>(thisptr));
+
+        return S_OK;
+    });
+}
+
+SEAL_C_FUNC KSwitchKeys_GetRnsData(void *thisptr, uint64_t index, uint64_t *count, uint64_t *data)
+{
+    if (!thisptr || !count)
+    {
+        return E_POINTER;
+    }
+
+    return guard([&]() -> HRESULT {
+        auto &keys = static_cast<KSwitchKeys *>(thisptr)->data();
+
+        if (index >= keys.size())
+        {
+            return E_INVALIDARG;
+        }
+
+        vector<uint64_t> values;
+
+        for (auto &key : keys[index])
+        {
+            auto &cipher = key.data();
+            values.insert(
+                values.end(), cipher.data(),
+                cipher.data() + cipher.size() * cipher.poly_modulus_degree() * cipher.coeff_modulus_size());
+        }
+
+        return export_data(values.data(), values.size(), count, data);
+    });
+}
+
+SEAL_C_FUNC KSwitchKeys_SetRnsData(
+    void *thisptr, void *context, uint64_t index, uint64_t key_count, uint64_t count, const uint64_t *data)
+{
+    if (!thisptr || !context || !data)
+    {
+        return E_POINTER;
+    }
+
+    return guard([&]() -> HRESULT {
+        auto keys = static_cast<KSwitchKeys *>(thisptr);
+        auto ctx = static_cast<SEALContext *>(context);
+        size_t key_len = 2 * key_poly_len(*ctx);
+
+        if (count != key_count * key_len)
+        {
+            return E_INVALIDARG;
+        }
+
+        vector<PublicKey> replacement(key_count);
+
+        for (size_t j = 0; j < key_count; j++)
+        {
+            import_public_key(*ctx, data + j * key_len, replacement[j]);
+        }
+
+        if (keys->data().size() <= index)
+        {
+            keys->data().resize(index + 1);
+        }
+
+        keys->data()[index] = move(replacement);
+        keys->parms_id() = ctx->key_parms_id();
+
+        return S_OK;
+    });
+}
+
+SEAL_C_FUNC SEALContext_Copy(void *thisptr, void **copy)
+{
+    if (!thisptr || !copy)
+    {
+        return E_POINTER;
+    }
+
+    return guard([&]() -> HRESULT {
+        *copy = new SEALContext(*static_cast<SEALContext *>(thisptr));
+
+        return S_OK;
+    });
+}
+
+SEAL_C_FUNC SEALContext_SampleError(void *thisptr, uint64_t *count, uint64_t *data)
+{
+    if (!thisptr || !count)
+    {
+        return E_POINTER;
+    }
+
+    return guard([&]() -> HRESULT {
+        auto ctx = static_cast<SEALContext *>(thisptr);
+        auto context_data = ctx->key_context_data();
+        auto &parms = context_data->parms();
+        size_t len = key_poly_len(*ctx);
+
+        if (!data)
+        {
+            *count = len;
+            return S_OK;
+        }
+
+        if (*count != len)
+        {
+            return E_INVALIDARG;
+        }
+
+        auto prng = parms.random_generator() ? parms.random_generator()->create()
+                                             : UniformRandomGeneratorFactory::DefaultFactory()->create();
+
+        SEAL_NOISE_SAMPLER(prng, parms, data);
+        ntt_negacyclic_harvey(
+            RNSIter(data, parms.poly_modulus_degree()), parms.coeff_modulus().size(),
+            context_data->small_ntt_tables());
+
+        return S_OK;
+    });
+}
+
+SEAL_C_FUNC Evaluator_RotateRowsMany(
+    void *thisptr, void *context, void *encrypted, uint64_t count, const int *steps, void *galois_keys,
+    void **destinations)
+{
+    if (!thisptr || !context || !encrypted || !galois_keys || (count && (!steps || !destinations)))
+    {
+        return E_POINTER;
+    }
+
+    return guard([&]() -> HRESULT {
+        auto evaluator = static_cast<Evaluator *>(thisptr);
+        auto ctx = static_cast<SEALContext *>(context);
+        auto &cipher = *static_cast<Ciphertext *>(encrypted);
+        auto &keys = *static_cast<GaloisKeys *>(galois_keys);
+        auto context_data = ctx->get_context_data(cipher.parms_id());
+
+        if (!context_data || context_data->parms().scheme() != scheme_type::bfv || !ctx->using_keyswitching() ||
+            cipher.is_ntt_form() || cipher.size() != 2 || keys.parms_id() != ctx->key_parms_id())
+        {
+            return E_INVALIDARG;
+        }
+
+        auto galois_tool = ctx->key_context_data()->galois_tool();
+        vector<uint32_t> elts;
+        vector<Ciphertext *> hoisted;
+
+        for (uint64_t i = 0; i < count; i++)
+        {
+            auto destination = static_cast<Ciphertext *>(destinations[i]);
+
+            if (!destination || destination == &cipher)
+            {
+                return E_INVALIDARG;
+            }
+
+            if (steps[i] == 0)
+            {
+                *destination = cipher;
+                continue;
+            }
+
+            auto elt = galois_tool->get_elt_from_step(steps[i]);
+
+            if (keys.has_key(elt))
+            {
+                elts.push_back(elt);
+                hoisted.push_back(destination);
+            }
+            else
+            {
+                // SEAL composes steps without a key from several rotations.
+                evaluator->rotate_rows(cipher, steps[i], keys, *destination);
+            }
+        }
+
+        rotate_hoisted(*ctx, cipher, elts, keys, hoisted);
+
+        return S_OK;
+    });
+}
@@ -0,0 +1,57 @@
+// Functions Sunscreen needs that SEAL's C API doesn't expose. They follow the
+// C API's conventions: every function returns an HRESULT and functions that
+// export an array report its length in `count` when `data` is null.
+
+#pragma once
+
+#include <stdint.h>
+
+#include "seal/c/defines.h"
+
+// Copies the ciphertext's polynomials to `data`, polynomial-major and then
+// modulus-major.
+SEAL_C_FUNC Ciphertext_GetRnsData(void *thisptr, uint64_t *count, uint64_t *data);
+
+// Resizes the ciphertext to `size` polynomials at its current level and
+// overwrites them with `data`.
+SEAL_C_FUNC Ciphertext_SetRnsData(void *thisptr, void *context, uint64_t size, uint64_t count, const uint64_t *data);
+
+// Copies the secret key's polynomial (in NTT form) to `data`.
+SEAL_C_FUNC SecretKey_GetRnsData(void *thisptr, uint64_t *count, uint64_t *data);
+
+// Overwrites the secret key with the given polynomial in NTT form under the
+// key level modulus.
+SEAL_C_FUNC SecretKey_SetRnsData(void *thisptr, void *context, uint64_t count, const uint64_t *data);
+
+// Copies the public key's 2 polynomials (in NTT form) to `data`.
+SEAL_C_FUNC PublicKey_GetRnsData(void *thisptr, uint64_t *count, uint64_t *data);
+
+// Overwrites the public key with the given 2 polynomials in NTT form under the
+// key level modulus.
+SEAL_C_FUNC PublicKey_SetRnsData(void *thisptr, void *context, uint64_t count, const uint64_t *data);
+
+// Copies the polynomials of every key switching key at `index` to `data`, key
+// by key.
+SEAL_C_FUNC KSwitchKeys_GetRnsData(void *thisptr, uint64_t index, uint64_t *count, uint64_t *data);
+
+// Replaces the key switching keys at `index` with `key_count` keys whose
+// polynomials are given key by key, as in PublicKey_SetRnsData.
+SEAL_C_FUNC KSwitchKeys_SetRnsData(
+    void *thisptr, void *context, uint64_t index, uint64_t key_count, uint64_t count, const uint64_t *data);
+
+// Creates a new handle to the context. SEALContext copies share their
+// precomputed data, so this is cheap.
+SEAL_C_FUNC SEALContext_Copy(void *thisptr, void **copy);
+
+// Samples a polynomial from the error distribution and writes it to `data` in
+// NTT form under the key level modulus.
+SEAL_C_FUNC SEALContext_SampleError(void *thisptr, uint64_t *count, uint64_t *data);
+
+// Rotates the rows of a BFV ciphertext by each of `count` steps, writing the
+// results to `destinations`. Steps with a Galois key share a single
+// decomposition of the ciphertext (i.e. they're hoisted); SEAL's
+// Evaluator_RotateRows handles the others. The Evaluator doesn't expose its
+// context, so it's passed in `context`.
+SEAL_C_FUNC Evaluator_RotateRowsMany(
+    void *thisptr, void *context, void *encrypted, uint64_t count, const int *steps, void *galois_keys,
+    void **destinations);
@@ -1,10 +1,5 @@
-use std::ptr::null_mut;
-
 use crate::evaluator_base::EvaluatorBase;
-use crate::{
-    bindgen, error::convert_seal_error, Ciphertext, Context, Evaluator, GaloisKeys, Plaintext,
-    RelinearizationKeys, Result,
-};
+use crate::{Ciphertext, Context, Evaluator, GaloisKeys, Plaintext, RelinearizationKeys, Result};
 
 /**
  * An evaluator that contains additional operations specific to the BFV scheme.
@@ -98,6 +93,26 @@ impl Evaluator for BFVEvaluator {
         self.0.mod_switch_to_next_inplace_plaintext(a)
     }
 
+    fn rescale_to_next(&self, a: &Ciphertext) -> Result<Ciphertext> {
+        self.0.rescale_to_next(a)
+    }
+
+    fn rescale_to_next_inplace(&self, a: &mut Ciphertext) -> Result<()> {
+        self.0.rescale_to_next_inplace(a)
+    }
+
+    fn mod_switch_to(&self, a: &Ciphertext, parms_id: &[u64; 4]) -> Result<Ciphertext> {
+        self.0.mod_switch_to(a, parms_id)
+    }
+
+    fn mod_switch_to_inplace(&self, a: &mut Ciphertext, parms_id: &[u64; 4]) -> Result<()> {
+        self.0.mod_switch_to_inplace(a, parms_id)
+    }
+
+    fn mod_switch_to_plaintext(&self, a: &Plaintext, parms_id: &[u64; 4]) -> Result<Plaintext> {
+        self.0.mod_switch_to_plaintext(a, parms_id)
+    }
+
     fn exponentiate(
         &self,
         a: &Ciphertext,
@@ -145,33 +160,11 @@ impl Evaluator for BFVEvaluator {
         a: &mut Ciphertext,
         relin_keys: &RelinearizationKeys,
     ) -> Result<()> {
-        convert_seal_error(unsafe {
-            bindgen::Evaluator_Relinearize(
-                self.get_handle(),
-                a.get_handle(),
-                relin_keys.get_handle(),
-                a.get_handle(),
-                null_mut(),
-            )
-        })?;
-
-        Ok(())
+        self.0.relinearize_inplace(a, relin_keys)
     }
 
     fn relinearize(&self, a: &Ciphertext, relin_keys: &RelinearizationKeys) -> Result<Ciphertext> {
-        let out = Ciphertext::new()?;
-
-        convert_seal_error(unsafe {
-            bindgen::Evaluator_Relinearize(
-                self.get_handle(),
-                a.get_handle(),
-                relin_keys.get_handle(),
-                out.get_handle(),
-                null_mut(),
-            )
-        })?;
-
-        Ok(out)
+        self.0.relinearize(a, relin_keys)
     }
 
     fn rotate_rows(
@@ -180,20 +173,7 @@ impl Evaluator for BFVEvaluator {
         steps: i32,
         galois_keys: &GaloisKeys,
     ) -> Result<Ciphertext> {
-        let out = Ciphertext::new()?;
-
-        convert_seal_error(unsafe {
-            bindgen::Evaluator_RotateRows(
-                self.get_handle(),
-                a.get_handle(),
-                steps,
-                galois_keys.get_handle(),
-                out.get_handle(),
-                null_mut(),
-            )
-        })?;
-
-        Ok(out)
+        self.0.rotate_rows(a, steps, galois_keys)
     }
 
     fn rotate_rows_inplace(
@@ -202,48 +182,24 @@ impl Evaluator for BFVEvaluator {
         steps: i32,
         galois_keys: &GaloisKeys,
     ) -> Result<()> {
-        convert_seal_error(unsafe {
-            bindgen::Evaluator_RotateRows(
-                self.get_handle(),
-                a.get_handle(),
-                steps,
-                galois_keys.get_handle(),
-                a.get_handle(),
-                null_mut(),
-            )
-        })?;
+        self.0.rotate_rows_inplace(a, steps, galois_keys)
+    }
 
-        Ok(())
+    fn rotate_rows_many(
+        &self,
+        a: &Ciphertext,
+        steps: &[i32],
+        galois_keys: &GaloisKeys,
+    ) -> Result<Vec<Ciphertext>> {
+        self.0.rotate_rows_many(a, steps, galois_keys)
     }
 
     fn rotate_columns(&self, a: &Ciphertext, galois_keys: &GaloisKeys) -> Result<Ciphertext> {
-        let out = Ciphertext::new()?;
-
-        convert_seal_error(unsafe {
-            bindgen::Evaluator_RotateColumns(
-                self.get_handle(),
-                a.get_handle(),
-                galois_keys.get_handle(),
-                out.get_handle(),
-                null_mut(),
-            )
-        })?;
-
-        Ok(out)
+        self.0.rotate_columns(a, galois_keys)
     }
 
     fn rotate_columns_inplace(&self, a: &Ciphertext, galois_keys: &GaloisKeys) -> Result<()> {
-        convert_seal_error(unsafe {
-            bindgen::Evaluator_RotateColumns(
-                self.get_handle(),
-                a.get_handle(),
-                galois_keys.get_handle(),
-                a.get_handle(),
-                null_mut(),
-            )
-        })?;
-
-        Ok(())
+        self.0.rotate_columns_inplace(a, galois_keys)
     }
 }
 
@@ -923,6 +879,98 @@ mod tests {
         });
     }
 
+    /**
+     * Checks that hoisting `a_c`'s rotations by `steps` gives ciphertexts
+     * that decrypt to exactly the plaintexts rotating by each step does.
+     */
+    fn assert_rotate_rows_many_matches(
+        decryptor: &Decryptor,
+        evaluator: &BFVEvaluator,
+        galois_keys: &GaloisKeys,
+        a_c: &Ciphertext,
+        steps: &[i32],
+    ) {
+        let rotated = evaluator.rotate_rows_many(a_c, steps, galois_keys).unwrap();
+
+        assert_eq!(rotated.len(), steps.len());
+
+        for (s, c_c) in steps.iter().zip(rotated) {
+            let expected = evaluator.rotate_rows(a_c, *s, galois_keys).unwrap();
+
+            assert_eq!(c_c.coeff_modulus_size(), expected.coeff_modulus_size());
+            assert!(decryptor.decrypt(&c_c).unwrap() == decryptor.decrypt(&expected).unwrap());
+
+            // Hoisting changes the decomposition's representatives, not
+            // their size, so it costs the same noise as rotating directly.
+            let budget = decryptor.invariant_noise_budget(&c_c).unwrap();
+            let expected_budget = decryptor.invariant_noise_budget(&expected).unwrap();
+
+            assert!(budget + 1 >= expected_budget);
+        }
+    }
+
+    #[test]
+    fn can_rotate_rows_many() {
+        run_bfv_test(|decryptor, encoder, encryptor, evaluator, keygen| {
+            let galois_keys = keygen.create_galois_keys().unwrap();
+
+            let a = make_matrix(&encoder);
+            let a_p = encoder.encode_signed(&a).unwrap();
+            let a_c = encryptor.encrypt(&a_p).unwrap();
+
+            // 1, -1, 4 and -16 have keys, so they share one decomposition of
+            // a_c. 3 has no key and 0 doesn't rotate.
+            let steps = [1, -1, 4, -16, 3, 0];
+
+            assert_rotate_rows_many_matches(&decryptor, &evaluator, &galois_keys, &a_c, &steps);
+
+            let rotated = evaluator
+                .rotate_rows_many(&a_c, &steps, &galois_keys)
+                .unwrap();
+            let c = encoder
+                .decode_signed(&decryptor.decrypt(&rotated[0]).unwrap())
+                .unwrap();
+
+            assert_eq!(a[1], c[0]);
+            assert_eq!(a[4097], c[4096]);
+        });
+    }
+
+    #[test]
+    fn can_rotate_rows_many_after_mod_switch() {
+        let params = BfvEncryptionParametersBuilder::new()
+            .set_poly_modulus_degree(8192)
+            .set_coefficient_modulus(
+                CoefficientModulus::create(8192, &[50, 30, 30, 50, 50]).unwrap(),
+            )
+            .set_plain_modulus(PlainModulus::batching(8192, 32).unwrap())
+            .build()
+            .unwrap();
+
+        let ctx = Context::new(&params, true, SecurityLevel::TC128).unwrap();
+        let gen = KeyGenerator::new(&ctx).unwrap();
+        let galois_keys = gen.create_galois_keys().unwrap();
+
+        let encoder = BFVEncoder::new(&ctx).unwrap();
+        let encryptor = Encryptor::with_public_key(&ctx, &gen.create_public_key()).unwrap();
+        let decryptor = Decryptor::new(&ctx, &gen.secret_key()).unwrap();
+        let evaluator = BFVEvaluator::new(&ctx).unwrap();
+
+        let a = make_matrix(&encoder);
+        let a_c = encryptor
+            .encrypt(&encoder.encode_signed(&a).unwrap())
+            .unwrap();
+
+        // Drop 2 of the 4 data primes, so the decomposition has fewer
+        // limbs than the Galois keys.
+        let a_c = evaluator.mod_switch_to_next(&a_c).unwrap();
+        let a_c = evaluator.mod_switch_to_next(&a_c).unwrap();
+
+        assert_eq!(a_c.coeff_modulus_size(), 2);
+
+        assert_rotate_rows_many_matches(&decryptor, &evaluator, &galois_keys, &a_c, &[1, 2, -8]);
+    }
+
     #[test]
     fn can_rotate_columns() {
         run_bfv_test(|decryptor, encoder, encryptor, evaluator, keygen| {
@@ -0,0 +1,320 @@
+use crate::evaluator_base::EvaluatorBase;
+use crate::{Ciphertext, Context, Evaluator, GaloisKeys, Plaintext, RelinearizationKeys, Result};
+
+/**
+ * An evaluator that contains additional operations specific to the BGV scheme.
+ */
+pub struct BGVEvaluator(EvaluatorBase);
+
+impl std::ops::Deref for BGVEvaluator {
+    type Target = EvaluatorBase;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl BGVEvaluator {
+    /**
+     * Creates a BGVEvaluator instance initialized with the specified Context.
+     * * `ctx` - The context.
+     */
+    pub fn new(ctx: &Context) -> Result<BGVEvaluator> {
+        Ok(BGVEvaluator(EvaluatorBase::new(ctx)?))
+    }
+}
+
+impl Evaluator for BGVEvaluator {
+    fn negate_inplace(&self, a: &mut Ciphertext) -> Result<()> {
+        self.0.negate_inplace(a)
+    }
+
+    fn negate(&self, a: &Ciphertext) -> Result<Ciphertext> {
+        self.0.negate(a)
+    }
+
+    fn add_inplace(&self, a: &mut Ciphertext, b: &Ciphertext) -> Result<()> {
+        self.0.add_inplace(a, b)
+    }
+
+    fn add(&self, a: &Ciphertext, b: &Ciphertext) -> Result<Ciphertext> {
+        self.0.add(a, b)
+    }
+
+    fn add_many(&self, a: &[Ciphertext]) -> Result<Ciphertext> {
+        self.0.add_many(a)
+    }
+
+    fn multiply_many(
+        &self,
+        a: &[Ciphertext],
+        relin_keys: &RelinearizationKeys,
+    ) -> Result<Ciphertext> {
+        self.0.multiply_many(a, relin_keys)
+    }
+
+    fn sub_inplace(&self, a: &mut Ciphertext, b: &Ciphertext) -> Result<()> {
+        self.0.sub_inplace(a, b)
+    }
+
+    fn sub(&self, a: &Ciphertext, b: &Ciphertext) -> Result<Ciphertext> {
+        self.0.sub(a, b)
+    }
+
+    fn multiply_inplace(&self, a: &mut Ciphertext, b: &Ciphertext) -> Result<()> {
+        self.0.multiply_inplace(a, b)
+    }
+
+    fn multiply(&self, a: &Ciphertext, b: &Ciphertext) -> Result<Ciphertext> {
+        self.0.multiply(a, b)
+    }
+
+    fn square_inplace(&self, a: &mut Ciphertext) -> Result<()> {
+        self.0.square_inplace(a)
+    }
+
+    fn square(&self, a: &Ciphertext) -> Result<Ciphertext> {
+        self.0.square(a)
+    }
+
+    fn mod_switch_to_next(&self, a: &Ciphertext) -> Result<Ciphertext> {
+        self.0.mod_switch_to_next(a)
+    }
+
+    fn mod_switch_to_next_inplace(&self, a: &Ciphertext) -> Result<()> {
+        self.0.mod_switch_to_next_inplace(a)
+    }
+
+    fn mod_switch_to_next_plaintext(&self, a: &Plaintext) -> Result<Plaintext> {
+        self.0.mod_switch_to_next_plaintext(a)
+    }
+
+    fn mod_switch_to_next_inplace_plaintext(&self, a: &Plaintext) -> Result<()> {
+        self.0.mod_switch_to_next_inplace_plaintext(a)
+    }
+
+    fn rescale_to_next(&self, a: &Ciphertext) -> Result<Ciphertext> {
+        self.0.rescale_to_next(a)
+    }
+
+    fn rescale_to_next_inplace(&self, a: &mut Ciphertext) -> Result<()> {
+        self.0.rescale_to_next_inplace(a)
+    }
+
+    fn mod_switch_to(&self, a: &Ciphertext, parms_id: &[u64; 4]) -> Result<Ciphertext> {
+        self.0.mod_switch_to(a, parms_id)
+    }
+
+    fn mod_switch_to_inplace(&self, a: &mut Ciphertext, parms_id: &[u64; 4]) -> Result<()> {
+        self.0.mod_switch_to_inplace(a, parms_id)
+    }
+
+    fn mod_switch_to_plaintext(&self, a: &Plaintext, parms_id: &[u64; 4]) -> Result<Plaintext> {
+        self.0.mod_switch_to_plaintext(a, parms_id)
+    }
+
+    fn exponentiate(
+        &self,
+        a: &Ciphertext,
+        exponent: u64,
+        relin_keys: &RelinearizationKeys,
+    ) -> Result<Ciphertext> {
+        self.0.exponentiate(a, exponent, relin_keys)
+    }
+
+    fn exponentiate_inplace(
+        &self,
+        a: &Ciphertext,
+        exponent: u64,
+        relin_keys: &RelinearizationKeys,
+    ) -> Result<()> {
+        self.0.exponentiate_inplace(a, exponent, relin_keys)
+    }
+
+    fn add_plain(&self, a: &Ciphertext, b: &Plaintext) -> Result<Ciphertext> {
+        self.0.add_plain(a, b)
+    }
+
+    fn add_plain_inplace(&self, a: &mut Ciphertext, b: &Plaintext) -> Result<()> {
+        self.0.add_plain_inplace(a, b)
+    }
+
+    fn sub_plain(&self, a: &Ciphertext, b: &Plaintext) -> Result<Ciphertext> {
+        self.0.sub_plain(a, b)
+    }
+
+    fn sub_plain_inplace(&self, a: &mut Ciphertext, b: &Plaintext) -> Result<()> {
+        self.0.sub_plain_inplace(a, b)
+    }
+
+    fn multiply_plain(&self, a: &Ciphertext, b: &Plaintext) -> Result<Ciphertext> {
+        self.0.multiply_plain(a, b)
+    }
+
+    fn multiply_plain_inplace(&self, a: &mut Ciphertext, b: &Plaintext) -> Result<()> {
+        self.0.multiply_plain_inplace(a, b)
+    }
+
+    fn relinearize_inplace(
+        &self,
+        a: &mut Ciphertext,
+        relin_keys: &RelinearizationKeys,
+    ) -> Result<()> {
+        self.0.relinearize_inplace(a, relin_keys)
+    }
+
+    fn relinearize(&self, a: &Ciphertext, relin_keys: &RelinearizationKeys) -> Result<Ciphertext> {
+        self.0.relinearize(a, relin_keys)
+    }
+
+    fn rotate_rows(
+        &self,
+        a: &Ciphertext,
+        steps: i32,
+        galois_keys: &GaloisKeys,
+    ) -> Result<Ciphertext> {
+        self.0.rotate_rows(a, steps, galois_keys)
+    }
+
+    fn rotate_rows_inplace(
+        &self,
+        a: &Ciphertext,
+        steps: i32,
+        galois_keys: &GaloisKeys,
+    ) -> Result<()> {
+        self.0.rotate_rows_inplace(a, steps, galois_keys)
+    }
+
+    fn rotate_columns(&self, a: &Ciphertext, galois_keys: &GaloisKeys) -> Result<Ciphertext> {
+        self.0.rotate_columns(a, galois_keys)
+    }
+
+    fn rotate_columns_inplace(&self, a: &Ciphertext, galois_keys: &GaloisKeys) -> Result<()> {
+        self.0.rotate_columns_inplace(a, galois_keys)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+
+    fn run_bgv_test<F>(test: F)
+    where
+        F: FnOnce(Decryptor, BFVEncoder, Encryptor<SymAsym>, BGVEvaluator, KeyGenerator),
+    {
+        let params = BgvEncryptionParametersBuilder::new()
+            .set_poly_modulus_degree(8192)
+            .set_coefficient_modulus(
+                CoefficientModulus::create(8192, &[50, 30, 30, 50, 50]).unwrap(),
+            )
+            .set_plain_modulus(PlainModulus::batching(8192, 32).unwrap())
+            .build()
+            .unwrap();
+
+        let ctx = Context::new(&params, true, SecurityLevel::TC128).unwrap();
+        let gen = KeyGenerator::new(&ctx).unwrap();
+
+        let encoder = BFVEncoder::new(&ctx).unwrap();
+
+        let public_key = gen.create_public_key();
+        let secret_key = gen.secret_key();
+
+        let encryptor =
+            Encryptor::with_public_and_secret_key(&ctx, &public_key, &secret_key).unwrap();
+        let decryptor = Decryptor::new(&ctx, &secret_key).unwrap();
+        let evaluator = BGVEvaluator::new(&ctx).unwrap();
+
+        test(decryptor, encoder, encryptor, evaluator, gen);
+    }
+
+    fn make_vec(encoder: &BFVEncoder) -> Vec<i64> {
+        let mut data = vec![];
+
+        for i in 0..encoder.get_slot_count() {
+            data.push(encoder.get_slot_count() as i64 / 2i64 - i as i64)
+        }
+
+        data
+    }
+
+    #[test]
+    fn can_add() {
+        run_bgv_test(|decryptor, encoder, encryptor, evaluator, _| {
+            let a = make_vec(&encoder);
+            let b = make_vec(&encoder);
+            let a_p = encoder.encode_signed(&a).unwrap();
+            let b_p = encoder.encode_signed(&b).unwrap();
+            let a_c = encryptor.encrypt(&a_p).unwrap();
+            let b_c = encryptor.encrypt(&b_p).unwrap();
+
+            let c_c = evaluator.add(&a_c, &b_c).unwrap();
+
+            let c_p = decryptor.decrypt(&c_c).unwrap();
+            let c = encoder.decode_signed(&c_p).unwrap();
+
+            assert_eq!(a.len(), c.len());
+
+            for i in 0..a.len() {
+                assert_eq!(c[i], a[i] + b[i]);
+            }
+        });
+    }
+
+    #[test]
+    fn can_multiply_and_relinearize() {
+        run_bgv_test(|decryptor, encoder, encryptor, evaluator, keygen| {
+            let relin_keys = keygen.create_relinearization_keys().unwrap();
+
+            let a = make_vec(&encoder);
+            let b = make_vec(&encoder);
+            let a_p = encoder.encode_signed(&a).unwrap();
+            let b_p = encoder.encode_signed(&b).unwrap();
+            let a_c = encryptor.encrypt(&a_p).unwrap();
+            let b_c = encryptor.encrypt(&b_p).unwrap();
+
+            let c_c = evaluator.multiply(&a_c, &b_c).unwrap();
+            let c_c = evaluator.relinearize(&c_c, &relin_keys).unwrap();
+
+            let c_p = decryptor.decrypt(&c_c).unwrap();
+            let c = encoder.decode_signed(&c_p).unwrap();
+
+            assert_eq!(a.len(), c.len());
+
+            for i in 0..a.len() {
+                assert_eq!(c[i], a[i] * b[i]);
+            }
+        });
+    }
+
+    #[test]
+    fn can_mod_switch_after_multiply() {
+        run_bgv_test(|decryptor, encoder, encryptor, evaluator, keygen| {
+            let relin_keys = keygen.create_relinearization_keys().unwrap();
+
+            let a = make_vec(&encoder);
+            let a_p = encoder.encode_signed(&a).unwrap();
+            let a_c = encryptor.encrypt(&a_p).unwrap();
+
+            let c_c = evaluator.multiply(&a_c, &a_c).unwrap();
+            let c_c = evaluator.relinearize(&c_c, &relin_keys).unwrap();
+
+            let budget_before = decryptor.invariant_noise_budget(&c_c).unwrap();
+
+            let c_c = evaluator.mod_switch_to_next(&c_c).unwrap();
+
+            // In BGV, switching the modulus scales the noise down with the
+            // modulus, so we lose (at most) a few bits of budget rather than
+            // the bits of the dropped prime.
+            let budget_after = decryptor.invariant_noise_budget(&c_c).unwrap();
+            assert!(budget_before.saturating_sub(budget_after) <= 3);
+
+            let c_p = decryptor.decrypt(&c_c).unwrap();
+            let c = encoder.decode_signed(&c_p).unwrap();
+
+            for i in 0..a.len() {
+                assert_eq!(c[i], a[i] * a[i]);
+            }
+        });
+    }
+}
@@ -0,0 +1,408 @@
+use std::ptr::null_mut;
+
+use crate::bindgen;
+use crate::error::convert_seal_error;
+use crate::evaluator_base::EvaluatorBase;
+use crate::{Ciphertext, Context, Evaluator, GaloisKeys, Plaintext, RelinearizationKeys, Result};
+
+/**
+ * An evaluator that contains additional operations specific to the CKKS scheme.
+ *
+ * # Remarks
+ * Multiplying CKKS ciphertexts multiplies their scales, so one should call
+ * [`Evaluator::rescale_to_next`] after each multiplication to divide the
+ * scale back down. Binary operations require both operands to be at the same
+ * level of the modulus chain and to have (nearly) the same scale; use
+ * [`Evaluator::mod_switch_to`] to bring a ciphertext down to another's
+ * level.
+ *
+ * CKKS plaintexts are vectors of N/2 slots rather than 2-by-(N/2) matrices, so
+ * [`Evaluator::rotate_rows`] rotates the whole vector and
+ * [`Evaluator::rotate_columns`] computes the complex conjugate of each slot,
+ * which is the identity on real values.
+ */
+pub struct CKKSEvaluator(EvaluatorBase);
+
+impl std::ops::Deref for CKKSEvaluator {
+    type Target = EvaluatorBase;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl CKKSEvaluator {
+    /**
+     * Creates a CKKSEvaluator instance initialized with the specified Context.
+     * * `ctx` - The context.
+     */
+    pub fn new(ctx: &Context) -> Result<CKKSEvaluator> {
+        Ok(CKKSEvaluator(EvaluatorBase::new(ctx)?))
+    }
+}
+
+impl Evaluator for CKKSEvaluator {
+    fn negate_inplace(&self, a: &mut Ciphertext) -> Result<()> {
+        self.0.negate_inplace(a)
+    }
+
+    fn negate(&self, a: &Ciphertext) -> Result<Ciphertext> {
+        self.0.negate(a)
+    }
+
+    fn add_inplace(&self, a: &mut Ciphertext, b: &Ciphertext) -> Result<()> {
+        self.0.add_inplace(a, b)
+    }
+
+    fn add(&self, a: &Ciphertext, b: &Ciphertext) -> Result<Ciphertext> {
+        self.0.add(a, b)
+    }
+
+    fn add_many(&self, a: &[Ciphertext]) -> Result<Ciphertext> {
+        self.0.add_many(a)
+    }
+
+    fn multiply_many(
+        &self,
+        a: &[Ciphertext],
+        relin_keys: &RelinearizationKeys,
+    ) -> Result<Ciphertext> {
+        self.0.multiply_many(a, relin_keys)
+    }
+
+    fn sub_inplace(&self, a: &mut Ciphertext, b: &Ciphertext) -> Result<()> {
+        self.0.sub_inplace(a, b)
+    }
+
+    fn sub(&self, a: &Ciphertext, b: &Ciphertext) -> Result<Ciphertext> {
+        self.0.sub(a, b)
+    }
+
+    fn multiply_inplace(&self, a: &mut Ciphertext, b: &Ciphertext) -> Result<()> {
+        self.0.multiply_inplace(a, b)
+    }
+
+    fn multiply(&self, a: &Ciphertext, b: &Ciphertext) -> Result<Ciphertext> {
+        self.0.multiply(a, b)
+    }
+
+    fn square_inplace(&self, a: &mut Ciphertext) -> Result<()> {
+        self.0.square_inplace(a)
+    }
+
+    fn square(&self, a: &Ciphertext) -> Result<Ciphertext> {
+        self.0.square(a)
+    }
+
+    fn mod_switch_to_next(&self, a: &Ciphertext) -> Result<Ciphertext> {
+        self.0.mod_switch_to_next(a)
+    }
+
+    fn mod_switch_to_next_inplace(&self, a: &Ciphertext) -> Result<()> {
+        self.0.mod_switch_to_next_inplace(a)
+    }
+
+    fn mod_switch_to_next_plaintext(&self, a: &Plaintext) -> Result<Plaintext> {
+        self.0.mod_switch_to_next_plaintext(a)
+    }
+
+    fn mod_switch_to_next_inplace_plaintext(&self, a: &Plaintext) -> Result<()> {
+        self.0.mod_switch_to_next_inplace_plaintext(a)
+    }
+
+    fn rescale_to_next(&self, a: &Ciphertext) -> Result<Ciphertext> {
+        self.0.rescale_to_next(a)
+    }
+
+    fn rescale_to_next_inplace(&self, a: &mut Ciphertext) -> Result<()> {
+        self.0.rescale_to_next_inplace(a)
+    }
+
+    fn mod_switch_to(&self, a: &Ciphertext, parms_id: &[u64; 4]) -> Result<Ciphertext> {
+        self.0.mod_switch_to(a, parms_id)
+    }
+
+    fn mod_switch_to_inplace(&self, a: &mut Ciphertext, parms_id: &[u64; 4]) -> Result<()> {
+        self.0.mod_switch_to_inplace(a, parms_id)
+    }
+
+    fn mod_switch_to_plaintext(&self, a: &Plaintext, parms_id: &[u64; 4]) -> Result<Plaintext> {
+        self.0.mod_switch_to_plaintext(a, parms_id)
+    }
+
+    fn exponentiate(
+        &self,
+        a: &Ciphertext,
+        exponent: u64,
+        relin_keys: &RelinearizationKeys,
+    ) -> Result<Ciphertext> {
+        self.0.exponentiate(a, exponent, relin_keys)
+    }
+
+    fn exponentiate_inplace(
+        &self,
+        a: &Ciphertext,
+        exponent: u64,
+        relin_keys: &RelinearizationKeys,
+    ) -> Result<()> {
+        self.0.exponentiate_inplace(a, exponent, relin_keys)
+    }
+
+    fn add_plain(&self, a: &Ciphertext, b: &Plaintext) -> Result<Ciphertext> {
+        self.0.add_plain(a, b)
+    }
+
+    fn add_plain_inplace(&self, a: &mut Ciphertext, b: &Plaintext) -> Result<()> {
+        self.0.add_plain_inplace(a, b)
+    }
+
+    fn sub_plain(&self, a: &Ciphertext, b: &Plaintext) -> Result<Ciphertext> {
+        self.0.sub_plain(a, b)
+    }
+
+    fn sub_plain_inplace(&self, a: &mut Ciphertext, b: &Plaintext) -> Result<()> {
+        self.0.sub_plain_inplace(a, b)
+    }
+
+    fn multiply_plain(&self, a: &Ciphertext, b: &Plaintext) -> Result<Ciphertext> {
+        self.0.multiply_plain(a, b)
+    }
+
+    fn multiply_plain_inplace(&self, a: &mut Ciphertext, b: &Plaintext) -> Result<()> {
+        self.0.multiply_plain_inplace(a, b)
+    }
+
+    fn relinearize_inplace(
+        &self,
+        a: &mut Ciphertext,
+        relin_keys: &RelinearizationKeys,
+    ) -> Result<()> {
+        self.0.relinearize_inplace(a, relin_keys)
+    }
+
+    fn relinearize(&self, a: &Ciphertext, relin_keys: &RelinearizationKeys) -> Result<Ciphertext> {
+        self.0.relinearize(a, relin_keys)
+    }
+
+    fn rotate_rows(
+        &self,
+        a: &Ciphertext,
+        steps: i32,
+        galois_keys: &GaloisKeys,
+    ) -> Result<Ciphertext> {
+        let out = Ciphertext::new()?;
+
+        convert_seal_error(unsafe {
+            bindgen::Evaluator_RotateVector(
+                self.0.get_handle(),
+                a.get_handle(),
+                steps,
+                galois_keys.get_handle(),
+                out.get_handle(),
+                null_mut(),
+            )
+        })?;
+
+        Ok(out)
+    }
+
+    fn rotate_rows_inplace(
+        &self,
+        a: &Ciphertext,
+        steps: i32,
+        galois_keys: &GaloisKeys,
+    ) -> Result<()> {
+        convert_seal_error(unsafe {
+            bindgen::Evaluator_RotateVector(
+                self.0.get_handle(),
+                a.get_handle(),
+                steps,
+                galois_keys.get_handle(),
+                a.get_handle(),
+                null_mut(),
+            )
+        })?;
+
+        Ok(())
+    }
+
+    fn rotate_columns(&self, a: &Ciphertext, galois_keys: &GaloisKeys) -> Result<Ciphertext> {
+        let out = Ciphertext::new()?;
+
+        convert_seal_error(unsafe {
+            bindgen::Evaluator_ComplexConjugate(
+                self.0.get_handle(),
+                a.get_handle(),
+                galois_keys.get_handle(),
+                out.get_handle(),
+                null_mut(),
+            )
+        })?;
+
+        Ok(out)
+    }
+
+    fn rotate_columns_inplace(&self, a: &Ciphertext, galois_keys: &GaloisKeys) -> Result<()> {
+        convert_seal_error(unsafe {
+            bindgen::Evaluator_ComplexConjugate(
+                self.0.get_handle(),
+                a.get_handle(),
+                galois_keys.get_handle(),
+                a.get_handle(),
+                null_mut(),
+            )
+        })?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+
+    const SCALE: f64 = 1099511627776f64; // 2^40
+
+    fn run_ckks_test<F>(test: F)
+    where
+        F: FnOnce(Context, Decryptor, CKKSEncoder, Encryptor<SymAsym>, CKKSEvaluator, KeyGenerator),
+    {
+        let params = CkksEncryptionParametersBuilder::new()
+            .set_poly_modulus_degree(8192)
+            .set_coefficient_modulus(CoefficientModulus::create(8192, &[60, 40, 40, 60]).unwrap())
+            .build()
+            .unwrap();
+
+        let ctx = Context::new(&params, true, SecurityLevel::TC128).unwrap();
+        let gen = KeyGenerator::new(&ctx).unwrap();
+
+        let encoder = CKKSEncoder::new(&ctx).unwrap();
+
+        let public_key = gen.create_public_key();
+        let secret_key = gen.secret_key();
+
+        let encryptor =
+            Encryptor::with_public_and_secret_key(&ctx, &public_key, &secret_key).unwrap();
+        let decryptor = Decryptor::new(&ctx, &secret_key).unwrap();
+        let evaluator = CKKSEvaluator::new(&ctx).unwrap();
+
+        test(ctx, decryptor, encoder, encryptor, evaluator, gen);
+    }
+
+    fn make_vec(encoder: &CKKSEncoder) -> Vec<f64> {
+        (0..encoder.get_slot_count())
+            .map(|i| i as f64 / 1000. - 1.)
+            .collect()
+    }
+
+    fn assert_close(a: &[f64], b: &[f64]) {
+        assert_eq!(a.len(), b.len());
+
+        for (x, y) in a.iter().zip(b.iter()) {
+            assert!((x - y).abs() < 1e-4, "{} != {}", x, y);
+        }
+    }
+
+    #[test]
+    fn can_add() {
+        run_ckks_test(|ctx, decryptor, encoder, encryptor, evaluator, _| {
+            let a = make_vec(&encoder);
+            let a_p = encoder
+                .encode_f64(&a, &ctx.get_first_parms_id(), SCALE)
+                .unwrap();
+            let a_c = encryptor.encrypt(&a_p).unwrap();
+
+            let c_c = evaluator.add(&a_c, &a_c).unwrap();
+
+            let c_p = decryptor.decrypt(&c_c).unwrap();
+            let c = encoder.decode_f64(&c_p).unwrap();
+
+            let expected = a.iter().map(|x| x + x).collect::<Vec<_>>();
+
+            assert_close(&c, &expected);
+        });
+    }
+
+    #[test]
+    fn can_multiply_and_rescale() {
+        run_ckks_test(|ctx, decryptor, encoder, encryptor, evaluator, keygen| {
+            let relin_keys = keygen.create_relinearization_keys().unwrap();
+
+            let a = make_vec(&encoder);
+            let a_p = encoder
+                .encode_f64(&a, &ctx.get_first_parms_id(), SCALE)
+                .unwrap();
+            let a_c = encryptor.encrypt(&a_p).unwrap();
+
+            let c_c = evaluator.multiply(&a_c, &a_c).unwrap();
+            let c_c = evaluator.relinearize(&c_c, &relin_keys).unwrap();
+
+            assert_eq!(c_c.get_scale(), SCALE * SCALE);
+
+            let c_c = evaluator.rescale_to_next(&c_c).unwrap();
+
+            assert_eq!(c_c.coeff_modulus_size(), a_c.coeff_modulus_size() - 1);
+            assert!((c_c.get_scale().log2() - 40.).abs() < 0.01);
+
+            let c_p = decryptor.decrypt(&c_c).unwrap();
+            let c = encoder.decode_f64(&c_p).unwrap();
+
+            let expected = a.iter().map(|x| x * x).collect::<Vec<_>>();
+
+            assert_close(&c, &expected);
+        });
+    }
+
+    #[test]
+    fn can_mod_switch_to_match_levels() {
+        run_ckks_test(|ctx, decryptor, encoder, encryptor, evaluator, keygen| {
+            let relin_keys = keygen.create_relinearization_keys().unwrap();
+
+            let a = make_vec(&encoder);
+            let a_p = encoder
+                .encode_f64(&a, &ctx.get_first_parms_id(), SCALE)
+                .unwrap();
+            let a_c = encryptor.encrypt(&a_p).unwrap();
+
+            let mut sq = evaluator.multiply(&a_c, &a_c).unwrap();
+            evaluator.relinearize_inplace(&mut sq, &relin_keys).unwrap();
+            evaluator.rescale_to_next_inplace(&mut sq).unwrap();
+            sq.set_scale(SCALE).unwrap();
+
+            let a_c = evaluator.mod_switch_to(&a_c, &sq.get_parms_id()).unwrap();
+
+            let c_c = evaluator.add(&sq, &a_c).unwrap();
+
+            let c_p = decryptor.decrypt(&c_c).unwrap();
+            let c = encoder.decode_f64(&c_p).unwrap();
+
+            let expected = a.iter().map(|x| x * x + x).collect::<Vec<_>>();
+
+            assert_close(&c, &expected);
+        });
+    }
+
+    #[test]
+    fn can_rotate_vector() {
+        run_ckks_test(|ctx, decryptor, encoder, encryptor, evaluator, keygen| {
+            let galois_keys = keygen.create_galois_keys().unwrap();
+
+            let a = make_vec(&encoder);
+            let a_p = encoder
+                .encode_f64(&a, &ctx.get_first_parms_id(), SCALE)
+                .unwrap();
+            let a_c = encryptor.encrypt(&a_p).unwrap();
+
+            let c_c = evaluator.rotate_rows(&a_c, 1, &galois_keys).unwrap();
+
+            let c_p = decryptor.decrypt(&c_c).unwrap();
+            let c = encoder.decode_f64(&c_p).unwrap();
+
+            let mut expected = a.clone();
+            expected.rotate_left(1);
+
+            assert_close(&c, &expected);
+        });
+    }
+}
@@ -3,6 +3,7 @@ use std::os::raw::c_int;
 use std::ptr::null_mut;
 
 use crate::bindgen;
+use crate::data_structures::export_u64s;
 use crate::error::*;
 use crate::EncryptionParameters;
 use crate::SecurityLevel;
@@ -105,6 +106,62 @@ impl Context {
     pub fn get_handle(&self) -> *mut c_void {
         self.handle
     }
+
+    /**
+     * Returns the ParmsId of the first set of encryption parameters in the
+     * modulus switching chain. Freshly encrypted ciphertexts and newly
+     * encoded plaintexts use these parameters.
+     */
+    pub fn get_first_parms_id(&self) -> [u64; 4] {
+        let mut parms_id = [0u64; 4];
+
+        convert_seal_error(unsafe {
+            bindgen::SEALContext_FirstParmsId(self.handle, parms_id.as_mut_ptr())
+        })
+        .expect("Internal error in Context::get_first_parms_id().");
+
+        parms_id
+    }
+
+    /**
+     * Returns the ParmsId of the last set of encryption parameters in the
+     * modulus switching chain.
+     */
+    pub fn get_last_parms_id(&self) -> [u64; 4] {
+        let mut parms_id = [0u64; 4];
+
+        convert_seal_error(unsafe {
+            bindgen::SEALContext_LastParmsId(self.handle, parms_id.as_mut_ptr())
+        })
+        .expect("Internal error in Context::get_last_parms_id().");
+
+        parms_id
+    }
+
+    /**
+     * Creates another handle to this context. The handles share SEAL's
+     * precomputed data, so this doesn't redo the pre-computations in
+     * [`Context::new`].
+     */
+    pub(crate) fn try_clone(&self) -> Result<Self> {
+        let mut handle: *mut c_void = null_mut();
+
+        convert_seal_error(unsafe { bindgen::SEALContext_Copy(self.handle, &mut handle) })?;
+
+        Ok(Context { handle })
+    }
+
+    /**
+     * Samples a fresh polynomial from the error distribution SEAL uses to
+     * encrypt and generate keys. The result is in NTT and RNS form under the
+     * key level modulus (i.e. all moduli, including the special prime), like
+     * the data of a [`SecretKey`](crate::SecretKey).
+     */
+    pub fn sample_error(&self) -> Result<Vec<u64>> {
+        export_u64s(|count, data| unsafe {
+            bindgen::SEALContext_SampleError(self.handle, count, data)
+        })
+    }
 }
 
 impl Drop for Context {
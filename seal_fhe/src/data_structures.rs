@@ -1,4 +1,5 @@
 use std::ffi::c_void;
+use std::os::raw::c_long;
 use std::ptr::null_mut;
 
 use crate::bindgen;
@@ -311,6 +312,22 @@ impl Drop for PolynomialArray {
     }
 }
 
+/**
+ * Calls a SEAL function that exports an array of `u64`s, first with a null
+ * pointer to get the array's length and then with a buffer of that length.
+ */
+pub(crate) fn export_u64s(export: impl Fn(*mut u64, *mut u64) -> c_long) -> Result<Vec<u64>> {
+    let mut count: u64 = 0;
+
+    convert_seal_error(export(&mut count, null_mut()))?;
+
+    let mut data = vec![0; count as usize];
+
+    convert_seal_error(export(&mut count, data.as_mut_ptr()))?;
+
+    Ok(data)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
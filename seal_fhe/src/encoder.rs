@@ -282,6 +282,129 @@ impl Default for BFVScalarEncoder {
     }
 }
 
+/**
+ * Encodes vectors of real numbers into CKKS plaintexts. If the polynomial
+ * modulus degree is N, a plaintext holds N/2 slots. Each value is multiplied
+ * by a scale factor and rounded to an integer, so encoding and decoding
+ * values is only approximate; the scale determines the precision retained.
+ *
+ * # Mathematical Background
+ * CKKS uses the canonical embedding of `Z[X]/(X^N+1)` into `C^(N/2)` to map
+ * a vector of complex numbers to a polynomial with real coefficients, which
+ * is then multiplied by the scale and rounded. Because the embedding is a
+ * ring isomorphism, adding and multiplying plaintext polynomials adds and
+ * multiplies the encoded vectors slot-wise. Multiplication also multiplies
+ * the scales, which is why CKKS ciphertexts are rescaled after each
+ * multiplication.
+ *
+ * # Valid Parameters
+ * To construct a CKKSEncoder the user must provide a Context created from
+ * CKKS encryption parameters.
+ */
+pub struct CKKSEncoder {
+    handle: *mut c_void,
+}
+
+unsafe impl Sync for CKKSEncoder {}
+unsafe impl Send for CKKSEncoder {}
+
+impl CKKSEncoder {
+    /**
+     * Creates a CKKSEncoder for the given context.
+     *
+     * * `ctx` - The Context
+     */
+    pub fn new(ctx: &Context) -> Result<Self> {
+        let mut handle: *mut c_void = null_mut();
+
+        convert_seal_error(unsafe { bindgen::CKKSEncoder_Create(ctx.get_handle(), &mut handle) })?;
+
+        Ok(Self { handle })
+    }
+
+    /**
+     * Encodes a vector of `f64` values into a plaintext valid for the encryption
+     * parameters with the given ParmsId. Unused slots are set to zero.
+     *
+     * * `data` - The values to encode. Must have at most N/2 elements.
+     * * `parms_id` - The encryption parameters the plaintext should be valid
+     *   for. Use [`Context::get_first_parms_id`] for values you intend to
+     *   encrypt and the ciphertext's ParmsId for plaintext operands.
+     * * `scale` - The scale to multiply values by before rounding.
+     */
+    pub fn encode_f64(&self, data: &[f64], parms_id: &[u64; 4], scale: f64) -> Result<Plaintext> {
+        let plaintext = Plaintext::new()?;
+
+        // We pinky promise SEAL won't mutate data or parms_id, the C bindings
+        // just aren't const correct.
+        convert_seal_error(unsafe {
+            bindgen::CKKSEncoder_Encode1(
+                self.handle,
+                data.len() as u64,
+                data.as_ptr() as *mut f64,
+                parms_id.as_ptr() as *mut u64,
+                scale,
+                plaintext.get_handle(),
+                null_mut(),
+            )
+        })?;
+
+        Ok(plaintext)
+    }
+
+    /**
+     * Decodes a plaintext into its N/2 `f64` slot values. Since CKKS is
+     * approximate, the values will differ slightly from those encoded.
+     *
+     * * `plaintext` - The plaintext to decode
+     */
+    pub fn decode_f64(&self, plaintext: &Plaintext) -> Result<Vec<f64>> {
+        let mut data = Vec::with_capacity(self.get_slot_count());
+        let data_ptr = data.as_mut_ptr();
+        let mut size: u64 = 0;
+
+        convert_seal_error(unsafe {
+            bindgen::CKKSEncoder_Decode1(
+                self.handle,
+                plaintext.get_handle(),
+                &mut size,
+                data_ptr,
+                null_mut(),
+            )
+        })?;
+
+        if data.capacity() < size as usize {
+            panic!("Allocation overflow CKKSEncoder::decode_f64");
+        }
+
+        unsafe {
+            data.set_len(size as usize);
+        }
+
+        Ok(data)
+    }
+
+    /**
+     * Returns the number of slots this encoder packs into a plaintext,
+     * which is half the polynomial modulus degree.
+     */
+    pub fn get_slot_count(&self) -> usize {
+        let mut count: u64 = 0;
+
+        convert_seal_error(unsafe { bindgen::CKKSEncoder_SlotCount(self.handle, &mut count) })
+            .expect("Internal error in CKKSEncoder::get_slot_count().");
+
+        count as usize
+    }
+}
+
+impl Drop for CKKSEncoder {
+    fn drop(&mut self) {
+        convert_seal_error(unsafe { bindgen::CKKSEncoder_Destroy(self.handle) })
+            .expect("Internal error in CKKSEncoder::drop.");
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::*;
@@ -393,4 +516,33 @@ mod tests {
 
         assert_eq!(encoder.decode_signed(&p).unwrap(), 42);
     }
+
+    #[test]
+    fn can_encode_and_decode_ckks() {
+        let params = CkksEncryptionParametersBuilder::new()
+            .set_poly_modulus_degree(8192)
+            .set_coefficient_modulus(CoefficientModulus::create(8192, &[60, 40, 40, 60]).unwrap())
+            .build()
+            .unwrap();
+
+        let ctx = Context::new(&params, true, SecurityLevel::TC128).unwrap();
+
+        let encoder = CKKSEncoder::new(&ctx).unwrap();
+
+        assert_eq!(encoder.get_slot_count(), 4096);
+
+        let data = (0..4096).map(|x| x as f64 * 0.25 - 7.).collect::<Vec<_>>();
+
+        let plaintext = encoder
+            .encode_f64(&data, &ctx.get_first_parms_id(), 2f64.powi(40))
+            .unwrap();
+
+        assert_eq!(plaintext.get_scale(), 2f64.powi(40));
+
+        let decoded = encoder.decode_f64(&plaintext).unwrap();
+
+        for (a, b) in data.iter().zip(decoded.iter()) {
+            assert!((a - b).abs() < 1e-6);
+        }
+    }
 }
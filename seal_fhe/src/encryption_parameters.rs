@@ -23,6 +23,9 @@ pub enum SchemeType {
 
     /// Cheon-Kim-Kim-Song scheme
     Ckks = 0x2,
+
+    /// Brakerski-Gentry-Vaikuntanathan scheme
+    Bgv = 0x3,
 }
 
 impl SchemeType {
@@ -31,6 +34,7 @@ impl SchemeType {
             0x0 => SchemeType::None,
             0x1 => SchemeType::Bfv,
             0x2 => SchemeType::Ckks,
+            0x3 => SchemeType::Bgv,
             _ => panic!("Illegal scheme type"),
         }
     }
@@ -38,7 +42,8 @@ impl SchemeType {
 
 /**
  * An immutable collection of parameters that defines an encryption scheme.
- * Use either the CKKSBuilder or BFVBuilder to create one of these. Once created,
+ * Use the BfvEncryptionParametersBuilder, BgvEncryptionParametersBuilder, or
+ * CkksEncryptionParametersBuilder to create one of these. Once created,
  * these objects are effectively immutable.
  *
  * Picking appropriate encryption parameters is essential to enable a particular
@@ -268,54 +273,232 @@ impl BfvEncryptionParametersBuilder {
      * Validate the parameter choices and return the encryption parameters.
      */
     pub fn build(self) -> Result<EncryptionParameters, Error> {
-        let params = EncryptionParameters::new(SchemeType::Bfv)?;
+        build_params_with_plain_modulus(
+            SchemeType::Bfv,
+            self.poly_modulus_degree,
+            self.coefficient_modulus,
+            self.plain_modulus,
+        )
+    }
+}
 
-        convert_seal_error(unsafe {
-            bindgen::EncParams_SetPolyModulusDegree(
-                params.handle,
-                self.poly_modulus_degree.ok_or(Error::DegreeNotSet)?,
-            )
-        })?;
-
-        match self.coefficient_modulus {
-            CoefficientModulusType::NotSet => return Err(Error::CoefficientModulusNotSet),
-            CoefficientModulusType::Modulus(m) => {
-                convert_seal_error(unsafe {
-                    let modulus_ref = m
-                        .iter()
-                        .map(|m| m.get_handle())
-                        .collect::<Vec<*mut c_void>>();
-                    let modulus_ptr = modulus_ref.as_ptr() as *mut *mut c_void;
-
-                    bindgen::EncParams_SetCoeffModulus(params.handle, m.len() as u64, modulus_ptr)
-                })?;
-            }
-        };
+impl Default for BfvEncryptionParametersBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
-        match self.plain_modulus {
-            PlainModulusType::NotSet => return Err(Error::PlainModulusNotSet),
-            PlainModulusType::Constant(p) => {
-                convert_seal_error(unsafe {
-                    bindgen::EncParams_SetPlainModulus2(params.handle, p)
-                })?;
-            }
-            PlainModulusType::Modulus(m) => {
-                convert_seal_error(unsafe {
-                    bindgen::EncParams_SetPlainModulus1(params.handle, m.get_handle())
-                })?;
-            }
-        };
+/**
+ * Represents a builder that sets up and creates encryption scheme parameters
+ * for the BGV scheme. The parameters (most importantly PolyModulus, CoeffModulus,
+ * PlainModulus) significantly affect the performance, capabilities, and security
+ * of the encryption scheme.
+ *
+ * # Remarks
+ * BGV plaintexts are the same polynomials in Z_T[x]/(X^N+1) as BFV plaintexts,
+ * so the encoders that work with BFV also work with BGV. Unlike BFV, noise in a
+ * BGV ciphertext grows with the ciphertext modulus, so one should switch to the
+ * next modulus in the chain after multiplications to keep noise under control.
+ */
+pub struct BgvEncryptionParametersBuilder {
+    poly_modulus_degree: Option<u64>,
+    coefficient_modulus: CoefficientModulusType,
+    plain_modulus: PlainModulusType,
+}
 
-        Ok(params)
+impl BgvEncryptionParametersBuilder {
+    /**
+     * Creates a new builder.
+     */
+    pub fn new() -> Self {
+        Self {
+            poly_modulus_degree: None,
+            coefficient_modulus: CoefficientModulusType::NotSet,
+            plain_modulus: PlainModulusType::NotSet,
+        }
+    }
+
+    /**
+     * Set the degree of the polynomial used in the BGV scheme. Genrally,
+     * larger values provide more security and noise margin at the expense
+     * of performance.
+     */
+    pub fn set_poly_modulus_degree(mut self, degree: u64) -> Self {
+        self.poly_modulus_degree = Some(degree);
+        self
+    }
+
+    /**
+     * Sets the coefficient modulus parameter. See
+     * [`BfvEncryptionParametersBuilder::set_coefficient_modulus`]. In BGV,
+     * each prime after the first allows one modulus switch.
+     */
+    pub fn set_coefficient_modulus(mut self, modulus: Vec<Modulus>) -> Self {
+        self.coefficient_modulus = CoefficientModulusType::Modulus(modulus);
+        self
+    }
+
+    /**
+     * Set the plaintext modulus to a fixed size. Not recommended.
+     * Ideally, create a PlainModulus to set up batching and call
+     * set_plain_modulus.
+     */
+    pub fn set_plain_modulus_u64(mut self, modulus: u64) -> Self {
+        self.plain_modulus = PlainModulusType::Constant(modulus);
+        self
+    }
+
+    /**
+     * Set the plaintext modulus. This method enables batching, use
+     * `PlainModulus::batching()` to create a suitable modulus chain.
+     */
+    pub fn set_plain_modulus(mut self, modulus: Modulus) -> Self {
+        self.plain_modulus = PlainModulusType::Modulus(modulus);
+        self
+    }
+
+    /**
+     * Validate the parameter choices and return the encryption parameters.
+     */
+    pub fn build(self) -> Result<EncryptionParameters, Error> {
+        build_params_with_plain_modulus(
+            SchemeType::Bgv,
+            self.poly_modulus_degree,
+            self.coefficient_modulus,
+            self.plain_modulus,
+        )
     }
 }
 
-impl Default for BfvEncryptionParametersBuilder {
+impl Default for BgvEncryptionParametersBuilder {
     fn default() -> Self {
         Self::new()
     }
 }
 
+/**
+ * Represents a builder that sets up and creates encryption scheme parameters
+ * for the CKKS scheme. The parameters (most importantly PolyModulus and
+ * CoeffModulus) significantly affect the performance, capabilities, and
+ * security of the encryption scheme.
+ *
+ * # Remarks
+ * CKKS has no plaintext modulus. Instead, values are scaled by a scale
+ * factor and rounded when encoded. Each prime in the coefficient modulus
+ * except the first and last (special) ones allows one rescale, so the
+ * middle primes should be about as large as the scale you intend to use.
+ */
+pub struct CkksEncryptionParametersBuilder {
+    poly_modulus_degree: Option<u64>,
+    coefficient_modulus: CoefficientModulusType,
+}
+
+impl CkksEncryptionParametersBuilder {
+    /**
+     * Creates a new builder.
+     */
+    pub fn new() -> Self {
+        Self {
+            poly_modulus_degree: None,
+            coefficient_modulus: CoefficientModulusType::NotSet,
+        }
+    }
+
+    /**
+     * Set the degree of the polynomial used in the CKKS scheme. Genrally,
+     * larger values provide more security and a longer modulus chain at the
+     * expense of performance. CKKS packs half this many values into a
+     * plaintext.
+     */
+    pub fn set_poly_modulus_degree(mut self, degree: u64) -> Self {
+        self.poly_modulus_degree = Some(degree);
+        self
+    }
+
+    /**
+     * Sets the coefficient modulus parameter. See
+     * [`BfvEncryptionParametersBuilder::set_coefficient_modulus`]. Use
+     * [`CoefficientModulus::create`](crate::CoefficientModulus::create) to
+     * choose primes of the sizes your scale requires.
+     */
+    pub fn set_coefficient_modulus(mut self, modulus: Vec<Modulus>) -> Self {
+        self.coefficient_modulus = CoefficientModulusType::Modulus(modulus);
+        self
+    }
+
+    /**
+     * Validate the parameter choices and return the encryption parameters.
+     */
+    pub fn build(self) -> Result<EncryptionParameters, Error> {
+        build_params(
+            SchemeType::Ckks,
+            self.poly_modulus_degree,
+            self.coefficient_modulus,
+        )
+    }
+}
+
+impl Default for CkksEncryptionParametersBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn build_params(
+    scheme: SchemeType,
+    poly_modulus_degree: Option<u64>,
+    coefficient_modulus: CoefficientModulusType,
+) -> Result<EncryptionParameters, Error> {
+    let params = EncryptionParameters::new(scheme)?;
+
+    convert_seal_error(unsafe {
+        bindgen::EncParams_SetPolyModulusDegree(
+            params.handle,
+            poly_modulus_degree.ok_or(Error::DegreeNotSet)?,
+        )
+    })?;
+
+    match coefficient_modulus {
+        CoefficientModulusType::NotSet => return Err(Error::CoefficientModulusNotSet),
+        CoefficientModulusType::Modulus(m) => {
+            convert_seal_error(unsafe {
+                let modulus_ref = m
+                    .iter()
+                    .map(|m| m.get_handle())
+                    .collect::<Vec<*mut c_void>>();
+                let modulus_ptr = modulus_ref.as_ptr() as *mut *mut c_void;
+
+                bindgen::EncParams_SetCoeffModulus(params.handle, m.len() as u64, modulus_ptr)
+            })?;
+        }
+    };
+
+    Ok(params)
+}
+
+fn build_params_with_plain_modulus(
+    scheme: SchemeType,
+    poly_modulus_degree: Option<u64>,
+    coefficient_modulus: CoefficientModulusType,
+    plain_modulus: PlainModulusType,
+) -> Result<EncryptionParameters, Error> {
+    let params = build_params(scheme, poly_modulus_degree, coefficient_modulus)?;
+
+    match plain_modulus {
+        PlainModulusType::NotSet => return Err(Error::PlainModulusNotSet),
+        PlainModulusType::Constant(p) => {
+            convert_seal_error(unsafe { bindgen::EncParams_SetPlainModulus2(params.handle, p) })?;
+        }
+        PlainModulusType::Modulus(m) => {
+            convert_seal_error(unsafe {
+                bindgen::EncParams_SetPlainModulus1(params.handle, m.get_handle())
+            })?;
+        }
+    };
+
+    Ok(params)
+}
+
 impl Drop for EncryptionParameters {
     fn drop(&mut self) {
         unsafe { bindgen::EncParams_Destroy(self.handle) };
@@ -364,4 +547,33 @@ mod tests {
         assert_eq!(modulus[3].value(), 1125899906629633);
         assert_eq!(modulus[4].value(), 1125899906826241);
     }
+
+    #[test]
+    fn can_build_bgv_params() {
+        let params = BgvEncryptionParametersBuilder::new()
+            .set_poly_modulus_degree(8192)
+            .set_coefficient_modulus(
+                CoefficientModulus::bfv_default(8192, SecurityLevel::default()).unwrap(),
+            )
+            .set_plain_modulus(PlainModulus::batching(8192, 20).unwrap())
+            .build()
+            .unwrap();
+
+        assert_eq!(params.get_poly_modulus_degree(), 8192);
+        assert_eq!(params.get_scheme(), SchemeType::Bgv);
+        assert_eq!(params.get_coefficient_modulus().len(), 5);
+    }
+
+    #[test]
+    fn can_build_ckks_params() {
+        let params = CkksEncryptionParametersBuilder::new()
+            .set_poly_modulus_degree(8192)
+            .set_coefficient_modulus(CoefficientModulus::create(8192, &[60, 40, 40, 60]).unwrap())
+            .build()
+            .unwrap();
+
+        assert_eq!(params.get_poly_modulus_degree(), 8192);
+        assert_eq!(params.get_scheme(), SchemeType::Ckks);
+        assert_eq!(params.get_coefficient_modulus().len(), 4);
+    }
 }
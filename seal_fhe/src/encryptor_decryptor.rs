@@ -5,7 +5,7 @@ use std::ptr::null_mut;
 use crate::bindgen;
 use crate::data_structures::PolynomialArray;
 use crate::error::*;
-use crate::{Ciphertext, Context, Plaintext, PublicKey, SecretKey};
+use crate::{Ciphertext, CompactCiphertext, Context, Plaintext, PublicKey, SecretKey};
 
 /// The components to an asymmetric encryption.
 pub struct AsymmetricComponents {
@@ -432,6 +432,33 @@ impl<T: marker::Sym> Encryptor<T> {
         Ok(ciphertext)
     }
 
+    /**
+     * Encrypts a plaintext with the secret key and returns the ciphertext as
+     * a compact serializable object.
+     *
+     * Half of the ciphertext data is pseudo-randomly generated from a seed to
+     * reduce the object size. The resulting serializable object cannot be used
+     * directly and is meant to be serialized for the size reduction to have an
+     * impact.
+     *
+     * * `plainext` - The plaintext to encrypt.
+     */
+    pub fn encrypt_symmetric_compact(&self, plaintext: &Plaintext) -> Result<CompactCiphertext> {
+        let ciphertext = Ciphertext::new()?;
+
+        convert_seal_error(unsafe {
+            bindgen::Encryptor_EncryptSymmetric(
+                self.handle,
+                plaintext.get_handle(),
+                true,
+                ciphertext.get_handle(),
+                null_mut(),
+            )
+        })?;
+
+        Ok(CompactCiphertext(ciphertext))
+    }
+
     /**
      * DO NOT USE THIS FUNCTION IN PRODUCTION: IT PRODUCES DETERMINISTIC
      * ENCRYPTIONS. IT IS INHERENTLY INSECURE, AND ONLY MEANT FOR TESTING OR
@@ -99,7 +99,8 @@ pub trait Evaluator {
      *
      * If you haven't set up a modulus chain, don't use this.
      *
-     * TODO: what does this mean for CKKS?
+     * In CKKS, this drops a prime without changing the scale. To divide the scale
+     * after a multiplication, use [`Evaluator::rescale_to_next`] instead.
      */
     fn mod_switch_to_next(&self, a: &Ciphertext) -> Result<Ciphertext>;
 
@@ -113,7 +114,8 @@ pub trait Evaluator {
      *
      * If you haven't set up a modulus chain, don't use this.
      *
-     * TODO: what does this mean for CKKS?
+     * In CKKS, this drops a prime without changing the scale. To divide the scale
+     * after a multiplication, use [`Evaluator::rescale_to_next`] instead.
      */
     fn mod_switch_to_next_inplace(&self, a: &Ciphertext) -> Result<()>;
 
@@ -128,6 +130,46 @@ pub trait Evaluator {
      */
     fn mod_switch_to_next_inplace_plaintext(&self, a: &Plaintext) -> Result<()>;
 
+    /**
+     * Divides a CKKS ciphertext and its scale by the last prime in its coefficient
+     * modulus and drops that prime, moving the ciphertext to the next level of the
+     * modulus chain.
+     *
+     * # Remarks
+     * Only the CKKS scheme supports rescaling; other schemes return an error.
+     * Multiplication multiplies the scales of its operands, so one should rescale
+     * after each multiplication to keep the scale from overflowing the modulus.
+     */
+    fn rescale_to_next(&self, a: &Ciphertext) -> Result<Ciphertext>;
+
+    /**
+     * Rescales a CKKS ciphertext in-place. See [`Evaluator::rescale_to_next`].
+     */
+    fn rescale_to_next_inplace(&self, a: &mut Ciphertext) -> Result<()>;
+
+    /**
+     * Switches a ciphertext down the modulus chain to the encryption parameters with
+     * the given ParmsId.
+     *
+     * # Remarks
+     * The target parameters must be at or below the ciphertext's current level. Under
+     * CKKS, this does not change the ciphertext's scale, which makes it the way to
+     * bring operands to the same level before adding them.
+     */
+    fn mod_switch_to(&self, a: &Ciphertext, parms_id: &[u64; 4]) -> Result<Ciphertext>;
+
+    /**
+     * Switches a ciphertext down the modulus chain in-place. See
+     * [`Evaluator::mod_switch_to`].
+     */
+    fn mod_switch_to_inplace(&self, a: &mut Ciphertext, parms_id: &[u64; 4]) -> Result<()>;
+
+    /**
+     * Switches an NTT transformed plaintext down the modulus chain to the encryption
+     * parameters with the given ParmsId.
+     */
+    fn mod_switch_to_plaintext(&self, a: &Plaintext, parms_id: &[u64; 4]) -> Result<Plaintext>;
+
     /**
      * This functions raises encrypted to a power and stores the result in the destination parameter. Dynamic
      * memory allocations in the process are allocated from the memory pool pointed to by the given
@@ -252,6 +294,33 @@ pub trait Evaluator {
         galois_keys: &GaloisKeys,
     ) -> Result<()>;
 
+    /**
+     * Rotates plaintext matrix rows cyclically by each of the given steps,
+     * returning one ciphertext per step.
+     *
+     * # Remarks
+     * Every rotation of `a` key switches the same ciphertext, so an
+     * evaluator can decompose `a` once and reuse the decomposition for
+     * every step (i.e. hoist the rotations). [`BFVEvaluator`](crate::BFVEvaluator)
+     * hoists the steps that have a Galois key; the default implementation
+     * rotates by each step in turn.
+     *
+     * * `a` - The ciphertext to rotate
+     * * `steps` - The numbers of steps to rotate (positive left, negative right)
+     * * `galois_keys` - The Galois keys
+     */
+    fn rotate_rows_many(
+        &self,
+        a: &Ciphertext,
+        steps: &[i32],
+        galois_keys: &GaloisKeys,
+    ) -> Result<Vec<Ciphertext>> {
+        steps
+            .iter()
+            .map(|s| self.rotate_rows(a, *s, galois_keys))
+            .collect()
+    }
+
     /**
      * Rotates plaintext matrix columns cyclically.
      *
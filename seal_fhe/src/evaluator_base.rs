@@ -3,7 +3,7 @@ use std::ptr::null_mut;
 
 use crate::bindgen;
 use crate::error::*;
-use crate::{Ciphertext, Context, Plaintext, RelinearizationKeys};
+use crate::{Ciphertext, Context, GaloisKeys, Plaintext, RelinearizationKeys};
 
 /**
  * Provides operations on ciphertexts. Due to the properties of the encryption scheme, the arithmetic operations
@@ -54,6 +54,7 @@ use crate::{Ciphertext, Context, Plaintext, RelinearizationKeys};
 */
 pub struct EvaluatorBase {
     handle: *mut c_void,
+    context: Context,
 }
 
 unsafe impl Sync for EvaluatorBase {}
@@ -76,7 +77,10 @@ impl EvaluatorBase {
 
         convert_seal_error(unsafe { bindgen::Evaluator_Create(ctx.get_handle(), &mut handle) })?;
 
-        Ok(Self { handle })
+        Ok(Self {
+            handle,
+            context: ctx.try_clone()?,
+        })
     }
 
     /**
@@ -403,5 +407,232 @@ impl EvaluatorBase {
         Ok(())
     }
 
+    pub(crate) fn relinearize_inplace(
+        &self,
+        a: &Ciphertext,
+        relin_keys: &RelinearizationKeys,
+    ) -> Result<()> {
+        convert_seal_error(unsafe {
+            bindgen::Evaluator_Relinearize(
+                self.handle,
+                a.get_handle(),
+                relin_keys.get_handle(),
+                a.get_handle(),
+                null_mut(),
+            )
+        })?;
+
+        Ok(())
+    }
+
+    pub(crate) fn relinearize(
+        &self,
+        a: &Ciphertext,
+        relin_keys: &RelinearizationKeys,
+    ) -> Result<Ciphertext> {
+        let out = Ciphertext::new()?;
+
+        convert_seal_error(unsafe {
+            bindgen::Evaluator_Relinearize(
+                self.handle,
+                a.get_handle(),
+                relin_keys.get_handle(),
+                out.get_handle(),
+                null_mut(),
+            )
+        })?;
+
+        Ok(out)
+    }
+
+    pub(crate) fn rotate_rows(
+        &self,
+        a: &Ciphertext,
+        steps: i32,
+        galois_keys: &GaloisKeys,
+    ) -> Result<Ciphertext> {
+        let out = Ciphertext::new()?;
+
+        convert_seal_error(unsafe {
+            bindgen::Evaluator_RotateRows(
+                self.handle,
+                a.get_handle(),
+                steps,
+                galois_keys.get_handle(),
+                out.get_handle(),
+                null_mut(),
+            )
+        })?;
+
+        Ok(out)
+    }
+
+    pub(crate) fn rotate_rows_many(
+        &self,
+        a: &Ciphertext,
+        steps: &[i32],
+        galois_keys: &GaloisKeys,
+    ) -> Result<Vec<Ciphertext>> {
+        let out = steps
+            .iter()
+            .map(|_| Ciphertext::new())
+            .collect::<Result<Vec<_>>>()?;
+        let mut handles = out.iter().map(|c| c.get_handle()).collect::<Vec<_>>();
+
+        convert_seal_error(unsafe {
+            bindgen::Evaluator_RotateRowsMany(
+                self.handle,
+                self.context.get_handle(),
+                a.get_handle(),
+                steps.len() as u64,
+                steps.as_ptr(),
+                galois_keys.get_handle(),
+                handles.as_mut_ptr(),
+            )
+        })?;
+
+        Ok(out)
+    }
+
+    pub(crate) fn rotate_rows_inplace(
+        &self,
+        a: &Ciphertext,
+        steps: i32,
+        galois_keys: &GaloisKeys,
+    ) -> Result<()> {
+        convert_seal_error(unsafe {
+            bindgen::Evaluator_RotateRows(
+                self.handle,
+                a.get_handle(),
+                steps,
+                galois_keys.get_handle(),
+                a.get_handle(),
+                null_mut(),
+            )
+        })?;
+
+        Ok(())
+    }
+
+    pub(crate) fn rotate_columns(
+        &self,
+        a: &Ciphertext,
+        galois_keys: &GaloisKeys,
+    ) -> Result<Ciphertext> {
+        let out = Ciphertext::new()?;
+
+        convert_seal_error(unsafe {
+            bindgen::Evaluator_RotateColumns(
+                self.handle,
+                a.get_handle(),
+                galois_keys.get_handle(),
+                out.get_handle(),
+                null_mut(),
+            )
+        })?;
+
+        Ok(out)
+    }
+
+    pub(crate) fn rotate_columns_inplace(
+        &self,
+        a: &Ciphertext,
+        galois_keys: &GaloisKeys,
+    ) -> Result<()> {
+        convert_seal_error(unsafe {
+            bindgen::Evaluator_RotateColumns(
+                self.handle,
+                a.get_handle(),
+                galois_keys.get_handle(),
+                a.get_handle(),
+                null_mut(),
+            )
+        })?;
+
+        Ok(())
+    }
+
+    pub(crate) fn rescale_to_next(&self, a: &Ciphertext) -> Result<Ciphertext> {
+        let out = Ciphertext::new()?;
+
+        convert_seal_error(unsafe {
+            bindgen::Evaluator_RescaleToNext(
+                self.handle,
+                a.get_handle(),
+                out.get_handle(),
+                null_mut(),
+            )
+        })?;
+
+        Ok(out)
+    }
+
+    pub(crate) fn rescale_to_next_inplace(&self, a: &mut Ciphertext) -> Result<()> {
+        convert_seal_error(unsafe {
+            bindgen::Evaluator_RescaleToNext(
+                self.handle,
+                a.get_handle(),
+                a.get_handle(),
+                null_mut(),
+            )
+        })?;
+
+        Ok(())
+    }
+
+    pub(crate) fn mod_switch_to(&self, a: &Ciphertext, parms_id: &[u64; 4]) -> Result<Ciphertext> {
+        let out = Ciphertext::new()?;
+
+        // SEAL doesn't mutate parms_id, the C bindings just aren't const correct.
+        convert_seal_error(unsafe {
+            bindgen::Evaluator_ModSwitchTo1(
+                self.handle,
+                a.get_handle(),
+                parms_id.as_ptr() as *mut u64,
+                out.get_handle(),
+                null_mut(),
+            )
+        })?;
+
+        Ok(out)
+    }
+
+    pub(crate) fn mod_switch_to_inplace(
+        &self,
+        a: &mut Ciphertext,
+        parms_id: &[u64; 4],
+    ) -> Result<()> {
+        convert_seal_error(unsafe {
+            bindgen::Evaluator_ModSwitchTo1(
+                self.handle,
+                a.get_handle(),
+                parms_id.as_ptr() as *mut u64,
+                a.get_handle(),
+                null_mut(),
+            )
+        })?;
+
+        Ok(())
+    }
+
+    pub(crate) fn mod_switch_to_plaintext(
+        &self,
+        a: &Plaintext,
+        parms_id: &[u64; 4],
+    ) -> Result<Plaintext> {
+        let out = Plaintext::new()?;
+
+        convert_seal_error(unsafe {
+            bindgen::Evaluator_ModSwitchTo2(
+                self.handle,
+                a.get_handle(),
+                parms_id.as_ptr() as *mut u64,
+                out.get_handle(),
+            )
+        })?;
+
+        Ok(out)
+    }
+
     // TODO: NTT transform.
 }
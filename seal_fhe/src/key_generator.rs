@@ -2,6 +2,7 @@ use std::ffi::c_void;
 use std::ptr::null_mut;
 
 use crate::bindgen;
+use crate::data_structures::export_u64s;
 use crate::error::*;
 use crate::serialization::CompressionType;
 use crate::{Context, FromBytes, ToBytes};
@@ -171,6 +172,65 @@ impl KeyGenerator {
         self.create_galois_keys_internal(false)
     }
 
+    /**
+     * Generates Galois keys for the given rotation steps.
+     *
+     * # Remarks
+     * Every time this function is called, new Galois keys will be generated.
+     *
+     * The resulting keys can only rotate by exactly the given steps. Positive
+     * steps rotate left, negative steps rotate right, and a step of 0 swaps
+     * the rows of a BFV batched vector. Generating only the needed keys can
+     * be much faster and produce much smaller keys than
+     * [`create_galois_keys`](Self::create_galois_keys).
+     *
+     * * `steps` - The rotation steps for which to generate keys.
+     */
+    pub fn create_galois_keys_from_steps(&self, steps: &[i32]) -> Result<GaloisKeys> {
+        self.create_galois_keys_from_steps_internal(steps, false)
+    }
+
+    /**
+     * Generates Galois keys for the given rotation steps as a serializable
+     * object.
+     *
+     * Half of the key data is pseudo-randomly generated from a seed to reduce
+     * the object size. The resulting serializable object cannot be used
+     * directly and is meant to be serialized for the size reduction to have an
+     * impact.
+     *
+     * * `steps` - The rotation steps for which to generate keys.
+     */
+    pub fn create_compact_galois_keys_from_steps(
+        &self,
+        steps: &[i32],
+    ) -> Result<CompactGaloisKeys> {
+        Ok(CompactGaloisKeys(
+            self.create_galois_keys_from_steps_internal(steps, true)?,
+        ))
+    }
+
+    fn create_galois_keys_from_steps_internal(
+        &self,
+        steps: &[i32],
+        save_seed: bool,
+    ) -> Result<GaloisKeys> {
+        let mut handle = null_mut();
+        let mut steps = steps.to_owned();
+
+        convert_seal_error(unsafe {
+            bindgen::KeyGenerator_CreateGaloisKeysFromSteps(
+                self.handle,
+                steps.len() as u64,
+                steps.as_mut_ptr(),
+                save_seed,
+                &mut handle,
+            )
+        })?;
+
+        Ok(GaloisKeys { handle })
+    }
+
     fn create_galois_keys_internal(&self, save_seed: bool) -> Result<GaloisKeys> {
         let mut handle = null_mut();
 
@@ -201,11 +261,11 @@ unsafe impl Sync for PublicKey {}
 unsafe impl Send for PublicKey {}
 
 impl ToBytes for PublicKey {
-    fn as_bytes(&self) -> Result<Vec<u8>> {
+    fn as_bytes_with_compression(&self, compression: CompressionType) -> Result<Vec<u8>> {
         let mut num_bytes: i64 = 0;
 
         convert_seal_error(unsafe {
-            bindgen::PublicKey_SaveSize(self.handle, CompressionType::ZStd as u8, &mut num_bytes)
+            bindgen::PublicKey_SaveSize(self.handle, compression as u8, &mut num_bytes)
         })?;
 
         let mut data: Vec<u8> = Vec::with_capacity(num_bytes as usize);
@@ -218,7 +278,7 @@ impl ToBytes for PublicKey {
                 self.handle,
                 data_ptr,
                 num_bytes as u64,
-                CompressionType::ZStd as u8,
+                compression as u8,
                 &mut bytes_written,
             )
         })?;
@@ -263,6 +323,36 @@ impl PublicKey {
         Ok(Self { handle })
     }
 
+    /**
+     * Creates a public key from its 2 polynomials in NTT form under the key
+     * level modulus, laid out as in [`rns_data`](Self::rns_data).
+     */
+    pub fn from_rns_data(context: &Context, data: &[u64]) -> Result<Self> {
+        let key = PublicKey::new()?;
+
+        convert_seal_error(unsafe {
+            bindgen::PublicKey_SetRnsData(
+                key.handle,
+                context.handle,
+                data.len() as u64,
+                data.as_ptr(),
+            )
+        })?;
+
+        Ok(key)
+    }
+
+    /**
+     * Returns the key's 2 polynomials one after another, in NTT and RNS form
+     * under the key level modulus (i.e. all moduli, including the special
+     * prime).
+     */
+    pub fn rns_data(&self) -> Result<Vec<u64>> {
+        export_u64s(|count, data| unsafe {
+            bindgen::PublicKey_GetRnsData(self.handle, count, data)
+        })
+    }
+
     /**
      * Returns the handle to the underlying SEAL object.
      */
@@ -329,6 +419,35 @@ impl SecretKey {
         Ok(Self { handle })
     }
 
+    /**
+     * Creates a secret key from its polynomial in NTT form under the key level
+     * modulus, laid out as in [`rns_data`](Self::rns_data).
+     */
+    pub fn from_rns_data(context: &Context, data: &[u64]) -> Result<Self> {
+        let key = SecretKey::new()?;
+
+        convert_seal_error(unsafe {
+            bindgen::SecretKey_SetRnsData(
+                key.handle,
+                context.handle,
+                data.len() as u64,
+                data.as_ptr(),
+            )
+        })?;
+
+        Ok(key)
+    }
+
+    /**
+     * Returns the key's polynomial in NTT and RNS form under the key level
+     * modulus (i.e. all moduli, including the special prime).
+     */
+    pub fn rns_data(&self) -> Result<Vec<u64>> {
+        export_u64s(|count, data| unsafe {
+            bindgen::SecretKey_GetRnsData(self.handle, count, data)
+        })
+    }
+
     /**
      * Returns the handle to the underlying SEAL object.
      */
@@ -347,11 +466,11 @@ impl ToBytes for SecretKey {
     /**
      * Returns the key as a byte array.
      */
-    fn as_bytes(&self) -> Result<Vec<u8>> {
+    fn as_bytes_with_compression(&self, compression: CompressionType) -> Result<Vec<u8>> {
         let mut num_bytes: i64 = 0;
 
         convert_seal_error(unsafe {
-            bindgen::SecretKey_SaveSize(self.handle, CompressionType::ZStd as u8, &mut num_bytes)
+            bindgen::SecretKey_SaveSize(self.handle, compression as u8, &mut num_bytes)
         })?;
 
         let mut data: Vec<u8> = Vec::with_capacity(num_bytes as usize);
@@ -364,7 +483,7 @@ impl ToBytes for SecretKey {
                 self.handle,
                 data_ptr,
                 num_bytes as u64,
-                CompressionType::ZStd as u8,
+                compression as u8,
                 &mut bytes_written,
             )
         })?;
@@ -439,6 +558,11 @@ impl AsRef<SecretKey> for SecretKey {
     }
 }
 
+/**
+ * The index of the key switching keys for `s^2` in SEAL's relinearization keys.
+ */
+const RELIN_KEY_INDEX: u64 = 0;
+
 /**
  * Class to store relinearization keys.
  *
@@ -487,6 +611,59 @@ impl RelinearizationKeys {
         Ok(Self { handle })
     }
 
+    /**
+     * Creates relinearization keys from the key switching keys for `s^2`, one
+     * per modulus in the decomposition. Each key is laid out as a
+     * [`PublicKey`]'s [`rns_data`](PublicKey::rns_data).
+     */
+    pub fn from_rns_data(context: &Context, keys: &[Vec<u64>]) -> Result<Self> {
+        let relin_keys = RelinearizationKeys::new()?;
+        let data = keys.concat();
+
+        convert_seal_error(unsafe {
+            bindgen::KSwitchKeys_SetRnsData(
+                relin_keys.handle,
+                context.handle,
+                RELIN_KEY_INDEX,
+                keys.len() as u64,
+                data.len() as u64,
+                data.as_ptr(),
+            )
+        })?;
+
+        Ok(relin_keys)
+    }
+
+    /**
+     * Returns the key switching keys for `s^2`, laid out as in
+     * [`from_rns_data`](Self::from_rns_data).
+     */
+    pub fn rns_data(&self) -> Result<Vec<Vec<u64>>> {
+        let data = export_u64s(|count, data| unsafe {
+            bindgen::KSwitchKeys_GetRnsData(self.handle, RELIN_KEY_INDEX, count, data)
+        })?;
+
+        let mut decomposition_count: u64 = 0;
+
+        convert_seal_error(unsafe {
+            bindgen::KSwitchKeys_GetKeyList(
+                self.handle,
+                RELIN_KEY_INDEX,
+                &mut decomposition_count,
+                null_mut(),
+            )
+        })?;
+
+        if decomposition_count == 0 {
+            return Ok(vec![]);
+        }
+
+        Ok(data
+            .chunks_exact(data.len() / decomposition_count as usize)
+            .map(|k| k.to_vec())
+            .collect())
+    }
+
     /**
      * Returns the key as a byte array.
      */
@@ -525,11 +702,11 @@ impl PartialEq for RelinearizationKeys {
 }
 
 impl ToBytes for RelinearizationKeys {
-    fn as_bytes(&self) -> Result<Vec<u8>> {
+    fn as_bytes_with_compression(&self, compression: CompressionType) -> Result<Vec<u8>> {
         let mut num_bytes: i64 = 0;
 
         convert_seal_error(unsafe {
-            bindgen::KSwitchKeys_SaveSize(self.handle, CompressionType::ZStd as u8, &mut num_bytes)
+            bindgen::KSwitchKeys_SaveSize(self.handle, compression as u8, &mut num_bytes)
         })?;
 
         let mut data: Vec<u8> = Vec::with_capacity(num_bytes as usize);
@@ -542,7 +719,7 @@ impl ToBytes for RelinearizationKeys {
                 self.handle,
                 data_ptr,
                 num_bytes as u64,
-                CompressionType::ZStd as u8,
+                compression as u8,
                 &mut bytes_written,
             )
         })?;
@@ -659,11 +836,11 @@ impl PartialEq for GaloisKeys {
 }
 
 impl ToBytes for GaloisKeys {
-    fn as_bytes(&self) -> Result<Vec<u8>> {
+    fn as_bytes_with_compression(&self, compression: CompressionType) -> Result<Vec<u8>> {
         let mut num_bytes: i64 = 0;
 
         convert_seal_error(unsafe {
-            bindgen::KSwitchKeys_SaveSize(self.handle, CompressionType::ZStd as u8, &mut num_bytes)
+            bindgen::KSwitchKeys_SaveSize(self.handle, compression as u8, &mut num_bytes)
         })?;
 
         let mut data: Vec<u8> = Vec::with_capacity(num_bytes as usize);
@@ -676,7 +853,7 @@ impl ToBytes for GaloisKeys {
                 self.handle,
                 data_ptr,
                 num_bytes as u64,
-                CompressionType::ZStd as u8,
+                compression as u8,
                 &mut bytes_written,
             )
         })?;
@@ -858,4 +1035,91 @@ mod tests {
             serde_json::to_string(&secret_key).unwrap()
         );
     }
+
+    #[test]
+    fn can_round_trip_key_rns_data() {
+        let params = BfvEncryptionParametersBuilder::new()
+            .set_poly_modulus_degree(8192)
+            .set_coefficient_modulus(
+                CoefficientModulus::create(8192, &[50, 30, 30, 50, 50]).unwrap(),
+            )
+            .set_plain_modulus_u64(1234)
+            .build()
+            .unwrap();
+
+        let ctx = Context::new(&params, false, SecurityLevel::TC128).unwrap();
+        let gen = KeyGenerator::new(&ctx).unwrap();
+
+        let secret_key = gen.secret_key();
+        let data = secret_key.rns_data().unwrap();
+        assert_eq!(data.len(), 8192 * 5);
+        assert_eq!(SecretKey::from_rns_data(&ctx, &data).unwrap(), secret_key);
+
+        let public_key = gen.create_public_key();
+        let data = public_key.rns_data().unwrap();
+        assert_eq!(data.len(), 2 * 8192 * 5);
+        assert_eq!(PublicKey::from_rns_data(&ctx, &data).unwrap(), public_key);
+
+        // One key switching key per data modulus.
+        let relin_keys = gen.create_relinearization_keys().unwrap();
+        let data = relin_keys.rns_data().unwrap();
+        assert_eq!(data.len(), 4);
+        assert!(data.iter().all(|k| k.len() == 2 * 8192 * 5));
+        assert!(RelinearizationKeys::from_rns_data(&ctx, &data).unwrap() == relin_keys);
+
+        assert!(PublicKey::from_rns_data(&ctx, &data[0][1..]).is_err());
+    }
+
+    #[test]
+    fn sampled_error_forms_valid_public_key() {
+        let params = BfvEncryptionParametersBuilder::new()
+            .set_poly_modulus_degree(4096)
+            .set_coefficient_modulus(
+                CoefficientModulus::bfv_default(4096, SecurityLevel::TC128).unwrap(),
+            )
+            .set_plain_modulus_u64(1234)
+            .build()
+            .unwrap();
+
+        let ctx = Context::new(&params, false, SecurityLevel::TC128).unwrap();
+        let gen = KeyGenerator::new(&ctx).unwrap();
+        let moduli = CoefficientModulus::bfv_default(4096, SecurityLevel::TC128)
+            .unwrap()
+            .iter()
+            .map(|q| q.value())
+            .collect::<Vec<_>>();
+
+        let s = gen.secret_key().rns_data().unwrap();
+        let e = ctx.sample_error().unwrap();
+        assert_eq!(e.len(), s.len());
+        assert_ne!(e, ctx.sample_error().unwrap());
+
+        // Reuse a uniform a from a fresh public key to build (-(a * s + e), a).
+        let mut data = gen.create_public_key().rns_data().unwrap();
+        let (p0, a) = data.split_at_mut(s.len());
+
+        for (i, p0) in p0.iter_mut().enumerate() {
+            let q = moduli[i / 4096] as u128;
+            let x = (a[i] as u128 * s[i] as u128 + e[i] as u128) % q;
+
+            *p0 = ((q - x) % q) as u64;
+        }
+
+        let public_key = PublicKey::from_rns_data(&ctx, &data).unwrap();
+        let encryptor = Encryptor::with_public_key(&ctx, &public_key).unwrap();
+        let decryptor = Decryptor::new(&ctx, &gen.secret_key()).unwrap();
+        let encoder = BFVScalarEncoder::new();
+
+        let ciphertext = encryptor
+            .encrypt(&encoder.encode_unsigned(42).unwrap())
+            .unwrap();
+
+        assert!(decryptor.invariant_noise_budget(&ciphertext).unwrap() > 0);
+        assert_eq!(
+            encoder
+                .decode_unsigned(&decryptor.decrypt(&ciphertext).unwrap())
+                .unwrap(),
+            42
+        );
+    }
 }
@@ -9,7 +9,7 @@
 //! should safely hold. The internal handles should be of little use to you anyways.
 //!
 //! This crate intentionally omits more esoteric use cases to streamline the API and
-//! is currently incomplete (e.g. CKKS is not currently supported). If any underlying
+//! is currently incomplete (e.g. CKKS complex-valued encoding is not supported). If any underlying
 //! SEAL API you care about is missing, please add it in a pull request or file
 //! an [issue](https://github.com/Sunscreen-tech/Sunscreen/issues).
 
@@ -35,15 +35,27 @@ mod bindgen {
 }
 
 mod serialization {
+    /**
+     * The compression SEAL applies when serializing an object.
+     */
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
     #[repr(u8)]
     pub enum CompressionType {
-        // None = 0,
+        /**
+         * Store the raw coefficients.
+         */
+        None = 0,
         // ZLib = 1,
+        /**
+         * Compress with Zstandard.
+         */
         ZStd = 2,
     }
 }
 
 mod bfv_evaluator;
+mod bgv_evaluator;
+mod ckks_evaluator;
 mod context;
 mod data_structures;
 mod encoder;
@@ -57,9 +69,11 @@ mod modulus;
 mod plaintext_ciphertext;
 
 pub use bfv_evaluator::BFVEvaluator;
+pub use bgv_evaluator::BGVEvaluator;
+pub use ckks_evaluator::CKKSEvaluator;
 pub use context::Context;
 pub use data_structures::PolynomialArray;
-pub use encoder::{BFVEncoder, BFVScalarEncoder};
+pub use encoder::{BFVEncoder, BFVScalarEncoder, CKKSEncoder};
 pub use encryption_parameters::*;
 pub use encryptor_decryptor::{
     marker as enc_marker, Asym, AsymmetricComponents, AsymmetricEncryptor, Decryptor, Encryptor,
@@ -67,18 +81,29 @@ pub use encryptor_decryptor::{
 };
 pub use error::{Error, Result};
 pub use evaluator::Evaluator;
-pub use key_generator::{GaloisKeys, KeyGenerator, PublicKey, RelinearizationKeys, SecretKey};
+pub use key_generator::{
+    CompactGaloisKeys, CompactPublicKey, CompactRelinearizationKeys, GaloisKeys, KeyGenerator,
+    PublicKey, RelinearizationKeys, SecretKey,
+};
 pub use modulus::{CoefficientModulus, Modulus, PlainModulus, SecurityLevel};
-pub use plaintext_ciphertext::{Ciphertext, Plaintext};
+pub use plaintext_ciphertext::{Ciphertext, CompactCiphertext, Plaintext};
+pub use serialization::CompressionType;
 
 /**
  * A trait for converting objects into byte arrays.
  */
 pub trait ToBytes {
     /**
-     * Returns the object as a byte array.
+     * Returns the object as a byte array, compressed with Zstandard.
+     */
+    fn as_bytes(&self) -> Result<Vec<u8>> {
+        self.as_bytes_with_compression(CompressionType::ZStd)
+    }
+
+    /**
+     * Returns the object as a byte array using the given compression.
      */
-    fn as_bytes(&self) -> Result<Vec<u8>>;
+    fn as_bytes_with_compression(&self, compression: CompressionType) -> Result<Vec<u8>>;
 }
 
 /**
@@ -2,6 +2,7 @@ use core::hash::Hash;
 use std::ffi::{c_void, CString};
 use std::ptr::null_mut;
 
+use crate::data_structures::export_u64s;
 use crate::error::*;
 use crate::{bindgen, serialization::CompressionType, Context, FromBytes, ToBytes};
 
@@ -130,11 +131,11 @@ impl FromBytes for Plaintext {
 }
 
 impl ToBytes for Plaintext {
-    fn as_bytes(&self) -> Result<Vec<u8>> {
+    fn as_bytes_with_compression(&self, compression: CompressionType) -> Result<Vec<u8>> {
         let mut num_bytes: i64 = 0;
 
         convert_seal_error(unsafe {
-            bindgen::Plaintext_SaveSize(self.handle, CompressionType::ZStd as u8, &mut num_bytes)
+            bindgen::Plaintext_SaveSize(self.handle, compression as u8, &mut num_bytes)
         })?;
 
         let mut data: Vec<u8> = Vec::with_capacity(num_bytes as usize);
@@ -147,7 +148,7 @@ impl ToBytes for Plaintext {
                 self.handle,
                 data_ptr,
                 num_bytes as u64,
-                CompressionType::ZStd as u8,
+                compression as u8,
                 &mut bytes_written,
             )
         })?;
@@ -290,6 +291,34 @@ impl Plaintext {
 
         result
     }
+
+    /**
+     * Returns the scale this plaintext was encoded with. Only meaningful
+     * for CKKS plaintexts.
+     */
+    pub fn get_scale(&self) -> f64 {
+        let mut scale = 0f64;
+
+        convert_seal_error(unsafe { bindgen::Plaintext_Scale(self.handle, &mut scale) })
+            .expect("Fatal error in Plaintext::get_scale().");
+
+        scale
+    }
+
+    /**
+     * Returns the ParmsId of the encryption parameters this plaintext
+     * is valid for. Only meaningful for plaintexts in NTT form.
+     */
+    pub fn get_parms_id(&self) -> [u64; 4] {
+        let mut parms_id = [0u64; 4];
+
+        convert_seal_error(unsafe {
+            bindgen::Plaintext_GetParmsId(self.handle, parms_id.as_mut_ptr())
+        })
+        .expect("Fatal error in Plaintext::get_parms_id().");
+
+        parms_id
+    }
 }
 
 impl Drop for Plaintext {
@@ -440,6 +469,81 @@ impl Ciphertext {
 
         result
     }
+
+    /**
+     * Returns the scale of this ciphertext. Only meaningful for CKKS
+     * ciphertexts.
+     */
+    pub fn get_scale(&self) -> f64 {
+        let mut scale = 0f64;
+
+        convert_seal_error(unsafe { bindgen::Ciphertext_Scale(self.handle, &mut scale) })
+            .expect("Fatal error in Ciphertext::get_scale().");
+
+        scale
+    }
+
+    /**
+     * Overwrites the scale of this ciphertext without changing its contents.
+     *
+     * # Remarks
+     * Rescaling divides a CKKS ciphertext by a prime close to, but not
+     * exactly, its scale. Setting the scale back to the nominal value allows
+     * adding ciphertexts that went down different paths, at the cost of a
+     * tiny relative error.
+     */
+    pub fn set_scale(&mut self, scale: f64) -> Result<()> {
+        convert_seal_error(unsafe { bindgen::Ciphertext_SetScale(self.handle, scale) })
+    }
+
+    /**
+     * Returns the ParmsId of the encryption parameters this ciphertext
+     * is encrypted under. This changes after each modulus switch or rescale.
+     */
+    pub fn get_parms_id(&self) -> [u64; 4] {
+        let mut parms_id = [0u64; 4];
+
+        convert_seal_error(unsafe {
+            bindgen::Ciphertext_ParmsId(self.handle, parms_id.as_mut_ptr())
+        })
+        .expect("Fatal error in Ciphertext::get_parms_id().");
+
+        parms_id
+    }
+
+    /**
+     * Returns the ciphertext's polynomials one after another. Each polynomial
+     * is in RNS form, i.e. its coefficients modulo the first modulus followed
+     * by those modulo the second and so on, and in the ciphertext's current
+     * (NTT or coefficient) form.
+     */
+    pub fn rns_data(&self) -> Result<Vec<u64>> {
+        export_u64s(|count, data| unsafe {
+            bindgen::Ciphertext_GetRnsData(self.handle, count, data)
+        })
+    }
+
+    /**
+     * Resizes the ciphertext to `num_polynomials` polynomials at its current
+     * level and overwrites them with `data`, laid out as in
+     * [`rns_data`](Self::rns_data).
+     */
+    pub fn set_rns_data(
+        &mut self,
+        context: &Context,
+        num_polynomials: u64,
+        data: &[u64],
+    ) -> Result<()> {
+        convert_seal_error(unsafe {
+            bindgen::Ciphertext_SetRnsData(
+                self.handle,
+                context.handle,
+                num_polynomials,
+                data.len() as u64,
+                data.as_ptr(),
+            )
+        })
+    }
 }
 
 impl PartialEq for Ciphertext {
@@ -449,11 +553,11 @@ impl PartialEq for Ciphertext {
 }
 
 impl ToBytes for Ciphertext {
-    fn as_bytes(&self) -> Result<Vec<u8>> {
+    fn as_bytes_with_compression(&self, compression: CompressionType) -> Result<Vec<u8>> {
         let mut num_bytes: i64 = 0;
 
         convert_seal_error(unsafe {
-            bindgen::Ciphertext_SaveSize(self.handle, CompressionType::ZStd as u8, &mut num_bytes)
+            bindgen::Ciphertext_SaveSize(self.handle, compression as u8, &mut num_bytes)
         })?;
 
         let mut data: Vec<u8> = Vec::with_capacity(num_bytes as usize);
@@ -466,7 +570,7 @@ impl ToBytes for Ciphertext {
                 self.handle,
                 data_ptr,
                 num_bytes as u64,
-                CompressionType::ZStd as u8,
+                compression as u8,
                 &mut bytes_written,
             )
         })?;
@@ -503,9 +607,28 @@ impl Drop for Ciphertext {
     }
 }
 
+/**
+ * A ciphertext that stores a random number seed to generate half of its data.
+ * This form isn't directly usable, but serializes in a compact representation.
+ */
+pub struct CompactCiphertext(pub(crate) Ciphertext);
+
+impl CompactCiphertext {
+    /**
+     * Returns the ciphertext as a byte array.
+     */
+    pub fn as_bytes(&self) -> Result<Vec<u8>> {
+        self.0.as_bytes()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{
+        BFVScalarEncoder, BfvEncryptionParametersBuilder, CoefficientModulus, Decryptor, Encryptor,
+        KeyGenerator, SecurityLevel,
+    };
 
     #[test]
     fn can_create_and_destroy_ciphertext() {
@@ -529,4 +652,50 @@ mod tests {
         assert_eq!(plaintext.get_coefficient(1), 0);
         assert_eq!(plaintext.get_coefficient(2), 0x1234);
     }
+
+    #[test]
+    fn can_set_ciphertext_rns_data() {
+        let params = BfvEncryptionParametersBuilder::new()
+            .set_poly_modulus_degree(4096)
+            .set_coefficient_modulus(
+                CoefficientModulus::bfv_default(4096, SecurityLevel::TC128).unwrap(),
+            )
+            .set_plain_modulus_u64(1234)
+            .build()
+            .unwrap();
+
+        let ctx = Context::new(&params, true, SecurityLevel::TC128).unwrap();
+        let gen = KeyGenerator::new(&ctx).unwrap();
+        let encryptor = Encryptor::with_public_key(&ctx, &gen.create_public_key()).unwrap();
+        let decryptor = Decryptor::new(&ctx, &gen.secret_key()).unwrap();
+        let encoder = BFVScalarEncoder::new();
+
+        let ciphertext = encryptor
+            .encrypt(&encoder.encode_unsigned(42).unwrap())
+            .unwrap();
+
+        // Ciphertexts don't carry the special prime.
+        let data = ciphertext.rns_data().unwrap();
+        assert_eq!(data.len(), 2 * 4096 * 2);
+
+        // (c0, c1, 0) decrypts to the same value as (c0, c1).
+        let mut extended = ciphertext.clone();
+        extended
+            .set_rns_data(&ctx, 3, &[data.as_slice(), &[0; 4096 * 2]].concat())
+            .unwrap();
+
+        assert_eq!(extended.num_polynomials(), 3);
+        assert_eq!(extended.get_parms_id(), ciphertext.get_parms_id());
+        assert_eq!(
+            encoder
+                .decode_unsigned(&decryptor.decrypt(&extended).unwrap())
+                .unwrap(),
+            42
+        );
+
+        extended.set_rns_data(&ctx, 2, &data).unwrap();
+        assert_eq!(extended, ciphertext);
+
+        assert!(extended.set_rns_data(&ctx, 3, &data).is_err());
+    }
 }
@@ -59,6 +59,9 @@ fn benchmark(params: &Params) {
 
         let now = Instant::now();
         let (public, private) = runtime.generate_keys().unwrap();
+        let evaluation_keys = runtime
+            .generate_evaluation_keys(&private, app.get_fhe_programs().map(|(_, p)| p))
+            .unwrap();
 
         keygen_time += now.elapsed().as_secs_f64();
 
@@ -82,14 +85,14 @@ fn benchmark(params: &Params) {
             .run(
                 app.get_fhe_program(add).unwrap(),
                 vec![send_a.clone(), send_b.clone()],
-                &public,
+                &evaluation_keys,
             )
             .unwrap();
         let sub_res = runtime
             .run(
                 app.get_fhe_program(sub).unwrap(),
                 vec![send_a.clone(), send_b],
-                &public,
+                &evaluation_keys,
             )
             .unwrap();
 
@@ -112,7 +115,7 @@ fn benchmark(params: &Params) {
         let now = Instant::now();
 
         let add_res = runtime
-            .run(app.get_fhe_program(add_pt).unwrap(), args, &public)
+            .run(app.get_fhe_program(add_pt).unwrap(), args, &evaluation_keys)
             .unwrap();
 
         shield += now.elapsed().as_secs_f64();
@@ -129,7 +132,7 @@ fn benchmark(params: &Params) {
         let now = Instant::now();
 
         let sub_res = runtime
-            .run(app.get_fhe_program(sub_pt).unwrap(), args, &public)
+            .run(app.get_fhe_program(sub_pt).unwrap(), args, &evaluation_keys)
             .unwrap();
 
         unshield += now.elapsed().as_secs_f64();
@@ -1,9 +1,9 @@
-use crate::fhe::{FheCompile, FheFrontendCompilation};
-use crate::params::{determine_params, PlainModulusConstraint};
+use crate::fhe::{self, FheCompile, FheFrontendCompilation};
+use crate::params::{determine_params, insert_mod_switches, PlainModulusConstraint};
 use crate::zkp::{Linked, NotLinked};
 use crate::{
-    zkp, Application, CallSignature, Error, FheProgramMetadata, Params, RequiredKeys, Result,
-    SchemeType, SecurityLevel, ZkpProgramFn,
+    zkp, Application, CallSignature, Error, FheProgramMetadata, OptimizationLevel, Params,
+    RequiredKeys, Result, SchemeType, SecurityLevel, ZkpProgramFn,
 };
 use std::collections::{HashMap, HashSet};
 use std::marker::PhantomData;
@@ -143,6 +143,7 @@ struct FheCompilerData {
     plain_modulus_constraint: PlainModulusConstraint,
     security_level: SecurityLevel,
     noise_margin: u32,
+    mod_switch_outputs: bool,
 }
 
 impl Default for FheCompilerData {
@@ -155,6 +156,7 @@ impl Default for FheCompilerData {
             plain_modulus_constraint: PlainModulusConstraint::Raw(262_144),
             security_level: SecurityLevel::TC128,
             noise_margin: 20,
+            mod_switch_outputs: false,
         }
     }
 }
@@ -260,6 +262,7 @@ impl<B> CompilerData<B> {
 // method.
 pub struct GenericCompiler<T, B> {
     data: CompilerData<B>,
+    optimization_level: OptimizationLevel,
     _phantom: PhantomData<T>,
 }
 
@@ -276,6 +279,7 @@ impl Compiler {
     pub fn new() -> Self {
         Self {
             data: CompilerData::None,
+            optimization_level: OptimizationLevel::default(),
             _phantom: PhantomData,
         }
     }
@@ -294,6 +298,7 @@ impl Compiler {
 
         FheCompiler {
             data,
+            optimization_level: self.optimization_level,
             _phantom: PhantomData,
         }
     }
@@ -306,11 +311,23 @@ impl Compiler {
 
         ZkpCompiler {
             data,
+            optimization_level: self.optimization_level,
             _phantom: PhantomData,
         }
     }
 }
 
+impl<T, B> GenericCompiler<T, B> {
+    /**
+     * Set how aggressively the compiler optimizes FHE and ZKP programs.
+     * Defaults to [`OptimizationLevel::Basic`].
+     */
+    pub fn optimization_level(mut self, level: OptimizationLevel) -> Self {
+        self.optimization_level = level;
+        self
+    }
+}
+
 // This generic impl can contain public methods where the builder remains the same type, or
 // internal methods that are restricted to FHE-capable builder types.
 impl<T: marker::Fhe, B> GenericCompiler<T, B> {
@@ -357,6 +374,25 @@ impl<T: marker::Fhe, B> GenericCompiler<T, B> {
         self
     }
 
+    /**
+     * Insert modulus switches into each FHE program, dropping the primes in the
+     * coefficient modulus its noise doesn't need. Switches go as early in the
+     * program as the noise allows, so later operations (including
+     * multiplications) run faster under the smaller modulus. Smaller moduli also
+     * make output ciphertexts smaller, which helps when sending many results over
+     * a network.
+     *
+     * # Remarks
+     * Outputs still retain [`additional_noise_budget`](Self::additional_noise_budget)
+     * bits of noise budget. However, switched outputs can't be passed as
+     * arguments to other FHE programs, so this does nothing for programs with
+     * a chain count greater than 1. Defaults to `false`.
+     */
+    pub fn mod_switch_outputs(mut self, enabled: bool) -> Self {
+        self.data.fhe_data_mut().mod_switch_outputs = enabled;
+        self
+    }
+
     fn compile_fhe(&self) -> Result<HashMap<String, CompiledFheProgram>> {
         let fhe_data: &FheCompilerData = self.data.fhe_data();
 
@@ -420,6 +456,7 @@ impl<T: marker::Fhe, B> GenericCompiler<T, B> {
                 fhe_data.security_level,
                 fhe_data.noise_margin,
                 scheme,
+                self.optimization_level,
             )?,
         };
 
@@ -427,9 +464,13 @@ impl<T: marker::Fhe, B> GenericCompiler<T, B> {
             .fhe_program_fns
             .iter()
             .map(|prog| {
-                let execution_graph = prog.build(&params);
+                let execution_graph = fhe::optimize(prog.build(&params)?, self.optimization_level);
                 let mut required_keys = vec![];
-                let fhe_program_fn = execution_graph?.compile();
+                let mut fhe_program_fn = execution_graph.compile();
+
+                if fhe_data.mod_switch_outputs && prog.chain_count() == 1 {
+                    insert_mod_switches(&mut fhe_program_fn, &params, fhe_data.noise_margin);
+                }
 
                 if fhe_program_fn.requires_relin_keys() {
                     required_keys.push(RequiredKeys::Relin);
@@ -443,6 +484,7 @@ impl<T: marker::Fhe, B> GenericCompiler<T, B> {
                     params: params.clone(),
                     required_keys,
                     signature: prog.signature(),
+                    galois_steps: fhe_program_fn.galois_steps(),
                 };
 
                 let compiled_program = CompiledFheProgram {
@@ -468,16 +510,9 @@ impl<T: marker::Zkp, B: FieldSpec> GenericCompiler<T, B> {
             // As long as we've properly maintained invariants internal to the compiler, linked
             // programs should only be present when params are available.
             let params = params.expect("no params; please file a bug!").clone();
-            // Note: this is currently unsupported because determining the exact arbitrary
-            // plaintext modulus from the dynamic length of a linked ZKP input in general is not
-            // possible (as x.ilog2() is not injective). We may support this in the future.
-            if !params.plain_modulus.is_power_of_two() {
-                return Err(Error::unsupported(
-                    "Plaintext modulus must be a power of two for ZKP programs with #[linked] arguments.",
-                ));
-            }
             let result = prog.build(params.plain_modulus)?;
-            let result = zkp::compile(&result);
+            let result = zkp::optimize::<B>(result, self.optimization_level);
+            let (result, source_locations) = zkp::compile(&result);
             let metadata = ZkpProgramMetadata {
                 params: Some(params),
                 signature: prog.signature(),
@@ -485,6 +520,7 @@ impl<T: marker::Zkp, B: FieldSpec> GenericCompiler<T, B> {
             let compiled_program = CompiledZkpProgram {
                 zkp_program_fn: result,
                 metadata,
+                source_locations,
             };
             Ok((prog.name().to_owned(), compiled_program))
         });
@@ -493,7 +529,8 @@ impl<T: marker::Zkp, B: FieldSpec> GenericCompiler<T, B> {
             .iter()
             .map(|prog| {
                 let result = prog.build(())?;
-                let result = zkp::compile(&result);
+                let result = zkp::optimize::<B>(result, self.optimization_level);
+                let (result, source_locations) = zkp::compile(&result);
                 let metadata = ZkpProgramMetadata {
                     params: None,
                     signature: prog.signature(),
@@ -501,6 +538,7 @@ impl<T: marker::Zkp, B: FieldSpec> GenericCompiler<T, B> {
                 let compiled_program = CompiledZkpProgram {
                     zkp_program_fn: result,
                     metadata,
+                    source_locations,
                 };
 
                 Ok((prog.name().to_owned(), compiled_program))
@@ -526,6 +564,7 @@ where
 
         FheZkpCompiler {
             data: CompilerData::new_fhe_zkp(fhe_data, self.data.unwrap_zkp()),
+            optimization_level: self.optimization_level,
             _phantom: PhantomData,
         }
     }
@@ -599,6 +638,7 @@ impl FheCompiler {
 
         FheZkpCompiler {
             data,
+            optimization_level: self.optimization_level,
             _phantom: PhantomData,
         }
     }
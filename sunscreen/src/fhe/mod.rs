@@ -11,6 +11,10 @@ use sunscreen_runtime::{InnerPlaintext, Params};
 
 use std::cell::RefCell;
 
+mod optimize;
+
+pub(crate) use optimize::optimize;
+
 #[derive(Clone, Debug, Deserialize, Hash, Serialize, PartialEq, Eq)]
 /**
  * Represents a literal node's data.
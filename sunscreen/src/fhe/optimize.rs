@@ -0,0 +1,151 @@
+use std::collections::HashMap;
+use std::convert::Infallible;
+
+use petgraph::{stable_graph::NodeIndex, visit::EdgeRef, Direction};
+use sunscreen_compiler_common::{
+    forward_traverse_mut,
+    transforms::{common_subexpression_elimination, GraphTransforms},
+    NodeInfo,
+};
+use sunscreen_runtime::InnerPlaintext;
+
+use super::{FheFrontendCompilation, FheOperation, Literal};
+use crate::optimize::identity_operand;
+use crate::OptimizationLevel;
+
+/**
+ * Runs the optimization passes enabled by `level` on the given
+ * frontend FHE program.
+ */
+pub(crate) fn optimize(
+    mut ir: FheFrontendCompilation,
+    level: OptimizationLevel,
+) -> FheFrontendCompilation {
+    if level >= OptimizationLevel::Full {
+        dedup_literals(&mut ir);
+        simplify(&mut ir);
+    }
+
+    if level >= OptimizationLevel::Basic {
+        common_subexpression_elimination(&mut ir.0);
+    }
+
+    ir
+}
+
+/**
+ * Whether we can reason about the plaintext's value by looking at its
+ * coefficients. CKKS plaintexts are stored in NTT form and carry a
+ * scale, so we leave them alone.
+ */
+fn in_coefficient_form(p: &InnerPlaintext) -> bool {
+    match p {
+        InnerPlaintext::Seal(p) => p.iter().all(|p| !p.data.is_ntt_form()),
+    }
+}
+
+/**
+ * Whether the given literal is the plaintext polynomial `c`, which is
+ * either 0 or 1.
+ */
+fn is_constant_polynomial(literal: &Literal, c: u64) -> bool {
+    match literal {
+        Literal::Plaintext(p) if in_coefficient_form(p) && p.len() == 1 => {
+            let p = &p.as_seal_plaintext().unwrap()[0].data;
+
+            // SEAL trims trailing zero coefficients, so 0 may have none.
+            let coefficient = |i| if i < p.len() { p.get_coefficient(i) } else { 0 };
+
+            coefficient(0) == c && (1..p.len()).all(|i| p.get_coefficient(i) == 0)
+        }
+        _ => false,
+    }
+}
+
+/**
+ * Merges literal nodes with the same value so later passes see them as
+ * the same operand.
+ */
+fn dedup_literals(ir: &mut FheFrontendCompilation) {
+    let mut literals = HashMap::<Literal, NodeIndex>::new();
+    let mut duplicates = vec![];
+
+    for id in ir.node_indices() {
+        let literal = match &ir[id].operation {
+            FheOperation::Literal(Literal::Plaintext(p)) if !in_coefficient_form(p) => continue,
+            FheOperation::Literal(x) => x,
+            _ => continue,
+        };
+
+        match literals.get(literal) {
+            Some(existing) => duplicates.push((id, *existing)),
+            None => {
+                literals.insert(literal.clone(), id);
+            }
+        }
+    }
+
+    for (duplicate, existing) in duplicates {
+        let edges = ir
+            .edges_directed(duplicate, Direction::Outgoing)
+            .map(|e| (e.target(), *e.weight()))
+            .collect::<Vec<_>>();
+
+        for (target, info) in edges {
+            ir.add_edge(existing, target, info);
+        }
+
+        ir.remove_node(duplicate);
+    }
+}
+
+/**
+ * Removes operations that don't change their operand:
+ * * Adding or subtracting a plaintext 0.
+ * * Multiplying by a plaintext 1.
+ * * Negating a negation.
+ */
+fn simplify(ir: &mut FheFrontendCompilation) {
+    forward_traverse_mut(&mut ir.0, |query, id| {
+        let mut transforms = GraphTransforms::<NodeInfo<FheOperation>, _>::new();
+
+        let is_literal = |x: NodeIndex, c: u64| match &query.get_node(x).unwrap().operation {
+            FheOperation::Literal(l) => is_constant_polynomial(l, c),
+            _ => false,
+        };
+
+        let replacement = match query.get_node(id).unwrap().operation {
+            FheOperation::Negate => {
+                let x = query.get_unary_operand(id).unwrap();
+
+                match query.get_node(x).unwrap().operation {
+                    FheOperation::Negate => Some(query.get_unary_operand(x).unwrap()),
+                    _ => None,
+                }
+            }
+            FheOperation::AddPlaintext => {
+                let (left, right) = query.get_binary_operands(id).unwrap();
+
+                identity_operand(left, right, |x| is_literal(x, 0), true)
+            }
+            FheOperation::SubPlaintext => {
+                let (left, right) = query.get_binary_operands(id).unwrap();
+
+                identity_operand(left, right, |x| is_literal(x, 0), false)
+            }
+            FheOperation::MultiplyPlaintext => {
+                let (left, right) = query.get_binary_operands(id).unwrap();
+
+                identity_operand(left, right, |x| is_literal(x, 1), true)
+            }
+            _ => None,
+        };
+
+        if let Some(x) = replacement {
+            transforms.replace_node(&query, id, x.into());
+        }
+
+        Ok::<_, Infallible>(transforms)
+    })
+    .unwrap();
+}
@@ -18,6 +18,7 @@
 //!     let runtime = simple_multiply.runtime()?;
 //!
 //!     let (public_key, private_key) = runtime.generate_keys()?;
+//!     let evaluation_keys = runtime.generate_evaluation_keys(&private_key, [&multiply_program])?;
 //!
 //!     let a = runtime.encrypt(Signed::from(15), &public_key)?;
 //!     let b = runtime.encrypt(Signed::from(5), &public_key)?;
@@ -25,7 +26,7 @@
 //!     let results = runtime.run(
 //!         &multiply_program,
 //!         vec![a, b],
-//!         &public_key
+//!         &evaluation_keys
 //!     )?;
 //!
 //!     let c: Signed = runtime.decrypt(&results[0], &private_key)?;
@@ -38,6 +39,7 @@
 
 mod compiler;
 mod error;
+mod optimize;
 mod params;
 
 #[cfg(feature = "linkedproofs")]
@@ -61,19 +63,29 @@ use std::marker::PhantomData;
 
 pub use compiler::{Compiler, FheProgramFn, FheProgramFnExt, GenericCompiler};
 pub use error::{Error, Result};
+pub use optimize::OptimizationLevel;
 pub use params::PlainModulusConstraint;
 pub use seal_fhe::Plaintext as SealPlaintext;
 pub use sunscreen_compiler_macros::*;
 pub use sunscreen_fhe_program::{SchemeType, SecurityLevel};
 pub use sunscreen_runtime::{
-    CallSignature, Ciphertext, CompiledFheProgram, CompiledZkpProgram, Error as RuntimeError,
+    CallSignature, Ciphertext, CommonReferenceString, CompactOptions, CompactSerialize,
+    CompiledFheProgram, CompiledZkpProgram, DecryptionShare, Error as RuntimeError, EvaluationKeys,
     FheProgramInput, FheProgramInputTrait, FheProgramMetadata, FheRuntime, FheZkpRuntime,
-    InnerCiphertext, InnerPlaintext, Params, Plaintext, PrivateKey, ProofBuilder, PublicKey,
-    RequiredKeys, Runtime, VerificationBuilder, WithContext, ZkpProgramInput, ZkpRuntime,
+    InnerCiphertext, InnerPlaintext, KeySwitchError, KeySwitchKey, Params, Plaintext, PrivateKey,
+    ProofBuilder, PublicKey, PublicKeyShare, RelinKeyEphemeral, RelinKeyRoundOne,
+    RelinKeyRoundOneShare, RelinKeyRoundTwoShare, RequiredKeys, Runtime, SecretKeyFragment,
+    Serializable, ThresholdError, ThresholdKey, VerificationBuilder, WithContext, ZkpProgramInput,
+    ZkpRuntime,
 };
 #[cfg(feature = "bulletproofs")]
 pub use sunscreen_zkp_backend::bulletproofs;
-pub use sunscreen_zkp_backend::{Error as ZkpError, Proof, Result as ZkpResult, ZkpBackend};
+#[cfg(feature = "groth16")]
+pub use sunscreen_zkp_backend::groth16;
+pub use sunscreen_zkp_backend::{
+    ConstraintViolation, Error as ZkpError, GadgetFailure, Proof, Result as ZkpResult,
+    SourceLocation, WitnessFailure, WitnessReport, ZkpBackend,
+};
 pub use zkp::{invoke_gadget, ZkpProgramFn, ZkpProgramFnExt};
 
 #[derive(Clone)]
@@ -0,0 +1,55 @@
+use petgraph::stable_graph::NodeIndex;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+/**
+ * How aggressively the compiler optimizes FHE and ZKP programs before
+ * lowering them to their backends.
+ */
+pub enum OptimizationLevel {
+    /**
+     * Compile programs exactly as written.
+     */
+    None,
+
+    /**
+     * Eliminate common subexpressions, so repeated computations on the
+     * same operands happen only once.
+     */
+    Basic,
+
+    /**
+     * In addition to [`OptimizationLevel::Basic`], fold ZKP operations on
+     * constants, merge duplicate FHE literals and remove operations that
+     * don't change their operand (e.g. `x * 1`, `x + 0` and `-(-x)`).
+     */
+    Full,
+}
+
+impl Default for OptimizationLevel {
+    fn default() -> Self {
+        Self::Basic
+    }
+}
+
+/**
+ * If one operand of a binary operation is that operation's identity
+ * element, returns the other operand. Only the right operand can be the
+ * identity unless the operation is commutative.
+ */
+pub(crate) fn identity_operand<F>(
+    left: NodeIndex,
+    right: NodeIndex,
+    is_identity: F,
+    commutative: bool,
+) -> Option<NodeIndex>
+where
+    F: Fn(NodeIndex) -> bool,
+{
+    if is_identity(right) {
+        Some(left)
+    } else if commutative && is_identity(left) {
+        Some(right)
+    } else {
+        None
+    }
+}
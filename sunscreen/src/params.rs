@@ -1,9 +1,13 @@
-use crate::{fhe::FheCompile, Error, FheProgramFn, Result, SecurityLevel};
+use crate::{
+    fhe::{self, FheCompile},
+    Error, FheProgramFn, OptimizationLevel, Result, SecurityLevel,
+};
 
 use log::{debug, trace};
 
 use seal_fhe::{
-    BfvEncryptionParametersBuilder, CoefficientModulus, Context, KeyGenerator, Modulus,
+    BfvEncryptionParametersBuilder, BgvEncryptionParametersBuilder,
+    CkksEncryptionParametersBuilder, CoefficientModulus, Context, KeyGenerator, Modulus,
     PlainModulus,
 };
 use sunscreen_backend::noise_model::{
@@ -87,11 +91,22 @@ fn can_make_required_keys(fhe_program: &FheProgram, params: &Params) -> Result<b
         .map(|x| Modulus::new(*x).map_err(Error::from))
         .collect::<Result<Vec<Modulus>>>()?;
 
-    let enc_params = BfvEncryptionParametersBuilder::new()
-        .set_plain_modulus(plain_modulus)
-        .set_coefficient_modulus(modulus_chain)
-        .set_poly_modulus_degree(params.lattice_dimension)
-        .build()?;
+    let enc_params = match params.scheme_type {
+        SchemeType::Bfv => BfvEncryptionParametersBuilder::new()
+            .set_plain_modulus(plain_modulus)
+            .set_coefficient_modulus(modulus_chain)
+            .set_poly_modulus_degree(params.lattice_dimension)
+            .build()?,
+        SchemeType::Bgv => BgvEncryptionParametersBuilder::new()
+            .set_plain_modulus(plain_modulus)
+            .set_coefficient_modulus(modulus_chain)
+            .set_poly_modulus_degree(params.lattice_dimension)
+            .build()?,
+        SchemeType::Ckks => CkksEncryptionParametersBuilder::new()
+            .set_coefficient_modulus(modulus_chain)
+            .set_poly_modulus_degree(params.lattice_dimension)
+            .build()?,
+    };
 
     let context = Context::new(&enc_params, true, params.security_level).unwrap();
 
@@ -112,9 +127,188 @@ fn can_make_required_keys(fhe_program: &FheProgram, params: &Params) -> Result<b
     Ok(create_galois && create_relin)
 }
 
+/**
+ * The base-2 logarithm of the scale Sunscreen encodes CKKS values with. This
+ * is also the size of each prime consumed by a rescale.
+ */
+const CKKS_SCALE_BITS: u32 = 40;
+
+/**
+ * The size of the first and special primes in a CKKS modulus chain. The first
+ * prime must exceed the scale by the number of bits needed to represent the
+ * integer part of results.
+ */
+const CKKS_OUTER_PRIME_BITS: u32 = 60;
+
+/**
+ * Creates CKKS parameters for the given lattice dimension with enough primes
+ * to rescale `depth` times.
+ */
+fn ckks_params(
+    lattice_dimension: u64,
+    depth: u32,
+    security_level: SecurityLevel,
+) -> Result<Params> {
+    let bit_sizes = std::iter::once(CKKS_OUTER_PRIME_BITS)
+        .chain(std::iter::repeat(CKKS_SCALE_BITS).take(depth as usize))
+        .chain(std::iter::once(CKKS_OUTER_PRIME_BITS))
+        .map(|x| x as i32)
+        .collect::<Vec<i32>>();
+
+    let coeff = CoefficientModulus::create(lattice_dimension, &bit_sizes)?;
+
+    Ok(Params {
+        coeff_modulus: coeff.iter().map(|v| v.value()).collect(),
+        lattice_dimension,
+        plain_modulus: CKKS_SCALE_BITS as u64,
+        security_level,
+        scheme_type: SchemeType::Ckks,
+    })
+}
+
+/**
+ * Determines the minimal CKKS parameters that support the deepest chain of
+ * rescales in the given FHE programs.
+ *
+ * # Remarks
+ * Under CKKS, noise is part of the approximation error, so rather than running
+ * a noise model, this chooses the smallest lattice dimension whose modulus chain
+ * can hold one prime per rescale (times the chain count) at the given security
+ * level.
+ */
+fn determine_ckks_params(
+    fhe_program_fns: &[Box<dyn FheProgramFn>],
+    security_level: SecurityLevel,
+    optimization_level: OptimizationLevel,
+) -> Result<Params> {
+    'params_loop: for n in LATTICE_DIMENSIONS {
+        let max_bits = CoefficientModulus::max_bit_count(*n, security_level);
+
+        if max_bits < 2 * CKKS_OUTER_PRIME_BITS {
+            continue;
+        }
+
+        let max_depth = (max_bits - 2 * CKKS_OUTER_PRIME_BITS) / CKKS_SCALE_BITS;
+
+        // Build the programs with the largest chain this dimension supports
+        // to measure their depth. The compiler rebuilds them with the
+        // final parameters.
+        let candidate = match ckks_params(*n, max_depth, security_level) {
+            Ok(v) => v,
+            Err(_) => continue 'params_loop,
+        };
+
+        let mut depth = 0;
+        let mut irs = vec![];
+
+        for program in fhe_program_fns {
+            trace!("Running backend compilation for {}", program.name());
+            let ir = fhe::optimize(program.build(&candidate)?, optimization_level).compile();
+
+            ir.validate().map_err(Error::FheProgramError)?;
+
+            let program_depth = (ir.rescale_depth() * program.chain_count()) as u32;
+
+            if program_depth > max_depth {
+                trace!(
+                    "Program {} needs depth {} but lattice dimension {} supports {}",
+                    program.name(),
+                    program_depth,
+                    n,
+                    max_depth
+                );
+                continue 'params_loop;
+            }
+
+            depth = u32::max(depth, program_depth);
+            irs.push(ir);
+        }
+
+        let params = ckks_params(*n, depth, security_level)?;
+
+        for ir in &irs {
+            match can_make_required_keys(ir, &params) {
+                Ok(true) => {}
+                _ => continue 'params_loop,
+            }
+        }
+
+        debug!(
+            "Using CKKS params lattice_dimension={} and depth={}",
+            n, depth
+        );
+
+        return Ok(params);
+    }
+
+    Err(Error::NoParams)
+}
+
+/**
+ * Whether the given program's multiplicative depth alone rules out the
+ * given BFV or BGV parameters, so we needn't measure its noise.
+ *
+ * # Remarks
+ * Each ciphertext multiplication grows noise by at least a factor of the
+ * plain modulus `p`, and a fresh ciphertext starts with less than
+ * `log2(q / p)` bits of noise budget. Thus, a program of depth `d` needs
+ * more than `(d + 1) * log2(p)` bits in its coefficient modulus `q`, plus
+ * the requested noise margin. Deep programs (e.g. ones that compare
+ * encrypted values) would otherwise run under the noise model for every
+ * lattice dimension.
+ */
+fn exceeds_depth_budget(ir: &FheProgram, params: &Params, noise_margin_bits: u32) -> bool {
+    let bits = |x: &u64| 64 - x.leading_zeros();
+
+    // The last prime in a chain of several is the special prime, which
+    // only appears during key switching.
+    let data_primes = match params.coeff_modulus.len() {
+        1 => &params.coeff_modulus[..],
+        n => &params.coeff_modulus[..n - 1],
+    };
+
+    let q_bits: u32 = data_primes.iter().map(bits).sum();
+    let p_bits = bits(&params.plain_modulus);
+
+    (ir.multiplicative_depth() as u32 + 1) * p_bits + noise_margin_bits > q_bits
+}
+
+/**
+ * Returns the noise each of the given FHE program's inputs should have when
+ * measuring its noise. Ciphertext inputs are fresh encryptions unless
+ * `chain_noise_level` is nonzero, in which case they have that much invariant
+ * noise.
+ */
+fn noise_targets(ir: &FheProgram, chain_noise_level: f64) -> Vec<TargetNoiseLevel> {
+    ir.graph
+        .node_weights()
+        .filter(|n| {
+            matches!(
+                n.operation,
+                Operation::InputCiphertext(_) | Operation::InputPlaintext(_)
+            )
+        })
+        .map(|n| match n.operation {
+            Operation::InputCiphertext(_) => {
+                if chain_noise_level == 0f64 {
+                    TargetNoiseLevel::Fresh
+                } else {
+                    TargetNoiseLevel::InvariantNoise(chain_noise_level)
+                }
+            }
+            Operation::InputPlaintext(_) => TargetNoiseLevel::NotApplicable,
+            _ => unreachable!(),
+        })
+        .collect()
+}
+
 /**
  * Determines the minimal parameters required to satisfy the noise constraint for
  * the given FHE program and plaintext modulo and security level.
+ *
+ * # Remarks
+ * Under CKKS, `plaintext_constraint` and `noise_margin_bits` don't apply;
+ * parameters are chosen based on the programs' multiplicative depth instead.
  */
 pub fn determine_params(
     fhe_program_fns: &[Box<dyn FheProgramFn>],
@@ -122,7 +316,12 @@ pub fn determine_params(
     security_level: SecurityLevel,
     noise_margin_bits: u32,
     scheme_type: SchemeType,
+    optimization_level: OptimizationLevel,
 ) -> Result<Params> {
+    if scheme_type == SchemeType::Ckks {
+        return determine_ckks_params(fhe_program_fns, security_level, optimization_level);
+    }
+
     'params_loop: for (i, n) in LATTICE_DIMENSIONS.iter().enumerate() {
         // Select a plain modulus that meets needs of the passed
         // constraint.
@@ -155,11 +354,21 @@ pub fn determine_params(
         for program in fhe_program_fns {
             trace!("Successfully created parameters.");
             trace!("Running backend compilation for {}", program.name());
-            let ir = program.build(&params)?.compile();
+            let ir = fhe::optimize(program.build(&params)?, optimization_level).compile();
 
             ir.validate().map_err(Error::FheProgramError)?;
             trace!("Built and validated {}", program.name());
 
+            if exceeds_depth_budget(&ir, &params, noise_margin_bits) {
+                trace!(
+                    "Program {} with depth {} is too deep for lattice dimension {}",
+                    program.name(),
+                    ir.multiplicative_depth(),
+                    n
+                );
+                continue 'params_loop;
+            }
+
             match can_make_required_keys(&ir, &params) {
                 Ok(can_make_keys) => {
                     if !can_make_keys {
@@ -174,27 +383,7 @@ pub fn determine_params(
             let mut chain_noise_level = 0f64;
 
             for _ in 0..program.chain_count() {
-                let noise_targets = ir
-                    .graph
-                    .node_weights()
-                    .filter(|n| {
-                        matches!(
-                            n.operation,
-                            Operation::InputCiphertext(_) | Operation::InputPlaintext(_)
-                        )
-                    })
-                    .map(|n| match n.operation {
-                        Operation::InputCiphertext(_) => {
-                            if chain_noise_level == 0f64 {
-                                TargetNoiseLevel::Fresh
-                            } else {
-                                TargetNoiseLevel::InvariantNoise(chain_noise_level)
-                            }
-                        }
-                        Operation::InputPlaintext(_) => TargetNoiseLevel::NotApplicable,
-                        _ => unreachable!(),
-                    })
-                    .collect::<Vec<TargetNoiseLevel>>();
+                let noise_targets = noise_targets(&ir, chain_noise_level);
 
                 let model = match MeasuredModel::new(&ir, &params, &noise_targets) {
                     Ok(v) => v,
@@ -234,3 +423,91 @@ pub fn determine_params(
 
     Err(Error::NoParams)
 }
+
+/**
+ * Inserts modulus switches into the given compiled FHE program, dropping any
+ * primes it doesn't need to retain `noise_margin_bits` of noise budget in its
+ * outputs. The noise is measured by running the program under `params` with
+ * freshly encrypted inputs.
+ *
+ * # Remarks
+ * Switches start before the outputs and then move up the program, past as many
+ * multiplications as the measured noise allows, so intermediate operations run
+ * under smaller moduli too.
+ *
+ * Does nothing under CKKS or if the program's noise can't be measured.
+ */
+pub(crate) fn insert_mod_switches(ir: &mut FheProgram, params: &Params, noise_margin_bits: u32) {
+    if params.scheme_type == SchemeType::Ckks {
+        return;
+    }
+
+    let model = match MeasuredModel::new(ir, params, &noise_targets(ir, 0f64)) {
+        Ok(v) => v,
+        Err(e) => {
+            trace!("Not inserting modulus switches: {:#?}", e);
+            return;
+        }
+    };
+
+    let target_noise = noise_budget_to_noise(noise_margin_bits as f64);
+
+    let with_ceilings = |ceilings: &[usize]| {
+        let mut switched = ir.clone();
+
+        sunscreen_backend::insert_mod_switches(
+            &mut switched,
+            &model,
+            params,
+            target_noise,
+            ceilings,
+        );
+
+        switched
+    };
+
+    let meets_noise_target = |switched: &FheProgram| match MeasuredModel::new(
+        switched,
+        params,
+        &noise_targets(switched, 0f64),
+    ) {
+        Ok(m) => predict_noise(&m, switched)
+            .into_iter()
+            .all(|noise| noise <= target_noise),
+        Err(_) => false,
+    };
+
+    // Greedily lower the modulus of operations further from the outputs, one
+    // multiplication at a time, as long as the measured noise allows it.
+    let mut ceilings: Vec<usize> = vec![];
+    let mut best = with_ceilings(&ceilings);
+
+    for _ in 0..=ir.multiplicative_depth() {
+        let max = ceilings
+            .last()
+            .copied()
+            .unwrap_or_else(|| params.coeff_modulus.len().saturating_sub(2));
+
+        let found = (1..=max).rev().find_map(|ceiling| {
+            let switched = with_ceilings(&[ceilings.as_slice(), &[ceiling]].concat());
+
+            if meets_noise_target(&switched) {
+                Some((ceiling, switched))
+            } else {
+                None
+            }
+        });
+
+        match found {
+            Some((ceiling, switched)) => {
+                ceilings.push(ceiling);
+                best = switched;
+            }
+            None => break,
+        }
+    }
+
+    trace!("Switching the modulus with ceilings {:?}", ceilings);
+
+    *ir = best;
+}
@@ -53,6 +53,23 @@ use sunscreen_runtime::{Error as RuntimeError, Result as RuntimeResult};
  * For example, `[0, 1, 2, 3; 4, 5, 6, 7] >> 1` yields `[3, 0, 1, 2; 7, 4, 5, 6]`.
  * * `x.swap_rows()` swaps the rows. For example, `[0, 1, 2, 3; 4, 5, 6, 7].swap_rows()` yields `[4, 5, 6, 7; 0, 1, 2, 3]`.
  *
+ * Encrypted Batched values also provide `x.sum_lanes()` and
+ * `x.dot_product(y)`, which reduce over every lane using a logarithmic
+ * number of rotations.
+ *
+ * Since each lane is an integer modulo the plain modulus, encrypted
+ * Batched values also support lane-wise comparisons. `x.equals(y)`
+ * and `x.less_than(y, range)` yield 1 in lanes where the comparison
+ * holds and 0 elsewhere, while `x.max(y, range)`, `x.min(y, range)`,
+ * and `x.to_bits(n)` build on them. These evaluate polynomials over the
+ * plaintext field and are much deeper than the arithmetic operators.
+ * Encrypted [`Signed`](crate::types::bfv::Signed) and
+ * [`Unsigned`](crate::types::bfv::Unsigned) values offer the same
+ * comparisons, returning an encrypted [`Bool`](crate::types::bfv::Bool).
+ * They first decode their digits into the lanes of a Batched value,
+ * which takes rotations but no multiplications, so they too need a plain
+ * modulus that supports batching.
+ *
  * # Performance
  * The BFV scheme is parameterized by a number of values. Generally,
  * the polynomial degree has primacy in determining execution time.
@@ -91,6 +108,30 @@ impl<const LANES: usize> TypeNameInstance for Batched<LANES> {
     }
 }
 
+#[cfg(feature = "linkedproofs")]
+mod sharing {
+    use super::*;
+    use crate::types::zkp::BfvBatched;
+    use sunscreen_runtime::LinkWithZkp;
+    use sunscreen_zkp_backend::FieldSpec;
+
+    impl<const LANES: usize> LinkWithZkp for Batched<LANES> {
+        type ZkpType<F: FieldSpec> = BfvBatched<F, LANES>;
+        /// Batching spreads each lane across every coefficient, so we link all of them.
+        const DEGREE_BOUND: usize = 2 * LANES;
+
+        fn validate_link_params(params: &Params) -> RuntimeResult<()> {
+            if params.lattice_dimension as usize != 2 * LANES {
+                return Err(RuntimeError::fhe_type_error(
+                    "LANES must be exactly polynomial degree / 2 to link a Batched",
+                ));
+            }
+
+            Ok(())
+        }
+    }
+}
+
 impl<const LANES: usize> FheProgramInputTrait for Batched<LANES> {}
 impl<const LANES: usize> FheType for Batched<LANES> {}
 impl<const LANES: usize> BfvType for Batched<LANES> {}
@@ -585,6 +626,40 @@ impl<const LANES: usize> GraphCipherConstMul for Batched<LANES> {
     }
 }
 
+impl<const LANES: usize> GraphCipherConstAdd for Batched<LANES> {
+    type Left = Self;
+    type Right = i64;
+
+    fn graph_cipher_const_add(
+        a: FheProgramNode<Cipher<Self::Left>>,
+        b: Self::Right,
+    ) -> FheProgramNode<Cipher<Self::Left>> {
+        let l = Self::graph_cipher_insert(b);
+        with_fhe_ctx(|ctx| {
+            let n = ctx.add_addition_plaintext(a.ids[0], l.ids[0]);
+
+            FheProgramNode::new(&[n])
+        })
+    }
+}
+
+impl<const LANES: usize> GraphCipherConstSub for Batched<LANES> {
+    type Left = Self;
+    type Right = i64;
+
+    fn graph_cipher_const_sub(
+        a: FheProgramNode<Cipher<Self::Left>>,
+        b: Self::Right,
+    ) -> FheProgramNode<Cipher<Self::Left>> {
+        let l = Self::graph_cipher_insert(b);
+        with_fhe_ctx(|ctx| {
+            let n = ctx.add_subtraction_plaintext(a.ids[0], l.ids[0]);
+
+            FheProgramNode::new(&[n])
+        })
+    }
+}
+
 impl<const LANES: usize> GraphCipherSwapRows for Batched<LANES> {
     fn graph_cipher_swap_rows(x: FheProgramNode<Cipher<Self>>) -> FheProgramNode<Cipher<Self>> {
         with_fhe_ctx(|ctx| {
@@ -641,6 +716,44 @@ impl<const LANES: usize> LaneCount for Batched<LANES> {
     }
 }
 
+impl<const LANES: usize> FheProgramNode<Cipher<Batched<LANES>>> {
+    /**
+     * Sums every lane in both rows, broadcasting the total to every lane
+     * of the result.
+     *
+     * # Remarks
+     * This adds `x << 1`, `x << 2`, ..., `x << (LANES / 2)` in a tree of
+     * `log2(LANES)` rotations and additions, then adds the swapped rows.
+     * Rotations and additions don't increase multiplicative depth. The
+     * program requires Galois keys for exactly these rotations.
+     */
+    pub fn sum_lanes(self) -> Self {
+        let mut sum = self;
+        let mut step = 1;
+
+        while step < LANES {
+            let rotated = Batched::<LANES>::graph_cipher_rotate_left(sum, step as u64);
+            sum = Batched::<LANES>::graph_cipher_add(sum, rotated);
+            step *= 2;
+        }
+
+        let swapped = Batched::<LANES>::graph_cipher_swap_rows(sum);
+
+        Batched::<LANES>::graph_cipher_add(sum, swapped)
+    }
+
+    /**
+     * Computes the dot product of this vector and `rhs` over all lanes of
+     * both rows, broadcasting the result to every lane.
+     *
+     * # Remarks
+     * This costs one multiplication followed by [`sum_lanes`](Self::sum_lanes).
+     */
+    pub fn dot_product(self, rhs: Self) -> Self {
+        Batched::<LANES>::graph_cipher_mul(self, rhs).sum_lanes()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,308 @@
+use seal_fhe::Plaintext as SealPlaintext;
+
+use crate as sunscreen;
+use crate::{
+    fhe::{with_fhe_ctx, FheContextOps},
+    types::{intern::FheProgramNode, BfvType, Cipher, FheType, TypeNameInstance},
+    FheProgramInputTrait, Params, TypeName as DeriveTypeName, WithContext,
+};
+
+use sunscreen_runtime::{
+    InnerPlaintext, NumCiphertexts, Plaintext, TryFromPlaintext, TryIntoPlaintext,
+};
+
+use std::ops::*;
+
+#[derive(Debug, Clone, Copy, DeriveTypeName, PartialEq, Eq, Default)]
+/**
+ * A single boolean value.
+ *
+ * # Remarks
+ * A [`Bool`] encodes as the constant polynomial 0 or 1. Multiplying
+ * any BFV plaintext by this polynomial either zeroes it or leaves it
+ * unchanged, so an encrypted [`Bool`] can [`select`] between values of
+ * any BFV type.
+ *
+ * In FHE programs, `&`, `|`, `^`, and `!` compute encrypted `and`,
+ * `or`, `xor`, and `not` respectively. `and` costs a multiplication,
+ * `or` and `xor` cost a multiplication and 2 additions or subtractions,
+ * and `not` costs a plaintext subtraction.
+ */
+pub struct Bool {
+    val: bool,
+}
+
+impl NumCiphertexts for Bool {
+    const NUM_CIPHERTEXTS: usize = 1;
+}
+
+impl FheProgramInputTrait for Bool {}
+impl FheType for Bool {}
+impl BfvType for Bool {}
+
+impl std::fmt::Display for Bool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.val)
+    }
+}
+
+impl TryIntoPlaintext for Bool {
+    fn try_into_plaintext(
+        &self,
+        params: &Params,
+    ) -> std::result::Result<Plaintext, sunscreen_runtime::Error> {
+        let mut seal_plaintext = SealPlaintext::new()?;
+
+        seal_plaintext.resize(1);
+        seal_plaintext.set_coefficient(0, u64::from(self.val));
+
+        Ok(Plaintext {
+            data_type: self.type_name_instance(),
+            inner: InnerPlaintext::Seal(vec![WithContext {
+                params: params.clone(),
+                data: seal_plaintext,
+            }]),
+        })
+    }
+}
+
+impl TryFromPlaintext for Bool {
+    fn try_from_plaintext(
+        plaintext: &Plaintext,
+        _params: &Params,
+    ) -> std::result::Result<Self, sunscreen_runtime::Error> {
+        let val = match &plaintext.inner {
+            InnerPlaintext::Seal(p) => {
+                if p.len() != 1 {
+                    return Err(sunscreen_runtime::Error::IncorrectCiphertextCount);
+                }
+
+                let coeffs = (0..p[0].len())
+                    .map(|i| p[0].get_coefficient(i))
+                    .collect::<Vec<u64>>();
+
+                match coeffs.split_first() {
+                    None => false,
+                    Some((c, rest)) if *c <= 1 && rest.iter().all(|x| *x == 0) => *c == 1,
+                    _ => {
+                        return Err(sunscreen_runtime::Error::fhe_type_error(
+                            "Plaintext doesn't encode 0 or 1",
+                        ))
+                    }
+                }
+            }
+        };
+
+        Ok(Self { val })
+    }
+}
+
+impl From<bool> for Bool {
+    fn from(val: bool) -> Self {
+        Self { val }
+    }
+}
+
+impl From<Bool> for bool {
+    fn from(b: Bool) -> Self {
+        b.val
+    }
+}
+
+impl BitAnd for Bool {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        Self {
+            val: self.val & rhs.val,
+        }
+    }
+}
+
+impl BitOr for Bool {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self {
+            val: self.val | rhs.val,
+        }
+    }
+}
+
+impl BitXor for Bool {
+    type Output = Self;
+
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        Self {
+            val: self.val ^ rhs.val,
+        }
+    }
+}
+
+impl Not for Bool {
+    type Output = Self;
+
+    fn not(self) -> Self::Output {
+        Self { val: !self.val }
+    }
+}
+
+impl BitAnd for FheProgramNode<Cipher<Bool>> {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        with_fhe_ctx(|ctx| {
+            let n = ctx.add_multiplication(self.ids[0], rhs.ids[0]);
+
+            FheProgramNode::new(&[n])
+        })
+    }
+}
+
+impl BitOr for FheProgramNode<Cipher<Bool>> {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        // a + b - ab
+        with_fhe_ctx(|ctx| {
+            let sum = ctx.add_addition(self.ids[0], rhs.ids[0]);
+            let product = ctx.add_multiplication(self.ids[0], rhs.ids[0]);
+            let n = ctx.add_subtraction(sum, product);
+
+            FheProgramNode::new(&[n])
+        })
+    }
+}
+
+impl BitXor for FheProgramNode<Cipher<Bool>> {
+    type Output = Self;
+
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        // a + b - 2ab
+        with_fhe_ctx(|ctx| {
+            let sum = ctx.add_addition(self.ids[0], rhs.ids[0]);
+            let product = ctx.add_multiplication(self.ids[0], rhs.ids[0]);
+            let twice_product = ctx.add_addition(product, product);
+            let n = ctx.add_subtraction(sum, twice_product);
+
+            FheProgramNode::new(&[n])
+        })
+    }
+}
+
+impl Not for FheProgramNode<Cipher<Bool>> {
+    type Output = Self;
+
+    fn not(self) -> Self::Output {
+        // 1 - a
+        with_fhe_ctx(|ctx| {
+            let one = Bool::from(true).try_into_plaintext(&ctx.data).unwrap();
+            let one = ctx.add_plaintext_literal(one.inner);
+
+            let n = ctx.add_subtraction_plaintext(self.ids[0], one);
+            let n = ctx.add_negate(n);
+
+            FheProgramNode::new(&[n])
+        })
+    }
+}
+
+/**
+ * A value an encrypted [`Bool`] can choose between in an
+ * [`fhe_program`](crate::fhe_program). See [`select`].
+ */
+pub trait Select {
+    /**
+     * Returns `a` if `cond` is true and `b` otherwise.
+     */
+    fn select(cond: FheProgramNode<Cipher<Bool>>, a: Self, b: Self) -> Self;
+}
+
+impl<T> Select for FheProgramNode<Cipher<T>>
+where
+    T: BfvType,
+{
+    fn select(cond: FheProgramNode<Cipher<Bool>>, a: Self, b: Self) -> Self {
+        // b + cond * (a - b) for each of the value's ciphertexts.
+        with_fhe_ctx(|ctx| {
+            let ids = a
+                .ids
+                .iter()
+                .zip(b.ids.iter())
+                .map(|(a, b)| {
+                    let diff = ctx.add_subtraction(*a, *b);
+                    let chosen = ctx.add_multiplication(cond.ids[0], diff);
+
+                    ctx.add_addition(*b, chosen)
+                })
+                .collect::<Vec<_>>();
+
+            FheProgramNode::new(&ids)
+        })
+    }
+}
+
+impl<T, const N: usize> Select for [T; N]
+where
+    T: Select + Copy,
+{
+    fn select(cond: FheProgramNode<Cipher<Bool>>, a: Self, b: Self) -> Self {
+        let mut out = a;
+
+        for (o, b) in out.iter_mut().zip(b.iter()) {
+            *o = T::select(cond, *o, *b);
+        }
+
+        out
+    }
+}
+
+/**
+ * Returns `a` if the encrypted `cond` is true and `b` otherwise. `a` and
+ * `b` may be encrypted values of any BFV type (e.g.
+ * [`Signed`](crate::types::bfv::Signed) or
+ * [`Batched`](crate::types::bfv::Batched)) or arrays thereof.
+ *
+ * # Remarks
+ * This computes `b + cond * (a - b)`, which costs a subtraction, a
+ * multiplication, and an addition per ciphertext in the selected type.
+ *
+ * ```
+ * # use sunscreen::{fhe_program, types::{bfv::{select, Bool, Signed}, Cipher}};
+ * #[fhe_program(scheme = "bfv")]
+ * fn choose(cond: Cipher<Bool>, a: Cipher<Signed>, b: Cipher<Signed>) -> Cipher<Signed> {
+ *     select(cond, a, b)
+ * }
+ * ```
+ */
+pub fn select<T>(cond: FheProgramNode<Cipher<Bool>>, a: T, b: T) -> T
+where
+    T: Select,
+{
+    T::select(cond, a, b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SchemeType;
+    use seal_fhe::SecurityLevel;
+
+    #[test]
+    fn can_roundtrip_encode_bool() {
+        let params = Params {
+            lattice_dimension: 4096,
+            plain_modulus: 500,
+            coeff_modulus: vec![],
+            scheme_type: SchemeType::Bfv,
+            security_level: SecurityLevel::TC128,
+        };
+
+        for val in [false, true] {
+            let plaintext = Bool::from(val).try_into_plaintext(&params).unwrap();
+            let decoded = Bool::try_from_plaintext(&plaintext, &params).unwrap();
+
+            assert_eq!(bool::from(decoded), val);
+        }
+    }
+}
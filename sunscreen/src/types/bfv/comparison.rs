@@ -0,0 +1,583 @@
+use petgraph::stable_graph::NodeIndex;
+use seal_fhe::Plaintext as SealPlaintext;
+
+use crate::{
+    fhe::{with_fhe_ctx, FheContextOps, Literal},
+    types::{
+        bfv::{select, Batched, Bool, Signed, Unsigned},
+        intern::{powers, Cipher, FheProgramNode},
+    },
+    InnerPlaintext, WithContext,
+};
+
+type BatchedNode<const LANES: usize> = FheProgramNode<Cipher<Batched<LANES>>>;
+
+fn mul_mod(a: u64, b: u64, p: u64) -> u64 {
+    ((a as u128 * b as u128) % p as u128) as u64
+}
+
+fn pow_mod(mut a: u64, mut e: u64, p: u64) -> u64 {
+    let mut result = 1;
+
+    while e > 0 {
+        if e & 1 == 1 {
+            result = mul_mod(result, a, p);
+        }
+
+        a = mul_mod(a, a, p);
+        e >>= 1;
+    }
+
+    result
+}
+
+/**
+ * The inverse of `a` modulo the prime `p`.
+ */
+fn inv_mod(a: u64, p: u64) -> u64 {
+    pow_mod(a, p - 2, p)
+}
+
+/**
+ * Maps a signed value into `[0, p)`.
+ */
+fn to_residue(x: i64, p: u64) -> u64 {
+    x.rem_euclid(p as i64) as u64
+}
+
+/**
+ * Maps a value in `[0, p)` to its representative in `[-p/2, p/2]`, which
+ * is how batched lanes encode.
+ */
+fn centered(x: u64, p: u64) -> i64 {
+    if x > p / 2 {
+        x as i64 - p as i64
+    } else {
+        x as i64
+    }
+}
+
+/**
+ * Returns the coefficients, lowest degree first, of the unique polynomial
+ * over `Z_p` of degree less than `points.len()` passing through the given
+ * `(x, y)` points. The `x` values must be distinct modulo the prime `p`.
+ */
+fn interpolate(points: &[(u64, u64)], p: u64) -> Vec<u64> {
+    let m = points.len();
+
+    // The product of (x - x_j) over all the points.
+    let mut master = vec![1];
+
+    for (x, _) in points {
+        let mut next = vec![0; master.len() + 1];
+
+        for (i, c) in master.iter().enumerate() {
+            next[i + 1] = (next[i + 1] + c) % p;
+            next[i] = (next[i] + p - mul_mod(*c, *x, p)) % p;
+        }
+
+        master = next;
+    }
+
+    let mut coeffs = vec![0; m];
+
+    for (x_j, y_j) in points {
+        if *y_j == 0 {
+            continue;
+        }
+
+        // Divide out (x - x_j) to get the Lagrange basis polynomial's
+        // numerator.
+        let mut basis = vec![0; m];
+        let mut carry = 0;
+
+        for i in (0..m).rev() {
+            carry = (master[i + 1] + mul_mod(carry, *x_j, p)) % p;
+            basis[i] = carry;
+        }
+
+        let denominator = basis
+            .iter()
+            .rev()
+            .fold(0, |acc, c| (mul_mod(acc, *x_j, p) + c) % p);
+
+        let scale = mul_mod(*y_j, inv_mod(denominator, p), p);
+
+        for (c, b) in coeffs.iter_mut().zip(basis.iter()) {
+            *c = (*c + mul_mod(scale, *b, p)) % p;
+        }
+    }
+
+    coeffs
+}
+
+/**
+ * Evaluates the polynomial with the given coefficients in `Z_p`, lowest
+ * degree first, using precomputed `powers` of its argument.
+ *
+ * # Panics
+ * If the polynomial is constant, as in [`eval_poly`](FheProgramNode::eval_poly).
+ */
+fn eval_with_powers<const LANES: usize>(
+    powers: &[BatchedNode<LANES>],
+    coeffs: &[u64],
+    p: u64,
+) -> BatchedNode<LANES> {
+    let sum = coeffs
+        .iter()
+        .enumerate()
+        .skip(1)
+        .filter(|(_, c)| **c != 0)
+        .map(|(k, c)| match centered(*c, p) {
+            1 => powers[k - 1],
+            c => powers[k - 1] * c,
+        })
+        .reduce(|a, b| a + b);
+
+    // Rather than multiplying by 0, which gives a transparent ciphertext.
+    let sum = match sum {
+        Some(sum) => sum,
+        None => panic!("Can't evaluate a constant polynomial on a ciphertext"),
+    };
+
+    match centered(coeffs[0], p) {
+        0 => sum,
+        c => sum + c,
+    }
+}
+
+/**
+ * Evaluates the function `f` on each lane of `x`, given that every lane
+ * holds a value in `domain`.
+ */
+fn eval_on_domain<const LANES: usize, I, F>(
+    x: BatchedNode<LANES>,
+    domain: I,
+    f: F,
+) -> BatchedNode<LANES>
+where
+    I: Iterator<Item = i64>,
+    F: Fn(i64) -> i64,
+{
+    let p = BatchedNode::<LANES>::get_plain_modulus();
+
+    let points = domain
+        .map(|d| (to_residue(d, p), to_residue(f(d), p)))
+        .collect::<Vec<_>>();
+
+    let coeffs = interpolate(&points, p)
+        .into_iter()
+        .map(|c| centered(c, p))
+        .collect::<Vec<_>>();
+
+    x.eval_poly(&coeffs)
+}
+
+/**
+ * Returns 1 in each lane of `x` holding 0 and 0 elsewhere. By Fermat's
+ * little theorem, `x^(p - 1)` is 1 unless `x` is 0 modulo the plain
+ * modulus `p`.
+ */
+fn is_zero<const LANES: usize>(x: BatchedNode<LANES>) -> BatchedNode<LANES> {
+    let p = BatchedNode::<LANES>::get_plain_modulus();
+
+    -(x.pow(p - 1) - 1i64)
+}
+
+/**
+ * Returns 1 in each lane of `x` holding a value in `(-range, 0)` and 0 in
+ * each lane holding a value in `[0, range)`.
+ */
+fn is_negative<const LANES: usize>(x: BatchedNode<LANES>, range: u64) -> BatchedNode<LANES> {
+    let range = range as i64;
+
+    eval_on_domain(x, -(range - 1)..range, |d| i64::from(d < 0))
+}
+
+/**
+ * Panics unless `range` is a valid bound on the difference of two lanes
+ * under the current plain modulus.
+ */
+fn check_range(range: u64, p: u64) {
+    assert!(
+        range > 1 && 2 * range - 1 <= p,
+        "Comparison range {} must be between 2 and (p + 1) / 2 for plain modulus {}",
+        range,
+        p
+    );
+}
+
+impl<const LANES: usize> FheProgramNode<Cipher<Batched<LANES>>> {
+    /**
+     * Returns 1 in each lane where `self` and `rhs` are equal and 0
+     * elsewhere.
+     *
+     * # Remarks
+     * By Fermat's little theorem, `(a - b)^(p - 1)` is 1 unless `a == b`
+     * modulo the plain modulus `p`, in which case it's 0. This works for
+     * any lane values, but needs multiplicative depth of about
+     * `log2(p)`, which generally requires the largest lattice dimension.
+     */
+    pub fn equals(self, rhs: Self) -> Self {
+        is_zero(self - rhs)
+    }
+
+    /**
+     * Returns 1 in each lane where `self` is less than `rhs` and 0
+     * elsewhere.
+     *
+     * # Remarks
+     * Every lane of `self - rhs` must lie strictly between `-range` and
+     * `range`; otherwise, the result in that lane is undefined. This
+     * evaluates the degree `2 * range - 2` polynomial that agrees with
+     * the comparison on these values, which needs multiplicative depth
+     * `ceil(log2(2 * range - 2))`, so keep `range` small. Setting
+     * `range` to `(p + 1) / 2` compares all values, but requires about
+     * `sqrt(p)` multiplications.
+     *
+     * # Panics
+     * If `range` is less than 2 or greater than `(p + 1) / 2`, where `p`
+     * is the plain modulus. With a range of 1 the lanes must be equal, so
+     * the result wouldn't depend on them.
+     */
+    pub fn less_than(self, rhs: Self, range: u64) -> Self {
+        check_range(range, Self::get_plain_modulus());
+
+        is_negative(self - rhs, range)
+    }
+
+    /**
+     * Returns 1 in each lane where `self` is greater than `rhs` and 0
+     * elsewhere. See [`less_than`](Self::less_than) for the meaning of
+     * `range`.
+     */
+    pub fn greater_than(self, rhs: Self, range: u64) -> Self {
+        rhs.less_than(self, range)
+    }
+
+    /**
+     * Returns the larger of `self` and `rhs` in each lane. See
+     * [`less_than`](Self::less_than) for the meaning of `range`.
+     */
+    pub fn max(self, rhs: Self, range: u64) -> Self {
+        self + self.less_than(rhs, range) * (rhs - self)
+    }
+
+    /**
+     * Returns the smaller of `self` and `rhs` in each lane. See
+     * [`less_than`](Self::less_than) for the meaning of `range`.
+     */
+    pub fn min(self, rhs: Self, range: u64) -> Self {
+        rhs + self.less_than(rhs, range) * (self - rhs)
+    }
+
+    /**
+     * Decomposes each lane into its `bits` least significant bits, least
+     * significant first. Each returned value holds 0 or 1 in every lane.
+     *
+     * # Remarks
+     * Every lane must hold a value in `[0, 2^bits)`; otherwise, the
+     * result in that lane is undefined. Each bit is a degree `2^bits - 1`
+     * polynomial in `self`, so this needs multiplicative depth `bits`.
+     * The bits share the powers of `self`, so decomposing into all the
+     * bits costs about as much as computing one of them.
+     *
+     * # Panics
+     * If `2^bits` exceeds the plain modulus.
+     */
+    pub fn to_bits(self, bits: usize) -> Vec<Self> {
+        let p = Self::get_plain_modulus();
+
+        assert!(
+            bits > 0 && bits < 64 && 1u64 << bits <= p,
+            "Can't decompose into {} bits under plain modulus {}",
+            bits,
+            p
+        );
+
+        let size = 1i64 << bits;
+        let powers = powers(self, usize::max(size as usize - 1, 1));
+
+        (0..bits)
+            .map(|i| {
+                let points = (0..size)
+                    .map(|d| (d as u64, ((d >> i) & 1) as u64))
+                    .collect::<Vec<_>>();
+
+                eval_with_powers(&powers, &interpolate(&points, p), p)
+            })
+            .collect()
+    }
+}
+
+/**
+ * Returns the number of steps to rotate rows left and whether to then swap
+ * rows so a ciphertext undergoes the automorphism `X -> X^elt`, where `elt`
+ * is odd and `n` is the lattice dimension.
+ *
+ * # Remarks
+ * Rotating left by `k` steps applies `X -> X^(3^k)` and swapping rows
+ * applies `X -> X^(2n - 1)`. These generate every automorphism, since
+ * every odd residue modulo `2n` is `3^k` or `-3^k` for some `k < n / 2`.
+ */
+fn galois_rotation(elt: u64, n: u64) -> (u64, bool) {
+    let m = 2 * n;
+    let mut power = 1;
+
+    for k in 0..n / 2 {
+        if power == elt {
+            return (k, false);
+        } else if power == m - elt {
+            return (k, true);
+        }
+
+        power = power * 3 % m;
+    }
+
+    unreachable!("{} isn't a unit modulo {}", elt, m);
+}
+
+/**
+ * Returns `x(2)` modulo the plain modulus in every lane, given the
+ * ciphertext at `x` encrypting the polynomial `x(X)`. [`Signed`] and
+ * [`Unsigned`] values encode as such polynomials, with their binary digits
+ * as coefficients.
+ *
+ * # Remarks
+ * Since `X^n = -1`, where `n` is the lattice dimension, multiplying `x(X)`
+ * by `(1 - 2 X^(n - 1) - 4 X^(n - 2) - ...) / n` moves `x(2) / n` into the
+ * constant coefficient. Summing the images of this product under the `n`
+ * automorphisms `X -> X^k` for odd `k` (i.e. its trace) cancels every
+ * other coefficient and multiplies the constant coefficient by `n`. This
+ * takes `log2(n)` rotations and row swaps but no ciphertext
+ * multiplications. The result is a constant polynomial, which batches as
+ * the same value in every lane.
+ *
+ * # Panics
+ * If the plain modulus doesn't support batching, since key switching
+ * for rotations and computing each lane both require it.
+ */
+fn to_lanes(x: NodeIndex) -> BatchedNode<1> {
+    with_fhe_ctx(|ctx| {
+        let params = ctx.data.clone();
+        let p = params.plain_modulus;
+        let n = params.lattice_dimension;
+
+        assert!(
+            p % (2 * n) == 1,
+            "Comparing Signed or Unsigned values needs a plain modulus that supports batching, but {} doesn't for lattice dimension {}",
+            p,
+            n
+        );
+
+        let n_inv = inv_mod(n % p, p);
+
+        let mut plaintext = SealPlaintext::new().unwrap();
+        plaintext.resize(n as usize);
+        plaintext.set_coefficient(0, n_inv);
+
+        let mut power = 1;
+
+        for i in 1..n as usize {
+            power = mul_mod(power, 2, p);
+            plaintext.set_coefficient(n as usize - i, p - mul_mod(power, n_inv, p));
+        }
+
+        let shift = ctx.add_plaintext_literal(InnerPlaintext::Seal(vec![WithContext {
+            params,
+            data: plaintext,
+        }]));
+
+        let mut y = ctx.add_multiplication_plaintext(x, shift);
+
+        // Each step sums over a subgroup twice as large as the last, using
+        // the automorphisms X -> X^(n + 1), X -> X^(n / 2 + 1), ...,
+        // X -> X^3.
+        let mut k = n;
+
+        while k > 1 {
+            let (steps, swap) = galois_rotation(k + 1, n);
+
+            let mut image = y;
+
+            if steps > 0 {
+                let steps = ctx.add_literal(Literal::U64(steps));
+                image = ctx.add_rotate_left(image, steps);
+            }
+
+            if swap {
+                image = ctx.add_swap_rows(image);
+            }
+
+            y = ctx.add_addition(y, image);
+            k /= 2;
+        }
+
+        FheProgramNode::new(&[y])
+    })
+}
+
+/**
+ * Returns `a(2) - b(2)` modulo the plain modulus in every lane. See
+ * [`to_lanes`].
+ */
+fn digit_difference(a: NodeIndex, b: NodeIndex) -> BatchedNode<1> {
+    // Decoding is linear, so we only need to decode the difference.
+    let diff = with_fhe_ctx(|ctx| ctx.add_subtraction(a, b));
+
+    to_lanes(diff)
+}
+
+macro_rules! impl_digit_comparisons {
+    (@methods) => {
+        /**
+         * Returns whether `self` and `rhs` are equal modulo the plain
+         * modulus `p`.
+         *
+         * # Remarks
+         * This decodes the difference of the values into the lanes of a
+         * [`Batched`] value and tests it as `Batched` values' `equals`
+         * does, which needs multiplicative depth of about `log2(p)`.
+         *
+         * # Panics
+         * If the plain modulus doesn't support batching (e.g. use
+         * [`PlainModulusConstraint::BatchingMinimum`](crate::PlainModulusConstraint::BatchingMinimum)).
+         */
+        pub fn equals(self, rhs: Self) -> FheProgramNode<Cipher<Bool>> {
+            let eq = is_zero(digit_difference(self.ids[0], rhs.ids[0]));
+
+            FheProgramNode::new(eq.ids)
+        }
+
+        /**
+         * Returns whether `self` is less than `rhs`.
+         *
+         * # Remarks
+         * `self - rhs` must lie strictly between `-range` and `range`;
+         * otherwise, the result is undefined. This decodes the difference
+         * of the values into the lanes of a [`Batched`] value and compares
+         * it as `Batched` values' `less_than` does, which needs
+         * multiplicative depth `ceil(log2(2 * range - 2))`.
+         *
+         * # Panics
+         * If `range` is less than 2 or greater than `(p + 1) / 2`, where
+         * `p` is the plain modulus, or if the plain modulus doesn't support
+         * batching.
+         */
+        pub fn less_than(self, rhs: Self, range: u64) -> FheProgramNode<Cipher<Bool>> {
+            check_range(range, Self::get_plain_modulus());
+
+            let lt = is_negative(digit_difference(self.ids[0], rhs.ids[0]), range);
+
+            FheProgramNode::new(lt.ids)
+        }
+
+        /**
+         * Returns whether `self` is greater than `rhs`. See
+         * [`less_than`](Self::less_than) for the meaning of `range`.
+         */
+        pub fn greater_than(self, rhs: Self, range: u64) -> FheProgramNode<Cipher<Bool>> {
+            rhs.less_than(self, range)
+        }
+
+        /**
+         * Returns the larger of `self` and `rhs`. See
+         * [`less_than`](Self::less_than) for the meaning of `range`.
+         */
+        pub fn max(self, rhs: Self, range: u64) -> Self {
+            select(self.less_than(rhs, range), rhs, self)
+        }
+
+        /**
+         * Returns the smaller of `self` and `rhs`. See
+         * [`less_than`](Self::less_than) for the meaning of `range`.
+         */
+        pub fn min(self, rhs: Self, range: u64) -> Self {
+            select(self.less_than(rhs, range), self, rhs)
+        }
+    };
+    ($ty:ty, const $n:ident) => {
+        impl<const $n: usize> FheProgramNode<Cipher<$ty>> {
+            impl_digit_comparisons!(@methods);
+        }
+    };
+    ($ty:ty) => {
+        impl FheProgramNode<Cipher<$ty>> {
+            impl_digit_comparisons!(@methods);
+        }
+    };
+}
+
+impl_digit_comparisons!(Signed);
+impl_digit_comparisons!(Unsigned<LIMBS>, const LIMBS);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eval(coeffs: &[u64], x: u64, p: u64) -> u64 {
+        coeffs
+            .iter()
+            .rev()
+            .fold(0, |acc, c| (mul_mod(acc, x, p) + c) % p)
+    }
+
+    #[test]
+    fn interpolates_comparison() {
+        let p = 65537;
+        let range = 8i64;
+
+        let points = (-(range - 1)..range)
+            .map(|d| (to_residue(d, p), u64::from(d < 0)))
+            .collect::<Vec<_>>();
+
+        let coeffs = interpolate(&points, p);
+
+        assert_eq!(coeffs.len(), 15);
+
+        for (x, y) in points {
+            assert_eq!(eval(&coeffs, x, p), y);
+        }
+    }
+
+    #[test]
+    fn interpolates_over_entire_field() {
+        let p = 17;
+
+        let points = (0..p).map(|x| (x, x % 3)).collect::<Vec<_>>();
+
+        let coeffs = interpolate(&points, p);
+
+        for (x, y) in points {
+            assert_eq!(eval(&coeffs, x, p), y);
+        }
+    }
+
+    #[test]
+    fn finds_rotations_for_trace_automorphisms() {
+        for n in [1024u64, 4096, 32768] {
+            let m = 2 * n;
+            let mut k = n;
+
+            while k > 1 {
+                let (steps, swap) = galois_rotation(k + 1, n);
+
+                assert!(steps < n / 2);
+
+                let elt = pow_mod(3, steps, m);
+                let elt = if swap { m - elt } else { elt };
+
+                assert_eq!(elt, k + 1);
+
+                k /= 2;
+            }
+        }
+    }
+
+    #[test]
+    fn centers_residues() {
+        assert_eq!(centered(to_residue(-3, 17), 17), -3);
+        assert_eq!(centered(to_residue(8, 17), 17), 8);
+        assert_eq!(centered(to_residue(9, 17), 17), -8);
+    }
+}
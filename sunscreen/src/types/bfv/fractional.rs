@@ -174,6 +174,33 @@ impl<const INT_BITS: usize> NumCiphertexts for Fractional<INT_BITS> {
     const NUM_CIPHERTEXTS: usize = 1;
 }
 
+#[cfg(feature = "linkedproofs")]
+mod sharing {
+    use super::*;
+    use crate::types::zkp::BfvFractional;
+    use sunscreen_runtime::LinkWithZkp;
+    use sunscreen_zkp_backend::FieldSpec;
+
+    impl<const INT_BITS: usize> LinkWithZkp for Fractional<INT_BITS> {
+        type ZkpType<F: FieldSpec> = BfvFractional<F, INT_BITS>;
+        /// The integer part of the value lives in the `INT_BITS` lowest coefficients.
+        const DEGREE_BOUND: usize = INT_BITS;
+        /// The fractional part lives in the highest coefficients. We link 64 of them, which
+        /// covers the 52 bit mantissa of any value that isn't vanishingly small.
+        const HIGH_DEGREE_BOUND: usize = 64;
+
+        fn validate_link_params(params: &Params) -> sunscreen_runtime::Result<()> {
+            if INT_BITS + Self::HIGH_DEGREE_BOUND > params.lattice_dimension as usize {
+                return Err(sunscreen_runtime::Error::fhe_type_error(
+                    "INT_BITS + 64 must be <= polynomial degree to link a Fractional",
+                ));
+            }
+
+            Ok(())
+        }
+    }
+}
+
 impl<const INT_BITS: usize> FheProgramInputTrait for Fractional<INT_BITS> {}
 
 impl<const INT_BITS: usize> Default for Fractional<INT_BITS> {
@@ -1,10 +1,13 @@
 mod batched;
+mod boolean;
+mod comparison;
 mod fractional;
 mod rational;
 mod signed;
 mod unsigned;
 
 pub use batched::*;
+pub use boolean::*;
 pub use fractional::*;
 pub use rational::*;
 pub use signed::*;
@@ -0,0 +1,3 @@
+mod real;
+
+pub use real::*;
@@ -0,0 +1,683 @@
+use crate::{
+    fhe::{with_fhe_ctx, FheContextOps, Literal},
+    types::{
+        intern::{Cipher, FheProgramNode},
+        ops::*,
+        CkksType, FheType, LaneCount, NumCiphertexts, TryFromPlaintext, TryIntoPlaintext, Type,
+        TypeName, TypeNameInstance, Version,
+    },
+    FheProgramInputTrait, InnerPlaintext, Params, Plaintext, SchemeType, WithContext,
+};
+use seal_fhe::{
+    CKKSEncoder, CkksEncryptionParametersBuilder, Context as SealContext, Modulus,
+    Result as SealResult,
+};
+use std::ops::*;
+use sunscreen_runtime::{Error as RuntimeError, Result as RuntimeResult};
+
+/**
+ * A batched vector of approximate real numbers. The vector has `LANES`
+ * elements. The `LANES` value must be a power of 2 up to 16384.
+ *
+ * # Remarks
+ * Unlike the BFV types, the CKKS scheme computes on approximate values.
+ * Each encryption, addition, and multiplication introduces a tiny amount
+ * of error into the least significant bits of each lane, so decrypted
+ * results should be compared against a tolerance rather than for
+ * equality. In exchange, fixed-point arithmetic on reals doesn't suffer
+ * the overflow conditions of [`Fractional`](crate::types::bfv::Fractional).
+ *
+ * Values encode scaled by `2^40`, which gives roughly 40 bits of
+ * precision. After each multiplication, the compiler inserts a rescale
+ * operation that divides the scale back down and consumes one level of the
+ * modulus chain. The compiler chooses the shortest modulus chain that
+ * supports your FHE program's multiplicative depth, so deep programs
+ * require larger polynomial degrees.
+ *
+ * Plaintexts in the CKKS scheme have `N/2` slots, where N is the scheme's
+ * polynomial degree. Should the compiler choose a degree larger than
+ * `2*LANES`, the Real type will automatically repeat the lanes so that
+ * rotation operations behave as if you only have `LANES` elements.
+ *
+ * Rotations are exposed as the `<<` and `>>` operators:
+ * * `x << n`, where n is a u64 rotates each lane n places to the left.
+ * For example, `[0, 1, 2, 3] << 3` yields `[3, 0, 1, 2]`.
+ * * `x >> n`, where n is a u64 rotates each lane n places to the right.
+ * For example, `[0, 1, 2, 3] >> 1` yields `[3, 0, 1, 2]`.
+ *
+ * CKKS slots hold complex numbers, but this type only uses their real
+ * parts: it encodes lanes with a zero imaginary part and decodes only the
+ * real part. Sunscreen doesn't have a complex batched type, so complex
+ * conjugation is only available through `seal_fhe`'s `CKKSEvaluator`.
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Real<const LANES: usize> {
+    data: [f64; LANES],
+}
+
+impl<const LANES: usize> Real<LANES> {
+    /**
+     * Returns whether each lane in `self` is within `tolerance` of the
+     * corresponding lane in `other`.
+     */
+    pub fn approx_eq(&self, other: &Self, tolerance: f64) -> bool {
+        self.data
+            .iter()
+            .zip(other.data.iter())
+            .all(|(x, y)| (x - y).abs() <= tolerance)
+    }
+}
+
+impl<const LANES: usize> NumCiphertexts for Real<LANES> {
+    const NUM_CIPHERTEXTS: usize = 1;
+}
+
+impl<const LANES: usize> TypeName for Real<LANES> {
+    fn type_name() -> Type {
+        let version = env!("CARGO_PKG_VERSION");
+
+        Type {
+            name: format!("sunscreen::types::ckks::Real<{}>", LANES),
+            version: Version::parse(version).expect("Crate version is not a valid semver"),
+            is_encrypted: false,
+        }
+    }
+}
+
+impl<const LANES: usize> TypeNameInstance for Real<LANES> {
+    fn type_name_instance(&self) -> Type {
+        Self::type_name()
+    }
+}
+
+impl<const LANES: usize> FheProgramInputTrait for Real<LANES> {}
+impl<const LANES: usize> FheType for Real<LANES> {}
+impl<const LANES: usize> CkksType for Real<LANES> {}
+
+impl<const LANES: usize> std::fmt::Display for Real<LANES> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let chars_remaining = f.width().unwrap_or(usize::MAX);
+
+        if chars_remaining > "[..]".len() {
+            let mut row_chars = chars_remaining - "[]".len();
+
+            write!(f, "[")?;
+
+            for (i, val) in self.data.iter().enumerate() {
+                let val = if i + 1 < self.data.len() {
+                    format!("{}, ", val)
+                } else {
+                    format!("{}", val)
+                };
+
+                if val.len() > row_chars {
+                    write!(f, "..")?;
+                    break;
+                }
+
+                write!(f, "{}", val)?;
+                row_chars -= val.len();
+            }
+
+            write!(f, "]")
+        } else if chars_remaining >= "[..]".len() {
+            write!(f, "[..]")
+        } else {
+            write!(f, "")
+        }
+    }
+}
+
+fn make_ckks_context(params: &Params) -> RuntimeResult<SealContext> {
+    if params.scheme_type != SchemeType::Ckks {
+        return Err(RuntimeError::fhe_type_error(
+            "Real values require the CKKS scheme",
+        ));
+    }
+
+    let encryption_params = CkksEncryptionParametersBuilder::new()
+        .set_poly_modulus_degree(params.lattice_dimension)
+        .set_coefficient_modulus(
+            params
+                .coeff_modulus
+                .iter()
+                .map(|x| Modulus::new(*x))
+                .collect::<SealResult<Vec<Modulus>>>()?,
+        )
+        .build()?;
+
+    Ok(SealContext::new(
+        &encryption_params,
+        false,
+        params.security_level,
+    )?)
+}
+
+impl<const LANES: usize> TryIntoPlaintext for Real<LANES> {
+    fn try_into_plaintext(
+        &self,
+        params: &Params,
+    ) -> std::result::Result<Plaintext, sunscreen_runtime::Error> {
+        if (params.lattice_dimension / 2) as usize % LANES != 0 {
+            return Err(RuntimeError::fhe_type_error("LANES must be a power two"));
+        }
+
+        if 2 * LANES > params.lattice_dimension as usize {
+            return Err(RuntimeError::fhe_type_error(
+                "LANES must be <= polynomial degree / 2",
+            ));
+        }
+
+        let context = make_ckks_context(params)?;
+        let encoder = CKKSEncoder::new(&context)?;
+
+        let reps = params.lattice_dimension as usize / (2 * LANES);
+        let data = self.data.repeat(reps);
+
+        // Under CKKS, the plain modulus parameter holds the log2 of the scale.
+        let scale = 2f64.powi(params.plain_modulus as i32);
+
+        let plaintext = encoder.encode_f64(&data, &context.get_first_parms_id(), scale)?;
+
+        Ok(Plaintext {
+            data_type: Self::type_name(),
+            inner: InnerPlaintext::Seal(vec![WithContext {
+                params: params.clone(),
+                data: plaintext,
+            }]),
+        })
+    }
+}
+
+impl<const LANES: usize> TryFromPlaintext for Real<LANES> {
+    fn try_from_plaintext(
+        plaintext: &Plaintext,
+        params: &Params,
+    ) -> std::result::Result<Self, sunscreen_runtime::Error> {
+        let plaintext = plaintext.inner_as_seal_plaintext()?;
+
+        if plaintext.len() != 1 {
+            return Err(sunscreen_runtime::Error::fhe_type_error(
+                "Expected 1 plaintext",
+            ));
+        }
+
+        if plaintext[0].params != *params {
+            return Err(sunscreen_runtime::Error::ParameterMismatch);
+        }
+
+        let context = make_ckks_context(params)?;
+        let encoder = CKKSEncoder::new(&context)?;
+
+        let data = encoder.decode_f64(&plaintext[0].data)?;
+
+        Ok(Self {
+            data: data
+                .iter()
+                .take(LANES)
+                .copied()
+                .collect::<Vec<f64>>()
+                .try_into()
+                .map_err(|_| {
+                    RuntimeError::fhe_type_error(&format!(
+                        "Failed to convert Vec to [f64;{}]",
+                        LANES
+                    ))
+                })?,
+        })
+    }
+}
+
+impl<const LANES: usize> TryFrom<Vec<f64>> for Real<LANES> {
+    type Error = RuntimeError;
+
+    fn try_from(data: Vec<f64>) -> RuntimeResult<Self> {
+        Ok(Self {
+            data: data.try_into().map_err(|_| {
+                RuntimeError::fhe_type_error(&format!("Failed to convert Vec to [f64;{}]", LANES))
+            })?,
+        })
+    }
+}
+
+impl<const LANES: usize> From<Real<LANES>> for Vec<f64> {
+    fn from(val: Real<LANES>) -> Self {
+        val.data.into()
+    }
+}
+
+impl<const LANES: usize> From<[f64; LANES]> for Real<LANES> {
+    fn from(data: [f64; LANES]) -> Self {
+        Self { data }
+    }
+}
+
+impl<const LANES: usize> From<Real<LANES>> for [f64; LANES] {
+    fn from(val: Real<LANES>) -> Self {
+        val.data
+    }
+}
+
+impl<const LANES: usize> From<f64> for Real<LANES> {
+    fn from(data: f64) -> Self {
+        // Splat the input across all the lanes.
+        Self {
+            data: [data; LANES],
+        }
+    }
+}
+
+impl<const LANES: usize> Add for Real<LANES> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let data: [f64; LANES] = self
+            .data
+            .iter()
+            .zip(rhs.data.iter())
+            .map(|(x, y)| x + y)
+            .collect::<Vec<f64>>()
+            .try_into()
+            .unwrap();
+
+        Self { data }
+    }
+}
+
+impl<const LANES: usize> Sub for Real<LANES> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        let data: [f64; LANES] = self
+            .data
+            .iter()
+            .zip(rhs.data.iter())
+            .map(|(x, y)| x - y)
+            .collect::<Vec<f64>>()
+            .try_into()
+            .unwrap();
+
+        Self { data }
+    }
+}
+
+impl<const LANES: usize> Mul for Real<LANES> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let data: [f64; LANES] = self
+            .data
+            .iter()
+            .zip(rhs.data.iter())
+            .map(|(x, y)| x * y)
+            .collect::<Vec<f64>>()
+            .try_into()
+            .unwrap();
+
+        Self { data }
+    }
+}
+
+impl<const LANES: usize> Neg for Real<LANES> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        let data: [f64; LANES] = self
+            .data
+            .iter()
+            .map(|x| -x)
+            .collect::<Vec<f64>>()
+            .try_into()
+            .unwrap();
+
+        Self { data }
+    }
+}
+
+impl<const LANES: usize> Shl<u64> for Real<LANES> {
+    type Output = Self;
+
+    fn shl(self, x: u64) -> Self::Output {
+        let mut data = self.data;
+        data.rotate_left(x as usize % LANES);
+
+        Self { data }
+    }
+}
+
+impl<const LANES: usize> Shr<u64> for Real<LANES> {
+    type Output = Self;
+
+    fn shr(self, x: u64) -> Self::Output {
+        let mut data = self.data;
+        data.rotate_right(x as usize % LANES);
+
+        Self { data }
+    }
+}
+
+impl<const LANES: usize> Index<usize> for Real<LANES> {
+    type Output = f64;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.data[index]
+    }
+}
+
+impl<const LANES: usize> GraphCipherAdd for Real<LANES> {
+    type Left = Self;
+    type Right = Self;
+
+    fn graph_cipher_add(
+        a: FheProgramNode<Cipher<Self::Left>>,
+        b: FheProgramNode<Cipher<Self::Right>>,
+    ) -> FheProgramNode<Cipher<Self::Left>> {
+        with_fhe_ctx(|ctx| {
+            let n = ctx.add_addition(a.ids[0], b.ids[0]);
+
+            FheProgramNode::new(&[n])
+        })
+    }
+}
+
+impl<const LANES: usize> GraphCipherPlainAdd for Real<LANES> {
+    type Left = Self;
+    type Right = Self;
+
+    fn graph_cipher_plain_add(
+        a: FheProgramNode<Cipher<Self::Left>>,
+        b: FheProgramNode<Self::Right>,
+    ) -> FheProgramNode<Cipher<Self::Left>> {
+        with_fhe_ctx(|ctx| {
+            let n = ctx.add_addition_plaintext(a.ids[0], b.ids[0]);
+
+            FheProgramNode::new(&[n])
+        })
+    }
+}
+
+impl<const LANES: usize> GraphCipherConstAdd for Real<LANES> {
+    type Left = Self;
+    type Right = f64;
+
+    fn graph_cipher_const_add(
+        a: FheProgramNode<Cipher<Self::Left>>,
+        b: Self::Right,
+    ) -> FheProgramNode<Cipher<Self::Left>> {
+        let l = Self::graph_cipher_insert(b);
+        with_fhe_ctx(|ctx| {
+            let n = ctx.add_addition_plaintext(a.ids[0], l.ids[0]);
+
+            FheProgramNode::new(&[n])
+        })
+    }
+}
+
+impl<const LANES: usize> GraphCipherSub for Real<LANES> {
+    type Left = Self;
+    type Right = Self;
+
+    fn graph_cipher_sub(
+        a: FheProgramNode<Cipher<Self::Left>>,
+        b: FheProgramNode<Cipher<Self::Right>>,
+    ) -> FheProgramNode<Cipher<Self::Left>> {
+        with_fhe_ctx(|ctx| {
+            let n = ctx.add_subtraction(a.ids[0], b.ids[0]);
+
+            FheProgramNode::new(&[n])
+        })
+    }
+}
+
+impl<const LANES: usize> GraphCipherPlainSub for Real<LANES> {
+    type Left = Self;
+    type Right = Self;
+
+    fn graph_cipher_plain_sub(
+        a: FheProgramNode<Cipher<Self::Left>>,
+        b: FheProgramNode<Self::Right>,
+    ) -> FheProgramNode<Cipher<Self::Left>> {
+        with_fhe_ctx(|ctx| {
+            let n = ctx.add_subtraction_plaintext(a.ids[0], b.ids[0]);
+
+            FheProgramNode::new(&[n])
+        })
+    }
+}
+
+impl<const LANES: usize> GraphPlainCipherSub for Real<LANES> {
+    type Left = Self;
+    type Right = Self;
+
+    fn graph_plain_cipher_sub(
+        a: FheProgramNode<Self::Left>,
+        b: FheProgramNode<Cipher<Self::Right>>,
+    ) -> FheProgramNode<Cipher<Self::Left>> {
+        with_fhe_ctx(|ctx| {
+            let n = ctx.add_subtraction_plaintext(b.ids[0], a.ids[0]);
+            let n = ctx.add_negate(n);
+
+            FheProgramNode::new(&[n])
+        })
+    }
+}
+
+impl<const LANES: usize> GraphCipherConstSub for Real<LANES> {
+    type Left = Self;
+    type Right = f64;
+
+    fn graph_cipher_const_sub(
+        a: FheProgramNode<Cipher<Self::Left>>,
+        b: Self::Right,
+    ) -> FheProgramNode<Cipher<Self::Left>> {
+        let l = Self::graph_cipher_insert(b);
+        with_fhe_ctx(|ctx| {
+            let n = ctx.add_subtraction_plaintext(a.ids[0], l.ids[0]);
+
+            FheProgramNode::new(&[n])
+        })
+    }
+}
+
+impl<const LANES: usize> GraphConstCipherSub for Real<LANES> {
+    type Left = f64;
+    type Right = Self;
+
+    fn graph_const_cipher_sub(
+        a: Self::Left,
+        b: FheProgramNode<Cipher<Self::Right>>,
+    ) -> FheProgramNode<Cipher<Self::Right>> {
+        let l = Self::graph_cipher_insert(a);
+        with_fhe_ctx(|ctx| {
+            let n = ctx.add_subtraction_plaintext(b.ids[0], l.ids[0]);
+            let n = ctx.add_negate(n);
+
+            FheProgramNode::new(&[n])
+        })
+    }
+}
+
+impl<const LANES: usize> GraphCipherMul for Real<LANES> {
+    type Left = Self;
+    type Right = Self;
+
+    fn graph_cipher_mul(
+        a: FheProgramNode<Cipher<Self::Left>>,
+        b: FheProgramNode<Cipher<Self::Right>>,
+    ) -> FheProgramNode<Cipher<Self::Left>> {
+        with_fhe_ctx(|ctx| {
+            let n = ctx.add_multiplication(a.ids[0], b.ids[0]);
+
+            FheProgramNode::new(&[n])
+        })
+    }
+}
+
+impl<const LANES: usize> GraphCipherPlainMul for Real<LANES> {
+    type Left = Self;
+    type Right = Self;
+
+    fn graph_cipher_plain_mul(
+        a: FheProgramNode<Cipher<Self::Left>>,
+        b: FheProgramNode<Self::Right>,
+    ) -> FheProgramNode<Cipher<Self::Left>> {
+        with_fhe_ctx(|ctx| {
+            let n = ctx.add_multiplication_plaintext(a.ids[0], b.ids[0]);
+
+            FheProgramNode::new(&[n])
+        })
+    }
+}
+
+impl<const LANES: usize> GraphCipherConstMul for Real<LANES> {
+    type Left = Self;
+    type Right = f64;
+
+    fn graph_cipher_const_mul(
+        a: FheProgramNode<Cipher<Self::Left>>,
+        b: Self::Right,
+    ) -> FheProgramNode<Cipher<Self::Left>> {
+        let l = Self::graph_cipher_insert(b);
+        with_fhe_ctx(|ctx| {
+            let n = ctx.add_multiplication_plaintext(a.ids[0], l.ids[0]);
+
+            FheProgramNode::new(&[n])
+        })
+    }
+}
+
+impl<const LANES: usize> GraphCipherInsert for Real<LANES> {
+    type Lit = f64;
+    type Val = Self;
+
+    fn graph_cipher_insert(lit: Self::Lit) -> FheProgramNode<Self::Val> {
+        with_fhe_ctx(|ctx| {
+            let lit = Self::from(lit).try_into_plaintext(&ctx.data).unwrap();
+            let l = ctx.add_plaintext_literal(lit.inner);
+
+            FheProgramNode::new(&[l])
+        })
+    }
+}
+
+impl<const LANES: usize> GraphCipherRotateLeft for Real<LANES> {
+    fn graph_cipher_rotate_left(
+        x: FheProgramNode<Cipher<Self>>,
+        y: u64,
+    ) -> FheProgramNode<Cipher<Self>> {
+        with_fhe_ctx(|ctx| {
+            let y = ctx.add_literal(Literal::U64(y));
+            let n = ctx.add_rotate_left(x.ids[0], y);
+
+            FheProgramNode::new(&[n])
+        })
+    }
+}
+
+impl<const LANES: usize> GraphCipherRotateRight for Real<LANES> {
+    fn graph_cipher_rotate_right(
+        x: FheProgramNode<Cipher<Self>>,
+        y: u64,
+    ) -> FheProgramNode<Cipher<Self>> {
+        with_fhe_ctx(|ctx| {
+            let y = ctx.add_literal(Literal::U64(y));
+            let n = ctx.add_rotate_right(x.ids[0], y);
+
+            FheProgramNode::new(&[n])
+        })
+    }
+}
+
+impl<const LANES: usize> GraphCipherNeg for Real<LANES> {
+    type Val = Self;
+
+    fn graph_cipher_neg(x: FheProgramNode<Cipher<Self>>) -> FheProgramNode<Cipher<Self::Val>> {
+        with_fhe_ctx(|ctx| {
+            let n = ctx.add_negate(x.ids[0]);
+
+            FheProgramNode::new(&[n])
+        })
+    }
+}
+
+impl<const LANES: usize> LaneCount for Real<LANES> {
+    fn lane_count() -> usize {
+        LANES
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use seal_fhe::{CoefficientModulus, SecurityLevel};
+
+    #[test]
+    fn can_roundtrip_encode_real() {
+        let params = Params {
+            lattice_dimension: 8192,
+            plain_modulus: 40,
+            coeff_modulus: CoefficientModulus::create(8192, &[60, 40, 40, 60])
+                .unwrap()
+                .iter()
+                .map(|x| x.value())
+                .collect::<Vec<u64>>(),
+            scheme_type: SchemeType::Ckks,
+            security_level: SecurityLevel::TC128,
+        };
+
+        let x = Real::<4>::from([0.5, -1.25, 3.0, 1e3]);
+
+        let plaintext = x.try_into_plaintext(&params).unwrap();
+        let y = Real::<4>::try_from_plaintext(&plaintext, &params).unwrap();
+
+        assert!(x.approx_eq(&y, 1e-6));
+    }
+
+    const A_VEC: [f64; 4] = [1., 2., 3., 4.];
+    const B_VEC: [f64; 4] = [5., 6., 7., 8.];
+
+    #[test]
+    fn can_add_non_fhe() {
+        let a = Real::<4>::from(A_VEC);
+        let b = Real::<4>::from(B_VEC);
+
+        assert_eq!(a + b, [6., 8., 10., 12.].into());
+    }
+
+    #[test]
+    fn can_mul_non_fhe() {
+        let a = Real::<4>::from(A_VEC);
+        let b = Real::<4>::from(B_VEC);
+
+        assert_eq!(a * b, [5., 12., 21., 32.].into());
+    }
+
+    #[test]
+    fn can_sub_non_fhe() {
+        let a = Real::<4>::from(A_VEC);
+        let b = Real::<4>::from(B_VEC);
+
+        assert_eq!(a - b, [-4., -4., -4., -4.].into());
+    }
+
+    #[test]
+    fn can_neg_non_fhe() {
+        let a = Real::<4>::from(A_VEC);
+
+        assert_eq!(-a, [-1., -2., -3., -4.].into());
+    }
+
+    #[test]
+    fn can_shl_non_fhe() {
+        let a = Real::<4>::from(A_VEC);
+
+        assert_eq!(a << 3, [4., 1., 2., 3.].into());
+    }
+
+    #[test]
+    fn can_shr_non_fhe() {
+        let a = Real::<4>::from(A_VEC);
+
+        assert_eq!(a >> 3, [2., 3., 4., 1.].into());
+    }
+}
@@ -2,10 +2,12 @@ mod fhe_literal;
 mod fhe_program_node;
 mod input;
 mod output;
+mod polynomial;
 mod u64_literal;
 
 pub use fhe_literal::*;
 pub use fhe_program_node::*;
 pub use input::*;
 pub use output::*;
+pub(crate) use polynomial::powers;
 pub use u64_literal::*;
@@ -0,0 +1,205 @@
+use crate::types::{
+    intern::{Cipher, FheLiteral, FheProgramNode},
+    ops::{GraphCipherAdd, GraphCipherConstAdd, GraphCipherConstMul, GraphCipherMul},
+    BfvType,
+};
+
+/**
+ * Returns `[x, x^2, ..., x^degree]`. Each `x^k` has the minimal
+ * multiplicative depth `ceil(log2(k))`.
+ */
+pub(crate) fn powers<T>(
+    x: FheProgramNode<Cipher<T>>,
+    degree: usize,
+) -> Vec<FheProgramNode<Cipher<T>>>
+where
+    T: BfvType + GraphCipherMul<Left = T, Right = T>,
+{
+    let mut powers = vec![x];
+
+    for k in 2..=degree {
+        // Split k at its highest set bit.
+        let high = if k.is_power_of_two() {
+            k / 2
+        } else {
+            1 << (usize::BITS - 1 - k.leading_zeros())
+        };
+
+        let x_k = T::graph_cipher_mul(powers[high - 1], powers[k - high - 1]);
+        powers.push(x_k);
+    }
+
+    powers
+}
+
+impl<T> FheProgramNode<Cipher<T>>
+where
+    T: BfvType + GraphCipherMul<Left = T, Right = T>,
+{
+    /**
+     * Raises this value to the power `e`.
+     *
+     * # Remarks
+     * This uses square-and-multiply, which needs at most
+     * `2 * log2(e)` multiplications. Multiplying the squares in
+     * increasing order gives the result the minimal multiplicative depth
+     * `ceil(log2(e))`.
+     *
+     * # Panics
+     * If `e` is 0.
+     */
+    pub fn pow(self, e: u64) -> Self {
+        assert!(e > 0, "Can't raise a ciphertext to the power 0");
+
+        let mut e = e;
+        let mut square = self;
+        let mut result = None;
+
+        loop {
+            if e & 1 == 1 {
+                result = Some(match result {
+                    Some(r) => T::graph_cipher_mul(r, square),
+                    None => square,
+                });
+            }
+
+            e >>= 1;
+
+            if e == 0 {
+                break;
+            }
+
+            square = T::graph_cipher_mul(square, square);
+        }
+
+        result.unwrap()
+    }
+
+    /**
+     * Evaluates the polynomial `coeffs[0] + coeffs[1] * x + ... +
+     * coeffs[d] * x^d` on this value.
+     *
+     * # Remarks
+     * This uses the Paterson–Stockmeyer method, which needs on the order
+     * of `sqrt(d)` ciphertext multiplications rather than the `d - 1`
+     * needed to compute every power of `x`. Multiplications by
+     * coefficients are plaintext multiplications, which don't increase
+     * multiplicative depth, so the result has the minimal multiplicative
+     * depth `ceil(log2(d))`.
+     *
+     * # Panics
+     * If `coeffs` is empty or every coefficient but the first is 0. The
+     * value of a constant polynomial doesn't depend on `self`, and SEAL
+     * rejects the transparent ciphertext we'd get by multiplying `self` by
+     * 0, so use the constant directly instead.
+     */
+    pub fn eval_poly<L>(self, coeffs: &[L]) -> Self
+    where
+        T: GraphCipherAdd<Left = T, Right = T>
+            + GraphCipherConstMul<Left = T, Right = L>
+            + GraphCipherConstAdd<Left = T, Right = L>,
+        L: FheLiteral + Copy + Default + PartialEq,
+    {
+        assert!(
+            !coeffs.is_empty(),
+            "Can't evaluate a polynomial with no terms"
+        );
+        assert!(
+            coeffs.iter().skip(1).any(|c| *c != L::default()),
+            "Can't evaluate a constant polynomial on a ciphertext"
+        );
+
+        let n = coeffs.len();
+
+        // The baby step size. We evaluate blocks of k coefficients using
+        // the powers x..x^(k-1) and combine them with the giant steps
+        // x^k, x^(2k), x^(4k), ...
+        let mut k = 2;
+
+        while 2 * k * k < n {
+            k *= 2;
+        }
+
+        let baby = powers(self, usize::max(usize::min(k, n) - 1, 1));
+
+        let mut giants = vec![];
+
+        while k << giants.len() < n {
+            let giant = match giants.last() {
+                Some(g) => T::graph_cipher_mul(*g, *g),
+                None => T::graph_cipher_mul(baby[k / 2 - 1], baby[k / 2 - 1]),
+            };
+
+            giants.push(giant);
+        }
+
+        let (node, constant) = paterson_stockmeyer(coeffs, &baby, &giants, k);
+
+        // We checked above that some non-constant coefficient is nonzero.
+        let node = node.unwrap();
+
+        if constant == L::default() {
+            node
+        } else {
+            T::graph_cipher_const_add(node, constant)
+        }
+    }
+}
+
+/**
+ * Evaluates the polynomial with the given coefficients, returning its
+ * non-constant part (if any coefficient but the first is nonzero) and its
+ * constant term.
+ */
+fn paterson_stockmeyer<T, L>(
+    coeffs: &[L],
+    baby: &[FheProgramNode<Cipher<T>>],
+    giants: &[FheProgramNode<Cipher<T>>],
+    k: usize,
+) -> (Option<FheProgramNode<Cipher<T>>>, L)
+where
+    T: BfvType
+        + GraphCipherMul<Left = T, Right = T>
+        + GraphCipherAdd<Left = T, Right = T>
+        + GraphCipherConstMul<Left = T, Right = L>,
+    L: FheLiteral + Copy + Default + PartialEq,
+{
+    if coeffs.len() <= k {
+        let node = coeffs
+            .iter()
+            .enumerate()
+            .skip(1)
+            .filter(|(_, c)| **c != L::default())
+            .map(|(i, c)| T::graph_cipher_const_mul(baby[i - 1], *c))
+            .reduce(T::graph_cipher_add);
+
+        return (node, coeffs[0]);
+    }
+
+    // Split off the terms at and above the largest giant step below the
+    // degree, so both halves need at most the giant step's depth.
+    let mut t = 0;
+
+    while k << (t + 1) < coeffs.len() {
+        t += 1;
+    }
+
+    let (low, high) = coeffs.split_at(k << t);
+
+    let (low_node, low_constant) = paterson_stockmeyer(low, baby, giants, k);
+    let (high_node, high_constant) = paterson_stockmeyer(high, baby, giants, k);
+
+    let high_node = high_node.map(|h| T::graph_cipher_mul(h, giants[t]));
+    let high_constant = if high_constant == L::default() {
+        None
+    } else {
+        Some(T::graph_cipher_const_mul(giants[t], high_constant))
+    };
+
+    let node = [low_node, high_node, high_constant]
+        .into_iter()
+        .flatten()
+        .reduce(T::graph_cipher_add);
+
+    (node, low_constant)
+}
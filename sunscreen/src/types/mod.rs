@@ -39,6 +39,10 @@
  * Arithmetic operations semantically execute per-lane, enabling high-throughput;
  * e.g. a single addition operation `a + b` will element-wise add the many lanes of a to the
  * many lanes in b.
+ * * The [`Bool`](crate::types::bfv::Bool) type represents a single boolean value.
+ * It supports encrypted `&`, `|`, `^`, and `!`, and can choose between
+ * encrypted values of any of the above types with
+ * [`select`](crate::types::bfv::select).
  * Type comparison:
  *
  * | Type       | # ciphertexts | overflow conditions | values            | ops/add        | ops/mul | ops/sub        | ops/neg | ops/div |
@@ -56,6 +60,32 @@
  */
 pub mod bfv;
 
+/**
+ * This module contains build-in types you can use as inputs and outputs
+ * from FHE programs using the CKKS scheme.
+ *
+ * # CKKS Scheme types
+ * The CKKS scheme computes on approximate real numbers. Each operation
+ * introduces a small amount of error into the low-order bits of the result,
+ * which makes CKKS a good fit for numeric workloads (e.g. statistics or
+ * machine learning inference) that tolerate approximation.
+ *
+ * * The [`Real`](crate::types::ckks::Real) type packs thousands of
+ * real numbers into lanes. Like [`Batched`](crate::types::bfv::Batched),
+ * arithmetic operations execute per-lane and rotations move values between
+ * lanes.
+ *
+ * There is no complex-valued type yet: [`Real`](crate::types::ckks::Real)
+ * only encodes the real part of each CKKS slot, and FHE programs can't
+ * conjugate.
+ *
+ * Multiplications under CKKS grow the scale of the encoded values, so the
+ * compiler automatically inserts rescale operations after each
+ * multiplication and chooses a modulus chain long enough to accommodate
+ * your FHE program's multiplicative depth.
+ */
+pub mod ckks;
+
 /**
  * This module contains implementation details used to support
  * Sunscreen's domain specific language under the
@@ -75,7 +105,7 @@ mod ops;
 pub mod zkp;
 
 pub use sunscreen_runtime::{
-    BfvType, FheType, NumCiphertexts, TryFromPlaintext, TryIntoPlaintext, Type, TypeName,
+    BfvType, CkksType, FheType, NumCiphertexts, TryFromPlaintext, TryIntoPlaintext, Type, TypeName,
     TypeNameInstance, Version,
 };
 
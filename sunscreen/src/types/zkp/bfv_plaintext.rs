@@ -7,13 +7,16 @@ use sunscreen_zkp_backend::{BigInt, FieldSpec};
 use crate::{
     invoke_gadget,
     types::{
-        bfv::{Signed, Unsigned128, Unsigned64},
+        bfv::{Batched, Fractional, Signed, Unsigned128, Unsigned64},
         zkp::ProgramNode,
     },
     zkp::{with_zkp_ctx, ZkpContextOps},
 };
 
-use super::{gadgets::SignedModulus, DynamicNumFieldElements, Field, ToNativeFields};
+use super::{
+    gadgets::{SignedModulus, ToUInt},
+    DynamicNumFieldElements, Field, ToNativeFields,
+};
 
 use crate as sunscreen;
 
@@ -34,8 +37,7 @@ impl<F: FieldSpec, const N: usize, const M: usize> DynamicNumFieldElements
     for BfvSignedEncoding<F, N, M>
 {
     fn num_native_field_elements(plaintext_modulus: u64) -> usize {
-        let log_p = plaintext_modulus.ilog2() + 1;
-        log_p as usize * N
+        coefficient_bits(plaintext_modulus) * N
     }
 }
 
@@ -60,50 +62,88 @@ pub trait ConstrainFresh {
     fn constrain_fresh_encoding(&self);
 }
 
-impl<const N: usize, const M: usize, F: FieldSpec> ProgramNode<BfvSignedEncoding<F, N, M>> {
-    fn extract_coefficients(&self) -> Vec<NodeIndex> {
-        let bound = self.ids.len() / N;
-        let plain_modulus = 2u64.pow(bound as u32 - 1);
-
-        let (plain_modulus, plain_modulus_1, two, mut coeffs) = with_zkp_ctx(|ctx| {
-            let plain_modulus = ctx.add_constant(&BigInt::from(plain_modulus));
-            let one = ctx.add_constant(&BigInt::from_u32(1));
-            let two = ctx.add_constant(&BigInt::from_u32(2));
-            let plain_modulus_1 = ctx.add_addition(plain_modulus, one);
-            // Get coeffs via 2s complement construction
-            let coeffs = self
-                .ids
-                .chunks(bound)
-                .map(|xs| {
-                    let mut c = ctx.add_constant(&BigInt::ZERO);
-                    for (i, x) in xs.iter().enumerate() {
-                        let pow = ctx.add_constant(&(BigInt::ONE << i));
-                        let mul = ctx.add_multiplication(pow, *x);
-                        if i == bound - 1 {
-                            c = ctx.add_subtraction(c, mul);
-                        } else {
-                            c = ctx.add_addition(c, mul);
-                        }
+/// The number of bits in the binary expansion of each linked coefficient, i.e.
+/// `ceil(log2(p)) + 1`.
+fn coefficient_bits(plaintext_modulus: u64) -> usize {
+    (u64::BITS - (plaintext_modulus - 1).leading_zeros()) as usize + 1
+}
+
+/// The plaintext modulus of the current ZKP program's linked inputs.
+fn linked_plain_modulus() -> u64 {
+    with_zkp_ctx(|ctx| ctx.data.plain_modulus)
+        .expect("Linked plaintexts can only be decoded in a ZKP program with #[linked] inputs.")
+}
+
+/// Reassembles `num_coeffs` coefficients from their 2s complement binary expansions.
+fn decode_twos_complement(ids: &[NodeIndex], num_coeffs: usize) -> Vec<NodeIndex> {
+    let bound = ids.len() / num_coeffs;
+
+    with_zkp_ctx(|ctx| {
+        ids.chunks(bound)
+            .map(|xs| {
+                let mut c = ctx.add_constant(&BigInt::ZERO);
+                for (i, x) in xs.iter().enumerate() {
+                    let pow = ctx.add_constant(&(BigInt::ONE << i));
+                    let mul = ctx.add_multiplication(pow, *x);
+                    if i == bound - 1 {
+                        c = ctx.add_subtraction(c, mul);
+                    } else {
+                        c = ctx.add_addition(c, mul);
                     }
-                    c
-                })
-                .collect::<Vec<_>>();
-            (plain_modulus, plain_modulus_1, two, coeffs)
+                }
+                c
+            })
+            .collect::<Vec<_>>()
+    })
+}
+
+/// Reassembles `num_coeffs` coefficients and maps them from `[0, p)` to `(-p/2, p/2]`.
+fn extract_coefficients<F: FieldSpec>(ids: &[NodeIndex], num_coeffs: usize) -> Vec<NodeIndex> {
+    let plain_modulus = linked_plain_modulus();
+
+    let mut coeffs = decode_twos_complement(ids, num_coeffs);
+
+    let (plain_modulus, plain_modulus_1, two) = with_zkp_ctx(|ctx| {
+        let plain_modulus = ctx.add_constant(&BigInt::from(plain_modulus));
+        let one = ctx.add_constant(&BigInt::from_u32(1));
+        let two = ctx.add_constant(&BigInt::from_u32(2));
+        let plain_modulus_1 = ctx.add_addition(plain_modulus, one);
+        (plain_modulus, plain_modulus_1, two)
+    });
+
+    // Translate coefficients into field modulus
+    let cutoff_divider = SignedModulus::new(F::FIELD_MODULUS, 1);
+    let divider = SignedModulus::new(F::FIELD_MODULUS, 63);
+    let neg_cutoff = invoke_gadget(cutoff_divider, &[plain_modulus_1, two])[0];
+    for c in coeffs.iter_mut() {
+        let is_negative = invoke_gadget(divider, &[*c, neg_cutoff])[0];
+        *c = with_zkp_ctx(|ctx| {
+            let shift = ctx.add_multiplication(plain_modulus, is_negative);
+            ctx.add_subtraction(*c, shift)
         });
+    }
 
-        // Translate coefficients into field modulus
-        let cutoff_divider = SignedModulus::new(F::FIELD_MODULUS, 1);
-        let divider = SignedModulus::new(F::FIELD_MODULUS, 63);
-        let neg_cutoff = invoke_gadget(cutoff_divider, &[plain_modulus_1, two])[0];
-        for c in coeffs.iter_mut() {
-            let is_negative = invoke_gadget(divider, &[*c, neg_cutoff])[0];
-            *c = with_zkp_ctx(|ctx| {
-                let shift = ctx.add_multiplication(plain_modulus, is_negative);
-                ctx.add_subtraction(*c, shift)
-            });
+    coeffs
+}
+
+/// Constrains each coefficient to be ternary, as in a fresh encoding.
+fn constrain_ternary(coeffs: &[NodeIndex]) {
+    with_zkp_ctx(|ctx| {
+        let one = ctx.add_constant(&BigInt::ONE);
+
+        for c in coeffs {
+            let c_minus_1 = ctx.add_subtraction(*c, one);
+            let c_plus_1 = ctx.add_addition(*c, one);
+            let poly = ctx.add_multiplication(*c, c_minus_1);
+            let poly = ctx.add_multiplication(poly, c_plus_1);
+            ctx.add_constraint(poly, &BigInt::ZERO);
         }
+    });
+}
 
-        coeffs
+impl<const N: usize, const M: usize, F: FieldSpec> ProgramNode<BfvSignedEncoding<F, N, M>> {
+    fn extract_coefficients(&self) -> Vec<NodeIndex> {
+        extract_coefficients::<F>(self.ids, N)
     }
 }
 
@@ -133,22 +173,15 @@ impl<const N: usize, const M: usize, F: FieldSpec> ConstrainFresh
 {
     fn constrain_fresh_encoding(&self) {
         let coeffs = self.extract_coefficients();
+        let (fresh, rest) = coeffs.split_at(M);
 
-        with_zkp_ctx(|ctx| {
-            let one = ctx.add_constant(&BigInt::ONE);
+        // Constrain coefficients within the fresh degree bound to ternary
+        constrain_ternary(fresh);
 
-            for (i, c) in coeffs.iter().enumerate() {
-                // Constrain coefficients within the fresh degree bound to ternary
-                if i < M {
-                    let c_minus_1 = ctx.add_subtraction(*c, one);
-                    let c_plus_1 = ctx.add_addition(*c, one);
-                    let poly = ctx.add_multiplication(*c, c_minus_1);
-                    let poly = ctx.add_multiplication(poly, c_plus_1);
-                    ctx.add_constraint(poly, &BigInt::ZERO);
-                // Constrain coefficients greater than the degree bound to zero
-                } else {
-                    ctx.add_constraint(*c, &BigInt::ZERO);
-                }
+        // Constrain coefficients greater than the degree bound to zero
+        with_zkp_ctx(|ctx| {
+            for c in rest {
+                ctx.add_constraint(*c, &BigInt::ZERO);
             }
         });
     }
@@ -264,6 +297,234 @@ impl<F: FieldSpec> ConstrainFresh for ProgramNode<BfvRational<F>> {
     }
 }
 
+/// A [BFV fractional value](Fractional) that has been linked to a ZKP program.
+///
+/// Use the [`AsFieldElement::into_field_elem`] method to decode the value into a field element
+/// within a ZKP program. Since field elements are integers, the decoded value is scaled by
+/// `2^64`; e.g. `3.25` decodes to `13 * 2^62`.
+///
+/// Only the `INT_BITS` integer coefficients and the 64 highest-degree fractional coefficients
+/// are linked, so values with bits below `2^-64` can't be proven.
+#[derive(Debug, Clone, TypeName)]
+pub struct BfvFractional<F: FieldSpec, const INT_BITS: usize> {
+    data: Vec<Field<F>>,
+}
+
+impl<F: FieldSpec, const INT_BITS: usize> BfvFractional<F, INT_BITS> {
+    const NUM_COEFFICIENTS: usize = <Fractional<INT_BITS> as LinkWithZkp>::DEGREE_BOUND
+        + <Fractional<INT_BITS> as LinkWithZkp>::HIGH_DEGREE_BOUND;
+}
+
+impl<F: FieldSpec, const INT_BITS: usize> DynamicNumFieldElements for BfvFractional<F, INT_BITS> {
+    fn num_native_field_elements(plaintext_modulus: u64) -> usize {
+        coefficient_bits(plaintext_modulus) * Self::NUM_COEFFICIENTS
+    }
+}
+
+impl<F: FieldSpec, const INT_BITS: usize> ToNativeFields for BfvFractional<F, INT_BITS> {
+    fn to_native_fields(&self) -> Vec<BigInt> {
+        self.data.iter().map(|x| x.val).collect()
+    }
+}
+
+impl<F: FieldSpec, const INT_BITS: usize> ProgramNode<BfvFractional<F, INT_BITS>> {
+    fn extract_coefficients(&self) -> Vec<NodeIndex> {
+        extract_coefficients::<F>(self.ids, BfvFractional::<F, INT_BITS>::NUM_COEFFICIENTS)
+    }
+}
+
+impl<F: FieldSpec, const INT_BITS: usize> AsFieldElement<F>
+    for ProgramNode<BfvFractional<F, INT_BITS>>
+{
+    type Output = ProgramNode<Field<F>>;
+
+    fn into_field_elem(self) -> Self::Output {
+        let coeffs = self.extract_coefficients();
+        let (int_coeffs, frac_coeffs) = coeffs.split_at(INT_BITS);
+
+        ProgramNode::new(&[with_zkp_ctx(|ctx| {
+            let mut x = ctx.add_constant(&BigInt::ZERO);
+
+            // Integer coefficient i has weight 2^i, scaled by 2^64.
+            for (i, c) in int_coeffs.iter().enumerate() {
+                let pow = ctx.add_constant(&(BigInt::ONE << (i + 64)));
+                let mul = ctx.add_multiplication(pow, *c);
+                x = ctx.add_addition(x, mul);
+            }
+
+            // Coefficient n - k has weight -2^-k due to the negacyclic wraparound. After
+            // scaling, the jth linked fractional coefficient (k = 64 - j) has weight -2^j.
+            for (j, c) in frac_coeffs.iter().enumerate() {
+                let pow = ctx.add_constant(&(BigInt::ONE << j));
+                let mul = ctx.add_multiplication(pow, *c);
+                x = ctx.add_subtraction(x, mul);
+            }
+
+            x
+        })])
+    }
+}
+
+impl<F: FieldSpec, const INT_BITS: usize> ConstrainFresh
+    for ProgramNode<BfvFractional<F, INT_BITS>>
+{
+    fn constrain_fresh_encoding(&self) {
+        // A fresh encoding places each bit of the f64 in its own coefficient.
+        constrain_ternary(&self.extract_coefficients());
+    }
+}
+
+/// A [BFV batched vector](Batched) that has been linked to a ZKP program.
+///
+/// Use the [`AsFieldElement::into_field_elem`] method to decode both rows of lanes into field
+/// elements within a ZKP program, or [`lane`](ProgramNode::lane) to decode a single lane.
+///
+/// Linking requires the lattice dimension to be exactly `2 * LANES` and the plaintext modulus
+/// to be a prime congruent to 1 mod `4 * LANES`, as required for batching.
+#[derive(Debug, Clone, TypeName)]
+pub struct BfvBatched<F: FieldSpec, const LANES: usize> {
+    data: Vec<Field<F>>,
+}
+
+impl<F: FieldSpec, const LANES: usize> DynamicNumFieldElements for BfvBatched<F, LANES> {
+    fn num_native_field_elements(plaintext_modulus: u64) -> usize {
+        coefficient_bits(plaintext_modulus) * <Batched<LANES> as LinkWithZkp>::DEGREE_BOUND
+    }
+}
+
+impl<F: FieldSpec, const LANES: usize> ToNativeFields for BfvBatched<F, LANES> {
+    fn to_native_fields(&self) -> Vec<BigInt> {
+        self.data.iter().map(|x| x.val).collect()
+    }
+}
+
+fn mul_mod(a: u64, b: u64, p: u64) -> u64 {
+    ((a as u128 * b as u128) % p as u128) as u64
+}
+
+fn pow_mod(mut base: u64, mut exp: u64, p: u64) -> u64 {
+    let mut result = 1 % p;
+    base %= p;
+
+    while exp > 0 {
+        if exp & 0x1 == 1 {
+            result = mul_mod(result, base, p);
+        }
+        base = mul_mod(base, base, p);
+        exp >>= 1;
+    }
+
+    result
+}
+
+/// Finds the smallest primitive `2n`-th root of unity modulo `p`. SEAL's batching NTT uses the
+/// same root, so the slots of a plaintext are its evaluations at odd powers of this root.
+fn minimal_primitive_root(n: u64, p: u64) -> u64 {
+    let degree = 2 * n;
+    assert_eq!(
+        (p - 1) % degree,
+        0,
+        "Batching requires the plaintext modulus to be 1 mod 2n."
+    );
+
+    let root = (2..p)
+        .map(|x| pow_mod(x, (p - 1) / degree, p))
+        .find(|g| pow_mod(*g, n, p) == p - 1)
+        .expect("Plaintext modulus must be prime.");
+
+    // Every odd power of a primitive root is also a primitive root.
+    let generator = mul_mod(root, root, p);
+    let mut current = root;
+    let mut minimal = root;
+    for _ in 0..n {
+        minimal = minimal.min(current);
+        current = mul_mod(current, generator, p);
+    }
+
+    minimal
+}
+
+impl<F: FieldSpec, const LANES: usize> ProgramNode<BfvBatched<F, LANES>> {
+    fn extract_coefficients(&self) -> Vec<NodeIndex> {
+        extract_coefficients::<F>(self.ids, <Batched<LANES> as LinkWithZkp>::DEGREE_BOUND)
+    }
+
+    /// Evaluates the plaintext polynomial at the root of unity backing the slot at `row`, `col`
+    /// and returns the signed lane value.
+    fn eval_lane(coeffs: &[NodeIndex], row: usize, col: usize) -> ProgramNode<Field<F>> {
+        let plain_modulus = linked_plain_modulus();
+        let n = coeffs.len() as u64;
+        let degree = 2 * n;
+
+        // Slot (0, col) holds m(psi^(3^col)) and slot (1, col) holds m(psi^(-3^col)).
+        let root = minimal_primitive_root(n, plain_modulus);
+        let exp = pow_mod(3, col as u64, degree);
+        let exp = if row == 0 { exp } else { degree - exp };
+        let zeta = pow_mod(root, exp, plain_modulus);
+
+        // Coefficients lie in (-p/2, p/2], so adding 2np^2 keeps the sum positive. The extra
+        // (p - 1) / 2 centers the remainder so we can recover the signed lane.
+        let half = (plain_modulus - 1) / 2;
+        let shift = BigInt::from(degree)
+            .wrapping_mul(&BigInt::from(plain_modulus))
+            .wrapping_mul(&BigInt::from(plain_modulus))
+            .wrapping_add(&BigInt::from(half));
+
+        let (sum, modulus) = with_zkp_ctx(|ctx| {
+            let mut sum = ctx.add_constant(&BigInt::from(shift));
+            let mut power = 1;
+
+            for c in coeffs {
+                let pow = ctx.add_constant(&BigInt::from(power));
+                let mul = ctx.add_multiplication(pow, *c);
+                sum = ctx.add_addition(sum, mul);
+                power = mul_mod(power, zeta, plain_modulus);
+            }
+
+            (sum, ctx.add_constant(&BigInt::from(plain_modulus)))
+        });
+
+        let remainder_bits = (u64::BITS - (plain_modulus - 1).leading_zeros()) as usize;
+        let divider = SignedModulus::new(F::FIELD_MODULUS, remainder_bits);
+        let outputs = invoke_gadget(divider, &[sum, modulus]);
+
+        // The remainder is range checked by the gadget, but the quotient isn't. Bound it too so
+        // the prover can't choose a different remainder by wrapping around the field.
+        let quotient_bound = 4 * n as u128 * plain_modulus as u128;
+        let quotient_bits = (u128::BITS - quotient_bound.leading_zeros()) as usize;
+        invoke_gadget(ToUInt::new(quotient_bits), &[outputs[0]]);
+
+        ProgramNode::new(&[with_zkp_ctx(|ctx| {
+            let half = ctx.add_constant(&BigInt::from(half));
+            ctx.add_subtraction(outputs[1], half)
+        })])
+    }
+
+    /// Decodes the lane at (`row`, `col`) into a field element.
+    ///
+    /// # Panics
+    /// If `row > 1` or `col >= LANES`.
+    pub fn lane(&self, row: usize, col: usize) -> ProgramNode<Field<F>> {
+        assert!(row < 2 && col < LANES, "Lane index out of bounds.");
+
+        Self::eval_lane(&self.extract_coefficients(), row, col)
+    }
+}
+
+impl<F: FieldSpec, const LANES: usize> AsFieldElement<F> for ProgramNode<BfvBatched<F, LANES>> {
+    type Output = [Vec<ProgramNode<Field<F>>>; 2];
+
+    fn into_field_elem(self) -> Self::Output {
+        let coeffs = self.extract_coefficients();
+
+        [0, 1].map(|row| {
+            (0..LANES)
+                .map(|col| Self::eval_lane(&coeffs, row, col))
+                .collect()
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -284,7 +545,6 @@ mod tests {
     fn doggie() {}
 
     #[test]
-    #[ignore = "currently unsupported"]
     fn can_decode_signed_for_arbitrary_plain_moduli() {
         test_plain_modulus(4095);
         test_plain_modulus(4097);
@@ -313,7 +573,7 @@ mod tests {
 
         let runtime = ZkpRuntime::new(BulletproofsBackend::new()).unwrap();
 
-        let log_p = plain_modulus.ilog2() as usize + 1;
+        let log_p = coefficient_bits(plain_modulus);
 
         for (coeff, equiv) in [(511, 511), (512, -512), (513, -511)] {
             let mut signed_encoding = [0; <Signed as LinkWithZkp>::DEGREE_BOUND];
@@ -364,7 +624,7 @@ mod tests {
 
         let runtime = ZkpRuntime::new(BulletproofsBackend::new()).unwrap();
 
-        let log_p = plain_modulus.ilog2() as usize + 1;
+        let log_p = coefficient_bits(plain_modulus);
 
         for val in [3i64, -3] {
             // Simulate the polynomial signed encoding
@@ -0,0 +1,408 @@
+use std::ops::{BitAnd, BitOr, BitXor, Not};
+
+use petgraph::stable_graph::NodeIndex;
+use sunscreen_compiler_macros::TypeName;
+use sunscreen_zkp_backend::{BigInt, FieldSpec};
+
+use crate::{
+    types::zkp::{
+        gadgets::{assert_binary, mark_binary},
+        ConstrainEqVarVar, Field, IntoProgramNode, NumFieldElements, ProgramNode, ToNativeFields,
+        ZkpType,
+    },
+    zkp::{with_source_location, with_zkp_ctx, ZkpContext, ZkpContextOps},
+};
+
+use super::integer::{FromField, IntoField};
+
+use crate as sunscreen;
+
+#[derive(Debug, Clone, TypeName)]
+/**
+ * A boolean in a [`zkp_program`](crate::zkp_program), stored as 0 or 1.
+ *
+ * # Remarks
+ * The first operation on a boolean input proves it's 0 or 1 with the
+ * [`AssertBinary`](crate::types::zkp::gadgets::AssertBinary) gadget,
+ * which costs a multiplication. Results of `&`, `|`, `^`, and `!` are
+ * binary by construction, so we don't check them again. `&`, `|`, and `^`
+ * cost a multiplication each, while `!` is free.
+ *
+ * Use [`select`] to choose between values with a boolean.
+ */
+pub struct ZkpBool<F: FieldSpec> {
+    val: Field<F>,
+}
+
+impl<F: FieldSpec> From<bool> for ZkpBool<F> {
+    fn from(x: bool) -> Self {
+        Self {
+            val: Field::from(x as u8),
+        }
+    }
+}
+
+impl<F: FieldSpec> Copy for ZkpBool<F> {}
+
+impl<F: FieldSpec> NumFieldElements for ZkpBool<F> {
+    const NUM_NATIVE_FIELD_ELEMENTS: usize = 1;
+}
+
+impl<F: FieldSpec> ToNativeFields for ZkpBool<F> {
+    fn to_native_fields(&self) -> Vec<BigInt> {
+        self.val.to_native_fields()
+    }
+}
+
+impl<F: FieldSpec> From<ZkpBool<F>> for Field<F> {
+    fn from(x: ZkpBool<F>) -> Self {
+        x.val
+    }
+}
+
+impl<F: FieldSpec> IntoProgramNode for ZkpBool<F> {
+    type Output = Self;
+
+    fn into_program_node(self) -> ProgramNode<Self> {
+        with_zkp_ctx(|ctx| ProgramNode::new(&[ctx.add_constant(&self.val.val)]))
+    }
+}
+
+impl<F: FieldSpec> IntoField<F> for ProgramNode<ZkpBool<F>> {
+    /**
+     * Returns 1 for true and 0 for false.
+     */
+    fn into_field(self) -> ProgramNode<Field<F>> {
+        assert_binary(self.ids[0]);
+
+        ProgramNode::new(self.ids)
+    }
+}
+
+impl<F: FieldSpec> FromField<F> for ZkpBool<F> {
+    /**
+     * Interprets 1 as true and 0 as false. The proof fails for other
+     * values.
+     */
+    fn from_field(x: ProgramNode<Field<F>>) -> ProgramNode<Self> {
+        assert_binary(x.ids[0]);
+
+        ProgramNode::new(x.ids)
+    }
+}
+
+impl<F: FieldSpec> ConstrainEqVarVar for ZkpBool<F> {
+    fn constrain_eq(lhs: ProgramNode<Self>, rhs: ProgramNode<Self>) -> ProgramNode<Self> {
+        let c = Field::<F>::constrain_eq(lhs.into_field(), rhs.into_field());
+
+        ProgramNode::new(c.ids)
+    }
+}
+
+/**
+ * Proves the operands are binary, applies `op`, and records the result
+ * as binary. `op` must map binary operands to a binary result.
+ */
+fn logical_op<F: FieldSpec>(
+    lhs: ProgramNode<ZkpBool<F>>,
+    rhs: ProgramNode<ZkpBool<F>>,
+    op: impl FnOnce(&mut ZkpContext, NodeIndex, NodeIndex) -> NodeIndex,
+) -> ProgramNode<ZkpBool<F>> {
+    assert_binary(lhs.ids[0]);
+    assert_binary(rhs.ids[0]);
+
+    let o = with_zkp_ctx(|ctx| op(ctx, lhs.ids[0], rhs.ids[0]));
+
+    mark_binary(o);
+
+    ProgramNode::new(&[o])
+}
+
+impl<F: FieldSpec> BitAnd for ProgramNode<ZkpBool<F>> {
+    type Output = Self;
+
+    #[track_caller]
+    fn bitand(self, rhs: Self) -> Self::Output {
+        with_source_location(|| logical_op(self, rhs, |ctx, a, b| ctx.add_multiplication(a, b)))
+    }
+}
+
+impl<F: FieldSpec> BitOr for ProgramNode<ZkpBool<F>> {
+    type Output = Self;
+
+    #[track_caller]
+    fn bitor(self, rhs: Self) -> Self::Output {
+        // a + b - ab
+        with_source_location(|| {
+            logical_op(self, rhs, |ctx, a, b| {
+                let sum = ctx.add_addition(a, b);
+                let prod = ctx.add_multiplication(a, b);
+
+                ctx.add_subtraction(sum, prod)
+            })
+        })
+    }
+}
+
+impl<F: FieldSpec> BitXor for ProgramNode<ZkpBool<F>> {
+    type Output = Self;
+
+    #[track_caller]
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        // a + b - 2ab
+        with_source_location(|| {
+            logical_op(self, rhs, |ctx, a, b| {
+                let sum = ctx.add_addition(a, b);
+                let prod = ctx.add_multiplication(a, b);
+                let twice_prod = ctx.add_addition(prod, prod);
+
+                ctx.add_subtraction(sum, twice_prod)
+            })
+        })
+    }
+}
+
+impl<F: FieldSpec> Not for ProgramNode<ZkpBool<F>> {
+    type Output = Self;
+
+    #[track_caller]
+    fn not(self) -> Self::Output {
+        with_source_location(|| {
+            assert_binary(self.ids[0]);
+
+            let o = with_zkp_ctx(|ctx| {
+                let one = ctx.add_constant(&BigInt::ONE);
+
+                ctx.add_subtraction(one, self.ids[0])
+            });
+
+            mark_binary(o);
+
+            ProgramNode::new(&[o])
+        })
+    }
+}
+
+/**
+ * Returns `a` if `cond` is true and `b` otherwise.
+ *
+ * # Remarks
+ * We compute `b + cond * (a - b)` for each field element of `T`, which
+ * costs a multiplication per element. Since the result is always one of
+ * the operands, values stay within their type's range and need no
+ * further checks.
+ *
+ * # Panics
+ * Calling this function outside a [`zkp_program`](crate::zkp_program)
+ * will panic.
+ */
+pub fn select<F, T>(
+    cond: ProgramNode<ZkpBool<F>>,
+    a: ProgramNode<T>,
+    b: ProgramNode<T>,
+) -> ProgramNode<T>
+where
+    F: FieldSpec,
+    T: ZkpType,
+{
+    let c = cond.ids[0];
+
+    assert_binary(c);
+
+    let ids = with_zkp_ctx(|ctx| {
+        a.ids
+            .iter()
+            .zip(b.ids.iter())
+            .map(|(a, b)| {
+                let diff = ctx.add_subtraction(*a, *b);
+                let scaled = ctx.add_multiplication(c, diff);
+                let o = ctx.add_addition(*b, scaled);
+
+                if ctx.data.booleans.contains(a) && ctx.data.booleans.contains(b) {
+                    ctx.data.booleans.insert(o);
+                }
+
+                o
+            })
+            .collect::<Vec<_>>()
+    });
+
+    ProgramNode::new(&ids)
+}
+
+#[cfg(test)]
+mod tests {
+    use sunscreen_runtime::{Runtime, ZkpProgramInput};
+    use sunscreen_zkp_backend::bulletproofs::BulletproofsBackend;
+    use sunscreen_zkp_backend::ZkpBackend;
+
+    use super::*;
+    use crate::types::zkp::{gadgets::AssertBinary, SelectIndex};
+    use crate::{zkp_program, Compiler};
+
+    type BPFieldSpec = <BulletproofsBackend as ZkpBackend>::Field;
+    type BPField = Field<BPFieldSpec>;
+    type BPBool = ZkpBool<BPFieldSpec>;
+
+    #[test]
+    fn can_evaluate_logic() {
+        #[zkp_program]
+        fn logic<F: FieldSpec>(
+            a: ZkpBool<F>,
+            b: ZkpBool<F>,
+            #[public] and: ZkpBool<F>,
+            #[public] or: ZkpBool<F>,
+            #[public] xor: ZkpBool<F>,
+            #[public] nand: ZkpBool<F>,
+        ) {
+            (a & b).constrain_eq(and);
+            (a | b).constrain_eq(or);
+            (a ^ b).constrain_eq(xor);
+            (!(a & b)).constrain_eq(nand);
+        }
+
+        let app = Compiler::new()
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(logic)
+            .compile()
+            .unwrap();
+
+        let runtime = Runtime::new_zkp(BulletproofsBackend::new()).unwrap();
+
+        let prog = app.get_zkp_program(logic).unwrap();
+
+        for a in [false, true] {
+            for b in [false, true] {
+                let public = vec![
+                    BPBool::from(a && b),
+                    BPBool::from(a || b),
+                    BPBool::from(a ^ b),
+                    BPBool::from(!(a && b)),
+                ];
+
+                let proof = runtime
+                    .prove(
+                        prog,
+                        vec![BPBool::from(a), BPBool::from(b)],
+                        public.clone(),
+                        vec![],
+                    )
+                    .unwrap();
+
+                runtime.verify(prog, &proof, public, vec![]).unwrap();
+            }
+        }
+
+        let metrics = BulletproofsBackend::new()
+            .gadget_metrics(&prog.zkp_program_fn)
+            .unwrap();
+
+        // We assert each private input is binary once, and the public
+        // inputs when constraining against them.
+        assert_eq!(
+            metrics.gadgets[std::any::type_name::<AssertBinary>()].invocations,
+            6
+        );
+    }
+
+    #[test]
+    fn rejects_non_binary_inputs() {
+        #[zkp_program]
+        fn negate<F: FieldSpec>(a: Field<F>, #[public] b: ZkpBool<F>) {
+            (!ZkpBool::from_field(a)).constrain_eq(b);
+        }
+
+        let app = Compiler::new()
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(negate)
+            .compile()
+            .unwrap();
+
+        let runtime = Runtime::new_zkp(BulletproofsBackend::new()).unwrap();
+
+        let prog = app.get_zkp_program(negate).unwrap();
+
+        let prove = |a: u8, b: bool| {
+            runtime.prove(prog, vec![BPField::from(a)], vec![BPBool::from(b)], vec![])
+        };
+
+        prove(1, false).unwrap();
+        prove(0, true).unwrap();
+
+        assert!(prove(2, false).is_err());
+        assert!(prove(2, true).is_err());
+    }
+
+    #[test]
+    fn can_select_values() {
+        #[zkp_program]
+        fn max<F: FieldSpec>(
+            a: Field<F>,
+            b: Field<F>,
+            a_is_larger: ZkpBool<F>,
+            #[public] m: Field<F>,
+        ) {
+            select(a_is_larger, a, b).constrain_eq(m);
+        }
+
+        let app = Compiler::new()
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(max)
+            .compile()
+            .unwrap();
+
+        let runtime = Runtime::new_zkp(BulletproofsBackend::new()).unwrap();
+
+        let prog = app.get_zkp_program(max).unwrap();
+
+        let prove = |a: u8, b: u8, cond: bool, m: u8| {
+            let private: Vec<ZkpProgramInput> = vec![
+                BPField::from(a).into(),
+                BPField::from(b).into(),
+                BPBool::from(cond).into(),
+            ];
+
+            runtime.prove(prog, private, vec![BPField::from(m)], vec![])
+        };
+
+        prove(7, 3, true, 7).unwrap();
+        prove(3, 7, false, 7).unwrap();
+
+        assert!(prove(7, 3, false, 7).is_err());
+    }
+
+    #[test]
+    fn can_index_with_private_index() {
+        #[zkp_program]
+        fn index<F: FieldSpec>(values: [Field<F>; 4], i: Field<F>, #[public] v: Field<F>) {
+            values.select_index(i).constrain_eq(v);
+        }
+
+        let app = Compiler::new()
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(index)
+            .compile()
+            .unwrap();
+
+        let runtime = Runtime::new_zkp(BulletproofsBackend::new()).unwrap();
+
+        let prog = app.get_zkp_program(index).unwrap();
+
+        let values = [10u8, 20, 30, 40].map(BPField::from);
+
+        let prove = |i: u8, v: u8| {
+            let private: Vec<ZkpProgramInput> = vec![values.into(), BPField::from(i).into()];
+
+            runtime.prove(prog, private, vec![BPField::from(v)], vec![])
+        };
+
+        let proof = prove(2, 30).unwrap();
+
+        runtime
+            .verify(prog, &proof, vec![BPField::from(30u8)], vec![])
+            .unwrap();
+
+        assert!(prove(2, 40).is_err());
+        assert!(prove(4, 0).is_err());
+    }
+}
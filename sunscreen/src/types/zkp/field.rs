@@ -12,8 +12,8 @@ use crate::{
 };
 
 use crate::types::zkp::{
-    gadgets::ToUInt, ConstrainEqVarVar, IntoProgramNode, MulVar, NegVar, NumFieldElements,
-    ToNativeFields, ZkpType,
+    gadgets::{self, ToUInt},
+    ConstrainEqVarVar, IntoProgramNode, MulVar, NegVar, NumFieldElements, ToNativeFields, ZkpType,
 };
 
 use crate as sunscreen;
@@ -330,6 +330,172 @@ impl<F: FieldSpec> ToBinary<F> for ProgramNode<Field<F>> {
     }
 }
 
+/**
+ * Methods for proving a value lies in a range or set.
+ */
+pub trait ConstrainMembership<F: FieldSpec> {
+    /**
+     * Proves `lo <= self <= hi`, treating all values as unsigned. Range
+     * checks on the same value share their binary decompositions.
+     *
+     * See [`gadgets::constrain_in_range`](crate::types::zkp::gadgets::constrain_in_range).
+     */
+    fn constrain_in_range(&self, lo: Field<F>, hi: Field<F>);
+
+    /**
+     * Proves `self` equals one of `values`.
+     *
+     * See [`gadgets::constrain_in_set`](crate::types::zkp::gadgets::constrain_in_set).
+     */
+    fn constrain_in_set(&self, values: &[Field<F>]);
+}
+
+impl<F: FieldSpec> ConstrainMembership<F> for ProgramNode<Field<F>> {
+    fn constrain_in_range(&self, lo: Field<F>, hi: Field<F>) {
+        gadgets::constrain_in_range(self.ids[0], &lo.val, &hi.val);
+    }
+
+    fn constrain_in_set(&self, values: &[Field<F>]) {
+        let values = values.iter().map(|v| v.val).collect::<Vec<_>>();
+
+        gadgets::constrain_in_set(self.ids[0], &values);
+    }
+}
+
+/**
+ * Methods for looking up values in a table.
+ */
+pub trait Lookup<F: FieldSpec> {
+    /**
+     * Returns `table[self]`. The proof fails if `self` isn't less than
+     * `table.len()`. Lookups with the same index share their encoding.
+     *
+     * See [`gadgets::lookup`](crate::types::zkp::gadgets::lookup).
+     */
+    fn lookup(&self, table: &[Field<F>]) -> ProgramNode<Field<F>>;
+}
+
+impl<F: FieldSpec> Lookup<F> for ProgramNode<Field<F>> {
+    fn lookup(&self, table: &[Field<F>]) -> ProgramNode<Field<F>> {
+        let table = table.iter().map(|v| v.val).collect::<Vec<_>>();
+
+        Self::new(&[gadgets::lookup(self.ids[0], &table)])
+    }
+}
+
+/**
+ * Methods for indexing an array of values with a private index.
+ */
+pub trait SelectIndex<F: FieldSpec, T> {
+    /**
+     * Returns `self[index]`. The proof fails if `index` isn't less than
+     * `self.len()`. As with [`Lookup`], lookups with the same index share
+     * their encoding.
+     *
+     * See [`gadgets::select_index`](crate::types::zkp::gadgets::select_index).
+     */
+    fn select_index(&self, index: ProgramNode<Field<F>>) -> ProgramNode<T>;
+}
+
+impl<F: FieldSpec, T: ZkpType> SelectIndex<F, T> for [ProgramNode<T>] {
+    fn select_index(&self, index: ProgramNode<Field<F>>) -> ProgramNode<T> {
+        let ids = (0..T::NUM_NATIVE_FIELD_ELEMENTS)
+            .map(|i| {
+                let values = self.iter().map(|v| v.ids[i]).collect::<Vec<_>>();
+
+                gadgets::select_index(index.ids[0], &values)
+            })
+            .collect::<Vec<_>>();
+
+        ProgramNode::new(&ids)
+    }
+}
+
+/**
+ * Methods for hashing a sequence of values.
+ */
+pub trait ZkpHash<F: FieldSpec> {
+    /**
+     * Computes the [`Poseidon`](crate::types::zkp::gadgets::Poseidon)
+     * hash of these values.
+     */
+    fn poseidon(&self) -> ProgramNode<Field<F>>;
+
+    /**
+     * Computes the [`MiMC`](crate::types::zkp::gadgets::MiMC) hash of
+     * these values.
+     */
+    fn mimc(&self) -> ProgramNode<Field<F>>;
+
+    /**
+     * Computes the SHA-256 digest of these values, which must be bytes.
+     * Returns the 8 digest words, most significant first.
+     */
+    fn sha256(&self) -> [ProgramNode<Field<F>>; 8];
+}
+
+impl<F: FieldSpec> ZkpHash<F> for [ProgramNode<Field<F>>] {
+    fn poseidon(&self) -> ProgramNode<Field<F>> {
+        let inputs = self.iter().map(|x| x.ids[0]).collect::<Vec<_>>();
+
+        ProgramNode::new(&[gadgets::poseidon_hash(&inputs, &F::FIELD_MODULUS)])
+    }
+
+    fn mimc(&self) -> ProgramNode<Field<F>> {
+        let inputs = self.iter().map(|x| x.ids[0]).collect::<Vec<_>>();
+
+        ProgramNode::new(&[gadgets::mimc_hash(&inputs, &F::FIELD_MODULUS)])
+    }
+
+    fn sha256(&self) -> [ProgramNode<Field<F>>; 8] {
+        let inputs = self.iter().map(|x| x.ids[0]).collect::<Vec<_>>();
+        let words = gadgets::sha256(&inputs);
+
+        let mut vals = [ProgramNode::new(&[words[0]]); 8];
+
+        for (i, word) in words.iter().enumerate() {
+            vals[i] = ProgramNode::new(&[*word]);
+        }
+
+        vals
+    }
+}
+
+/**
+ * Methods for proving membership in a Merkle tree.
+ */
+pub trait MerklePath<F: FieldSpec> {
+    /**
+     * Computes the root of the Merkle tree containing this leaf at
+     * `index`, given its authentication `path` from the bottom up. The
+     * proof fails unless `index < 2^path.len()`.
+     *
+     * See [`gadgets::merkle_root`](crate::types::zkp::gadgets::merkle_root).
+     */
+    fn merkle_root(
+        &self,
+        index: ProgramNode<Field<F>>,
+        path: &[ProgramNode<Field<F>>],
+    ) -> ProgramNode<Field<F>>;
+}
+
+impl<F: FieldSpec> MerklePath<F> for ProgramNode<Field<F>> {
+    fn merkle_root(
+        &self,
+        index: ProgramNode<Field<F>>,
+        path: &[ProgramNode<Field<F>>],
+    ) -> ProgramNode<Field<F>> {
+        let path = path.iter().map(|x| x.ids[0]).collect::<Vec<_>>();
+
+        Self::new(&[gadgets::merkle_root(
+            self.ids[0],
+            index.ids[0],
+            &path,
+            &F::FIELD_MODULUS,
+        )])
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::ops::{Add, Mul, Neg, Sub};
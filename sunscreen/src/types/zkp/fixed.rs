@@ -0,0 +1,320 @@
+use sunscreen_compiler_macros::TypeName;
+use sunscreen_zkp_backend::{BigInt, FieldSpec};
+
+use crate::{
+    invoke_gadget,
+    types::zkp::{
+        gadgets::SignedModulus, AddVar, ConstrainCmpVarVar, ConstrainEqVarVar, Field,
+        IntoProgramNode, MulVar, NegVar, NumFieldElements, ProgramNode, SubVar, ToNativeFields,
+    },
+    zkp::{with_zkp_ctx, ZkpContextOps},
+};
+
+use super::integer::{checked_binary, encode_signed, range_check, FromField, IntoField};
+
+use crate as sunscreen;
+
+#[derive(Debug, Clone, TypeName)]
+/**
+ * A signed fixed-point number in a [`zkp_program`](crate::zkp_program)
+ * with `FRAC_BITS` bits after the binary point. We store `x * 2^FRAC_BITS`
+ * as a 64-bit signed integer.
+ *
+ * # Remarks
+ * As with [`ZkpI64`](crate::types::zkp::ZkpI64), arithmetic proves the
+ * stored value doesn't overflow. Multiplication rounds towards negative
+ * infinity and additionally costs `FRAC_BITS` multiplications to prove
+ * the discarded remainder.
+ */
+pub struct ZkpFixed<F: FieldSpec, const FRAC_BITS: usize> {
+    val: Field<F>,
+}
+
+impl<F: FieldSpec, const FRAC_BITS: usize> ZkpFixed<F, FRAC_BITS> {
+    /**
+     * Creates a [`ZkpFixed`] whose stored value is `raw`, i.e. the number
+     * `raw / 2^FRAC_BITS`.
+     */
+    pub fn from_raw(raw: i64) -> Self {
+        Self {
+            val: encode_signed(raw),
+        }
+    }
+}
+
+impl<F: FieldSpec, const FRAC_BITS: usize> From<f64> for ZkpFixed<F, FRAC_BITS> {
+    /**
+     * Rounds `x` to the nearest representable value.
+     *
+     * # Panics
+     * * If `x` is out of range or not finite
+     */
+    fn from(x: f64) -> Self {
+        let raw = (x * (1u64 << FRAC_BITS) as f64).round();
+
+        assert!(
+            raw.is_finite() && raw >= i64::MIN as f64 && raw < i64::MAX as f64,
+            "{x} is out of range for a fixed-point value with {FRAC_BITS} fractional bits."
+        );
+
+        Self::from_raw(raw as i64)
+    }
+}
+
+impl<F: FieldSpec, const FRAC_BITS: usize> From<i64> for ZkpFixed<F, FRAC_BITS> {
+    /**
+     * # Panics
+     * * If `x * 2^FRAC_BITS` overflows an `i64`
+     */
+    fn from(x: i64) -> Self {
+        let raw = x
+            .checked_mul(1 << FRAC_BITS)
+            .expect("Value out of range for fixed-point.");
+
+        Self::from_raw(raw)
+    }
+}
+
+impl<F: FieldSpec, const FRAC_BITS: usize> Copy for ZkpFixed<F, FRAC_BITS> {}
+
+impl<F: FieldSpec, const FRAC_BITS: usize> NumFieldElements for ZkpFixed<F, FRAC_BITS> {
+    const NUM_NATIVE_FIELD_ELEMENTS: usize = 1;
+}
+
+impl<F: FieldSpec, const FRAC_BITS: usize> ToNativeFields for ZkpFixed<F, FRAC_BITS> {
+    fn to_native_fields(&self) -> Vec<BigInt> {
+        self.val.to_native_fields()
+    }
+}
+
+impl<F: FieldSpec, const FRAC_BITS: usize> From<ZkpFixed<F, FRAC_BITS>> for Field<F> {
+    /**
+     * Returns the stored value `x * 2^FRAC_BITS`.
+     */
+    fn from(x: ZkpFixed<F, FRAC_BITS>) -> Self {
+        x.val
+    }
+}
+
+impl<F: FieldSpec, const FRAC_BITS: usize> IntoProgramNode for ZkpFixed<F, FRAC_BITS> {
+    type Output = Self;
+
+    fn into_program_node(self) -> ProgramNode<Self> {
+        with_zkp_ctx(|ctx| ProgramNode::new(&[ctx.add_constant(&self.val.val)]))
+    }
+}
+
+impl<F: FieldSpec, const FRAC_BITS: usize> IntoField<F> for ProgramNode<ZkpFixed<F, FRAC_BITS>> {
+    /**
+     * Returns the stored value `x * 2^FRAC_BITS`.
+     */
+    fn into_field(self) -> ProgramNode<Field<F>> {
+        ProgramNode::new(self.ids)
+    }
+}
+
+impl<F: FieldSpec, const FRAC_BITS: usize> FromField<F> for ZkpFixed<F, FRAC_BITS> {
+    /**
+     * Interprets `x` as the stored value `x * 2^FRAC_BITS`.
+     */
+    fn from_field(x: ProgramNode<Field<F>>) -> ProgramNode<Self> {
+        range_check::<F>(x.ids[0], true);
+
+        ProgramNode::new(x.ids)
+    }
+}
+
+impl<F: FieldSpec, const FRAC_BITS: usize> AddVar for ZkpFixed<F, FRAC_BITS> {
+    fn add(lhs: ProgramNode<Self>, rhs: ProgramNode<Self>) -> ProgramNode<Self> {
+        checked_binary::<F, _>(lhs, rhs, true, |ctx, a, b| ctx.add_addition(a, b))
+    }
+}
+
+impl<F: FieldSpec, const FRAC_BITS: usize> SubVar for ZkpFixed<F, FRAC_BITS> {
+    fn sub(lhs: ProgramNode<Self>, rhs: ProgramNode<Self>) -> ProgramNode<Self> {
+        checked_binary::<F, _>(lhs, rhs, true, |ctx, a, b| ctx.add_subtraction(a, b))
+    }
+}
+
+impl<F: FieldSpec, const FRAC_BITS: usize> NegVar for ZkpFixed<F, FRAC_BITS> {
+    fn neg(lhs: ProgramNode<Self>) -> ProgramNode<Self> {
+        range_check::<F>(lhs.ids[0], true);
+
+        let o = with_zkp_ctx(|ctx| ctx.add_negate(lhs.ids[0]));
+
+        range_check::<F>(o, true);
+
+        ProgramNode::new(&[o])
+    }
+}
+
+impl<F: FieldSpec, const FRAC_BITS: usize> MulVar for ZkpFixed<F, FRAC_BITS> {
+    fn mul(lhs: ProgramNode<Self>, rhs: ProgramNode<Self>) -> ProgramNode<Self> {
+        range_check::<F>(lhs.ids[0], true);
+        range_check::<F>(rhs.ids[0], true);
+
+        let (product, scale) = with_zkp_ctx(|ctx| {
+            (
+                ctx.add_multiplication(lhs.ids[0], rhs.ids[0]),
+                ctx.add_constant(&BigInt::from(1u128 << FRAC_BITS)),
+            )
+        });
+
+        // The product is scaled by 2^(2 * FRAC_BITS); divide one out.
+        let o = if FRAC_BITS == 0 {
+            product
+        } else {
+            invoke_gadget(
+                SignedModulus::new(F::FIELD_MODULUS, FRAC_BITS),
+                &[product, scale],
+            )[0]
+        };
+
+        range_check::<F>(o, true);
+
+        ProgramNode::new(&[o])
+    }
+}
+
+impl<F: FieldSpec, const FRAC_BITS: usize> ConstrainEqVarVar for ZkpFixed<F, FRAC_BITS> {
+    fn constrain_eq(lhs: ProgramNode<Self>, rhs: ProgramNode<Self>) -> ProgramNode<Self> {
+        let c = Field::<F>::constrain_eq(lhs.into_field(), rhs.into_field());
+
+        ProgramNode::new(c.ids)
+    }
+}
+
+impl<F: FieldSpec, const FRAC_BITS: usize> ConstrainCmpVarVar for ZkpFixed<F, FRAC_BITS> {
+    fn constrain_le_bounded(lhs: ProgramNode<Self>, rhs: ProgramNode<Self>, bits: usize) {
+        range_check::<F>(lhs.ids[0], true);
+        range_check::<F>(rhs.ids[0], true);
+
+        Field::<F>::constrain_le_bounded(lhs.into_field(), rhs.into_field(), bits);
+    }
+
+    fn constrain_lt_bounded(lhs: ProgramNode<Self>, rhs: ProgramNode<Self>, bits: usize) {
+        range_check::<F>(lhs.ids[0], true);
+        range_check::<F>(rhs.ids[0], true);
+
+        Field::<F>::constrain_lt_bounded(lhs.into_field(), rhs.into_field(), bits);
+    }
+
+    fn constrain_ge_bounded(lhs: ProgramNode<Self>, rhs: ProgramNode<Self>, bits: usize) {
+        range_check::<F>(lhs.ids[0], true);
+        range_check::<F>(rhs.ids[0], true);
+
+        Field::<F>::constrain_ge_bounded(lhs.into_field(), rhs.into_field(), bits);
+    }
+
+    fn constrain_gt_bounded(lhs: ProgramNode<Self>, rhs: ProgramNode<Self>, bits: usize) {
+        range_check::<F>(lhs.ids[0], true);
+        range_check::<F>(rhs.ids[0], true);
+
+        Field::<F>::constrain_gt_bounded(lhs.into_field(), rhs.into_field(), bits);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sunscreen_runtime::Runtime;
+    use sunscreen_zkp_backend::bulletproofs::BulletproofsBackend;
+    use sunscreen_zkp_backend::ZkpBackend;
+
+    use super::*;
+    use crate::types::zkp::ConstrainCmp;
+    use crate::{zkp_program, Compiler};
+
+    type BPFieldSpec = <BulletproofsBackend as ZkpBackend>::Field;
+    type Fixed = ZkpFixed<BPFieldSpec, 16>;
+
+    #[test]
+    fn can_compute_interest() {
+        #[zkp_program]
+        fn interest<F: FieldSpec>(
+            principal: ZkpFixed<F, 16>,
+            #[public] rate: ZkpFixed<F, 16>,
+            #[public] floor: ZkpFixed<F, 16>,
+        ) {
+            let balance = principal + principal * rate;
+
+            balance.constrain_ge_bounded(floor, 64);
+        }
+
+        let app = Compiler::new()
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(interest)
+            .compile()
+            .unwrap();
+
+        let runtime = Runtime::new_zkp(BulletproofsBackend::new()).unwrap();
+
+        let prog = app.get_zkp_program(interest).unwrap();
+
+        let prove = |principal: f64, rate: f64, floor: f64| {
+            runtime.prove(
+                prog,
+                vec![Fixed::from(principal)],
+                vec![Fixed::from(rate), Fixed::from(floor)],
+                vec![],
+            )
+        };
+
+        // 1000 * 1.05 == 1050
+        let proof = prove(1000.0, 0.05, 1049.5).unwrap();
+
+        runtime
+            .verify(
+                prog,
+                &proof,
+                vec![Fixed::from(0.05), Fixed::from(1049.5)],
+                vec![],
+            )
+            .unwrap();
+
+        assert!(prove(1000.0, 0.05, 1050.5).is_err());
+
+        // Negative values work too.
+        prove(-1000.0, -0.05, -950.0).unwrap();
+    }
+
+    #[test]
+    fn multiplication_rounds_down() {
+        #[zkp_program]
+        fn mul<F: FieldSpec>(a: ZkpFixed<F, 4>, b: ZkpFixed<F, 4>, #[public] c: ZkpFixed<F, 4>) {
+            (a * b).constrain_eq(c);
+        }
+
+        let app = Compiler::new()
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(mul)
+            .compile()
+            .unwrap();
+
+        let runtime = Runtime::new_zkp(BulletproofsBackend::new()).unwrap();
+
+        let prog = app.get_zkp_program(mul).unwrap();
+
+        let prove = |a: i64, b: i64, c: i64| {
+            runtime.prove(
+                prog,
+                vec![
+                    ZkpFixed::<BPFieldSpec, 4>::from_raw(a),
+                    ZkpFixed::from_raw(b),
+                ],
+                vec![ZkpFixed::from_raw(c)],
+                vec![],
+            )
+        };
+
+        // (3/16) * (5/16) = 15/256, which rounds to 0/16.
+        prove(3, 5, 0).unwrap();
+
+        // (-3/16) * (5/16) = -15/256, which rounds to -1/16.
+        prove(-3, 5, -1).unwrap();
+
+        assert!(prove(-3, 5, 0).is_err());
+
+        // 2^40 * 2^40 / 2^4 overflows.
+        assert!(prove(1 << 40, 1 << 40, 0).is_err());
+    }
+}
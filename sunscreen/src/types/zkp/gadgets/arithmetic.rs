@@ -6,6 +6,10 @@ use crate::zkp::{invoke_gadget, with_zkp_ctx, ZkpContextOps};
 
 use super::ToUInt;
 
+/**
+ * For values x and m, computes the quotient q and remainder r of x / m
+ * and proves `m * q + r == x` and `0 <= r < m`.
+ */
 #[derive(Clone, Copy)]
 pub struct SignedModulus {
     field_modulus: BigInt,
@@ -134,6 +138,12 @@ pub struct Inverse {
 }
 
 impl Inverse {
+    /**
+     * Creates a new [`Inverse`] gadget.
+     *
+     * # Panics
+     * * When `field_modulus == 0`
+     */
     pub fn new(field_modulus: BigInt) -> Self {
         if field_modulus == BigInt::ZERO {
             panic!("Field modulus cannot be zero.");
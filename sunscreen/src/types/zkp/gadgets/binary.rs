@@ -1,6 +1,7 @@
+use petgraph::stable_graph::NodeIndex;
 use sunscreen_zkp_backend::{BigInt, Error as ZkpError, Gadget, Result as ZkpResult};
 
-use crate::zkp::{invoke_gadget, with_zkp_ctx, ZkpContextOps};
+use crate::zkp::{invoke_gadget, with_zkp_ctx, Operation, ZkpContextOps};
 
 /**
  * Expands a field element into N-bit unsigned binary.
@@ -149,6 +150,57 @@ impl Gadget for AssertBinary {
     }
 }
 
+/**
+ * Proves `x` is 0 or 1 using [`AssertBinary`], unless we already did so
+ * in this [`zkp_program`](crate::zkp_program). Use [`mark_binary`] to
+ * record nodes that are binary by construction.
+ *
+ * # Panics
+ * * If `x` is a constant other than 0 or 1
+ * * Calling this function outside a [`zkp_program`](crate::zkp_program)
+ */
+pub fn assert_binary(x: NodeIndex) {
+    let (known, constant) = with_zkp_ctx(|ctx| {
+        let constant = match &ctx.graph[x].operation {
+            Operation::Constant(c) => Some(*c),
+            _ => None,
+        };
+
+        (ctx.data.booleans.contains(&x), constant)
+    });
+
+    if known {
+        return;
+    }
+
+    match constant {
+        Some(c) => {
+            if c != BigInt::ZERO && c != BigInt::ONE {
+                panic!("Constant is not binary.");
+            }
+        }
+        None => {
+            invoke_gadget(AssertBinary, &[x]);
+        }
+    }
+
+    mark_binary(x);
+}
+
+/**
+ * Records that `x` is 0 or 1 without adding constraints. Only call this
+ * for nodes computed from other binary nodes in a way that preserves
+ * this (e.g. `a * b` or `1 - a`).
+ *
+ * # Panics
+ * * Calling this function outside a [`zkp_program`](crate::zkp_program)
+ */
+pub fn mark_binary(x: NodeIndex) {
+    with_zkp_ctx(|ctx| {
+        ctx.data.booleans.insert(x);
+    });
+}
+
 #[cfg(test)]
 mod tests {
     use sunscreen_runtime::{Runtime, ZkpProgramInput};
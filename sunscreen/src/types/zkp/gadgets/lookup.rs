@@ -0,0 +1,286 @@
+use petgraph::stable_graph::NodeIndex;
+use sunscreen_zkp_backend::{BigInt, Error as ZkpError, Gadget, Result as ZkpResult};
+
+use crate::zkp::{invoke_gadget, with_zkp_ctx, ZkpContextOps};
+
+use super::AssertBinary;
+
+/**
+ * For an index `i < n`, computes the `n` element vector with a 1 in
+ * position `i` and 0 elsewhere. We prove:
+ * * Each element is 0 or 1
+ * * The elements sum to 1
+ * * `sum(j * s_j) == i`
+ */
+pub struct OneHot {
+    n: usize,
+}
+
+impl OneHot {
+    /**
+     * Creates a new [`OneHot`] gadget for indices less than `n`.
+     *
+     * # Panics
+     * * If `n == 0`
+     */
+    pub fn new(n: usize) -> Self {
+        if n == 0 {
+            panic!("Cannot create a 0 element one-hot vector.");
+        }
+
+        Self { n }
+    }
+}
+
+impl Gadget for OneHot {
+    fn compute_hidden_inputs(&self, gadget_inputs: &[BigInt]) -> ZkpResult<Vec<BigInt>> {
+        let index = gadget_inputs[0];
+
+        if index >= BigInt::from(self.n as u64) {
+            return Err(ZkpError::gadget_error(&format!(
+                "Index out of bounds for {} element table.",
+                self.n
+            )));
+        }
+
+        Ok((0..self.n)
+            .map(|j| {
+                if BigInt::from(j as u64) == index {
+                    BigInt::ONE
+                } else {
+                    BigInt::ZERO
+                }
+            })
+            .collect())
+    }
+
+    fn gen_circuit(
+        &self,
+        gadget_inputs: &[NodeIndex],
+        hidden_inputs: &[NodeIndex],
+    ) -> Vec<NodeIndex> {
+        let index = gadget_inputs[0];
+
+        for s in hidden_inputs {
+            invoke_gadget(AssertBinary, &[*s]);
+        }
+
+        with_zkp_ctx(|ctx| {
+            let mut sum = hidden_inputs[0];
+            let mut weighted_sum = None;
+
+            for (j, s) in hidden_inputs.iter().enumerate().skip(1) {
+                sum = ctx.add_addition(sum, *s);
+
+                let j = ctx.add_constant(&BigInt::from(j as u64));
+                let term = ctx.add_multiplication(*s, j);
+
+                weighted_sum = Some(match weighted_sum {
+                    Some(w) => ctx.add_addition(w, term),
+                    None => term,
+                });
+            }
+
+            ctx.add_constraint(sum, &BigInt::ONE);
+
+            match weighted_sum {
+                Some(w) => {
+                    let diff = ctx.add_subtraction(w, index);
+                    ctx.add_constraint(diff, &BigInt::ZERO);
+                }
+                None => {
+                    ctx.add_constraint(index, &BigInt::ZERO);
+                }
+            }
+        });
+
+        hidden_inputs.to_owned()
+    }
+
+    fn gadget_input_count(&self) -> usize {
+        1
+    }
+
+    fn hidden_input_count(&self) -> usize {
+        self.n
+    }
+}
+
+/**
+ * Returns the [`OneHot`] encoding of `index` for tables with `n`
+ * entries.
+ *
+ * # Remarks
+ * Encodings are shared within a [`zkp_program`](crate::zkp_program), so
+ * looking up the same index in several tables of the same size pays for
+ * the encoding once.
+ *
+ * # Panics
+ * * If `n == 0`
+ * * Calling this function outside a [`zkp_program`](crate::zkp_program)
+ */
+pub fn one_hot(index: NodeIndex, n: usize) -> Vec<NodeIndex> {
+    let existing = with_zkp_ctx(|ctx| ctx.data.one_hots.get(&(index, n)).cloned());
+
+    match existing {
+        Some(s) => s,
+        None => {
+            let s = invoke_gadget(OneHot::new(n), &[index]);
+
+            with_zkp_ctx(|ctx| {
+                ctx.data.one_hots.insert((index, n), s.clone());
+                ctx.data.booleans.extend(s.iter().copied());
+            });
+
+            s
+        }
+    }
+}
+
+/**
+ * Returns a node equal to `table[index]` and proves `index` lies in
+ * `0..table.len()`.
+ *
+ * # Remarks
+ * This costs a multiplication per table entry to encode the index (see
+ * [`one_hot`]). Selecting the entry is linear in the encoding and free.
+ *
+ * # Panics
+ * * If `table` is empty
+ * * Calling this function outside a [`zkp_program`](crate::zkp_program)
+ */
+pub fn lookup(index: NodeIndex, table: &[BigInt]) -> NodeIndex {
+    let s = one_hot(index, table.len());
+
+    with_zkp_ctx(|ctx| {
+        let mut value = None;
+
+        for (s, t) in s.iter().zip(table.iter()) {
+            if *t == BigInt::ZERO {
+                continue;
+            }
+
+            let t = ctx.add_constant(t);
+            let term = ctx.add_multiplication(*s, t);
+
+            value = Some(match value {
+                Some(v) => ctx.add_addition(v, term),
+                None => term,
+            });
+        }
+
+        value.unwrap_or_else(|| ctx.add_constant(&BigInt::ZERO))
+    })
+}
+
+/**
+ * Returns a node equal to `values[index]` and proves `index` lies in
+ * `0..values.len()`. Unlike [`lookup`], the entries may be private.
+ *
+ * # Remarks
+ * This shares the [`one_hot`] encoding of `index` with other lookups
+ * and costs an additional multiplication per entry to select the value.
+ *
+ * # Panics
+ * * If `values` is empty
+ * * Calling this function outside a [`zkp_program`](crate::zkp_program)
+ */
+pub fn select_index(index: NodeIndex, values: &[NodeIndex]) -> NodeIndex {
+    let s = one_hot(index, values.len());
+
+    with_zkp_ctx(|ctx| {
+        let mut value = ctx.add_multiplication(s[0], values[0]);
+
+        for (s, v) in s.iter().zip(values.iter()).skip(1) {
+            let term = ctx.add_multiplication(*s, *v);
+
+            value = ctx.add_addition(value, term);
+        }
+
+        value
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use sunscreen_runtime::Runtime;
+    use sunscreen_zkp_backend::bulletproofs::BulletproofsBackend;
+    use sunscreen_zkp_backend::{FieldSpec, ZkpBackend};
+
+    use crate::types::zkp::{Field, Lookup};
+    use crate::{self as sunscreen};
+    use crate::{zkp_program, Compiler};
+
+    type BPField = Field<<BulletproofsBackend as ZkpBackend>::Field>;
+
+    #[test]
+    fn can_lookup_table() {
+        #[zkp_program]
+        fn test<F: FieldSpec>(
+            index: Field<F>,
+            #[public] squared: Field<F>,
+            #[public] cubed: Field<F>,
+        ) {
+            let squares = [0u32, 1, 4, 9, 16].map(Field::from);
+            let cubes = [0u32, 1, 8, 27, 64].map(Field::from);
+
+            index.lookup(&squares).constrain_eq(squared);
+            index.lookup(&cubes).constrain_eq(cubed);
+        }
+
+        let app = Compiler::new()
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(test)
+            .compile()
+            .unwrap();
+
+        let runtime = Runtime::new_zkp(BulletproofsBackend::new()).unwrap();
+
+        let prog = app.get_zkp_program(test).unwrap();
+
+        let proof = runtime
+            .prove(
+                prog,
+                vec![BPField::from(3u8)],
+                vec![BPField::from(9u8), BPField::from(27u8)],
+                vec![],
+            )
+            .unwrap();
+
+        runtime
+            .verify(
+                prog,
+                &proof,
+                vec![BPField::from(9u8), BPField::from(27u8)],
+                vec![],
+            )
+            .unwrap();
+
+        // Wrong entry
+        let result = runtime.prove(
+            prog,
+            vec![BPField::from(3u8)],
+            vec![BPField::from(16u8), BPField::from(27u8)],
+            vec![],
+        );
+
+        assert!(result.is_err());
+
+        // Out of bounds
+        let result = runtime.prove(
+            prog,
+            vec![BPField::from(5u8)],
+            vec![BPField::from(0u8), BPField::from(0u8)],
+            vec![],
+        );
+
+        assert!(result.is_err());
+
+        // Both lookups share one encoding of the index.
+        let metrics = BulletproofsBackend::new()
+            .gadget_metrics(&prog.zkp_program_fn)
+            .unwrap();
+
+        assert_eq!(metrics.total().multipliers, 5);
+    }
+}
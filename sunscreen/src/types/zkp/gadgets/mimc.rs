@@ -0,0 +1,220 @@
+use petgraph::stable_graph::NodeIndex;
+use sunscreen_zkp_backend::{BigInt, Gadget, Result as ZkpResult};
+
+use crate::zkp::{invoke_gadget, with_zkp_ctx, ZkpContextOps};
+
+use super::poseidon::{assert_pow5_permutes, hash_to_field, pow5, pow5_node};
+
+#[derive(Clone)]
+/**
+ * The MiMC block cipher with the `x^5` S-box. Each round computes
+ * `x <- (x + k + c_i)^5`, where `c_0 = 0`, and we add `k` once more at
+ * the end. We use `ceil(log_5(p))` rounds.
+ *
+ * The gadget inputs are the plaintext and key, and the output is the
+ * ciphertext.
+ *
+ * # Remarks
+ * We derive round constants by hashing with SHA-256, so ciphertexts
+ * won't match other MiMC instantiations; use [`MiMC::encrypt`] to
+ * compute values outside a [`zkp_program`](crate::zkp_program).
+ *
+ * Each round costs 3 multiplications, or 327 for a 253-bit field.
+ */
+pub struct MiMC {
+    field_modulus: BigInt,
+    round_constants: Vec<BigInt>,
+}
+
+impl MiMC {
+    /**
+     * Creates the MiMC cipher over the field with the given modulus.
+     *
+     * # Panics
+     * * If `gcd(5, field_modulus - 1) != 1`
+     */
+    pub fn new(field_modulus: BigInt) -> Self {
+        assert_pow5_permutes(&field_modulus);
+
+        let rounds = (field_modulus.bits_vartime() as f64 / 5f64.log2()).ceil() as usize;
+
+        let round_constants = (0..rounds)
+            .map(|i| {
+                if i == 0 {
+                    BigInt::ZERO
+                } else {
+                    hash_to_field(
+                        &[&b"sunscreen_mimc"[..], &(i as u32).to_be_bytes()].concat(),
+                        &field_modulus,
+                    )
+                }
+            })
+            .collect();
+
+        Self {
+            field_modulus,
+            round_constants,
+        }
+    }
+
+    /**
+     * Encrypts `x` under `key` natively.
+     */
+    pub fn encrypt(&self, x: &BigInt, key: &BigInt) -> BigInt {
+        let p = &self.field_modulus;
+
+        let x = self
+            .round_constants
+            .iter()
+            .fold(*x, |x, c| pow5(&x.add_fp(key, p).add_fp(c, p), p));
+
+        x.add_fp(key, p)
+    }
+
+    /**
+     * Hashes `inputs` natively in Miyaguchi-Preneel mode, starting from
+     * `h = 0` and computing `h <- E_h(m) + h + m` for each input `m`.
+     *
+     * # Remarks
+     * Each input should be less than the field modulus.
+     */
+    pub fn hash(&self, inputs: &[BigInt]) -> BigInt {
+        let p = &self.field_modulus;
+
+        inputs.iter().fold(BigInt::ZERO, |h, m| {
+            self.encrypt(m, &h).add_fp(&h, p).add_fp(m, p)
+        })
+    }
+}
+
+impl Gadget for MiMC {
+    fn compute_hidden_inputs(&self, _gadget_inputs: &[BigInt]) -> ZkpResult<Vec<BigInt>> {
+        Ok(vec![])
+    }
+
+    fn gen_circuit(
+        &self,
+        gadget_inputs: &[NodeIndex],
+        _hidden_inputs: &[NodeIndex],
+    ) -> Vec<NodeIndex> {
+        let x = gadget_inputs[0];
+        let key = gadget_inputs[1];
+
+        with_zkp_ctx(|ctx| {
+            let x = self.round_constants.iter().fold(x, |x, c| {
+                let c = ctx.add_constant(c);
+                let x = ctx.add_addition(x, key);
+                let x = ctx.add_addition(x, c);
+
+                pow5_node(ctx, x)
+            });
+
+            vec![ctx.add_addition(x, key)]
+        })
+    }
+
+    fn gadget_input_count(&self) -> usize {
+        2
+    }
+
+    fn hidden_input_count(&self) -> usize {
+        0
+    }
+}
+
+/**
+ * Computes the [`MiMC`] hash of `inputs` in a
+ * [`zkp_program`](crate::zkp_program). This matches [`MiMC::hash`].
+ *
+ * # Panics
+ * * If `gcd(5, field_modulus - 1) != 1`
+ * * Calling this function outside a [`zkp_program`](crate::zkp_program)
+ */
+pub fn mimc_hash(inputs: &[NodeIndex], field_modulus: &BigInt) -> NodeIndex {
+    let mimc = MiMC::new(*field_modulus);
+
+    let zero = with_zkp_ctx(|ctx| ctx.add_constant(&BigInt::ZERO));
+
+    inputs.iter().fold(zero, |h, m| {
+        let e = invoke_gadget(mimc.clone(), &[*m, h])[0];
+
+        with_zkp_ctx(|ctx| {
+            let e_h = ctx.add_addition(e, h);
+            ctx.add_addition(e_h, *m)
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crypto_bigint::U512;
+    use sunscreen_runtime::Runtime;
+    use sunscreen_zkp_backend::bulletproofs::BulletproofsBackend;
+    use sunscreen_zkp_backend::{FieldSpec, ZkpBackend};
+
+    use super::*;
+    use crate::types::zkp::{Field, ZkpHash};
+    use crate::{self as sunscreen};
+    use crate::{zkp_program, Compiler};
+
+    type BPFieldSpec = <BulletproofsBackend as ZkpBackend>::Field;
+    type BPField = Field<BPFieldSpec>;
+
+    #[test]
+    fn native_encryption_depends_on_key() {
+        let mimc = MiMC::new(BPFieldSpec::FIELD_MODULUS);
+
+        let x = BigInt::from(42u8);
+
+        assert_eq!(mimc.round_constants.len(), 109);
+        assert_ne!(mimc.encrypt(&x, &BigInt::ZERO), x);
+        assert_ne!(
+            mimc.encrypt(&x, &BigInt::ZERO),
+            mimc.encrypt(&x, &BigInt::ONE)
+        );
+    }
+
+    #[test]
+    fn can_hash_with_mimc() {
+        #[zkp_program]
+        fn hash<F: FieldSpec>(a: Field<F>, b: Field<F>, #[public] h: Field<F>) {
+            [a, b].mimc().constrain_eq(h);
+        }
+
+        let app = Compiler::new()
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(hash)
+            .compile()
+            .unwrap();
+
+        let runtime = Runtime::new_zkp(BulletproofsBackend::new()).unwrap();
+
+        let prog = app.get_zkp_program(hash).unwrap();
+
+        let inputs = [7u8, 11].map(BigInt::from);
+        let h = MiMC::new(BPFieldSpec::FIELD_MODULUS).hash(&inputs);
+
+        let private = inputs.map(BPField::from).to_vec();
+
+        let proof = runtime
+            .prove(prog, private.clone(), vec![BPField::from(h)], vec![])
+            .unwrap();
+
+        runtime
+            .verify(prog, &proof, vec![BPField::from(h)], vec![])
+            .unwrap();
+
+        let wrong = BPField::from(BigInt::from(h.wrapping_add(&U512::ONE)));
+
+        assert!(runtime.prove(prog, private, vec![wrong], vec![]).is_err());
+
+        let metrics = BulletproofsBackend::new()
+            .gadget_metrics(&prog.zkp_program_fn)
+            .unwrap();
+
+        let mimc = &metrics.gadgets[std::any::type_name::<MiMC>()];
+
+        assert_eq!(mimc.invocations, 2);
+        assert_eq!(mimc.metrics.multipliers, 2 * 3 * 109);
+    }
+}
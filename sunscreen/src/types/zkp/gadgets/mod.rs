@@ -1,5 +1,15 @@
 mod arithmetic;
 mod binary;
+mod lookup;
+mod mimc;
+mod poseidon;
+mod range;
+mod sha256;
 
 pub use arithmetic::*;
 pub use binary::*;
+pub use lookup::*;
+pub use mimc::*;
+pub use poseidon::*;
+pub use range::*;
+pub use sha256::*;
@@ -0,0 +1,472 @@
+use crypto_bigint::{NonZero, U512};
+use petgraph::stable_graph::NodeIndex;
+use sunscreen_zkp_backend::{BigInt, Gadget, Result as ZkpResult};
+
+use crate::zkp::{invoke_gadget, with_zkp_ctx, ZkpContext, ZkpContextOps};
+
+use super::{decompose, Sha256Compression};
+
+const WIDTH: usize = 3;
+const RATE: usize = WIDTH - 1;
+const FULL_ROUNDS: usize = 8;
+const PARTIAL_ROUNDS: usize = 57;
+
+/**
+ * Derives a field element from `seed` by hashing it with SHA-256.
+ */
+pub(super) fn hash_to_field(seed: &[u8], field_modulus: &BigInt) -> BigInt {
+    let mut bytes = [0u8; 64];
+    bytes[32..].copy_from_slice(&Sha256Compression::digest(seed));
+
+    let p = NonZero::from_uint(field_modulus.0);
+
+    BigInt::from(U512::from_be_slice(&bytes).rem(&p))
+}
+
+/**
+ * Panics unless `x -> x^5` is a permutation of the field, i.e.
+ * `gcd(5, p - 1) == 1`.
+ */
+pub(super) fn assert_pow5_permutes(field_modulus: &BigInt) {
+    let five = NonZero::from_uint(U512::from_u8(5));
+
+    assert_ne!(
+        field_modulus.wrapping_sub(&U512::ONE).rem(&five),
+        U512::ZERO,
+        "x^5 is not a permutation of the field."
+    );
+}
+
+pub(super) fn pow5(x: &BigInt, p: &BigInt) -> BigInt {
+    let x2 = x.mul_fp(x, p);
+    let x4 = x2.mul_fp(&x2, p);
+
+    x4.mul_fp(x, p)
+}
+
+pub(super) fn pow5_node(ctx: &mut ZkpContext, x: NodeIndex) -> NodeIndex {
+    let x2 = ctx.add_multiplication(x, x);
+    let x4 = ctx.add_multiplication(x2, x2);
+
+    ctx.add_multiplication(x4, x)
+}
+
+#[derive(Clone)]
+/**
+ * The Poseidon permutation with width 3, 8 full rounds, 57 partial
+ * rounds, and the `x^5` S-box. This targets 128-bit security in ~255-bit
+ * fields such as the Bulletproofs scalar field.
+ *
+ * The gadget inputs and outputs are the 3 state elements.
+ *
+ * # Remarks
+ * We derive round constants by hashing with SHA-256 and use the Cauchy
+ * matrix `1 / (i + j + 3)` for the MDS layer. Hashes thus won't match
+ * other Poseidon instantiations; use [`Poseidon::hash`] to compute
+ * values outside a [`zkp_program`](crate::zkp_program).
+ *
+ * A permutation costs 243 multiplications.
+ */
+pub struct Poseidon {
+    field_modulus: BigInt,
+    round_constants: Vec<[BigInt; WIDTH]>,
+    mds: [[BigInt; WIDTH]; WIDTH],
+}
+
+impl Poseidon {
+    /**
+     * Creates the Poseidon permutation over the field with the given
+     * modulus.
+     *
+     * # Panics
+     * * If `gcd(5, field_modulus - 1) != 1`
+     */
+    pub fn new(field_modulus: BigInt) -> Self {
+        assert_pow5_permutes(&field_modulus);
+
+        let round_constants = (0..FULL_ROUNDS + PARTIAL_ROUNDS)
+            .map(|r| {
+                let mut c = [BigInt::ZERO; WIDTH];
+
+                for (i, c) in c.iter_mut().enumerate() {
+                    *c = hash_to_field(
+                        &[&b"sunscreen_poseidon"[..], &[r as u8, i as u8]].concat(),
+                        &field_modulus,
+                    );
+                }
+
+                c
+            })
+            .collect();
+
+        let mut mds = [[BigInt::ZERO; WIDTH]; WIDTH];
+
+        for (i, row) in mds.iter_mut().enumerate() {
+            for (j, m) in row.iter_mut().enumerate() {
+                *m = BigInt::from((i + j + WIDTH) as u64).inverse_fp(&field_modulus);
+            }
+        }
+
+        Self {
+            field_modulus,
+            round_constants,
+            mds,
+        }
+    }
+
+    fn is_full_round(r: usize) -> bool {
+        r < FULL_ROUNDS / 2 || r >= FULL_ROUNDS / 2 + PARTIAL_ROUNDS
+    }
+
+    /**
+     * Applies the permutation to `state` natively.
+     */
+    pub fn permute(&self, state: &mut [BigInt; WIDTH]) {
+        let p = &self.field_modulus;
+
+        for (r, c) in self.round_constants.iter().enumerate() {
+            for (s, c) in state.iter_mut().zip(c) {
+                *s = s.add_fp(c, p);
+            }
+
+            let sbox_count = if Self::is_full_round(r) { WIDTH } else { 1 };
+
+            for s in state.iter_mut().take(sbox_count) {
+                *s = pow5(s, p);
+            }
+
+            let mut mixed = [BigInt::ZERO; WIDTH];
+
+            for (m, row) in mixed.iter_mut().zip(&self.mds) {
+                for (s, x) in state.iter().zip(row) {
+                    *m = m.add_fp(&s.mul_fp(x, p), p);
+                }
+            }
+
+            *state = mixed;
+        }
+    }
+
+    /**
+     * Hashes `inputs` natively with a sponge over the permutation. The
+     * capacity element starts as `inputs.len()` to separate lengths.
+     *
+     * # Remarks
+     * Each input should be less than the field modulus.
+     */
+    pub fn hash(&self, inputs: &[BigInt]) -> BigInt {
+        let p = &self.field_modulus;
+
+        let mut state = [BigInt::ZERO; WIDTH];
+        state[0] = BigInt::from(inputs.len() as u64);
+
+        for chunk in inputs.chunks(RATE) {
+            for (s, x) in state[1..].iter_mut().zip(chunk) {
+                *s = s.add_fp(x, p);
+            }
+
+            self.permute(&mut state);
+        }
+
+        if inputs.is_empty() {
+            self.permute(&mut state);
+        }
+
+        state[1]
+    }
+
+    /**
+     * Computes the root of a Merkle tree natively, given a `leaf` at
+     * `index` and its authentication `path` from the bottom up. Each
+     * node is the [`hash`](Self::hash) of its left and right children.
+     */
+    pub fn merkle_root(&self, leaf: BigInt, index: u64, path: &[BigInt]) -> BigInt {
+        path.iter().enumerate().fold(leaf, |cur, (i, sibling)| {
+            if (index >> i) & 0x1 == 0 {
+                self.hash(&[cur, *sibling])
+            } else {
+                self.hash(&[*sibling, cur])
+            }
+        })
+    }
+
+    fn hash_nodes(&self, inputs: &[NodeIndex]) -> NodeIndex {
+        let mut state = with_zkp_ctx(|ctx| {
+            let len = ctx.add_constant(&BigInt::from(inputs.len() as u64));
+            let zero = ctx.add_constant(&BigInt::ZERO);
+
+            vec![len, zero, zero]
+        });
+
+        for chunk in inputs.chunks(RATE) {
+            with_zkp_ctx(|ctx| {
+                for (s, x) in state[1..].iter_mut().zip(chunk) {
+                    *s = ctx.add_addition(*s, *x);
+                }
+            });
+
+            state = invoke_gadget(self.clone(), &state);
+        }
+
+        if inputs.is_empty() {
+            state = invoke_gadget(self.clone(), &state);
+        }
+
+        state[1]
+    }
+}
+
+impl Gadget for Poseidon {
+    fn compute_hidden_inputs(&self, _gadget_inputs: &[BigInt]) -> ZkpResult<Vec<BigInt>> {
+        Ok(vec![])
+    }
+
+    fn gen_circuit(
+        &self,
+        gadget_inputs: &[NodeIndex],
+        _hidden_inputs: &[NodeIndex],
+    ) -> Vec<NodeIndex> {
+        with_zkp_ctx(|ctx| {
+            let mut state = gadget_inputs.to_vec();
+
+            for (r, c) in self.round_constants.iter().enumerate() {
+                for (s, c) in state.iter_mut().zip(c) {
+                    let c = ctx.add_constant(c);
+                    *s = ctx.add_addition(*s, c);
+                }
+
+                let sbox_count = if Self::is_full_round(r) { WIDTH } else { 1 };
+
+                for s in state.iter_mut().take(sbox_count) {
+                    *s = pow5_node(ctx, *s);
+                }
+
+                state = self
+                    .mds
+                    .iter()
+                    .map(|row| {
+                        let terms = state
+                            .iter()
+                            .zip(row)
+                            .map(|(s, m)| {
+                                let m = ctx.add_constant(m);
+                                ctx.add_multiplication(*s, m)
+                            })
+                            .collect::<Vec<_>>();
+
+                        terms[1..]
+                            .iter()
+                            .fold(terms[0], |acc, t| ctx.add_addition(acc, *t))
+                    })
+                    .collect();
+            }
+
+            state
+        })
+    }
+
+    fn gadget_input_count(&self) -> usize {
+        WIDTH
+    }
+
+    fn hidden_input_count(&self) -> usize {
+        0
+    }
+}
+
+/**
+ * Computes the [`Poseidon`] hash of `inputs` in a
+ * [`zkp_program`](crate::zkp_program). This matches
+ * [`Poseidon::hash`].
+ *
+ * # Panics
+ * * If `gcd(5, field_modulus - 1) != 1`
+ * * Calling this function outside a [`zkp_program`](crate::zkp_program)
+ */
+pub fn poseidon_hash(inputs: &[NodeIndex], field_modulus: &BigInt) -> NodeIndex {
+    Poseidon::new(*field_modulus).hash_nodes(inputs)
+}
+
+/**
+ * Computes the root of a Merkle tree in a
+ * [`zkp_program`](crate::zkp_program), given a `leaf` at `index` and its
+ * authentication `path` from the bottom up. This matches
+ * [`Poseidon::merkle_root`].
+ *
+ * # Remarks
+ * We decompose `index` into `path.len()` bits, which proves it lies in
+ * the tree. Each level then costs one multiplication to order the
+ * children plus a [`Poseidon`] permutation.
+ *
+ * # Panics
+ * * If `gcd(5, field_modulus - 1) != 1`
+ * * Calling this function outside a [`zkp_program`](crate::zkp_program)
+ */
+pub fn merkle_root(
+    leaf: NodeIndex,
+    index: NodeIndex,
+    path: &[NodeIndex],
+    field_modulus: &BigInt,
+) -> NodeIndex {
+    if path.is_empty() {
+        with_zkp_ctx(|ctx| ctx.add_constraint(index, &BigInt::ZERO));
+
+        return leaf;
+    }
+
+    let poseidon = Poseidon::new(*field_modulus);
+
+    let bits = decompose(index, path.len());
+
+    bits.iter().zip(path).fold(leaf, |cur, (bit, sibling)| {
+        let (left, right) = with_zkp_ctx(|ctx| {
+            // When the bit is 1, cur is the right child.
+            let diff = ctx.add_subtraction(*sibling, cur);
+            let offset = ctx.add_multiplication(*bit, diff);
+            let left = ctx.add_addition(cur, offset);
+            let sum = ctx.add_addition(cur, *sibling);
+            let right = ctx.add_subtraction(sum, left);
+
+            (left, right)
+        });
+
+        poseidon.hash_nodes(&[left, right])
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use sunscreen_runtime::Runtime;
+    use sunscreen_zkp_backend::bulletproofs::BulletproofsBackend;
+    use sunscreen_zkp_backend::{FieldSpec, ZkpBackend};
+
+    use super::*;
+    use crate::types::zkp::{Field, MerklePath, ZkpHash};
+    use crate::{self as sunscreen};
+    use crate::{zkp_program, Compiler};
+
+    type BPFieldSpec = <BulletproofsBackend as ZkpBackend>::Field;
+    type BPField = Field<BPFieldSpec>;
+
+    #[test]
+    fn native_hash_separates_inputs() {
+        let poseidon = Poseidon::new(BPFieldSpec::FIELD_MODULUS);
+
+        let a = poseidon.hash(&[BigInt::from(1u8), BigInt::from(2u8)]);
+        let b = poseidon.hash(&[BigInt::from(2u8), BigInt::from(1u8)]);
+        let c = poseidon.hash(&[BigInt::from(1u8), BigInt::from(2u8), BigInt::ZERO]);
+
+        assert_ne!(a, b);
+        assert_ne!(a, c);
+        assert!(a < BPFieldSpec::FIELD_MODULUS);
+    }
+
+    #[test]
+    fn can_hash_with_poseidon() {
+        #[zkp_program]
+        fn hash<F: FieldSpec>(a: Field<F>, b: Field<F>, c: Field<F>, #[public] h: Field<F>) {
+            [a, b, c].poseidon().constrain_eq(h);
+        }
+
+        let app = Compiler::new()
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(hash)
+            .compile()
+            .unwrap();
+
+        let runtime = Runtime::new_zkp(BulletproofsBackend::new()).unwrap();
+
+        let prog = app.get_zkp_program(hash).unwrap();
+
+        let inputs = [3u8, 1, 4].map(BigInt::from);
+        let h = Poseidon::new(BPFieldSpec::FIELD_MODULUS).hash(&inputs);
+
+        let private = inputs.map(BPField::from).to_vec();
+
+        let proof = runtime
+            .prove(prog, private.clone(), vec![BPField::from(h)], vec![])
+            .unwrap();
+
+        runtime
+            .verify(prog, &proof, vec![BPField::from(h)], vec![])
+            .unwrap();
+
+        let wrong = BPField::from(BigInt::from(h.wrapping_add(&U512::ONE)));
+
+        assert!(runtime.prove(prog, private, vec![wrong], vec![]).is_err());
+    }
+
+    #[test]
+    fn can_prove_merkle_membership() {
+        #[zkp_program]
+        fn member<F: FieldSpec>(
+            leaf: Field<F>,
+            index: Field<F>,
+            path: [Field<F>; 3],
+            #[public] root: Field<F>,
+        ) {
+            leaf.merkle_root(index, &path).constrain_eq(root);
+        }
+
+        let app = Compiler::new()
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(member)
+            .compile()
+            .unwrap();
+
+        let runtime = Runtime::new_zkp(BulletproofsBackend::new()).unwrap();
+
+        let prog = app.get_zkp_program(member).unwrap();
+
+        let poseidon = Poseidon::new(BPFieldSpec::FIELD_MODULUS);
+
+        // Build a depth 3 tree over 8 leaves.
+        let mut levels = vec![(0..8u8).map(|x| BigInt::from(100 + x)).collect::<Vec<_>>()];
+
+        while levels.last().unwrap().len() > 1 {
+            let next = levels
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|c| poseidon.hash(c))
+                .collect();
+
+            levels.push(next);
+        }
+
+        let root = levels[3][0];
+        let index = 5;
+        let path = (0..3)
+            .map(|i| levels[i][(index >> i) ^ 1])
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            poseidon.merkle_root(levels[0][index], index as u64, &path),
+            root
+        );
+
+        let prove = |leaf: BigInt, index: usize| {
+            let path = path.iter().map(|x| BPField::from(*x)).collect::<Vec<_>>();
+
+            runtime
+                .proof_builder(prog)
+                .private_input(BPField::from(leaf))
+                .private_input(BPField::from(index as u64))
+                .private_input(<[BPField; 3]>::try_from(path).unwrap())
+                .public_input(BPField::from(root))
+                .prove()
+        };
+
+        let proof = prove(levels[0][index], index).unwrap();
+
+        runtime
+            .verify(prog, &proof, vec![BPField::from(root)], vec![])
+            .unwrap();
+
+        // Wrong leaf
+        assert!(prove(levels[0][4], index).is_err());
+
+        // Index outside the tree
+        assert!(prove(levels[0][index], index + 8).is_err());
+    }
+}
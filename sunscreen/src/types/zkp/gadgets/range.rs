@@ -0,0 +1,287 @@
+use crypto_bigint::U512;
+use petgraph::stable_graph::NodeIndex;
+use sunscreen_zkp_backend::BigInt;
+
+use crate::zkp::{invoke_gadget, with_zkp_ctx, ZkpContextOps};
+
+use super::ToUInt;
+
+/**
+ * Decomposes `x` into `bits`-bit unsigned binary, which proves
+ * `0 <= x < 2^bits`. Returns the bits, least significant first.
+ *
+ * # Remarks
+ * Decompositions are shared within a
+ * [`zkp_program`](crate::zkp_program). If `x` was already decomposed
+ * into at least `bits` bits, we reuse those bits and constrain the
+ * extra high bits to zero, which costs no multiplications.
+ *
+ * # Panics
+ * * If `bits > 512`
+ * * Calling this function outside a [`zkp_program`](crate::zkp_program)
+ */
+pub fn decompose(x: NodeIndex, bits: usize) -> Vec<NodeIndex> {
+    let existing = with_zkp_ctx(|ctx| ctx.data.decompositions.get(&x).cloned());
+
+    match existing {
+        Some(existing) if existing.len() >= bits => {
+            with_zkp_ctx(|ctx| {
+                for bit in &existing[bits..] {
+                    ctx.add_constraint(*bit, &BigInt::ZERO);
+                }
+            });
+
+            existing[..bits].to_vec()
+        }
+        _ => {
+            let decomposition = invoke_gadget(ToUInt::new(bits), &[x]);
+
+            with_zkp_ctx(|ctx| {
+                ctx.data.decompositions.insert(x, decomposition.clone());
+            });
+
+            decomposition
+        }
+    }
+}
+
+/**
+ * Decomposes `x + 2^(bits - 1)` into `bits`-bit unsigned binary, which
+ * proves `-2^(bits - 1) <= x < 2^(bits - 1)` when we interpret the field
+ * as signed values. Returns the bits, least significant first.
+ *
+ * # Remarks
+ * As with [`decompose`], we share decompositions of the same value.
+ *
+ * # Panics
+ * * If `bits == 0` or `bits > 512`
+ * * Calling this function outside a [`zkp_program`](crate::zkp_program)
+ */
+pub fn decompose_signed(x: NodeIndex, bits: usize, field_modulus: &BigInt) -> Vec<NodeIndex> {
+    assert!(bits > 0, "Cannot decompose into 0 bit signed values.");
+
+    // x - (p - 2^(bits - 1)) == x + 2^(bits - 1)
+    let offset = BigInt::from(field_modulus.wrapping_sub(&U512::ONE.shl_vartime(bits - 1)));
+
+    decompose(difference(x, &offset, false), bits)
+}
+
+/**
+ * Returns a node computing `x - c` (or `c - x` if `negate`). Range
+ * checks reuse these nodes so their decompositions can be shared.
+ */
+fn difference(x: NodeIndex, c: &BigInt, negate: bool) -> NodeIndex {
+    if !negate && *c == BigInt::ZERO {
+        return x;
+    }
+
+    with_zkp_ctx(|ctx| {
+        let c = ctx.add_constant(c);
+        let (left, right) = if negate { (c, x) } else { (x, c) };
+
+        match ctx.data.differences.get(&(left, right)) {
+            Some(d) => *d,
+            None => {
+                let d = ctx.add_subtraction(left, right);
+                ctx.data.differences.insert((left, right), d);
+
+                d
+            }
+        }
+    })
+}
+
+/**
+ * Proves `lo <= x <= hi`, treating `x`, `lo`, and `hi` as unsigned.
+ *
+ * # Remarks
+ * We decompose `x - lo` and `hi - x` into `b` bits, where `b` is the
+ * number of bits in `hi - lo`. When `hi - lo + 1` is a power of 2,
+ * decomposing `x - lo` suffices. Each decomposed bit costs a
+ * multiplication, and [`decompose`] shares decompositions of the same
+ * value across range checks.
+ *
+ * `2^b` must be less than the backend field's modulus.
+ *
+ * # Panics
+ * * If `hi < lo`
+ * * Calling this function outside a [`zkp_program`](crate::zkp_program)
+ */
+pub fn constrain_in_range(x: NodeIndex, lo: &BigInt, hi: &BigInt) {
+    assert!(lo <= hi, "Range is empty: lo > hi.");
+
+    let width = hi.wrapping_sub(lo);
+
+    if width == U512::ZERO {
+        with_zkp_ctx(|ctx| ctx.add_constraint(x, lo));
+
+        return;
+    }
+
+    let bits = width.bits_vartime();
+
+    decompose(difference(x, lo, false), bits);
+
+    if width != U512::ONE.shl_vartime(bits).wrapping_sub(&U512::ONE) {
+        decompose(difference(x, hi, true), bits);
+    }
+}
+
+/**
+ * Proves `x` equals one of `values`.
+ *
+ * # Remarks
+ * We constrain `(x - v_1) * ... * (x - v_k) == 0`, which costs `k - 1`
+ * multiplications for `k` distinct values.
+ *
+ * # Panics
+ * * If `values` is empty
+ * * Calling this function outside a [`zkp_program`](crate::zkp_program)
+ */
+pub fn constrain_in_set(x: NodeIndex, values: &[BigInt]) {
+    assert!(!values.is_empty(), "Set is empty.");
+
+    let mut values = values.to_vec();
+    values.sort();
+    values.dedup();
+
+    with_zkp_ctx(|ctx| {
+        let mut product = None;
+
+        for v in &values {
+            let v = ctx.add_constant(v);
+            let diff = ctx.add_subtraction(x, v);
+
+            product = Some(match product {
+                Some(p) => ctx.add_multiplication(p, diff),
+                None => diff,
+            });
+        }
+
+        ctx.add_constraint(product.unwrap(), &BigInt::ZERO);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use sunscreen_runtime::{Runtime, ZkpProgramInput};
+    use sunscreen_zkp_backend::bulletproofs::BulletproofsBackend;
+    use sunscreen_zkp_backend::{FieldSpec, ZkpBackend};
+
+    use crate::types::zkp::{ConstrainMembership, Field};
+    use crate::{self as sunscreen};
+    use crate::{zkp_program, Compiler};
+
+    type BPField = Field<<BulletproofsBackend as ZkpBackend>::Field>;
+
+    #[test]
+    fn can_constrain_in_range() {
+        #[zkp_program]
+        fn test<F: FieldSpec>(a: Field<F>) {
+            a.constrain_in_range(Field::from(10u32), Field::from(100u32));
+        }
+
+        let app = Compiler::new()
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(test)
+            .compile()
+            .unwrap();
+
+        let runtime = Runtime::new_zkp(BulletproofsBackend::new()).unwrap();
+
+        let prog = app.get_zkp_program(test).unwrap();
+
+        for (x, expect_pass) in [
+            (9u8, false),
+            (10, true),
+            (57, true),
+            (100, true),
+            (101, false),
+        ] {
+            let result = runtime.prove(prog, vec![BPField::from(x)], vec![], vec![]);
+
+            if expect_pass {
+                runtime
+                    .verify(
+                        prog,
+                        &result.unwrap(),
+                        Vec::<ZkpProgramInput>::new(),
+                        vec![],
+                    )
+                    .unwrap();
+            } else {
+                assert!(result.is_err());
+            }
+        }
+    }
+
+    #[test]
+    fn can_constrain_in_set() {
+        #[zkp_program]
+        fn test<F: FieldSpec>(a: Field<F>) {
+            a.constrain_in_set(&[Field::from(3u32), Field::from(7u32), Field::from(12u32)]);
+        }
+
+        let app = Compiler::new()
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(test)
+            .compile()
+            .unwrap();
+
+        let runtime = Runtime::new_zkp(BulletproofsBackend::new()).unwrap();
+
+        let prog = app.get_zkp_program(test).unwrap();
+
+        for (x, expect_pass) in [(3u8, true), (7, true), (12, true), (4, false), (0, false)] {
+            let result = runtime.prove(prog, vec![BPField::from(x)], vec![], vec![]);
+
+            if expect_pass {
+                runtime
+                    .verify(
+                        prog,
+                        &result.unwrap(),
+                        Vec::<ZkpProgramInput>::new(),
+                        vec![],
+                    )
+                    .unwrap();
+            } else {
+                assert!(result.is_err());
+            }
+        }
+    }
+
+    #[test]
+    fn range_checks_share_decompositions() {
+        #[zkp_program]
+        fn shared<F: FieldSpec>(a: Field<F>) {
+            a.constrain_in_range(Field::from(0u32), Field::from(255u32));
+            a.constrain_in_range(Field::from(0u32), Field::from(127u32));
+        }
+
+        #[zkp_program]
+        fn single<F: FieldSpec>(a: Field<F>) {
+            a.constrain_in_range(Field::from(0u32), Field::from(255u32));
+        }
+
+        let app = Compiler::new()
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(shared)
+            .zkp_program(single)
+            .compile()
+            .unwrap();
+
+        let backend = BulletproofsBackend::new();
+
+        let shared = backend
+            .gadget_metrics(&app.get_zkp_program(shared).unwrap().zkp_program_fn)
+            .unwrap();
+        let single = backend
+            .gadget_metrics(&app.get_zkp_program(single).unwrap().zkp_program_fn)
+            .unwrap();
+
+        // The second range check reuses the first's 8 bits.
+        assert_eq!(shared.total().multipliers, 8);
+        assert_eq!(shared.total().multipliers, single.total().multipliers);
+        assert_eq!(shared.total().constraints, single.total().constraints + 1);
+    }
+}
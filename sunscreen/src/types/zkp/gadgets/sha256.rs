@@ -0,0 +1,457 @@
+use petgraph::stable_graph::NodeIndex;
+use sunscreen_zkp_backend::{BigInt, Gadget, Result as ZkpResult};
+
+use crate::zkp::{invoke_gadget, with_zkp_ctx, ZkpContext, ZkpContextOps};
+
+use super::{decompose, ToUInt};
+
+const K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+const IV: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+/**
+ * The SHA-256 compression function over bits.
+ *
+ * The gadget inputs are the 8 state words followed by the 16 message
+ * words, each as 32 bits, least significant first. The outputs are the
+ * 8 new state words in the same layout.
+ *
+ * # Remarks
+ * This gadget doesn't prove its inputs are binary; callers must
+ * (e.g. by taking them from a [`ToUInt`] decomposition). Each block costs
+ * roughly 27,000 multiplications, dominated by XORs and the binary
+ * decompositions that reduce additions modulo `2^32`.
+ */
+pub struct Sha256Compression;
+
+impl Sha256Compression {
+    /**
+     * Runs the compression function natively on `state`.
+     */
+    pub fn compress(state: &mut [u32; 8], block: &[u32; 16]) {
+        let mut w = [0u32; 64];
+        w[..16].copy_from_slice(block);
+
+        for t in 16..64 {
+            let s0 = w[t - 15].rotate_right(7) ^ w[t - 15].rotate_right(18) ^ (w[t - 15] >> 3);
+            let s1 = w[t - 2].rotate_right(17) ^ w[t - 2].rotate_right(19) ^ (w[t - 2] >> 10);
+
+            w[t] = s1
+                .wrapping_add(w[t - 7])
+                .wrapping_add(s0)
+                .wrapping_add(w[t - 16]);
+        }
+
+        let mut v = *state;
+
+        for t in 0..64 {
+            let s1 = v[4].rotate_right(6) ^ v[4].rotate_right(11) ^ v[4].rotate_right(25);
+            let ch = (v[4] & v[5]) ^ (!v[4] & v[6]);
+            let s0 = v[0].rotate_right(2) ^ v[0].rotate_right(13) ^ v[0].rotate_right(22);
+            let maj = (v[0] & v[1]) ^ (v[0] & v[2]) ^ (v[1] & v[2]);
+
+            let t1 = v[7]
+                .wrapping_add(s1)
+                .wrapping_add(ch)
+                .wrapping_add(K[t])
+                .wrapping_add(w[t]);
+            let t2 = s0.wrapping_add(maj);
+
+            v = [
+                t1.wrapping_add(t2),
+                v[0],
+                v[1],
+                v[2],
+                v[3].wrapping_add(t1),
+                v[4],
+                v[5],
+                v[6],
+            ];
+        }
+
+        for (s, v) in state.iter_mut().zip(v) {
+            *s = s.wrapping_add(v);
+        }
+    }
+
+    /**
+     * Computes the SHA-256 digest of `message` natively.
+     */
+    pub fn digest(message: &[u8]) -> [u8; 32] {
+        let mut state = IV;
+
+        let padded = [message, &padding(message.len())].concat();
+
+        for block in padded.chunks(64) {
+            let mut words = [0u32; 16];
+
+            for (w, bytes) in words.iter_mut().zip(block.chunks(4)) {
+                *w = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+            }
+
+            Self::compress(&mut state, &words);
+        }
+
+        let mut digest = [0u8; 32];
+
+        for (d, s) in digest.chunks_mut(4).zip(state) {
+            d.copy_from_slice(&s.to_be_bytes());
+        }
+
+        digest
+    }
+}
+
+impl Gadget for Sha256Compression {
+    fn compute_hidden_inputs(&self, _gadget_inputs: &[BigInt]) -> ZkpResult<Vec<BigInt>> {
+        Ok(vec![])
+    }
+
+    fn gen_circuit(
+        &self,
+        gadget_inputs: &[NodeIndex],
+        _hidden_inputs: &[NodeIndex],
+    ) -> Vec<NodeIndex> {
+        let words = gadget_inputs
+            .chunks(32)
+            .map(|w| w.to_vec())
+            .collect::<Vec<_>>();
+        let (state, block) = words.split_at(8);
+
+        let mut w = block.to_vec();
+
+        for t in 16..64 {
+            let s0 = with_zkp_ctx(|ctx| sigma(ctx, &w[t - 15], [7, 18], 3));
+            let s1 = with_zkp_ctx(|ctx| sigma(ctx, &w[t - 2], [17, 19], 10));
+
+            w.push(add_words(&[&s1, &w[t - 7], &s0, &w[t - 16]], 0));
+        }
+
+        let mut v = state.to_vec();
+
+        for t in 0..64 {
+            let (s1, ch, s0, maj) = with_zkp_ctx(|ctx| {
+                (
+                    big_sigma(ctx, &v[4], [6, 11, 25]),
+                    ch(ctx, &v[4], &v[5], &v[6]),
+                    big_sigma(ctx, &v[0], [2, 13, 22]),
+                    maj(ctx, &v[0], &v[1], &v[2]),
+                )
+            });
+
+            // We fold T1 into both sums rather than reducing it on its own.
+            let e = add_words(&[&v[3], &v[7], &s1, &ch, &w[t]], K[t]);
+            let a = add_words(&[&v[7], &s1, &ch, &w[t], &s0, &maj], K[t]);
+
+            v = vec![
+                a,
+                v[0].clone(),
+                v[1].clone(),
+                v[2].clone(),
+                e,
+                v[4].clone(),
+                v[5].clone(),
+                v[6].clone(),
+            ];
+        }
+
+        state
+            .iter()
+            .zip(v.iter())
+            .flat_map(|(s, v)| add_words(&[s, v], 0))
+            .collect()
+    }
+
+    fn gadget_input_count(&self) -> usize {
+        24 * 32
+    }
+
+    fn hidden_input_count(&self) -> usize {
+        0
+    }
+}
+
+/**
+ * Returns the SHA-256 padding for a message of `len` bytes.
+ */
+fn padding(len: usize) -> Vec<u8> {
+    let zeros = (64 + 55 - len % 64) % 64;
+
+    let mut padding = vec![0x80];
+    padding.extend(std::iter::repeat(0).take(zeros));
+    padding.extend(((len as u64) * 8).to_be_bytes());
+
+    padding
+}
+
+fn constant_bits(ctx: &mut ZkpContext, x: u32, n: usize) -> Vec<NodeIndex> {
+    (0..n)
+        .map(|i| ctx.add_constant(&BigInt::from(((x >> i) & 0x1) as u8)))
+        .collect()
+}
+
+fn xor(ctx: &mut ZkpContext, a: NodeIndex, b: NodeIndex) -> NodeIndex {
+    // a + b - 2ab
+    let two = ctx.add_constant(&BigInt::from(2u8));
+    let ab = ctx.add_multiplication(a, b);
+    let two_ab = ctx.add_multiplication(ab, two);
+    let sum = ctx.add_addition(a, b);
+
+    ctx.add_subtraction(sum, two_ab)
+}
+
+fn xor3(ctx: &mut ZkpContext, a: &[NodeIndex], b: &[NodeIndex], c: &[NodeIndex]) -> Vec<NodeIndex> {
+    (0..32)
+        .map(|i| {
+            let a_b = xor(ctx, a[i], b[i]);
+            xor(ctx, a_b, c[i])
+        })
+        .collect()
+}
+
+fn rotr(x: &[NodeIndex], n: usize) -> Vec<NodeIndex> {
+    (0..32).map(|i| x[(i + n) % 32]).collect()
+}
+
+fn shr(ctx: &mut ZkpContext, x: &[NodeIndex], n: usize) -> Vec<NodeIndex> {
+    let zero = ctx.add_constant(&BigInt::ZERO);
+
+    (0..32).map(|i| *x.get(i + n).unwrap_or(&zero)).collect()
+}
+
+fn sigma(ctx: &mut ZkpContext, x: &[NodeIndex], rot: [usize; 2], shift: usize) -> Vec<NodeIndex> {
+    let shifted = shr(ctx, x, shift);
+
+    xor3(ctx, &rotr(x, rot[0]), &rotr(x, rot[1]), &shifted)
+}
+
+fn big_sigma(ctx: &mut ZkpContext, x: &[NodeIndex], rot: [usize; 3]) -> Vec<NodeIndex> {
+    xor3(ctx, &rotr(x, rot[0]), &rotr(x, rot[1]), &rotr(x, rot[2]))
+}
+
+fn ch(ctx: &mut ZkpContext, e: &[NodeIndex], f: &[NodeIndex], g: &[NodeIndex]) -> Vec<NodeIndex> {
+    // g + e(f - g)
+    (0..32)
+        .map(|i| {
+            let f_min_g = ctx.add_subtraction(f[i], g[i]);
+            let t = ctx.add_multiplication(e[i], f_min_g);
+
+            ctx.add_addition(g[i], t)
+        })
+        .collect()
+}
+
+fn maj(ctx: &mut ZkpContext, a: &[NodeIndex], b: &[NodeIndex], c: &[NodeIndex]) -> Vec<NodeIndex> {
+    // ab + c(a xor b). The terms are never both 1, so we can add them.
+    (0..32)
+        .map(|i| {
+            let two = ctx.add_constant(&BigInt::from(2u8));
+            let ab = ctx.add_multiplication(a[i], b[i]);
+            let two_ab = ctx.add_multiplication(ab, two);
+            let sum = ctx.add_addition(a[i], b[i]);
+            let a_xor_b = ctx.add_subtraction(sum, two_ab);
+            let t = ctx.add_multiplication(c[i], a_xor_b);
+
+            ctx.add_addition(ab, t)
+        })
+        .collect()
+}
+
+/**
+ * Returns the field element a word's bits represent.
+ */
+fn word_value(ctx: &mut ZkpContext, x: &[NodeIndex]) -> NodeIndex {
+    let mut value = x[0];
+
+    for (i, bit) in x.iter().enumerate().skip(1) {
+        let c = ctx.add_constant(&BigInt::from(1u64 << i));
+        let term = ctx.add_multiplication(*bit, c);
+
+        value = ctx.add_addition(value, term);
+    }
+
+    value
+}
+
+/**
+ * Adds `words` and `constant` modulo `2^32`.
+ */
+fn add_words(words: &[&Vec<NodeIndex>], constant: u32) -> Vec<NodeIndex> {
+    let sum = with_zkp_ctx(|ctx| {
+        let mut sum = ctx.add_constant(&BigInt::from(constant));
+
+        for w in words {
+            let w = word_value(ctx, w);
+            sum = ctx.add_addition(sum, w);
+        }
+
+        sum
+    });
+
+    // The sum of k 32-bit terms needs ceil(log2(k)) carry bits.
+    let terms = words.len() + 1;
+    let carry_bits = (usize::BITS - (terms - 1).leading_zeros()) as usize;
+
+    let bits = invoke_gadget(ToUInt::new(32 + carry_bits), &[sum]);
+
+    bits[..32].to_vec()
+}
+
+/**
+ * Computes the SHA-256 digest of `message` in a
+ * [`zkp_program`](crate::zkp_program). Returns the 8 digest words as
+ * field elements, most significant first.
+ *
+ * # Remarks
+ * We prove each message element is a byte. The message length is fixed
+ * when compiling the program, so we pad with constants.
+ *
+ * # Panics
+ * * Calling this function outside a [`zkp_program`](crate::zkp_program)
+ */
+pub fn sha256(message: &[NodeIndex]) -> Vec<NodeIndex> {
+    let mut bytes = message.iter().map(|b| decompose(*b, 8)).collect::<Vec<_>>();
+
+    let (padding, mut state) = with_zkp_ctx(|ctx| {
+        let padding = padding(message.len())
+            .iter()
+            .map(|b| constant_bits(ctx, *b as u32, 8))
+            .collect::<Vec<_>>();
+        let state = IV
+            .iter()
+            .flat_map(|w| constant_bits(ctx, *w, 32))
+            .collect::<Vec<_>>();
+
+        (padding, state)
+    });
+
+    bytes.extend(padding);
+
+    for block in bytes.chunks(64) {
+        let mut inputs = state.clone();
+
+        // Words are big endian, while bits are least significant first.
+        for word in block.chunks(4) {
+            inputs.extend((0..32).map(|i| word[3 - i / 8][i % 8]));
+        }
+
+        state = invoke_gadget(Sha256Compression, &inputs);
+    }
+
+    with_zkp_ctx(|ctx| state.chunks(32).map(|w| word_value(ctx, w)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use sunscreen_zkp_backend::bulletproofs::BulletproofsBackend;
+    use sunscreen_zkp_backend::{FieldSpec, ZkpBackend};
+
+    use super::*;
+    use crate::types::zkp::{Field, ZkpHash};
+    use crate::{self as sunscreen};
+    use crate::{zkp_program, Compiler};
+
+    fn words(digest: [u8; 32]) -> Vec<u32> {
+        digest
+            .chunks(4)
+            .map(|w| u32::from_be_bytes([w[0], w[1], w[2], w[3]]))
+            .collect()
+    }
+
+    #[test]
+    fn native_digest_matches_test_vectors() {
+        assert_eq!(
+            words(Sha256Compression::digest(b"")),
+            [
+                0xe3b0c442, 0x98fc1c14, 0x9afbf4c8, 0x996fb924, 0x27ae41e4, 0x649b934c, 0xa495991b,
+                0x7852b855
+            ]
+        );
+
+        assert_eq!(
+            words(Sha256Compression::digest(b"abc")),
+            [
+                0xba7816bf, 0x8f01cfea, 0x414140de, 0x5dae2223, 0xb00361a3, 0x96177a9c, 0xb410ff61,
+                0xf20015ad
+            ]
+        );
+
+        // Padding spills into a second block.
+        assert_eq!(
+            words(Sha256Compression::digest(
+                b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"
+            )),
+            [
+                0x248d6a61, 0xd20638b8, 0xe5c02693, 0x0c3e6039, 0xa33ce459, 0x64ff2167, 0xf6ecedd4,
+                0x19db06c1
+            ]
+        );
+    }
+
+    #[test]
+    fn can_hash_with_sha256() {
+        #[zkp_program]
+        fn hash<F: FieldSpec>(message: [Field<F>; 3], #[public] digest: [Field<F>; 8]) {
+            for (x, y) in message.sha256().iter().zip(digest) {
+                x.constrain_eq(y);
+            }
+        }
+
+        let app = Compiler::new()
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(hash)
+            .compile()
+            .unwrap();
+
+        let prog = app.get_zkp_program(hash).unwrap();
+
+        let backend = BulletproofsBackend::new();
+
+        let message = b"abc".map(BigInt::from);
+        let digest = words(Sha256Compression::digest(b"abc"))
+            .into_iter()
+            .map(|w| BigInt::from(w as u64))
+            .collect::<Vec<_>>();
+
+        // A full proof takes a while in debug builds, so we just check the
+        // JIT finds the constraints satisfied.
+        backend
+            .jit_prover(&prog.zkp_program_fn, &message, &digest, &[])
+            .unwrap();
+
+        let wrong = b"abd".map(BigInt::from);
+
+        assert!(backend
+            .jit_prover(&prog.zkp_program_fn, &wrong, &digest, &[])
+            .is_err());
+
+        // Non-bytes fail too.
+        let not_bytes = [
+            BigInt::from(0x61u16 + 256),
+            BigInt::from(0x62u8),
+            BigInt::from(0x63u8),
+        ];
+
+        assert!(backend
+            .jit_prover(&prog.zkp_program_fn, &not_bytes, &digest, &[])
+            .is_err());
+
+        let metrics = backend.gadget_metrics(&prog.zkp_program_fn).unwrap();
+
+        assert_eq!(
+            metrics.gadgets[std::any::type_name::<Sha256Compression>()].invocations,
+            1
+        );
+    }
+}
@@ -0,0 +1,432 @@
+use petgraph::stable_graph::NodeIndex;
+use sunscreen_compiler_macros::TypeName;
+use sunscreen_zkp_backend::{BigInt, FieldSpec};
+
+use crate::{
+    invoke_gadget,
+    types::zkp::{
+        gadgets::{decompose, decompose_signed, SignedModulus},
+        AddVar, ConstrainCmpVarVar, ConstrainEqVarVar, DivVar, Field, IntoProgramNode, MulVar,
+        NegVar, NumFieldElements, ProgramNode, RemVar, SubVar, ToNativeFields,
+    },
+    zkp::{with_zkp_ctx, Operation, ZkpContext, ZkpContextOps},
+};
+
+use crate as sunscreen;
+
+/**
+ * Proves `x` is a 64-bit integer, unless it's a constant we created
+ * from a native value (which is in range by construction).
+ */
+pub(super) fn range_check<F: FieldSpec>(x: NodeIndex, signed: bool) {
+    let is_constant = with_zkp_ctx(|ctx| matches!(ctx.graph[x].operation, Operation::Constant(_)));
+
+    if is_constant {
+        return;
+    }
+
+    if signed {
+        decompose_signed(x, 64, &F::FIELD_MODULUS);
+    } else {
+        decompose(x, 64);
+    }
+}
+
+/**
+ * Range checks the operands, applies `op`, and range checks the result.
+ * As operands are 64-bit, `op` must not wrap around the field for any
+ * of them, which holds for addition, subtraction, and multiplication in
+ * fields larger than 2^129.
+ */
+pub(super) fn checked_binary<F, T>(
+    lhs: ProgramNode<T>,
+    rhs: ProgramNode<T>,
+    signed: bool,
+    op: impl FnOnce(&mut ZkpContext, NodeIndex, NodeIndex) -> NodeIndex,
+) -> ProgramNode<T>
+where
+    F: FieldSpec,
+{
+    range_check::<F>(lhs.ids[0], signed);
+    range_check::<F>(rhs.ids[0], signed);
+
+    let o = with_zkp_ctx(|ctx| op(ctx, lhs.ids[0], rhs.ids[0]));
+
+    range_check::<F>(o, signed);
+
+    ProgramNode::new(&[o])
+}
+
+/**
+ * Encodes `x` in the field, mapping negative values to `p - |x|`.
+ */
+pub(super) fn encode_signed<F: FieldSpec>(x: i64) -> Field<F> {
+    let abs = Field::<F>::from(x.unsigned_abs());
+
+    if x < 0 {
+        Field::from(BigInt::from(F::FIELD_MODULUS.wrapping_sub(&abs.val)))
+    } else {
+        abs
+    }
+}
+
+/**
+ * Methods for turning a value into a [`Field`] element inside a
+ * [`zkp_program`](crate::zkp_program). This is free.
+ */
+pub trait IntoField<F: FieldSpec> {
+    /**
+     * Returns this value as a field element. Signed values map negative
+     * numbers to `p - |x|`.
+     */
+    fn into_field(self) -> ProgramNode<Field<F>>;
+}
+
+/**
+ * Methods for turning a [`Field`] element into another type inside a
+ * [`zkp_program`](crate::zkp_program).
+ */
+pub trait FromField<F: FieldSpec>
+where
+    Self: Sized,
+{
+    /**
+     * Returns `x` as this type. The proof fails if `x` is out of this
+     * type's range.
+     */
+    fn from_field(x: ProgramNode<Field<F>>) -> ProgramNode<Self>;
+}
+
+macro_rules! impl_integer_common {
+    ($ty:ident, $signed:literal) => {
+        impl<F: FieldSpec> Copy for $ty<F> {}
+
+        impl<F: FieldSpec> NumFieldElements for $ty<F> {
+            const NUM_NATIVE_FIELD_ELEMENTS: usize = 1;
+        }
+
+        impl<F: FieldSpec> ToNativeFields for $ty<F> {
+            fn to_native_fields(&self) -> Vec<BigInt> {
+                self.val.to_native_fields()
+            }
+        }
+
+        impl<F: FieldSpec> From<$ty<F>> for Field<F> {
+            fn from(x: $ty<F>) -> Self {
+                x.val
+            }
+        }
+
+        impl<F: FieldSpec> IntoProgramNode for $ty<F> {
+            type Output = $ty<F>;
+
+            fn into_program_node(self) -> ProgramNode<Self> {
+                with_zkp_ctx(|ctx| ProgramNode::new(&[ctx.add_constant(&self.val.val)]))
+            }
+        }
+
+        impl<F: FieldSpec> IntoField<F> for ProgramNode<$ty<F>> {
+            fn into_field(self) -> ProgramNode<Field<F>> {
+                ProgramNode::new(self.ids)
+            }
+        }
+
+        impl<F: FieldSpec> FromField<F> for $ty<F> {
+            fn from_field(x: ProgramNode<Field<F>>) -> ProgramNode<Self> {
+                range_check::<F>(x.ids[0], $signed);
+
+                ProgramNode::new(x.ids)
+            }
+        }
+
+        impl<F: FieldSpec> AddVar for $ty<F> {
+            fn add(lhs: ProgramNode<Self>, rhs: ProgramNode<Self>) -> ProgramNode<Self> {
+                checked_binary::<F, _>(lhs, rhs, $signed, |ctx, a, b| ctx.add_addition(a, b))
+            }
+        }
+
+        impl<F: FieldSpec> SubVar for $ty<F> {
+            fn sub(lhs: ProgramNode<Self>, rhs: ProgramNode<Self>) -> ProgramNode<Self> {
+                checked_binary::<F, _>(lhs, rhs, $signed, |ctx, a, b| ctx.add_subtraction(a, b))
+            }
+        }
+
+        impl<F: FieldSpec> MulVar for $ty<F> {
+            fn mul(lhs: ProgramNode<Self>, rhs: ProgramNode<Self>) -> ProgramNode<Self> {
+                checked_binary::<F, _>(lhs, rhs, $signed, |ctx, a, b| ctx.add_multiplication(a, b))
+            }
+        }
+
+        impl<F: FieldSpec> ConstrainEqVarVar for $ty<F> {
+            fn constrain_eq(lhs: ProgramNode<Self>, rhs: ProgramNode<Self>) -> ProgramNode<Self> {
+                let c = Field::<F>::constrain_eq(lhs.into_field(), rhs.into_field());
+
+                ProgramNode::new(c.ids)
+            }
+        }
+
+        impl<F: FieldSpec> ConstrainCmpVarVar for $ty<F> {
+            fn constrain_le_bounded(lhs: ProgramNode<Self>, rhs: ProgramNode<Self>, bits: usize) {
+                range_check::<F>(lhs.ids[0], $signed);
+                range_check::<F>(rhs.ids[0], $signed);
+
+                Field::<F>::constrain_le_bounded(lhs.into_field(), rhs.into_field(), bits);
+            }
+
+            fn constrain_lt_bounded(lhs: ProgramNode<Self>, rhs: ProgramNode<Self>, bits: usize) {
+                range_check::<F>(lhs.ids[0], $signed);
+                range_check::<F>(rhs.ids[0], $signed);
+
+                Field::<F>::constrain_lt_bounded(lhs.into_field(), rhs.into_field(), bits);
+            }
+
+            fn constrain_ge_bounded(lhs: ProgramNode<Self>, rhs: ProgramNode<Self>, bits: usize) {
+                range_check::<F>(lhs.ids[0], $signed);
+                range_check::<F>(rhs.ids[0], $signed);
+
+                Field::<F>::constrain_ge_bounded(lhs.into_field(), rhs.into_field(), bits);
+            }
+
+            fn constrain_gt_bounded(lhs: ProgramNode<Self>, rhs: ProgramNode<Self>, bits: usize) {
+                range_check::<F>(lhs.ids[0], $signed);
+                range_check::<F>(rhs.ids[0], $signed);
+
+                Field::<F>::constrain_gt_bounded(lhs.into_field(), rhs.into_field(), bits);
+            }
+        }
+    };
+}
+
+#[derive(Debug, Clone, TypeName)]
+/**
+ * An unsigned 64-bit integer in a [`zkp_program`](crate::zkp_program).
+ *
+ * # Remarks
+ * Arithmetic proves the result doesn't overflow or underflow, so e.g.
+ * `a - b` fails to prove when `a < b`. Each operation decomposes its
+ * operands and result into 64 bits, though we share decompositions of the
+ * same value, so chained operations pay roughly 64 multiplications each.
+ *
+ * Division and remainder truncate as with Rust's `u64`. Dividing by zero
+ * fails to prove.
+ */
+pub struct ZkpU64<F: FieldSpec> {
+    val: Field<F>,
+}
+
+impl<F: FieldSpec> From<u64> for ZkpU64<F> {
+    fn from(x: u64) -> Self {
+        Self {
+            val: Field::from(x),
+        }
+    }
+}
+
+impl_integer_common!(ZkpU64, false);
+
+impl<F: FieldSpec> ZkpU64<F> {
+    fn div_rem(lhs: ProgramNode<Self>, rhs: ProgramNode<Self>) -> (NodeIndex, NodeIndex) {
+        range_check::<F>(lhs.ids[0], false);
+        range_check::<F>(rhs.ids[0], false);
+
+        let outputs = invoke_gadget(
+            SignedModulus::new(F::FIELD_MODULUS, 64),
+            &[lhs.ids[0], rhs.ids[0]],
+        );
+
+        // The remainder is less than rhs, but the gadget doesn't bound the
+        // quotient.
+        range_check::<F>(outputs[0], false);
+
+        (outputs[0], outputs[1])
+    }
+}
+
+impl<F: FieldSpec> DivVar for ZkpU64<F> {
+    fn div(lhs: ProgramNode<Self>, rhs: ProgramNode<Self>) -> ProgramNode<Self> {
+        ProgramNode::new(&[Self::div_rem(lhs, rhs).0])
+    }
+}
+
+impl<F: FieldSpec> RemVar for ZkpU64<F> {
+    fn rem(lhs: ProgramNode<Self>, rhs: ProgramNode<Self>) -> ProgramNode<Self> {
+        ProgramNode::new(&[Self::div_rem(lhs, rhs).1])
+    }
+}
+
+#[derive(Debug, Clone, TypeName)]
+/**
+ * A signed 64-bit integer in a [`zkp_program`](crate::zkp_program).
+ *
+ * # Remarks
+ * Arithmetic proves the result lies in `i64::MIN..=i64::MAX`. As with
+ * [`ZkpU64`], each operation costs roughly 64 multiplications.
+ */
+pub struct ZkpI64<F: FieldSpec> {
+    val: Field<F>,
+}
+
+impl<F: FieldSpec> From<i64> for ZkpI64<F> {
+    fn from(x: i64) -> Self {
+        Self {
+            val: encode_signed(x),
+        }
+    }
+}
+
+impl_integer_common!(ZkpI64, true);
+
+impl<F: FieldSpec> NegVar for ZkpI64<F> {
+    fn neg(lhs: ProgramNode<Self>) -> ProgramNode<Self> {
+        range_check::<F>(lhs.ids[0], true);
+
+        let o = with_zkp_ctx(|ctx| ctx.add_negate(lhs.ids[0]));
+
+        // Catches -i64::MIN.
+        range_check::<F>(o, true);
+
+        ProgramNode::new(&[o])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sunscreen_runtime::Runtime;
+    use sunscreen_zkp_backend::bulletproofs::BulletproofsBackend;
+    use sunscreen_zkp_backend::ZkpBackend;
+
+    use super::*;
+    use crate::types::zkp::ConstrainCmp;
+    use crate::{zkp_program, Compiler};
+
+    type BPFieldSpec = <BulletproofsBackend as ZkpBackend>::Field;
+
+    #[test]
+    fn unsigned_arithmetic_rejects_wraparound() {
+        #[zkp_program]
+        fn balance<F: FieldSpec>(balance: ZkpU64<F>, amount: ZkpU64<F>, #[public] fee: ZkpU64<F>) {
+            let remaining = balance - amount - fee;
+
+            remaining.constrain_ge_bounded(ZkpU64::from(10), 64);
+        }
+
+        let app = Compiler::new()
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(balance)
+            .compile()
+            .unwrap();
+
+        let runtime = Runtime::new_zkp(BulletproofsBackend::new()).unwrap();
+
+        let prog = app.get_zkp_program(balance).unwrap();
+
+        let prove = |balance: u64, amount: u64, fee: u64| {
+            runtime.prove(
+                prog,
+                vec![ZkpU64::<BPFieldSpec>::from(balance), ZkpU64::from(amount)],
+                vec![ZkpU64::from(fee)],
+                vec![],
+            )
+        };
+
+        let proof = prove(100, 50, 5).unwrap();
+
+        runtime
+            .verify(prog, &proof, vec![ZkpU64::<BPFieldSpec>::from(5)], vec![])
+            .unwrap();
+
+        // Underflow
+        assert!(prove(100, 101, 5).is_err());
+
+        // Out of range input
+        let too_big = ZkpU64::<BPFieldSpec> {
+            val: Field::from(u128::from(u64::MAX) + 200),
+        };
+
+        assert!(runtime
+            .prove(
+                prog,
+                vec![too_big, ZkpU64::from(250)],
+                vec![ZkpU64::from(5)],
+                vec![],
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn unsigned_division_truncates() {
+        #[zkp_program]
+        fn div<F: FieldSpec>(
+            a: ZkpU64<F>,
+            b: ZkpU64<F>,
+            #[public] q: ZkpU64<F>,
+            #[public] r: ZkpU64<F>,
+        ) {
+            (a / b).constrain_eq(q);
+            (a % b).constrain_eq(r);
+        }
+
+        let app = Compiler::new()
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(div)
+            .compile()
+            .unwrap();
+
+        let runtime = Runtime::new_zkp(BulletproofsBackend::new()).unwrap();
+
+        let prog = app.get_zkp_program(div).unwrap();
+
+        let prove = |a: u64, b: u64, q: u64, r: u64| {
+            runtime.prove(
+                prog,
+                vec![ZkpU64::<BPFieldSpec>::from(a), ZkpU64::from(b)],
+                vec![ZkpU64::from(q), ZkpU64::from(r)],
+                vec![],
+            )
+        };
+
+        prove(u64::MAX, 7, u64::MAX / 7, u64::MAX % 7).unwrap();
+        prove(3, 7, 0, 3).unwrap();
+
+        assert!(prove(10, 3, 3, 2).is_err());
+        assert!(prove(10, 0, 0, 10).is_err());
+    }
+
+    #[test]
+    fn signed_arithmetic_rejects_overflow() {
+        #[zkp_program]
+        fn mul<F: FieldSpec>(a: ZkpI64<F>, b: ZkpI64<F>, #[public] c: ZkpI64<F>) {
+            (-(a * b)).constrain_eq(c);
+        }
+
+        let app = Compiler::new()
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(mul)
+            .compile()
+            .unwrap();
+
+        let runtime = Runtime::new_zkp(BulletproofsBackend::new()).unwrap();
+
+        let prog = app.get_zkp_program(mul).unwrap();
+
+        let prove = |a: i64, b: i64, c: i64| {
+            runtime.prove(
+                prog,
+                vec![ZkpI64::<BPFieldSpec>::from(a), ZkpI64::from(b)],
+                vec![ZkpI64::from(c)],
+                vec![],
+            )
+        };
+
+        let proof = prove(-3, 7, 21).unwrap();
+
+        runtime
+            .verify(prog, &proof, vec![ZkpI64::<BPFieldSpec>::from(21)], vec![])
+            .unwrap();
+
+        prove(i64::MAX, -1, i64::MAX).unwrap();
+
+        assert!(prove(i64::MAX, 2, 2).is_err());
+        assert!(prove(i64::MIN, 1, 0).is_err());
+    }
+}
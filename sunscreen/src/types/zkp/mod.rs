@@ -1,13 +1,24 @@
 #[cfg(feature = "linkedproofs")]
 mod bfv_plaintext;
+mod boolean;
 mod field;
-mod gadgets;
+mod fixed;
+/**
+ * Gadgets for building circuits in a
+ * [`zkp_program`](crate::zkp_program), including range checks, set
+ * membership, and table lookups that share work across constraints.
+ */
+pub mod gadgets;
+mod integer;
 mod program_node;
 mod rns_polynomial;
 
 #[cfg(feature = "linkedproofs")]
 pub use bfv_plaintext::*;
+pub use boolean::*;
 pub use field::*;
+pub use fixed::*;
+pub use integer::*;
 pub use petgraph::stable_graph::NodeIndex;
 pub use program_node::*;
 pub use rns_polynomial::*;
@@ -8,7 +8,7 @@ use std::{
 
 use crate::{
     types::zkp::{AddVar, DivVar, IntoProgramNode, MulVar, NegVar, RemVar, SubVar, ZkpType},
-    zkp::{with_zkp_ctx, ZkpContextOps},
+    zkp::{with_source_location, with_zkp_ctx, ZkpContextOps},
     INDEX_ARENA,
 };
 
@@ -168,6 +168,10 @@ where
         let len = T::num_native_field_elements(plaintext_modulus);
         let mut ids = Vec::with_capacity(len);
 
+        // Decoding linked plaintexts needs the exact modulus, which we can't recover from
+        // the number of field elements.
+        with_zkp_ctx(|ctx| ctx.data.plain_modulus = Some(plaintext_modulus));
+
         for _ in 0..len {
             ids.push(with_zkp_ctx(|ctx| ctx.add_private_input()));
         }
@@ -182,8 +186,9 @@ where
 {
     type Output = Self;
 
+    #[track_caller]
     fn add(self, rhs: Self) -> Self::Output {
-        <T as AddVar>::add(self, rhs)
+        with_source_location(|| <T as AddVar>::add(self, rhs))
     }
 }
 
@@ -193,8 +198,9 @@ where
 {
     type Output = Self;
 
+    #[track_caller]
     fn mul(self, rhs: Self) -> Self::Output {
-        <T as MulVar>::mul(self, rhs)
+        with_source_location(|| <T as MulVar>::mul(self, rhs))
     }
 }
 
@@ -204,8 +210,9 @@ where
 {
     type Output = Self;
 
+    #[track_caller]
     fn div(self, rhs: Self) -> Self::Output {
-        <T as DivVar>::div(self, rhs)
+        with_source_location(|| <T as DivVar>::div(self, rhs))
     }
 }
 
@@ -215,8 +222,9 @@ where
 {
     type Output = Self;
 
+    #[track_caller]
     fn rem(self, rhs: Self) -> Self::Output {
-        <T as RemVar>::rem(self, rhs)
+        with_source_location(|| <T as RemVar>::rem(self, rhs))
     }
 }
 
@@ -226,8 +234,9 @@ where
 {
     type Output = Self;
 
+    #[track_caller]
     fn sub(self, rhs: Self) -> Self::Output {
-        <T as SubVar>::sub(self, rhs)
+        with_source_location(|| <T as SubVar>::sub(self, rhs))
     }
 }
 
@@ -237,8 +246,9 @@ where
 {
     type Output = Self;
 
+    #[track_caller]
     fn neg(self) -> Self::Output {
-        <T as NegVar>::neg(self)
+        with_source_location(|| <T as NegVar>::neg(self))
     }
 }
 
@@ -279,8 +289,9 @@ where
     /**
      * Constrains this native field to equal the right hand side
      */
+    #[track_caller]
     fn constrain_eq(self, rhs: T) -> ProgramNode<Self::Output> {
-        V::constrain_eq(self.into_program_node(), rhs.into_program_node())
+        with_source_location(|| V::constrain_eq(self.into_program_node(), rhs.into_program_node()))
     }
 }
 
@@ -351,19 +362,31 @@ where
     U: IntoProgramNode<Output = V> + Sized,
     V: ZkpType + Sized + ConstrainCmpVarVar,
 {
+    #[track_caller]
     fn constrain_le_bounded(self, rhs: T, bits: usize) {
-        V::constrain_le_bounded(self.into_program_node(), rhs.into_program_node(), bits);
+        with_source_location(|| {
+            V::constrain_le_bounded(self.into_program_node(), rhs.into_program_node(), bits);
+        });
     }
 
+    #[track_caller]
     fn constrain_lt_bounded(self, rhs: T, bits: usize) {
-        V::constrain_lt_bounded(self.into_program_node(), rhs.into_program_node(), bits);
+        with_source_location(|| {
+            V::constrain_lt_bounded(self.into_program_node(), rhs.into_program_node(), bits);
+        });
     }
 
+    #[track_caller]
     fn constrain_ge_bounded(self, rhs: T, bits: usize) {
-        V::constrain_ge_bounded(self.into_program_node(), rhs.into_program_node(), bits);
+        with_source_location(|| {
+            V::constrain_ge_bounded(self.into_program_node(), rhs.into_program_node(), bits);
+        });
     }
 
+    #[track_caller]
     fn constrain_gt_bounded(self, rhs: T, bits: usize) {
-        V::constrain_gt_bounded(self.into_program_node(), rhs.into_program_node(), bits);
+        with_source_location(|| {
+            V::constrain_gt_bounded(self.into_program_node(), rhs.into_program_node(), bits);
+        });
     }
 }
@@ -1,17 +1,23 @@
 use petgraph::Graph;
 use sunscreen_runtime::{CallSignature, ZkpRuntime};
 use sunscreen_zkp_backend::{
-    BigInt, CompiledZkpProgram, FieldSpec, Gadget, Operation as JitOperation, ZkpBackend,
+    BigInt, CompiledZkpProgram, FieldSpec, Gadget, Operation as JitOperation, SourceLocation,
+    ZkpBackend,
 };
 
 use crate::{Compiler, Result};
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::hash::Hash;
+use std::ops::{Deref, DerefMut};
 use std::sync::Arc;
 use std::vec;
 use std::{any::Any, cell::RefCell};
 
+mod optimize;
+
+pub(crate) use optimize::optimize;
+
 mod sealed {
     pub trait Sealed {}
     impl Sealed for super::Linked {}
@@ -371,6 +377,21 @@ pub struct ZkpData {
     // A lookup table to reuse constant nodes. Reduces the size
     // of the graph.
     constant_map: HashMap<BigInt, NodeIndex>,
+    // Binary decompositions by value, so range checks on the same
+    // value share bits.
+    pub(crate) decompositions: HashMap<NodeIndex, Vec<NodeIndex>>,
+    // `left - right` nodes created by range checks.
+    pub(crate) differences: HashMap<(NodeIndex, NodeIndex), NodeIndex>,
+    // One-hot encodings by (index, table length), so lookups with the
+    // same index share selectors.
+    pub(crate) one_hots: HashMap<(NodeIndex, usize), Vec<NodeIndex>>,
+    // Nodes proven to be 0 or 1, so we assert each boolean once.
+    pub(crate) booleans: HashSet<NodeIndex>,
+    // The FHE plaintext modulus, set when the program has linked inputs.
+    pub(crate) plain_modulus: Option<u64>,
+    // The user's expression that's currently adding nodes.
+    current_location: Option<SourceLocation>,
+    source_locations: HashMap<NodeIndex, SourceLocation>,
 }
 
 impl ZkpData {
@@ -383,8 +404,29 @@ impl ZkpData {
             next_public_input: 0,
             next_constant_input: 0,
             constant_map: HashMap::new(),
+            decompositions: HashMap::new(),
+            differences: HashMap::new(),
+            one_hots: HashMap::new(),
+            booleans: HashSet::new(),
+            plain_modulus: None,
+            current_location: None,
+            source_locations: HashMap::new(),
         }
     }
+
+    /**
+     * Records that the current expression created `node`. Shared nodes
+     * (e.g. constants) keep the first expression that created them.
+     */
+    fn record_location(&mut self, node: NodeIndex) -> NodeIndex {
+        if let Some(location) = &self.current_location {
+            self.source_locations
+                .entry(node)
+                .or_insert_with(|| location.clone());
+        }
+
+        node
+    }
 }
 
 impl Default for ZkpData {
@@ -402,13 +444,67 @@ impl Default for ZkpData {
  * For internal use only.
  */
 pub type ZkpContext = Context<Operation, ZkpData>;
+#[derive(Clone)]
 /**
  * Contains the results of compiling a [`#[zkp_program]`](crate::zkp_program) function.
  *
  * # Remarks
  * For internal use only.
  */
-pub type ZkpFrontendCompilation = CompilationResult<Operation>;
+pub struct ZkpFrontendCompilation {
+    /**
+     * The program's graph.
+     */
+    pub graph: CompilationResult<Operation>,
+
+    /**
+     * Where in the [`#[zkp_program]`](crate::zkp_program) we created
+     * each node. Only nodes created by an operator or constraint have a
+     * location.
+     */
+    pub source_locations: HashMap<NodeIndex, SourceLocation>,
+}
+
+impl ZkpFrontendCompilation {
+    /**
+     * Creates an empty [`ZkpFrontendCompilation`].
+     */
+    pub fn new() -> Self {
+        Self {
+            graph: CompilationResult::new(),
+            source_locations: HashMap::new(),
+        }
+    }
+}
+
+impl Default for ZkpFrontendCompilation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<ZkpContext> for ZkpFrontendCompilation {
+    fn from(ctx: ZkpContext) -> Self {
+        Self {
+            graph: ctx.graph,
+            source_locations: ctx.data.source_locations,
+        }
+    }
+}
+
+impl Deref for ZkpFrontendCompilation {
+    type Target = CompilationResult<Operation>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.graph
+    }
+}
+
+impl DerefMut for ZkpFrontendCompilation {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.graph
+    }
+}
 
 /**
  * Defines transformations to ZKP program graphs.
@@ -493,23 +589,33 @@ impl ZkpContextOps for ZkpContext {
     }
 
     fn add_hidden_input(&mut self, gadget_arg_id: usize) -> NodeIndex {
-        self.add_node(Operation::HiddenInput(gadget_arg_id))
+        let node = self.add_node(Operation::HiddenInput(gadget_arg_id));
+
+        self.data.record_location(node)
     }
 
     fn add_addition(&mut self, left: NodeIndex, right: NodeIndex) -> NodeIndex {
-        self.add_binary_operation(Operation::Add, left, right)
+        let node = self.add_binary_operation(Operation::Add, left, right);
+
+        self.data.record_location(node)
     }
 
     fn add_multiplication(&mut self, left: NodeIndex, right: NodeIndex) -> NodeIndex {
-        self.add_binary_operation(Operation::Mul, left, right)
+        let node = self.add_binary_operation(Operation::Mul, left, right);
+
+        self.data.record_location(node)
     }
 
     fn add_negate(&mut self, left: NodeIndex) -> NodeIndex {
-        self.add_unary_operation(Operation::Neg, left)
+        let node = self.add_unary_operation(Operation::Neg, left);
+
+        self.data.record_location(node)
     }
 
     fn add_subtraction(&mut self, left: NodeIndex, right: NodeIndex) -> NodeIndex {
-        self.add_binary_operation(Operation::Sub, left, right)
+        let node = self.add_binary_operation(Operation::Sub, left, right);
+
+        self.data.record_location(node)
     }
 
     fn add_constraint(&mut self, left: NodeIndex, val: &BigInt) -> NodeIndex {
@@ -517,7 +623,7 @@ impl ZkpContextOps for ZkpContext {
 
         self.add_edge(left, constraint, EdgeInfo::Unordered);
 
-        constraint
+        self.data.record_location(constraint)
     }
 
     fn add_constant(&mut self, val: &BigInt) -> NodeIndex {
@@ -528,13 +634,15 @@ impl ZkpContextOps for ZkpContext {
             None => {
                 let idx = self.add_node(Operation::Constant(*val));
                 self.data.constant_map.insert(*val, idx);
-                idx
+                self.data.record_location(idx)
             }
         }
     }
 
     fn add_invoke_gadget<G: Gadget>(&mut self, gadget: &Arc<G>) -> NodeIndex {
-        self.add_node(Operation::InvokeGadget(gadget.clone()))
+        let node = self.add_node(Operation::InvokeGadget(gadget.clone()));
+
+        self.data.record_location(node)
     }
 }
 
@@ -570,11 +678,67 @@ where
     })
 }
 
+/**
+ * Runs `f`, attributing the nodes it creates to the caller's location in
+ * the [`zkp_program`](crate::zkp_program). When nested, the outermost
+ * caller wins, so nodes created inside gadgets and helper types point at
+ * the user's expression.
+ *
+ * # Remarks
+ * Operators and constraint methods call this from `#[track_caller]`
+ * functions so a runtime's `debug_prove` can report where a failing
+ * constraint came from.
+ *
+ * # Panics
+ * Calling this function outside of a ZKP program will panic.
+ */
+#[track_caller]
+pub fn with_source_location<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    let location = SourceLocation::from(std::panic::Location::caller());
+
+    let is_outermost = with_zkp_ctx(|ctx| {
+        let is_outermost = ctx.data.current_location.is_none();
+
+        if is_outermost {
+            ctx.data.current_location = Some(location);
+        }
+
+        is_outermost
+    });
+
+    let result = f();
+
+    if is_outermost {
+        with_zkp_ctx(|ctx| ctx.data.current_location = None);
+    }
+
+    result
+}
+
 /**
  * Takes the parsed frontend program and turns into a format ready to be
- * run.
+ * run. Also returns the source locations keyed by the compiled program's
+ * node indices.
  */
-pub(crate) fn compile(program: &ZkpFrontendCompilation) -> CompiledZkpProgram {
+pub(crate) fn compile(
+    program: &ZkpFrontendCompilation,
+) -> (CompiledZkpProgram, HashMap<NodeIndex, SourceLocation>) {
+    // Compacting the graph below preserves the order of the remaining
+    // nodes, so a node's new index is its position in this order.
+    let source_locations = program
+        .node_indices()
+        .enumerate()
+        .filter_map(|(i, id)| {
+            program
+                .source_locations
+                .get(&id)
+                .map(|l| (NodeIndex::new(i), l.clone()))
+        })
+        .collect();
+
     let jit = program.0.map(
         |_, n| {
             let operation = match n.operation {
@@ -599,7 +763,7 @@ pub(crate) fn compile(program: &ZkpFrontendCompilation) -> CompiledZkpProgram {
     // Convert in and out of Graph to compact all the node indices.
     let jit = Graph::from(jit).into();
 
-    CompilationResult(jit)
+    (CompilationResult(jit), source_locations)
 }
 
 /**
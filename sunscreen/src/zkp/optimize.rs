@@ -0,0 +1,258 @@
+use std::convert::Infallible;
+
+use petgraph::stable_graph::NodeIndex;
+use sunscreen_compiler_common::{
+    forward_traverse_mut,
+    transforms::{common_subexpression_elimination, GraphTransforms, Transform},
+    EdgeInfo, GraphQuery, NodeInfo,
+};
+use sunscreen_zkp_backend::{BigInt, FieldSpec, ZkpInto};
+
+use super::{Operation, ZkpFrontendCompilation};
+use crate::optimize::identity_operand;
+use crate::OptimizationLevel;
+
+/**
+ * Runs the optimization passes enabled by `level` on the given
+ * frontend ZKP program.
+ *
+ * # Remarks
+ * Inputs, constraints and gadget invocations are never removed, so the
+ * optimized program accepts the same arguments and proves the same
+ * statement.
+ */
+pub(crate) fn optimize<F: FieldSpec>(
+    mut ir: ZkpFrontendCompilation,
+    level: OptimizationLevel,
+) -> ZkpFrontendCompilation {
+    if level >= OptimizationLevel::Full {
+        fold_constants::<F>(&mut ir);
+    }
+
+    if level >= OptimizationLevel::Basic {
+        common_subexpression_elimination(&mut ir.0);
+    }
+
+    ir
+}
+
+/**
+ * Returns the value of the given node in `F`'s field if it's a constant.
+ */
+fn constant_value<F: FieldSpec>(
+    query: &GraphQuery<NodeInfo<Operation>, EdgeInfo>,
+    id: NodeIndex,
+) -> Option<F::BackendField> {
+    match &query.get_node(id)?.operation {
+        Operation::Constant(x) => F::BackendField::try_from(*x).ok(),
+        _ => None,
+    }
+}
+
+/**
+ * Whether the given node is a constant equal to `x` in `F`'s field.
+ */
+fn is_constant<F: FieldSpec>(
+    query: &GraphQuery<NodeInfo<Operation>, EdgeInfo>,
+    id: NodeIndex,
+    x: BigInt,
+) -> bool {
+    constant_value::<F>(query, id).map(|v| v.zkp_into()) == Some(x)
+}
+
+/**
+ * Evaluates arithmetic on constants at compile time and removes
+ * operations that don't change their operand:
+ * * `x + 0`, `0 + x` and `x - 0`.
+ * * `x * 1` and `1 * x`.
+ * * `-(-x)`.
+ */
+fn fold_constants<F: FieldSpec>(ir: &mut ZkpFrontendCompilation) {
+    forward_traverse_mut(&mut ir.0, |query, id| {
+        let mut transforms = GraphTransforms::new();
+
+        let folded = match query.get_node(id).unwrap().operation {
+            Operation::Add | Operation::Sub | Operation::Mul => {
+                let (left, right) = query.get_binary_operands(id).unwrap();
+
+                constant_value::<F>(&query, left)
+                    .zip(constant_value::<F>(&query, right))
+                    .map(|(a, b)| match query.get_node(id).unwrap().operation {
+                        Operation::Add => a + b,
+                        Operation::Sub => a - b,
+                        _ => a * b,
+                    })
+            }
+            Operation::Neg => {
+                constant_value::<F>(&query, query.get_unary_operand(id).unwrap()).map(|a| -a)
+            }
+            _ => None,
+        };
+
+        if let Some(x) = folded {
+            let constant = transforms.push(Transform::AddNode(NodeInfo {
+                operation: Operation::Constant(x.zkp_into()),
+            }));
+
+            transforms.replace_node(&query, id, constant.into());
+
+            return Ok::<_, Infallible>(transforms);
+        }
+
+        let replacement = match query.get_node(id).unwrap().operation {
+            Operation::Neg => {
+                let x = query.get_unary_operand(id).unwrap();
+
+                match query.get_node(x).unwrap().operation {
+                    Operation::Neg => Some(query.get_unary_operand(x).unwrap()),
+                    _ => None,
+                }
+            }
+            Operation::Add => {
+                let (left, right) = query.get_binary_operands(id).unwrap();
+
+                identity_operand(
+                    left,
+                    right,
+                    |x| is_constant::<F>(&query, x, BigInt::ZERO),
+                    true,
+                )
+            }
+            Operation::Sub => {
+                let (left, right) = query.get_binary_operands(id).unwrap();
+
+                identity_operand(
+                    left,
+                    right,
+                    |x| is_constant::<F>(&query, x, BigInt::ZERO),
+                    false,
+                )
+            }
+            Operation::Mul => {
+                let (left, right) = query.get_binary_operands(id).unwrap();
+
+                identity_operand(
+                    left,
+                    right,
+                    |x| is_constant::<F>(&query, x, BigInt::ONE),
+                    true,
+                )
+            }
+            _ => None,
+        };
+
+        if let Some(x) = replacement {
+            transforms.replace_node(&query, id, x.into());
+        }
+
+        Ok::<_, Infallible>(transforms)
+    })
+    .unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sunscreen_zkp_backend::bulletproofs::BulletproofsBackend;
+    use sunscreen_zkp_backend::ZkpBackend;
+
+    type Field = <BulletproofsBackend as ZkpBackend>::Field;
+
+    fn node(operation: Operation) -> NodeInfo<Operation> {
+        NodeInfo { operation }
+    }
+
+    fn operations(ir: &ZkpFrontendCompilation) -> Vec<Operation> {
+        ir.node_weights().map(|n| n.operation.clone()).collect()
+    }
+
+    #[test]
+    fn folds_constant_arithmetic() {
+        let mut ir = ZkpFrontendCompilation::new();
+
+        let a = ir.add_node(node(Operation::PrivateInput(0)));
+        let two = ir.add_node(node(Operation::Constant(BigInt::from(2u32))));
+        let three = ir.add_node(node(Operation::Constant(BigInt::from(3u32))));
+        let mul = ir.add_node(node(Operation::Mul));
+        let neg = ir.add_node(node(Operation::Neg));
+        let add = ir.add_node(node(Operation::Add));
+        let constraint = ir.add_node(node(Operation::Constraint(BigInt::from(1u32))));
+
+        // a + -(2 * 3)
+        ir.add_edge(two, mul, EdgeInfo::Left);
+        ir.add_edge(three, mul, EdgeInfo::Right);
+        ir.add_edge(mul, neg, EdgeInfo::Unary);
+        ir.add_edge(a, add, EdgeInfo::Left);
+        ir.add_edge(neg, add, EdgeInfo::Right);
+        ir.add_edge(add, constraint, EdgeInfo::Unordered);
+
+        let ir = optimize::<Field>(ir, OptimizationLevel::Full);
+
+        let ops = operations(&ir);
+
+        assert!(!ops.contains(&Operation::Mul));
+        assert!(!ops.contains(&Operation::Neg));
+
+        let query = GraphQuery::new(&ir.0);
+        let (_, right) = query.get_binary_operands(add).unwrap();
+
+        let six = <Field as FieldSpec>::BackendField::try_from(BigInt::from(6u32)).unwrap();
+        let minus_six: BigInt = (-six).zkp_into();
+
+        assert_eq!(ir[right].operation, Operation::Constant(minus_six));
+    }
+
+    #[test]
+    fn removes_identities() {
+        let mut ir = ZkpFrontendCompilation::new();
+
+        let a = ir.add_node(node(Operation::PrivateInput(0)));
+        let zero = ir.add_node(node(Operation::Constant(BigInt::ZERO)));
+        let one = ir.add_node(node(Operation::Constant(BigInt::ONE)));
+        let add = ir.add_node(node(Operation::Add));
+        let mul = ir.add_node(node(Operation::Mul));
+        let neg_1 = ir.add_node(node(Operation::Neg));
+        let neg_2 = ir.add_node(node(Operation::Neg));
+        let constraint = ir.add_node(node(Operation::Constraint(BigInt::from(1u32))));
+
+        // -(-((0 + a) * 1))
+        ir.add_edge(zero, add, EdgeInfo::Left);
+        ir.add_edge(a, add, EdgeInfo::Right);
+        ir.add_edge(add, mul, EdgeInfo::Left);
+        ir.add_edge(one, mul, EdgeInfo::Right);
+        ir.add_edge(mul, neg_1, EdgeInfo::Unary);
+        ir.add_edge(neg_1, neg_2, EdgeInfo::Unary);
+        ir.add_edge(neg_2, constraint, EdgeInfo::Unordered);
+
+        let ir = optimize::<Field>(ir, OptimizationLevel::Full);
+
+        let operands = ir
+            .neighbors_directed(constraint, petgraph::Direction::Incoming)
+            .collect::<Vec<_>>();
+
+        assert_eq!(operands, vec![a]);
+    }
+
+    #[test]
+    fn respects_optimization_level() {
+        let mut ir = ZkpFrontendCompilation::new();
+
+        let a = ir.add_node(node(Operation::PrivateInput(0)));
+        let one = ir.add_node(node(Operation::Constant(BigInt::ONE)));
+        let mul_1 = ir.add_node(node(Operation::Mul));
+        let mul_2 = ir.add_node(node(Operation::Mul));
+
+        ir.add_edge(a, mul_1, EdgeInfo::Left);
+        ir.add_edge(one, mul_1, EdgeInfo::Right);
+        ir.add_edge(a, mul_2, EdgeInfo::Left);
+        ir.add_edge(one, mul_2, EdgeInfo::Right);
+
+        let ir = optimize::<Field>(ir, OptimizationLevel::None);
+
+        assert_eq!(ir.node_count(), 4);
+
+        let ir = optimize::<Field>(ir, OptimizationLevel::Basic);
+
+        assert_eq!(ir.node_count(), 3);
+    }
+}
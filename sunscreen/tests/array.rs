@@ -31,13 +31,20 @@ fn can_add_array_elements() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(add).unwrap()])
+        .unwrap();
 
     let a = Signed::from(2);
     let b = Signed::from(4);
     let a_c = runtime.encrypt([a, b], &public_key).unwrap();
 
     let result = runtime
-        .run(app.get_fhe_program(add).unwrap(), vec![a_c], &public_key)
+        .run(
+            app.get_fhe_program(add).unwrap(),
+            vec![a_c],
+            &evaluation_keys,
+        )
         .unwrap();
 
     let c: Signed = runtime.decrypt(&result[0], &private_key).unwrap();
@@ -74,6 +81,9 @@ fn multidimensional_arrays() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(determinant).unwrap()])
+        .unwrap();
 
     let mut matrix = <[[Signed; 3]; 3]>::default();
 
@@ -92,7 +102,7 @@ fn multidimensional_arrays() {
         .run(
             app.get_fhe_program(determinant).unwrap(),
             vec![a_c],
-            &public_key,
+            &evaluation_keys,
         )
         .unwrap();
 
@@ -119,6 +129,9 @@ fn multidimensional_is_row_major() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(determinant).unwrap()])
+        .unwrap();
 
     let mut matrix = <[[Signed; 3]; 3]>::default();
 
@@ -137,7 +150,7 @@ fn multidimensional_is_row_major() {
         .run(
             app.get_fhe_program(determinant).unwrap(),
             vec![a_c],
-            &public_key,
+            &evaluation_keys,
         )
         .unwrap();
 
@@ -170,6 +183,9 @@ fn cipher_plain_arrays() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(dot).unwrap()])
+        .unwrap();
 
     let mut data = <[Signed; 3]>::default();
     let mut select = <[Signed; 3]>::default();
@@ -184,7 +200,7 @@ fn cipher_plain_arrays() {
     let args: Vec<FheProgramInput> = vec![select_c.into(), data.into()];
 
     let result = runtime
-        .run(app.get_fhe_program(dot).unwrap(), args, &public_key)
+        .run(app.get_fhe_program(dot).unwrap(), args, &evaluation_keys)
         .unwrap();
 
     let c: Signed = runtime.decrypt(&result[0], &private_key).unwrap();
@@ -221,6 +237,9 @@ fn can_mutate_array() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(mult).unwrap()])
+        .unwrap();
 
     let mut a = <[Signed; 6]>::default();
 
@@ -231,7 +250,11 @@ fn can_mutate_array() {
     let a_enc = runtime.encrypt(a, &public_key).unwrap();
 
     let result = runtime
-        .run(app.get_fhe_program(mult).unwrap(), vec![a_enc], &public_key)
+        .run(
+            app.get_fhe_program(mult).unwrap(),
+            vec![a_enc],
+            &evaluation_keys,
+        )
         .unwrap();
 
     let c: Signed = runtime.decrypt(&result[0], &private_key).unwrap();
@@ -262,6 +285,9 @@ fn can_return_array() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(mult).unwrap()])
+        .unwrap();
 
     let mut a = <[Signed; 6]>::default();
 
@@ -272,7 +298,11 @@ fn can_return_array() {
     let a_enc = runtime.encrypt(a, &public_key).unwrap();
 
     let result = runtime
-        .run(app.get_fhe_program(mult).unwrap(), vec![a_enc], &public_key)
+        .run(
+            app.get_fhe_program(mult).unwrap(),
+            vec![a_enc],
+            &evaluation_keys,
+        )
         .unwrap();
 
     let c: [Signed; 6] = runtime.decrypt(&result[0], &private_key).unwrap();
@@ -0,0 +1,62 @@
+use sunscreen::{
+    fhe_program,
+    types::{bfv::Signed, Cipher},
+    Compiler, Error, FheProgramInput, PlainModulusConstraint, Runtime, SchemeType,
+};
+
+#[test]
+fn can_add_and_multiply_with_bgv() {
+    #[fhe_program(scheme = "bgv")]
+    fn mad(a: Cipher<Signed>, b: Cipher<Signed>, c: Signed) -> Cipher<Signed> {
+        a * b + c
+    }
+
+    let app = Compiler::new()
+        .fhe_program(mad)
+        .plain_modulus_constraint(PlainModulusConstraint::Raw(500))
+        .compile()
+        .unwrap();
+
+    assert_eq!(app.params().scheme_type, SchemeType::Bgv);
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(mad).unwrap()])
+        .unwrap();
+
+    let a_c = runtime.encrypt(Signed::from(15), &public_key).unwrap();
+    let b_c = runtime.encrypt(Signed::from(-5), &public_key).unwrap();
+
+    let args: Vec<FheProgramInput> = vec![a_c.into(), b_c.into(), Signed::from(7).into()];
+
+    let result = runtime
+        .run(app.get_fhe_program(mad).unwrap(), args, &evaluation_keys)
+        .unwrap();
+
+    let c: Signed = runtime.decrypt(&result[0], &private_key).unwrap();
+
+    assert_eq!(c, Signed::from(15 * -5 + 7));
+}
+
+#[test]
+fn cannot_mix_bfv_and_bgv_programs() {
+    #[fhe_program(scheme = "bgv")]
+    fn add_bgv(a: Cipher<Signed>, b: Cipher<Signed>) -> Cipher<Signed> {
+        a + b
+    }
+
+    #[fhe_program(scheme = "bfv")]
+    fn add_bfv(a: Cipher<Signed>, b: Cipher<Signed>) -> Cipher<Signed> {
+        a + b
+    }
+
+    let result = Compiler::new()
+        .fhe_program(add_bgv)
+        .fhe_program(add_bfv)
+        .plain_modulus_constraint(PlainModulusConstraint::Raw(500))
+        .compile();
+
+    assert!(matches!(result, Err(Error::SchemeMismatch)));
+}
@@ -0,0 +1,117 @@
+use sunscreen::{
+    fhe_program,
+    types::{
+        bfv::{select, Batched, Bool, Signed},
+        Cipher,
+    },
+    Compiler, FheProgramInput, PlainModulusConstraint, Runtime,
+};
+
+#[test]
+fn can_compute_logic_gates() {
+    #[fhe_program(scheme = "bfv")]
+    fn gates(
+        a: Cipher<Bool>,
+        b: Cipher<Bool>,
+    ) -> (Cipher<Bool>, Cipher<Bool>, Cipher<Bool>, Cipher<Bool>) {
+        (a & b, a | b, a ^ b, !a)
+    }
+
+    let app = Compiler::new()
+        .fhe_program(gates)
+        .plain_modulus_constraint(PlainModulusConstraint::Raw(500))
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(gates).unwrap()])
+        .unwrap();
+
+    for a in [false, true] {
+        for b in [false, true] {
+            let a_c = runtime.encrypt(Bool::from(a), &public_key).unwrap();
+            let b_c = runtime.encrypt(Bool::from(b), &public_key).unwrap();
+
+            let args: Vec<FheProgramInput> = vec![a_c.into(), b_c.into()];
+
+            let result = runtime
+                .run(app.get_fhe_program(gates).unwrap(), args, &evaluation_keys)
+                .unwrap();
+
+            let result = result
+                .iter()
+                .map(|x| runtime.decrypt::<Bool>(x, &private_key).unwrap().into())
+                .collect::<Vec<bool>>();
+
+            assert_eq!(result, vec![a & b, a | b, a ^ b, !a]);
+        }
+    }
+}
+
+#[test]
+fn can_select_values() {
+    #[fhe_program(scheme = "bfv")]
+    fn choose(
+        cond: Cipher<Bool>,
+        a: [Cipher<Signed>; 2],
+        b: [Cipher<Signed>; 2],
+        x: Cipher<Batched<4>>,
+        y: Cipher<Batched<4>>,
+    ) -> (Cipher<Signed>, Cipher<Signed>, Cipher<Batched<4>>) {
+        let c = select(cond, a, b);
+
+        (c[0], c[1], select(!cond, x, y))
+    }
+
+    let app = Compiler::new()
+        .fhe_program(choose)
+        .plain_modulus_constraint(PlainModulusConstraint::BatchingMinimum(0))
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(choose).unwrap()])
+        .unwrap();
+
+    let x = Batched::<4>::from([[1, 2, 3, 4], [5, 6, 7, 8]]);
+    let y = Batched::<4>::from([[-1, -2, -3, -4], [-5, -6, -7, -8]]);
+
+    for cond in [false, true] {
+        let args: Vec<FheProgramInput> = vec![
+            runtime
+                .encrypt(Bool::from(cond), &public_key)
+                .unwrap()
+                .into(),
+            runtime
+                .encrypt([Signed::from(3), Signed::from(-4)], &public_key)
+                .unwrap()
+                .into(),
+            runtime
+                .encrypt([Signed::from(10), Signed::from(20)], &public_key)
+                .unwrap()
+                .into(),
+            runtime.encrypt(x, &public_key).unwrap().into(),
+            runtime.encrypt(y, &public_key).unwrap().into(),
+        ];
+
+        let result = runtime
+            .run(app.get_fhe_program(choose).unwrap(), args, &evaluation_keys)
+            .unwrap();
+
+        let c_0: Signed = runtime.decrypt(&result[0], &private_key).unwrap();
+        let c_1: Signed = runtime.decrypt(&result[1], &private_key).unwrap();
+        let z: Batched<4> = runtime.decrypt(&result[2], &private_key).unwrap();
+
+        if cond {
+            assert_eq!((c_0, c_1, z), (Signed::from(3), Signed::from(-4), y));
+        } else {
+            assert_eq!((c_0, c_1, z), (Signed::from(10), Signed::from(20), x));
+        }
+    }
+}
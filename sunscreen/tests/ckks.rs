@@ -0,0 +1,82 @@
+use sunscreen::{
+    fhe_program,
+    types::{ckks::Real, Cipher},
+    Compiler, FheProgramInput, Runtime, SchemeType,
+};
+
+type Vector = Real<4096>;
+
+fn make_vector(f: impl Fn(usize) -> f64) -> Vector {
+    (0..4096).map(f).collect::<Vec<f64>>().try_into().unwrap()
+}
+
+#[test]
+fn can_multiply_and_add_with_ckks() {
+    #[fhe_program(scheme = "ckks")]
+    fn mad(a: Cipher<Vector>, b: Cipher<Vector>, c: Vector) -> Cipher<Vector> {
+        a * b * b + c - a * 2.5
+    }
+
+    let app = Compiler::new().fhe_program(mad).compile().unwrap();
+
+    assert_eq!(app.params().scheme_type, SchemeType::Ckks);
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(mad).unwrap()])
+        .unwrap();
+
+    let a = make_vector(|i| i as f64 / 1024.);
+    let b = make_vector(|i| 1. - i as f64 / 4096.);
+    let c = make_vector(|i| -(i as f64) / 2048.);
+
+    let a_c = runtime.encrypt(a, &public_key).unwrap();
+    let b_c = runtime.encrypt(b, &public_key).unwrap();
+
+    let args: Vec<FheProgramInput> = vec![a_c.into(), b_c.into(), c.into()];
+
+    let result = runtime
+        .run(app.get_fhe_program(mad).unwrap(), args, &evaluation_keys)
+        .unwrap();
+
+    let actual: Vector = runtime.decrypt(&result[0], &private_key).unwrap();
+
+    let expected = a * b * b + c - a * Vector::from(2.5);
+
+    assert!(actual.approx_eq(&expected, 1e-3));
+}
+
+#[test]
+fn can_rotate_with_ckks() {
+    #[fhe_program(scheme = "ckks")]
+    fn rotate(a: Cipher<Vector>) -> Cipher<Vector> {
+        (a << 3) + (a >> 1)
+    }
+
+    let app = Compiler::new().fhe_program(rotate).compile().unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(rotate).unwrap()])
+        .unwrap();
+
+    let a = make_vector(|i| i as f64);
+
+    let a_c = runtime.encrypt(a, &public_key).unwrap();
+
+    let result = runtime
+        .run(
+            app.get_fhe_program(rotate).unwrap(),
+            vec![a_c],
+            &evaluation_keys,
+        )
+        .unwrap();
+
+    let actual: Vector = runtime.decrypt(&result[0], &private_key).unwrap();
+
+    assert!(actual.approx_eq(&((a << 3) + (a >> 1)), 1e-3));
+}
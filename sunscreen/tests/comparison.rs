@@ -0,0 +1,268 @@
+use sunscreen::{
+    fhe_program,
+    types::{
+        bfv::{Batched, Bool, Signed, Unsigned64},
+        Cipher,
+    },
+    Compiler, FheProgramInput, PlainModulusConstraint, Runtime,
+};
+
+const A: [[i64; 4]; 2] = [[1, 5, 3, 7], [0, 2, 2, 6]];
+const B: [[i64; 4]; 2] = [[4, 5, 1, 7], [3, 2, 0, 1]];
+
+#[test]
+fn can_compare_batched() {
+    #[fhe_program(scheme = "bfv")]
+    fn compare(
+        a: Cipher<Batched<4>>,
+        b: Cipher<Batched<4>>,
+    ) -> (Cipher<Batched<4>>, Cipher<Batched<4>>, Cipher<Batched<4>>) {
+        (a.less_than(b, 8), a.max(b, 8), a.min(b, 8))
+    }
+
+    let app = Compiler::new()
+        .fhe_program(compare)
+        .plain_modulus_constraint(PlainModulusConstraint::BatchingMinimum(0))
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(compare).unwrap()])
+        .unwrap();
+
+    let a_c = runtime.encrypt(Batched::<4>::from(A), &public_key).unwrap();
+    let b_c = runtime.encrypt(Batched::<4>::from(B), &public_key).unwrap();
+
+    let args: Vec<FheProgramInput> = vec![a_c.into(), b_c.into()];
+
+    let result = runtime
+        .run(
+            app.get_fhe_program(compare).unwrap(),
+            args,
+            &evaluation_keys,
+        )
+        .unwrap();
+
+    let lt: Batched<4> = runtime.decrypt(&result[0], &private_key).unwrap();
+    let max: Batched<4> = runtime.decrypt(&result[1], &private_key).unwrap();
+    let min: Batched<4> = runtime.decrypt(&result[2], &private_key).unwrap();
+
+    assert_eq!(lt, [[1, 0, 0, 0], [1, 0, 0, 0]].into());
+    assert_eq!(max, [[4, 5, 3, 7], [3, 2, 2, 6]].into());
+    assert_eq!(min, [[1, 5, 1, 7], [0, 2, 0, 1]].into());
+}
+
+#[test]
+fn can_test_batched_equality() {
+    #[fhe_program(scheme = "bfv")]
+    fn equals(a: Cipher<Batched<4>>, b: Cipher<Batched<4>>) -> Cipher<Batched<4>> {
+        a.equals(b)
+    }
+
+    let app = Compiler::new()
+        .fhe_program(equals)
+        .plain_modulus_constraint(PlainModulusConstraint::BatchingMinimum(0))
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(equals).unwrap()])
+        .unwrap();
+
+    let a_c = runtime.encrypt(Batched::<4>::from(A), &public_key).unwrap();
+    let b_c = runtime.encrypt(Batched::<4>::from(B), &public_key).unwrap();
+
+    let args: Vec<FheProgramInput> = vec![a_c.into(), b_c.into()];
+
+    let result = runtime
+        .run(app.get_fhe_program(equals).unwrap(), args, &evaluation_keys)
+        .unwrap();
+
+    let c: Batched<4> = runtime.decrypt(&result[0], &private_key).unwrap();
+
+    assert_eq!(c, [[0, 1, 0, 1], [0, 1, 0, 0]].into());
+}
+
+#[test]
+fn can_decompose_batched_into_bits() {
+    #[fhe_program(scheme = "bfv")]
+    fn bits(a: Cipher<Batched<4>>) -> (Cipher<Batched<4>>, Cipher<Batched<4>>, Cipher<Batched<4>>) {
+        let bits = a.to_bits(3);
+
+        (bits[0], bits[1], bits[2])
+    }
+
+    let app = Compiler::new()
+        .fhe_program(bits)
+        .plain_modulus_constraint(PlainModulusConstraint::BatchingMinimum(0))
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(bits).unwrap()])
+        .unwrap();
+
+    let a_c = runtime.encrypt(Batched::<4>::from(A), &public_key).unwrap();
+
+    let args: Vec<FheProgramInput> = vec![a_c.into()];
+
+    let result = runtime
+        .run(app.get_fhe_program(bits).unwrap(), args, &evaluation_keys)
+        .unwrap();
+
+    let bits = result
+        .iter()
+        .map(|x| runtime.decrypt(x, &private_key).unwrap())
+        .collect::<Vec<Batched<4>>>();
+
+    assert_eq!(bits[0], [[1, 1, 1, 1], [0, 0, 0, 0]].into());
+    assert_eq!(bits[1], [[0, 0, 1, 1], [0, 1, 1, 1]].into());
+    assert_eq!(bits[2], [[0, 1, 0, 1], [0, 0, 0, 1]].into());
+}
+
+#[test]
+fn can_compare_signed() {
+    #[fhe_program(scheme = "bfv")]
+    fn compare(
+        a: Cipher<Signed>,
+        b: Cipher<Signed>,
+    ) -> (Cipher<Bool>, Cipher<Bool>, Cipher<Signed>, Cipher<Signed>) {
+        (
+            a.less_than(b, 16),
+            a.greater_than(b, 16),
+            a.max(b, 16),
+            a.min(b, 16),
+        )
+    }
+
+    let app = Compiler::new()
+        .fhe_program(compare)
+        .plain_modulus_constraint(PlainModulusConstraint::BatchingMinimum(0))
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(compare).unwrap()])
+        .unwrap();
+
+    for (a, b) in [(-3, 5), (7, -2), (4, 4)] {
+        let a_c = runtime.encrypt(Signed::from(a), &public_key).unwrap();
+        let b_c = runtime.encrypt(Signed::from(b), &public_key).unwrap();
+
+        let args: Vec<FheProgramInput> = vec![a_c.into(), b_c.into()];
+
+        let result = runtime
+            .run(
+                app.get_fhe_program(compare).unwrap(),
+                args,
+                &evaluation_keys,
+            )
+            .unwrap();
+
+        let lt: Bool = runtime.decrypt(&result[0], &private_key).unwrap();
+        let gt: Bool = runtime.decrypt(&result[1], &private_key).unwrap();
+        let max: Signed = runtime.decrypt(&result[2], &private_key).unwrap();
+        let min: Signed = runtime.decrypt(&result[3], &private_key).unwrap();
+
+        assert_eq!(lt, Bool::from(a < b));
+        assert_eq!(gt, Bool::from(a > b));
+        assert_eq!(max, Signed::from(a.max(b)));
+        assert_eq!(min, Signed::from(a.min(b)));
+    }
+}
+
+#[test]
+fn can_test_signed_equality() {
+    #[fhe_program(scheme = "bfv")]
+    fn equals(a: Cipher<Signed>, b: Cipher<Signed>) -> Cipher<Bool> {
+        a.equals(b)
+    }
+
+    let app = Compiler::new()
+        .fhe_program(equals)
+        .plain_modulus_constraint(PlainModulusConstraint::BatchingMinimum(0))
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(equals).unwrap()])
+        .unwrap();
+
+    for (a, b) in [(-6, -6), (-6, 6), (1000, 999)] {
+        let a_c = runtime.encrypt(Signed::from(a), &public_key).unwrap();
+        let b_c = runtime.encrypt(Signed::from(b), &public_key).unwrap();
+
+        let args: Vec<FheProgramInput> = vec![a_c.into(), b_c.into()];
+
+        let result = runtime
+            .run(app.get_fhe_program(equals).unwrap(), args, &evaluation_keys)
+            .unwrap();
+
+        let c: Bool = runtime.decrypt(&result[0], &private_key).unwrap();
+
+        assert_eq!(c, Bool::from(a == b));
+    }
+}
+
+#[test]
+fn can_compare_unsigned() {
+    #[fhe_program(scheme = "bfv")]
+    fn compare(
+        a: Cipher<Unsigned64>,
+        b: Cipher<Unsigned64>,
+    ) -> (Cipher<Bool>, Cipher<Unsigned64>, Cipher<Unsigned64>) {
+        (a.less_than(b, 32), a.max(b, 32), a.min(b, 32))
+    }
+
+    let app = Compiler::new()
+        .fhe_program(compare)
+        .plain_modulus_constraint(PlainModulusConstraint::BatchingMinimum(0))
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(compare).unwrap()])
+        .unwrap();
+
+    // Values may be large as long as they're close together.
+    for (a, b) in [(1_000_017u64, 1_000_003), (12, 31)] {
+        let a_c = runtime.encrypt(Unsigned64::from(a), &public_key).unwrap();
+        let b_c = runtime.encrypt(Unsigned64::from(b), &public_key).unwrap();
+
+        let args: Vec<FheProgramInput> = vec![a_c.into(), b_c.into()];
+
+        let result = runtime
+            .run(
+                app.get_fhe_program(compare).unwrap(),
+                args,
+                &evaluation_keys,
+            )
+            .unwrap();
+
+        let lt: Bool = runtime.decrypt(&result[0], &private_key).unwrap();
+        let max: Unsigned64 = runtime.decrypt(&result[1], &private_key).unwrap();
+        let min: Unsigned64 = runtime.decrypt(&result[2], &private_key).unwrap();
+
+        assert_eq!(lt, Bool::from(a < b));
+        assert_eq!(max, Unsigned64::from(a.max(b)));
+        assert_eq!(min, Unsigned64::from(a.min(b)));
+    }
+}
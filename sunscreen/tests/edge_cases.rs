@@ -19,6 +19,9 @@ fn unused_cipher_parameter_1() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(add).unwrap()])
+        .unwrap();
 
     let a = runtime.encrypt(Signed::from(15), &public_key).unwrap();
     let b = runtime.encrypt(Signed::from(5), &public_key).unwrap();
@@ -27,7 +30,7 @@ fn unused_cipher_parameter_1() {
         .run(
             app.get_fhe_program(add).unwrap(),
             vec![a.clone(), a, b],
-            &public_key,
+            &evaluation_keys,
         )
         .unwrap();
 
@@ -52,6 +55,9 @@ fn unused_cipher_parameter_2() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(add).unwrap()])
+        .unwrap();
 
     let a = runtime.encrypt(Signed::from(15), &public_key).unwrap();
     let b = runtime.encrypt(Signed::from(5), &public_key).unwrap();
@@ -60,7 +66,7 @@ fn unused_cipher_parameter_2() {
         .run(
             app.get_fhe_program(add).unwrap(),
             vec![a.clone(), a, b],
-            &public_key,
+            &evaluation_keys,
         )
         .unwrap();
 
@@ -85,6 +91,9 @@ fn unused_cipher_parameter_3() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(add).unwrap()])
+        .unwrap();
 
     let a = runtime.encrypt(Signed::from(15), &public_key).unwrap();
     let b = runtime.encrypt(Signed::from(5), &public_key).unwrap();
@@ -93,7 +102,7 @@ fn unused_cipher_parameter_3() {
         .run(
             app.get_fhe_program(add).unwrap(),
             vec![a, b.clone(), b],
-            &public_key,
+            &evaluation_keys,
         )
         .unwrap();
 
@@ -118,6 +127,9 @@ fn unused_plain_parameter_1() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(add).unwrap()])
+        .unwrap();
 
     let a = runtime.encrypt(Signed::from(15), &public_key).unwrap();
     let b = runtime.encrypt(Signed::from(5), &public_key).unwrap();
@@ -125,7 +137,7 @@ fn unused_plain_parameter_1() {
     let args: Vec<FheProgramInput> = vec![Signed::from(0).into(), a.into(), b.into()];
 
     let result = runtime
-        .run(app.get_fhe_program(add).unwrap(), args, &public_key)
+        .run(app.get_fhe_program(add).unwrap(), args, &evaluation_keys)
         .unwrap();
 
     let c: Signed = runtime.decrypt(&result[0], &private_key).unwrap();
@@ -149,6 +161,9 @@ fn unused_plain_parameter_2() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(add).unwrap()])
+        .unwrap();
 
     let a = runtime.encrypt(Signed::from(15), &public_key).unwrap();
     let b = runtime.encrypt(Signed::from(5), &public_key).unwrap();
@@ -156,7 +171,7 @@ fn unused_plain_parameter_2() {
     let args: Vec<FheProgramInput> = vec![a.into(), Signed::from(0).into(), b.into()];
 
     let result = runtime
-        .run(app.get_fhe_program(add).unwrap(), args, &public_key)
+        .run(app.get_fhe_program(add).unwrap(), args, &evaluation_keys)
         .unwrap();
 
     let c: Signed = runtime.decrypt(&result[0], &private_key).unwrap();
@@ -180,6 +195,9 @@ fn unused_plain_parameter_3() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(add).unwrap()])
+        .unwrap();
 
     let a = runtime.encrypt(Signed::from(15), &public_key).unwrap();
     let b = runtime.encrypt(Signed::from(5), &public_key).unwrap();
@@ -187,7 +205,7 @@ fn unused_plain_parameter_3() {
     let args: Vec<FheProgramInput> = vec![a.into(), b.into(), Signed::from(0).into()];
 
     let result = runtime
-        .run(app.get_fhe_program(add).unwrap(), args, &public_key)
+        .run(app.get_fhe_program(add).unwrap(), args, &evaluation_keys)
         .unwrap();
 
     let c: Signed = runtime.decrypt(&result[0], &private_key).unwrap();
@@ -17,12 +17,19 @@ fn can_encrypt_decrypt() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(foo).unwrap()])
+        .unwrap();
 
     let a = runtime.encrypt(Signed::from(15), &public_key).unwrap();
     let b = runtime.encrypt(Signed::from(5), &public_key).unwrap();
 
     let result = runtime
-        .run(app.get_fhe_program(foo).unwrap(), vec![a, b], &public_key)
+        .run(
+            app.get_fhe_program(foo).unwrap(),
+            vec![a, b],
+            &evaluation_keys,
+        )
         .unwrap();
 
     let c: Signed = runtime.decrypt(&result[0], &private_key).unwrap();
@@ -21,14 +21,17 @@ mod transparent_ciphertexts {
 
         let runtime = Runtime::new_fhe(app.params()).unwrap();
 
-        let (public, _) = runtime.generate_keys().unwrap();
+        let (public, private_key) = runtime.generate_keys().unwrap();
+        let evaluation_keys = runtime
+            .generate_evaluation_keys(&private_key, [app.get_fhe_program(add).unwrap()])
+            .unwrap();
 
         let a = runtime.encrypt(Signed::from(42), &public).unwrap();
 
         let args: Vec<FheProgramInput> = vec![a.into(), Signed::from(0).into()];
 
         runtime
-            .run(app.get_fhe_program(add).unwrap(), args, &public)
+            .run(app.get_fhe_program(add).unwrap(), args, &evaluation_keys)
             .unwrap();
     }
 }
@@ -48,14 +51,17 @@ mod transparent_ciphertexts {
 
         let runtime = Runtime::new_fhe(app.params()).unwrap();
 
-        let (public, _) = runtime.generate_keys().unwrap();
+        let (public, private_key) = runtime.generate_keys().unwrap();
+        let evaluation_keys = runtime
+            .generate_evaluation_keys(&private_key, [app.get_fhe_program(add).unwrap()])
+            .unwrap();
 
         let a = runtime.encrypt(Signed::from(42), &public).unwrap();
 
         let args: Vec<FheProgramInput> = vec![a.into(), Signed::from(0).into()];
 
         runtime
-            .run(app.get_fhe_program(add).unwrap(), args, &public)
+            .run(app.get_fhe_program(add).unwrap(), args, &evaluation_keys)
             .unwrap();
     }
 }
@@ -59,6 +59,9 @@ fn can_add() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, app.get_fhe_programs().map(|(_, p)| p))
+        .unwrap();
 
     let do_add = |a: f64, b: f64| {
         let a_p = Fractional::<64>::from(a);
@@ -68,27 +71,47 @@ fn can_add() {
 
         let args: Vec<FheProgramInput> = vec![a_c.clone().into(), b_c.clone().into()];
         let c_0 = runtime
-            .run(app.get_fhe_program(add_c_c).unwrap(), args, &public_key)
+            .run(
+                app.get_fhe_program(add_c_c).unwrap(),
+                args,
+                &evaluation_keys,
+            )
             .unwrap();
 
         let args: Vec<FheProgramInput> = vec![a_c.clone().into(), b_p.into()];
         let c_1 = runtime
-            .run(app.get_fhe_program(add_c_p).unwrap(), args, &public_key)
+            .run(
+                app.get_fhe_program(add_c_p).unwrap(),
+                args,
+                &evaluation_keys,
+            )
             .unwrap();
 
         let args: Vec<FheProgramInput> = vec![a_p.into(), b_c.clone().into()];
         let c_2 = runtime
-            .run(app.get_fhe_program(add_p_c).unwrap(), args, &public_key)
+            .run(
+                app.get_fhe_program(add_p_c).unwrap(),
+                args,
+                &evaluation_keys,
+            )
             .unwrap();
 
         let args: Vec<FheProgramInput> = vec![a_c.clone().into(), b_c.clone().into()];
         let c_3 = runtime
-            .run(app.get_fhe_program(add_c_l).unwrap(), args, &public_key)
+            .run(
+                app.get_fhe_program(add_c_l).unwrap(),
+                args,
+                &evaluation_keys,
+            )
             .unwrap();
 
         let args: Vec<FheProgramInput> = vec![a_c.into(), b_c.into()];
         let c_4 = runtime
-            .run(app.get_fhe_program(add_l_c).unwrap(), args, &public_key)
+            .run(
+                app.get_fhe_program(add_l_c).unwrap(),
+                args,
+                &evaluation_keys,
+            )
             .unwrap();
 
         let c_0: Fractional<64> = runtime.decrypt(&c_0[0], &private_key).unwrap();
@@ -165,6 +188,9 @@ fn can_mul() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, app.get_fhe_programs().map(|(_, p)| p))
+        .unwrap();
 
     let do_mul = |a: f64, b: f64| {
         let a_p = Fractional::<64>::from(a);
@@ -174,27 +200,47 @@ fn can_mul() {
 
         let args: Vec<FheProgramInput> = vec![a_c.clone().into(), b_c.clone().into()];
         let c_0 = runtime
-            .run(app.get_fhe_program(mul_c_c).unwrap(), args, &public_key)
+            .run(
+                app.get_fhe_program(mul_c_c).unwrap(),
+                args,
+                &evaluation_keys,
+            )
             .unwrap();
 
         let args: Vec<FheProgramInput> = vec![a_c.clone().into(), b_p.into()];
         let c_1 = runtime
-            .run(app.get_fhe_program(mul_c_p).unwrap(), args, &public_key)
+            .run(
+                app.get_fhe_program(mul_c_p).unwrap(),
+                args,
+                &evaluation_keys,
+            )
             .unwrap();
 
         let args: Vec<FheProgramInput> = vec![a_p.into(), b_c.into()];
         let c_2 = runtime
-            .run(app.get_fhe_program(mul_p_c).unwrap(), args, &public_key)
+            .run(
+                app.get_fhe_program(mul_p_c).unwrap(),
+                args,
+                &evaluation_keys,
+            )
             .unwrap();
 
         let args: Vec<FheProgramInput> = vec![a_c.clone().into()];
         let c_3 = runtime
-            .run(app.get_fhe_program(mul_c_l).unwrap(), args, &public_key)
+            .run(
+                app.get_fhe_program(mul_c_l).unwrap(),
+                args,
+                &evaluation_keys,
+            )
             .unwrap();
 
         let args: Vec<FheProgramInput> = vec![a_c.into()];
         let c_4 = runtime
-            .run(app.get_fhe_program(mul_l_c).unwrap(), args, &public_key)
+            .run(
+                app.get_fhe_program(mul_l_c).unwrap(),
+                args,
+                &evaluation_keys,
+            )
             .unwrap();
 
         assert_ne!(
@@ -291,6 +337,9 @@ fn can_sub() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, app.get_fhe_programs().map(|(_, p)| p))
+        .unwrap();
 
     let do_sub = |a: f64, b: f64| {
         let a_p = Fractional::<64>::from(a);
@@ -300,27 +349,47 @@ fn can_sub() {
 
         let args: Vec<FheProgramInput> = vec![a_c.clone().into(), b_c.clone().into()];
         let c_0 = runtime
-            .run(app.get_fhe_program(sub_c_c).unwrap(), args, &public_key)
+            .run(
+                app.get_fhe_program(sub_c_c).unwrap(),
+                args,
+                &evaluation_keys,
+            )
             .unwrap();
 
         let args: Vec<FheProgramInput> = vec![a_c.clone().into(), b_p.into()];
         let c_1 = runtime
-            .run(app.get_fhe_program(sub_c_p).unwrap(), args, &public_key)
+            .run(
+                app.get_fhe_program(sub_c_p).unwrap(),
+                args,
+                &evaluation_keys,
+            )
             .unwrap();
 
         let args: Vec<FheProgramInput> = vec![a_p.into(), b_c.clone().into()];
         let c_2 = runtime
-            .run(app.get_fhe_program(sub_p_c).unwrap(), args, &public_key)
+            .run(
+                app.get_fhe_program(sub_p_c).unwrap(),
+                args,
+                &evaluation_keys,
+            )
             .unwrap();
 
         let args: Vec<FheProgramInput> = vec![a_c.clone().into(), b_c.clone().into()];
         let c_3 = runtime
-            .run(app.get_fhe_program(sub_c_l).unwrap(), args, &public_key)
+            .run(
+                app.get_fhe_program(sub_c_l).unwrap(),
+                args,
+                &evaluation_keys,
+            )
             .unwrap();
 
         let args: Vec<FheProgramInput> = vec![a_c.into(), b_c.into()];
         let c_4 = runtime
-            .run(app.get_fhe_program(sub_l_c).unwrap(), args, &public_key)
+            .run(
+                app.get_fhe_program(sub_l_c).unwrap(),
+                args,
+                &evaluation_keys,
+            )
             .unwrap();
 
         let c_0: Fractional<64> = runtime.decrypt(&c_0[0], &private_key).unwrap();
@@ -366,6 +435,9 @@ fn can_div_cipher_const() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(mul).unwrap()])
+        .unwrap();
 
     let test_div = |a: f64| {
         let a_c = runtime
@@ -375,7 +447,7 @@ fn can_div_cipher_const() {
         let args: Vec<FheProgramInput> = vec![a_c.into()];
 
         let result = runtime
-            .run(app.get_fhe_program(mul).unwrap(), args, &public_key)
+            .run(app.get_fhe_program(mul).unwrap(), args, &evaluation_keys)
             .unwrap();
 
         let c: Fractional<64> = runtime.decrypt(&result[0], &private_key).unwrap();
@@ -410,6 +482,9 @@ fn can_negate() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(neg).unwrap()])
+        .unwrap();
 
     let test_div = |a: f64| {
         let a_c = runtime
@@ -419,7 +494,7 @@ fn can_negate() {
         let args: Vec<FheProgramInput> = vec![a_c.into()];
 
         let result = runtime
-            .run(app.get_fhe_program(neg).unwrap(), args, &public_key)
+            .run(app.get_fhe_program(neg).unwrap(), args, &evaluation_keys)
             .unwrap();
 
         let c: Fractional<64> = runtime.decrypt(&result[0], &private_key).unwrap();
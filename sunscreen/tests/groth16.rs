@@ -0,0 +1,296 @@
+#[cfg(feature = "groth16")]
+mod groth16_tests {
+    use std::env;
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    use revm::{
+        db::{CacheDB, EmptyDB},
+        primitives::{Bytes, ExecutionResult, Output, TransactTo, B160},
+        EVM,
+    };
+    use sunscreen::{
+        groth16::{solidity::SolidityVerifier, Groth16Backend},
+        types::zkp::Field,
+        zkp_program, Compiler, Proof, Runtime, ZkpBackend, ZkpProgramInput,
+    };
+    use sunscreen_zkp_backend::FieldSpec;
+
+    type G16Field = Field<<Groth16Backend as ZkpBackend>::Field>;
+
+    #[test]
+    fn can_prove_and_verify_with_groth16() {
+        #[zkp_program]
+        fn add_mul<F: FieldSpec>(a: Field<F>, b: Field<F>, c: Field<F>) {
+            let x = a * b + c;
+
+            x.constrain_eq(Field::from(42u32))
+        }
+
+        let app = Compiler::new()
+            .zkp_backend::<Groth16Backend>()
+            .zkp_program(add_mul)
+            .compile()
+            .unwrap();
+
+        let runtime = Runtime::new_zkp(Groth16Backend::new()).unwrap();
+
+        let program = app.get_zkp_program(add_mul).unwrap();
+
+        let proof = runtime
+            .prove(
+                program,
+                vec![
+                    G16Field::from(10u8),
+                    G16Field::from(4u8),
+                    G16Field::from(2u8),
+                ],
+                vec![],
+                vec![],
+            )
+            .unwrap();
+
+        assert!(matches!(proof, Proof::Groth16(_)));
+
+        runtime
+            .verify(program, &proof, Vec::<ZkpProgramInput>::new(), vec![])
+            .unwrap();
+    }
+
+    #[test]
+    fn groth16_public_inputs_are_bound_to_proof() {
+        #[zkp_program]
+        fn add_mul<F: FieldSpec>(b: Field<F>, c: Field<F>, #[public] a: Field<F>) {
+            let x = a * b + c;
+
+            x.constrain_eq(Field::from(42u32))
+        }
+
+        let app = Compiler::new()
+            .zkp_backend::<Groth16Backend>()
+            .zkp_program(add_mul)
+            .compile()
+            .unwrap();
+
+        let runtime = Runtime::new_zkp(Groth16Backend::new()).unwrap();
+
+        let program = app.get_zkp_program(add_mul).unwrap();
+
+        let proof = runtime
+            .prove(
+                program,
+                vec![G16Field::from(4u8), G16Field::from(2u8)],
+                vec![G16Field::from(10u8)],
+                vec![],
+            )
+            .unwrap();
+
+        runtime
+            .verify(program, &proof, vec![G16Field::from(10u8)], vec![])
+            .unwrap();
+
+        // The same setup serves other public inputs.
+        let proof = runtime
+            .prove(
+                program,
+                vec![G16Field::from(8u8), G16Field::from(2u8)],
+                vec![G16Field::from(5u8)],
+                vec![],
+            )
+            .unwrap();
+
+        runtime
+            .verify(program, &proof, vec![G16Field::from(5u8)], vec![])
+            .unwrap();
+
+        assert!(runtime
+            .verify(program, &proof, vec![G16Field::from(10u8)], vec![])
+            .is_err());
+    }
+
+    /**
+     * Compiles the contract `name` in `source` with solc, returning its
+     * creation bytecode. Set `SOLC` to use a solc other than the one on the
+     * `PATH`.
+     */
+    fn compile_contract(source: &str, name: &str) -> Vec<u8> {
+        let solc = env::var("SOLC").unwrap_or_else(|_| "solc".to_owned());
+
+        let mut child = Command::new(&solc)
+            .args(["--bin", "--optimize", "-"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap_or_else(|e| panic!("Failed to run {} (set SOLC to solc's path): {}", solc, e));
+
+        child
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(source.as_bytes())
+            .unwrap();
+
+        let output = child.wait_with_output().unwrap();
+
+        assert!(output.status.success());
+
+        // solc prints "======= <stdin>:<name> =======", "Binary:" and then the
+        // bytecode as hex.
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        let mut lines = stdout
+            .lines()
+            .skip_while(|l| *l != format!("======= <stdin>:{} =======", name));
+
+        assert_eq!(lines.nth(1), Some("Binary:"));
+
+        let hex = lines.next().unwrap().trim();
+
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    /**
+     * Deploys a contract with the given creation bytecode to a fresh EVM.
+     */
+    fn deploy(bytecode: Vec<u8>) -> (EVM<CacheDB<EmptyDB>>, B160) {
+        let mut evm = EVM::new();
+        evm.database(CacheDB::new(EmptyDB::default()));
+
+        evm.env.tx.transact_to = TransactTo::create();
+        evm.env.tx.data = Bytes::from(bytecode);
+
+        match evm.transact_commit().unwrap() {
+            ExecutionResult::Success {
+                output: Output::Create(_, Some(address)),
+                ..
+            } => (evm, address),
+            result => panic!("Failed to deploy verifier: {:#?}", result),
+        }
+    }
+
+    /**
+     * Calls the deployed verifier's `verifyProof` with the given calldata.
+     * Returns the function's result, or `None` if the call reverted.
+     */
+    fn evm_verify(evm: &mut EVM<CacheDB<EmptyDB>>, address: B160, calldata: &[u8]) -> Option<bool> {
+        evm.env.tx.transact_to = TransactTo::Call(address);
+        evm.env.tx.data = Bytes::from(calldata.to_vec());
+
+        match evm.transact_commit().unwrap() {
+            ExecutionResult::Success {
+                output: Output::Call(out),
+                ..
+            } => {
+                assert_eq!(out.len(), 32);
+                assert!(out[..31].iter().all(|b| *b == 0));
+
+                Some(out[31] == 1)
+            }
+            ExecutionResult::Revert { .. } => None,
+            result => panic!("verifyProof halted: {:#?}", result),
+        }
+    }
+
+    #[test]
+    fn can_verify_groth16_proofs_on_evm() {
+        #[zkp_program]
+        fn transfer<F: FieldSpec>(
+            b: Field<F>,
+            #[public] a: Field<F>,
+            #[public] total: Field<F>,
+            #[constant] fee: Field<F>,
+        ) {
+            let x = a * b + fee;
+
+            x.constrain_eq(total)
+        }
+
+        let app = Compiler::new()
+            .zkp_backend::<Groth16Backend>()
+            .zkp_program(transfer)
+            .compile()
+            .unwrap();
+
+        let backend = Groth16Backend::new();
+        let runtime = Runtime::new_zkp(backend.clone()).unwrap();
+
+        let program = app.get_zkp_program(transfer).unwrap();
+
+        let public_inputs = vec![G16Field::from(10u8), G16Field::from(42u8)];
+        let constant_inputs = vec![G16Field::from(2u8)];
+
+        let proof = runtime
+            .prove(
+                program,
+                vec![G16Field::from(4u8)],
+                public_inputs.clone(),
+                constant_inputs.clone(),
+            )
+            .unwrap();
+
+        let public_inputs = public_inputs.iter().map(|x| x.val).collect::<Vec<_>>();
+        let constant_inputs = constant_inputs.iter().map(|x| x.val).collect::<Vec<_>>();
+
+        let graph = backend
+            .jit_verifier(&program.zkp_program_fn, &constant_inputs, &public_inputs)
+            .unwrap();
+        let parameters = backend.parameters(&graph).unwrap().verifier_parameters();
+
+        let verifier =
+            SolidityVerifier::new(&program.zkp_program_fn, &constant_inputs, &parameters).unwrap();
+
+        assert_eq!(verifier.public_input_count(), 2);
+
+        let source = verifier.contract_source("TransferVerifier");
+        let (mut evm, address) = deploy(compile_contract(&source, "TransferVerifier"));
+
+        let calldata = verifier.encode_calldata(&proof, &public_inputs).unwrap();
+
+        assert_eq!(calldata.len(), 4 + 32 * 10);
+        assert_eq!(evm_verify(&mut evm, address, &calldata), Some(true));
+
+        // Changing a public input invalidates the proof.
+        let wrong_inputs = [public_inputs[0], G16Field::from(43u8).val];
+        let wrong_calldata = verifier.encode_calldata(&proof, &wrong_inputs).unwrap();
+
+        assert_eq!(evm_verify(&mut evm, address, &wrong_calldata), Some(false));
+
+        // The contract rejects public inputs outside the scalar field, even
+        // ones congruent to valid inputs. encode_calldata refuses them, so
+        // add the modulus to the last input by hand.
+        let r = (0..32).map(|i| {
+            u8::from_str_radix(
+                &"30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001"
+                    [2 * i..2 * i + 2],
+                16,
+            )
+            .unwrap()
+        });
+        let mut unreduced_calldata = calldata;
+        let mut carry = 0;
+
+        for (byte, r) in unreduced_calldata.iter_mut().rev().zip(r.rev()) {
+            let sum = *byte as u16 + r as u16 + carry;
+            *byte = sum as u8;
+            carry = sum >> 8;
+        }
+
+        assert_eq!(evm_verify(&mut evm, address, &unreduced_calldata), None);
+
+        // A verifier for another fee rejects the proof.
+        let verifier = SolidityVerifier::new(
+            &program.zkp_program_fn,
+            &[G16Field::from(3u8).val],
+            &parameters,
+        )
+        .unwrap();
+        let source = verifier.contract_source("TransferVerifier");
+        let (mut evm, address) = deploy(compile_contract(&source, "TransferVerifier"));
+
+        let calldata = verifier.encode_calldata(&proof, &public_inputs).unwrap();
+
+        assert_eq!(evm_verify(&mut evm, address, &calldata), Some(false));
+    }
+}
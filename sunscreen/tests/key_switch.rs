@@ -0,0 +1,108 @@
+use sunscreen::{
+    types::{bfv::Signed, Cipher},
+    *,
+};
+
+#[fhe_program(scheme = "bfv")]
+fn multiply(a: Cipher<Signed>, b: Cipher<Signed>) -> Cipher<Signed> {
+    a * b
+}
+
+fn setup() -> (FheApplication, FheRuntime) {
+    let app = Compiler::new()
+        .fhe_program(multiply)
+        .additional_noise_budget(10)
+        .compile()
+        .unwrap();
+
+    let runtime = FheRuntime::new(app.params()).unwrap();
+
+    (app, runtime)
+}
+
+#[test]
+fn can_switch_fresh_ciphertext_to_another_key() {
+    let (_, runtime) = setup();
+
+    let (alice_public, alice_private) = runtime.generate_keys().unwrap();
+    let (bob_public, bob_private) = runtime.generate_keys().unwrap();
+
+    let key = runtime
+        .generate_key_switch_key(&alice_private, &bob_public)
+        .unwrap();
+
+    let c = runtime.encrypt(Signed::from(-17), &alice_public).unwrap();
+
+    // Bob can't read Alice's ciphertext until it's switched.
+    let result = runtime.decrypt::<Signed>(&c, &bob_private);
+    assert!(!matches!(result, Ok(v) if v == Signed::from(-17)));
+
+    let switched = runtime.key_switch(&c, &key).unwrap();
+
+    assert_eq!(switched.data_type, c.data_type);
+
+    let result: Signed = runtime.decrypt(&switched, &bob_private).unwrap();
+    assert_eq!(result, Signed::from(-17));
+}
+
+#[test]
+fn can_switch_fhe_program_output_to_another_key() {
+    let (app, runtime) = setup();
+
+    let (alice_public, alice_private) = runtime.generate_keys().unwrap();
+    let alice_evaluation_keys = runtime
+        .generate_evaluation_keys(&alice_private, [app.get_fhe_program(multiply).unwrap()])
+        .unwrap();
+    let (bob_public, bob_private) = runtime.generate_keys().unwrap();
+
+    let a = runtime.encrypt(Signed::from(6), &alice_public).unwrap();
+    let b = runtime.encrypt(Signed::from(-7), &alice_public).unwrap();
+
+    let c = runtime
+        .run(
+            app.get_fhe_program(multiply).unwrap(),
+            vec![a, b],
+            &alice_evaluation_keys,
+        )
+        .unwrap()
+        .remove(0);
+
+    let key = runtime
+        .generate_key_switch_key(&alice_private, &bob_public)
+        .unwrap();
+    let switched = runtime.key_switch(&c, &key).unwrap();
+
+    // Key switching costs at most a few bits of noise budget.
+    let before = runtime.measure_noise_budget(&c, &alice_private).unwrap();
+    let after = runtime
+        .measure_noise_budget(&switched, &bob_private)
+        .unwrap();
+
+    assert!(after > 0);
+    assert!(after + 5 >= before);
+
+    let result: Signed = runtime.decrypt(&switched, &bob_private).unwrap();
+    assert_eq!(result, Signed::from(-42));
+}
+
+#[test]
+fn key_switch_keys_must_match_runtime_params() {
+    let (_, runtime) = setup();
+
+    let other_runtime = FheRuntime::new(
+        Compiler::new()
+            .fhe_program(multiply)
+            .additional_noise_budget(100)
+            .compile()
+            .unwrap()
+            .params(),
+    )
+    .unwrap();
+
+    let (_, alice_private) = runtime.generate_keys().unwrap();
+    let (bob_public, _) = other_runtime.generate_keys().unwrap();
+
+    let result = runtime.generate_key_switch_key(&alice_private, &bob_public);
+
+    assert!(matches!(result, Err(RuntimeError::ParameterMismatch)));
+}
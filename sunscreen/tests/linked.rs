@@ -1,11 +1,12 @@
 #[cfg(feature = "linkedproofs")]
 mod linked_tests {
     use lazy_static::lazy_static;
-    use logproof::rings::{SealQ128_1024, SealQ128_4096};
+    use logproof::rings::{SealQ128_1024, SealQ128_16384, SealQ128_4096};
     use num::Rational64;
-    use sunscreen::types::bfv::{Rational, Signed, Unsigned64};
+    use sunscreen::types::bfv::{Batched, Fractional, Rational, Signed, Unsigned64};
     use sunscreen::types::zkp::{
-        AsFieldElement, BfvRational, BfvSigned, BulletproofsField, ConstrainFresh,
+        AsFieldElement, BfvBatched, BfvFractional, BfvRational, BfvSigned, BigInt,
+        BulletproofsField, ConstrainFresh,
     };
     use sunscreen::types::Cipher;
     use sunscreen::PlainModulusConstraint;
@@ -28,6 +29,13 @@ mod linked_tests {
             scheme_type: SchemeType::Bfv,
             security_level: sunscreen::SecurityLevel::TC128,
         };
+        static ref BATCHING_PARAMS: Params = Params {
+            lattice_dimension: 128,
+            coeff_modulus: SealQ128_1024::Q.to_vec(),
+            plain_modulus: 257,
+            scheme_type: SchemeType::Bfv,
+            security_level: sunscreen::SecurityLevel::TC128,
+        };
     }
 
     #[fhe_program(scheme = "bfv")]
@@ -183,6 +191,181 @@ mod linked_tests {
         }
     }
 
+    #[test]
+    fn can_link_at_lattice_dimension_16384() {
+        let params = Params {
+            lattice_dimension: 16384,
+            coeff_modulus: SealQ128_16384::Q.to_vec(),
+            ..*TEST_PARAMS
+        };
+        let app = Compiler::new()
+            .fhe_program(doggie)
+            .with_params(&params)
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(is_eq_signed)
+            .compile()
+            .unwrap();
+        let rt = FheZkpRuntime::new(app.params(), &BulletproofsBackend::new()).unwrap();
+        let is_eq_zkp = app.get_zkp_program(is_eq_signed).unwrap();
+
+        let (public_key, _secret_key) = rt.generate_keys().unwrap();
+
+        let val = -7;
+        let mut proof_builder = LinkedProofBuilder::new(&rt);
+        let (ct, val_msg) = proof_builder
+            .encrypt_returning_link(&Signed::from(val), &public_key)
+            .unwrap();
+        let lp = proof_builder
+            .zkp_program(is_eq_zkp)
+            .unwrap()
+            .linked_input(val_msg)
+            .public_input(BulletproofsField::from(val))
+            .build()
+            .unwrap();
+
+        let mut verify_builder = LinkedProofVerificationBuilder::new(&rt);
+        verify_builder
+            .encrypt_returning_link::<Signed>(&ct, &public_key)
+            .unwrap();
+        verify_builder
+            .proof(lp)
+            .zkp_program(is_eq_zkp)
+            .unwrap()
+            .public_input(BulletproofsField::from(val))
+            .verify()
+            .expect("Failed to verify linked proof");
+    }
+
+    #[zkp_program]
+    fn is_eq_fractional<F: FieldSpec>(
+        #[linked] x: BfvFractional<F, 64>,
+        #[public] scaled: Field<F>,
+    ) {
+        x.into_field_elem().constrain_eq(scaled);
+    }
+
+    #[test]
+    fn test_fractional_encoding() {
+        let app = Compiler::new()
+            .fhe_program(doggie)
+            .with_params(&TEST_PARAMS)
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(is_eq_fractional)
+            .compile()
+            .unwrap();
+        let rt = FheZkpRuntime::new(app.params(), &BulletproofsBackend::new()).unwrap();
+        let is_eq_zkp = app.get_zkp_program(is_eq_fractional).unwrap();
+
+        let (public_key, _secret_key) = rt.generate_keys().unwrap();
+
+        // Decoded values are scaled by 2^64.
+        for (val, scaled) in [
+            (3.25, BigInt::from(13u8) << 62),
+            (0.5, BigInt::ONE << 63),
+            (0.0, BigInt::ZERO),
+        ] {
+            let mut proof_builder = LinkedProofBuilder::new(&rt);
+            let (ct, val_msg) = proof_builder
+                .encrypt_returning_link(&Fractional::<64>::from(val), &public_key)
+                .unwrap();
+            proof_builder
+                .zkp_program(is_eq_zkp)
+                .unwrap()
+                .linked_input(val_msg)
+                .public_input(BulletproofsField::from(scaled));
+
+            let lp = proof_builder
+                .build()
+                .unwrap_or_else(|e| panic!("Failed to prove {val}; {e}"));
+            let mut verify_builder = LinkedProofVerificationBuilder::new(&rt);
+            verify_builder
+                .encrypt_returning_link::<Fractional<64>>(&ct, &public_key)
+                .unwrap();
+            verify_builder
+                .proof(lp)
+                .zkp_program(is_eq_zkp)
+                .unwrap()
+                .public_input(BulletproofsField::from(scaled))
+                .verify()
+                .expect("Failed to verify linked proof");
+        }
+    }
+
+    #[zkp_program]
+    fn batched_lanes_eq<F: FieldSpec>(
+        #[linked] x: BfvBatched<F, 64>,
+        #[public] first: Field<F>,
+        #[public] last: Field<F>,
+    ) {
+        x.lane(0, 0).constrain_eq(first);
+        x.lane(1, 63).constrain_eq(last);
+    }
+
+    #[test]
+    fn test_batched_encoding() {
+        let app = Compiler::new()
+            .fhe_program(doggie)
+            .with_params(&BATCHING_PARAMS)
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(batched_lanes_eq)
+            .compile()
+            .unwrap();
+        let rt = FheZkpRuntime::new(app.params(), &BulletproofsBackend::new()).unwrap();
+        let lanes_zkp = app.get_zkp_program(batched_lanes_eq).unwrap();
+
+        let (public_key, _secret_key) = rt.generate_keys().unwrap();
+
+        let mut data = [[0i64; 64]; 2];
+        for (i, x) in data[0].iter_mut().enumerate() {
+            *x = i as i64 - 32;
+        }
+        for (i, x) in data[1].iter_mut().enumerate() {
+            *x = 100 - 3 * i as i64;
+        }
+        let (first, last) = (data[0][0], data[1][63]);
+
+        let mut proof_builder = LinkedProofBuilder::new(&rt);
+        let (ct, msg) = proof_builder
+            .encrypt_returning_link(&Batched::<64>::from(data), &public_key)
+            .unwrap();
+        let lp = proof_builder
+            .zkp_program(lanes_zkp)
+            .unwrap()
+            .linked_input(msg)
+            .public_input(BulletproofsField::from(first))
+            .public_input(BulletproofsField::from(last))
+            .build()
+            .unwrap();
+
+        let mut verify_builder = LinkedProofVerificationBuilder::new(&rt);
+        verify_builder
+            .encrypt_returning_link::<Batched<64>>(&ct, &public_key)
+            .unwrap();
+        verify_builder
+            .proof(lp)
+            .zkp_program(lanes_zkp)
+            .unwrap()
+            .public_input(BulletproofsField::from(first))
+            .public_input(BulletproofsField::from(last))
+            .verify()
+            .expect("Failed to verify linked proof");
+    }
+
+    #[test]
+    fn builder_enforces_batched_lanes_fill_plaintext() {
+        let app = Compiler::new()
+            .fhe_program(doggie)
+            .with_params(&BATCHING_PARAMS)
+            .compile()
+            .unwrap();
+        let rt = FheZkpRuntime::new(app.params(), &BulletproofsBackend::new()).unwrap();
+        let (public_key, _secret_key) = rt.generate_keys().unwrap();
+
+        let mut proof_builder = LinkedProofBuilder::new(&rt);
+        let res = proof_builder.encrypt_returning_link(&Batched::<32>::from(1), &public_key);
+        assert!(matches!(res, Err(sunscreen::RuntimeError::FheTypeError(_))));
+    }
+
     #[zkp_program]
     fn is_eq_rational<F: FieldSpec>(
         #[linked] x: BfvRational<F>,
@@ -522,6 +705,15 @@ mod linked_tests {
         let rt = FheZkpRuntime::new(app.params(), &BulletproofsBackend::new()).unwrap();
         let is_fresh_zkp = app.get_zkp_program(is_fresh).unwrap();
         let (public_key, private_key) = rt.generate_keys().unwrap();
+        let evaluation_keys = rt
+            .generate_evaluation_keys(
+                &private_key,
+                [
+                    app.get_fhe_program(double_signed).unwrap(),
+                    app.get_fhe_program(double_rational).unwrap(),
+                ],
+            )
+            .unwrap();
 
         for ix in 0..2 {
             // Make some random values
@@ -540,7 +732,7 @@ mod linked_tests {
                     .run(
                         app.get_fhe_program(double_signed).unwrap(),
                         vec![x_ct],
-                        &public_key,
+                        &evaluation_keys,
                     )
                     .unwrap()
                     .remove(0);
@@ -549,7 +741,7 @@ mod linked_tests {
                     .run(
                         app.get_fhe_program(double_rational).unwrap(),
                         vec![y_ct],
-                        &public_key,
+                        &evaluation_keys,
                     )
                     .unwrap()
                     .remove(0);
@@ -583,15 +775,15 @@ mod linked_tests {
     }
 
     #[test]
-    fn compiler_enforces_moduli_pow_2() {
-        // try to compile zkp program with plain modulus 100
+    fn compiler_allows_arbitrary_moduli() {
+        // compile zkp program with plain modulus 100
         let res = Compiler::new()
             .fhe_program(doggie)
             .plain_modulus_constraint(PlainModulusConstraint::Raw(100))
             .zkp_backend::<BulletproofsBackend>()
             .zkp_program(is_eq_signed)
             .compile();
-        assert!(matches!(res, Err(sunscreen::Error::Unsupported { .. })));
+        assert!(res.is_ok());
     }
 
     #[test]
@@ -0,0 +1,139 @@
+use seal_fhe::{CoefficientModulus, SecurityLevel};
+use sunscreen::{
+    fhe_program,
+    types::{bfv::Signed, Cipher},
+    Compiler, FheProgramInput, Params, PlainModulusConstraint, Runtime, SchemeType,
+};
+use sunscreen_fhe_program::Operation;
+
+#[fhe_program(scheme = "bfv")]
+fn add(a: Cipher<Signed>, b: Cipher<Signed>) -> Cipher<Signed> {
+    a + b
+}
+
+#[test]
+fn mod_switching_shrinks_outputs() {
+    let run = |mod_switch_outputs| {
+        let app = Compiler::new()
+            .fhe_program(add)
+            .plain_modulus_constraint(PlainModulusConstraint::Raw(500))
+            .mod_switch_outputs(mod_switch_outputs)
+            .compile()
+            .unwrap();
+
+        let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+        let (public_key, private_key) = runtime.generate_keys().unwrap();
+        let evaluation_keys = runtime
+            .generate_evaluation_keys(&private_key, [app.get_fhe_program(add).unwrap()])
+            .unwrap();
+
+        let a_c = runtime.encrypt(Signed::from(15), &public_key).unwrap();
+        let b_c = runtime.encrypt(Signed::from(-5), &public_key).unwrap();
+
+        let args: Vec<FheProgramInput> = vec![a_c.into(), b_c.into()];
+
+        let result = runtime
+            .run(app.get_fhe_program(add).unwrap(), args, &evaluation_keys)
+            .unwrap();
+
+        let c: Signed = runtime.decrypt(&result[0], &private_key).unwrap();
+
+        assert_eq!(c, Signed::from(10));
+
+        bincode::serialize(&result[0]).unwrap().len()
+    };
+
+    let full_size = run(false);
+    let switched_size = run(true);
+
+    assert!(switched_size < full_size);
+}
+
+#[test]
+fn mod_switching_skips_chained_programs() {
+    #[fhe_program(scheme = "bfv", chain_count = 2)]
+    fn chained_add(a: Cipher<Signed>, b: Cipher<Signed>) -> Cipher<Signed> {
+        a + b
+    }
+
+    let app = Compiler::new()
+        .fhe_program(chained_add)
+        .plain_modulus_constraint(PlainModulusConstraint::Raw(500))
+        .mod_switch_outputs(true)
+        .compile()
+        .unwrap();
+
+    let program = app.get_fhe_program(chained_add).unwrap();
+
+    assert!(program
+        .fhe_program_fn
+        .graph
+        .node_weights()
+        .all(|n| !matches!(n.operation, Operation::ModSwitch)));
+}
+
+#[test]
+fn mod_switches_move_before_multiplications() {
+    #[fhe_program(scheme = "bfv")]
+    fn multiply(a: Cipher<Signed>, b: Cipher<Signed>) -> Cipher<Signed> {
+        a * b
+    }
+
+    // These parameters have far more noise budget than a single multiplication
+    // needs, so the multiplication itself can run under a smaller modulus.
+    let params = Params {
+        lattice_dimension: 8192,
+        plain_modulus: 500,
+        coeff_modulus: CoefficientModulus::bfv_default(8192, SecurityLevel::TC128)
+            .unwrap()
+            .iter()
+            .map(|x| x.value())
+            .collect(),
+        scheme_type: SchemeType::Bfv,
+        security_level: SecurityLevel::TC128,
+    };
+
+    let app = Compiler::new()
+        .fhe_program(multiply)
+        .with_params(&params)
+        .mod_switch_outputs(true)
+        .compile()
+        .unwrap();
+
+    let program = app.get_fhe_program(multiply).unwrap();
+    let graph = &program.fhe_program_fn.graph;
+
+    let switched_multiply = graph.node_indices().any(|id| {
+        matches!(graph[id].operation, Operation::Multiply)
+            && graph
+                .neighbors_directed(id, petgraph::Direction::Incoming)
+                .all(|p| matches!(graph[p].operation, Operation::ModSwitch))
+    });
+
+    assert!(switched_multiply);
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [program])
+        .unwrap();
+
+    let a_c = runtime.encrypt(Signed::from(15), &public_key).unwrap();
+    let b_c = runtime.encrypt(Signed::from(-5), &public_key).unwrap();
+
+    let result = runtime
+        .run(program, vec![a_c, b_c], &evaluation_keys)
+        .unwrap();
+
+    let c: Signed = runtime.decrypt(&result[0], &private_key).unwrap();
+
+    assert_eq!(c, Signed::from(-75));
+    assert!(
+        runtime
+            .measure_noise_budget(&result[0], &private_key)
+            .unwrap()
+            >= 20
+    );
+}
@@ -0,0 +1,96 @@
+use sunscreen::{
+    fhe_program,
+    types::{bfv::Signed, zkp::Field, Cipher},
+    zkp_program, zkp_var, Compiler, FheProgramInput, OptimizationLevel, PlainModulusConstraint,
+    Runtime,
+};
+use sunscreen_zkp_backend::{
+    bulletproofs::BulletproofsBackend, FieldSpec, Operation as JitOperation, ZkpBackend,
+};
+
+type BPField = Field<<BulletproofsBackend as ZkpBackend>::Field>;
+
+#[fhe_program(scheme = "bfv")]
+fn redundant(a: Cipher<Signed>, b: Cipher<Signed>) -> Cipher<Signed> {
+    -(-(a * 1 + 0)) + a * b + a * b
+}
+
+#[test]
+fn optimizations_shrink_fhe_programs() {
+    let run = |level| {
+        let app = Compiler::new()
+            .fhe_program(redundant)
+            .plain_modulus_constraint(PlainModulusConstraint::Raw(500))
+            .optimization_level(level)
+            .compile()
+            .unwrap();
+
+        let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+        let (public_key, private_key) = runtime.generate_keys().unwrap();
+
+        let a_c = runtime.encrypt(Signed::from(3), &public_key).unwrap();
+        let b_c = runtime.encrypt(Signed::from(-5), &public_key).unwrap();
+
+        let args: Vec<FheProgramInput> = vec![a_c.into(), b_c.into()];
+
+        let program = app.get_fhe_program(redundant).unwrap();
+        let evaluation_keys = runtime
+            .generate_evaluation_keys(&private_key, [program])
+            .unwrap();
+
+        let result = runtime.run(program, args, &evaluation_keys).unwrap();
+
+        let c: Signed = runtime.decrypt(&result[0], &private_key).unwrap();
+
+        assert_eq!(c, Signed::from(-27));
+
+        program.fhe_program_fn.graph.node_count()
+    };
+
+    let unoptimized = run(OptimizationLevel::None);
+    let basic = run(OptimizationLevel::Basic);
+    let full = run(OptimizationLevel::Full);
+
+    assert!(basic < unoptimized);
+    assert!(full < basic);
+}
+
+#[test]
+fn optimizations_remove_zkp_identities() {
+    #[zkp_program]
+    fn identities<F: FieldSpec>(a: Field<F>, #[public] b: Field<F>) {
+        let x = -(-(a * zkp_var!(1) + zkp_var!(0)));
+
+        x.constrain_eq(b)
+    }
+
+    let app = Compiler::new()
+        .zkp_backend::<BulletproofsBackend>()
+        .zkp_program(identities)
+        .optimization_level(OptimizationLevel::Full)
+        .compile()
+        .unwrap();
+
+    let program = app.get_zkp_program(identities).unwrap();
+
+    assert!(program
+        .zkp_program_fn
+        .node_weights()
+        .all(|n| !matches!(n.operation, JitOperation::Mul | JitOperation::Add)));
+
+    let runtime = Runtime::new_zkp(BulletproofsBackend::new()).unwrap();
+
+    let proof = runtime
+        .prove(
+            program,
+            vec![BPField::from(7u8)],
+            vec![BPField::from(7u8)],
+            vec![],
+        )
+        .unwrap();
+
+    runtime
+        .verify(program, &proof, vec![BPField::from(7u8)], vec![])
+        .unwrap();
+}
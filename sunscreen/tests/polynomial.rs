@@ -0,0 +1,145 @@
+use sunscreen::{
+    fhe_program,
+    types::{
+        bfv::{Batched, Signed},
+        Cipher,
+    },
+    Compiler, FheProgramInput, PlainModulusConstraint, Runtime,
+};
+use sunscreen_fhe_program::FheProgramTrait;
+
+#[test]
+fn can_raise_to_power() {
+    #[fhe_program(scheme = "bfv")]
+    fn pow(a: Cipher<Signed>) -> Cipher<Signed> {
+        a.pow(5)
+    }
+
+    let app = Compiler::new()
+        .fhe_program(pow)
+        .plain_modulus_constraint(PlainModulusConstraint::Raw(500))
+        .compile()
+        .unwrap();
+
+    let program = app.get_fhe_program(pow).unwrap();
+
+    assert_eq!(program.fhe_program_fn.multiplicative_depth(), 3);
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [program])
+        .unwrap();
+
+    let a_c = runtime.encrypt(Signed::from(3), &public_key).unwrap();
+
+    let result = runtime.run(program, vec![a_c], &evaluation_keys).unwrap();
+
+    let c: Signed = runtime.decrypt(&result[0], &private_key).unwrap();
+
+    assert_eq!(c, Signed::from(243));
+}
+
+#[test]
+fn can_evaluate_polynomial() {
+    #[fhe_program(scheme = "bfv")]
+    fn poly(a: Cipher<Signed>) -> Cipher<Signed> {
+        // 3 - 2x + x^3 + x^7
+        a.eval_poly(&[3, -2, 0, 1, 0, 0, 0, 1])
+    }
+
+    let app = Compiler::new()
+        .fhe_program(poly)
+        .plain_modulus_constraint(PlainModulusConstraint::Raw(500))
+        .compile()
+        .unwrap();
+
+    let program = app.get_fhe_program(poly).unwrap();
+
+    assert_eq!(program.fhe_program_fn.multiplicative_depth(), 3);
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [program])
+        .unwrap();
+
+    let a_c = runtime.encrypt(Signed::from(2), &public_key).unwrap();
+
+    let result = runtime.run(program, vec![a_c], &evaluation_keys).unwrap();
+
+    let c: Signed = runtime.decrypt(&result[0], &private_key).unwrap();
+
+    assert_eq!(c, Signed::from(135));
+}
+
+#[test]
+fn can_evaluate_polynomial_on_lanes() {
+    #[fhe_program(scheme = "bfv")]
+    fn poly(a: Cipher<Batched<4>>) -> Cipher<Batched<4>> {
+        // 1 + x + x^2 + ... + x^16
+        a.eval_poly(&[1; 17])
+    }
+
+    let app = Compiler::new()
+        .fhe_program(poly)
+        .plain_modulus_constraint(PlainModulusConstraint::BatchingMinimum(0))
+        .compile()
+        .unwrap();
+
+    let program = app.get_fhe_program(poly).unwrap();
+
+    assert_eq!(program.fhe_program_fn.multiplicative_depth(), 4);
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [program])
+        .unwrap();
+
+    let data = [[0, 1, -1, 1], [1, 0, -1, -1]];
+    let expected = data.map(|row| row.map(|x: i64| (0..17).map(|i| x.pow(i)).sum::<i64>()));
+
+    let a_c = runtime
+        .encrypt(Batched::<4>::from(data), &public_key)
+        .unwrap();
+
+    let args: Vec<FheProgramInput> = vec![a_c.into()];
+
+    let result = runtime.run(program, args, &evaluation_keys).unwrap();
+
+    let c: Batched<4> = runtime.decrypt(&result[0], &private_key).unwrap();
+
+    assert_eq!(c, Batched::<4>::from(expected));
+}
+
+#[test]
+#[should_panic(expected = "Can't evaluate a constant polynomial on a ciphertext")]
+fn rejects_constant_polynomial() {
+    #[fhe_program(scheme = "bfv")]
+    fn poly(a: Cipher<Signed>) -> Cipher<Signed> {
+        a.eval_poly(&[3])
+    }
+
+    let _ = Compiler::new()
+        .fhe_program(poly)
+        .plain_modulus_constraint(PlainModulusConstraint::Raw(500))
+        .compile();
+}
+
+#[test]
+#[should_panic(expected = "Can't evaluate a constant polynomial on a ciphertext")]
+fn rejects_polynomial_with_only_zero_higher_terms() {
+    #[fhe_program(scheme = "bfv")]
+    fn poly(a: Cipher<Signed>) -> Cipher<Signed> {
+        a.eval_poly(&[3, 0, 0])
+    }
+
+    let _ = Compiler::new()
+        .fhe_program(poly)
+        .plain_modulus_constraint(PlainModulusConstraint::Raw(500))
+        .compile();
+}
@@ -25,13 +25,20 @@ fn can_encode_rational_numbers() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(no_op).unwrap()])
+        .unwrap();
 
     let a = runtime
         .encrypt(Rational::try_from(-3.14).unwrap(), &public_key)
         .unwrap();
 
     let result = runtime
-        .run(app.get_fhe_program(no_op).unwrap(), vec![a], &public_key)
+        .run(
+            app.get_fhe_program(no_op).unwrap(),
+            vec![a],
+            &evaluation_keys,
+        )
         .unwrap();
 
     let c: Rational = runtime.decrypt(&result[0], &private_key).unwrap();
@@ -65,6 +72,9 @@ fn can_add_cipher_cipher() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(add).unwrap()])
+        .unwrap();
 
     let a = Rational::try_from(-3.14).unwrap();
     let a_c = runtime.encrypt(a, &public_key).unwrap();
@@ -75,7 +85,7 @@ fn can_add_cipher_cipher() {
         .run(
             app.get_fhe_program(add).unwrap(),
             vec![a_c, b_c],
-            &public_key,
+            &evaluation_keys,
         )
         .unwrap();
 
@@ -101,6 +111,9 @@ fn can_add_cipher_plain() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(add).unwrap()])
+        .unwrap();
 
     let a = Rational::try_from(-3.14).unwrap();
     let a_c = runtime.encrypt(a, &public_key).unwrap();
@@ -109,7 +122,7 @@ fn can_add_cipher_plain() {
     let args: Vec<FheProgramInput> = vec![a_c.into(), b.into()];
 
     let result = runtime
-        .run(app.get_fhe_program(add).unwrap(), args, &public_key)
+        .run(app.get_fhe_program(add).unwrap(), args, &evaluation_keys)
         .unwrap();
 
     let c: Rational = runtime.decrypt(&result[0], &private_key).unwrap();
@@ -134,6 +147,9 @@ fn can_add_plain_cipher() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(add).unwrap()])
+        .unwrap();
 
     let a = Rational::try_from(-3.14).unwrap();
     let a_c = runtime.encrypt(a, &public_key).unwrap();
@@ -142,7 +158,7 @@ fn can_add_plain_cipher() {
     let args: Vec<FheProgramInput> = vec![a_c.into(), b.into()];
 
     let result = runtime
-        .run(app.get_fhe_program(add).unwrap(), args, &public_key)
+        .run(app.get_fhe_program(add).unwrap(), args, &evaluation_keys)
         .unwrap();
 
     let c: Rational = runtime.decrypt(&result[0], &private_key).unwrap();
@@ -167,6 +183,9 @@ fn can_add_cipher_literal() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(add).unwrap()])
+        .unwrap();
 
     let a = Rational::try_from(-6.28).unwrap();
     let a_c = runtime.encrypt(a, &public_key).unwrap();
@@ -174,7 +193,7 @@ fn can_add_cipher_literal() {
     let args: Vec<FheProgramInput> = vec![a_c.into()];
 
     let result = runtime
-        .run(app.get_fhe_program(add).unwrap(), args, &public_key)
+        .run(app.get_fhe_program(add).unwrap(), args, &evaluation_keys)
         .unwrap();
 
     let c: Rational = runtime.decrypt(&result[0], &private_key).unwrap();
@@ -199,6 +218,9 @@ fn can_add_literal_cipher() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(add).unwrap()])
+        .unwrap();
 
     let a = Rational::try_from(-6.28).unwrap();
     let a_c = runtime.encrypt(a, &public_key).unwrap();
@@ -206,7 +228,7 @@ fn can_add_literal_cipher() {
     let args: Vec<FheProgramInput> = vec![a_c.into()];
 
     let result = runtime
-        .run(app.get_fhe_program(add).unwrap(), args, &public_key)
+        .run(app.get_fhe_program(add).unwrap(), args, &evaluation_keys)
         .unwrap();
 
     let c: Rational = runtime.decrypt(&result[0], &private_key).unwrap();
@@ -238,6 +260,9 @@ fn can_sub_cipher_cipher() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(sub).unwrap()])
+        .unwrap();
 
     let a = Rational::try_from(-3.14).unwrap();
     let a_c = runtime.encrypt(a, &public_key).unwrap();
@@ -248,7 +273,7 @@ fn can_sub_cipher_cipher() {
         .run(
             app.get_fhe_program(sub).unwrap(),
             vec![a_c, b_c],
-            &public_key,
+            &evaluation_keys,
         )
         .unwrap();
 
@@ -274,6 +299,9 @@ fn can_sub_cipher_plain() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(sub).unwrap()])
+        .unwrap();
 
     let a = Rational::try_from(-3.14).unwrap();
     let a_c = runtime.encrypt(a, &public_key).unwrap();
@@ -282,7 +310,7 @@ fn can_sub_cipher_plain() {
     let args: Vec<FheProgramInput> = vec![a_c.into(), b.into()];
 
     let result = runtime
-        .run(app.get_fhe_program(sub).unwrap(), args, &public_key)
+        .run(app.get_fhe_program(sub).unwrap(), args, &evaluation_keys)
         .unwrap();
 
     let c: Rational = runtime.decrypt(&result[0], &private_key).unwrap();
@@ -307,6 +335,9 @@ fn can_sub_plain_cipher() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(sub).unwrap()])
+        .unwrap();
 
     let a = Rational::try_from(-3.14).unwrap();
 
@@ -316,7 +347,7 @@ fn can_sub_plain_cipher() {
     let args: Vec<FheProgramInput> = vec![a.into(), b_c.into()];
 
     let result = runtime
-        .run(app.get_fhe_program(sub).unwrap(), args, &public_key)
+        .run(app.get_fhe_program(sub).unwrap(), args, &evaluation_keys)
         .unwrap();
 
     let c: Rational = runtime.decrypt(&result[0], &private_key).unwrap();
@@ -341,6 +372,9 @@ fn can_sub_cipher_literal() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(sub).unwrap()])
+        .unwrap();
 
     let a = Rational::try_from(-6.28).unwrap();
     let a_c = runtime.encrypt(a, &public_key).unwrap();
@@ -348,7 +382,7 @@ fn can_sub_cipher_literal() {
     let args: Vec<FheProgramInput> = vec![a_c.into()];
 
     let result = runtime
-        .run(app.get_fhe_program(sub).unwrap(), args, &public_key)
+        .run(app.get_fhe_program(sub).unwrap(), args, &evaluation_keys)
         .unwrap();
 
     let c: Rational = runtime.decrypt(&result[0], &private_key).unwrap();
@@ -373,6 +407,9 @@ fn can_sub_literal_cipher() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(sub).unwrap()])
+        .unwrap();
 
     let a = Rational::try_from(-6.28).unwrap();
     let a_c = runtime.encrypt(a, &public_key).unwrap();
@@ -380,7 +417,7 @@ fn can_sub_literal_cipher() {
     let args: Vec<FheProgramInput> = vec![a_c.into()];
 
     let result = runtime
-        .run(app.get_fhe_program(sub).unwrap(), args, &public_key)
+        .run(app.get_fhe_program(sub).unwrap(), args, &evaluation_keys)
         .unwrap();
 
     let c: Rational = runtime.decrypt(&result[0], &private_key).unwrap();
@@ -412,6 +449,9 @@ fn can_mul_cipher_cipher() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(mul).unwrap()])
+        .unwrap();
 
     let a = Rational::try_from(-3.14).unwrap();
     let a_c = runtime.encrypt(a, &public_key).unwrap();
@@ -422,7 +462,7 @@ fn can_mul_cipher_cipher() {
         .run(
             app.get_fhe_program(mul).unwrap(),
             vec![a_c, b_c],
-            &public_key,
+            &evaluation_keys,
         )
         .unwrap();
 
@@ -448,6 +488,9 @@ fn can_mul_cipher_plain() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(mul).unwrap()])
+        .unwrap();
 
     let a = Rational::try_from(-3.14).unwrap();
     let a_c = runtime.encrypt(a, &public_key).unwrap();
@@ -456,7 +499,7 @@ fn can_mul_cipher_plain() {
     let args: Vec<FheProgramInput> = vec![a_c.into(), b.into()];
 
     let result = runtime
-        .run(app.get_fhe_program(mul).unwrap(), args, &public_key)
+        .run(app.get_fhe_program(mul).unwrap(), args, &evaluation_keys)
         .unwrap();
 
     let c: Rational = runtime.decrypt(&result[0], &private_key).unwrap();
@@ -481,6 +524,9 @@ fn can_mul_plain_cipher() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(mul).unwrap()])
+        .unwrap();
 
     let a = Rational::try_from(-3.14).unwrap();
 
@@ -490,7 +536,7 @@ fn can_mul_plain_cipher() {
     let args: Vec<FheProgramInput> = vec![a.into(), b_c.into()];
 
     let result = runtime
-        .run(app.get_fhe_program(mul).unwrap(), args, &public_key)
+        .run(app.get_fhe_program(mul).unwrap(), args, &evaluation_keys)
         .unwrap();
 
     let c: Rational = runtime.decrypt(&result[0], &private_key).unwrap();
@@ -515,6 +561,9 @@ fn can_mul_cipher_literal() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(mul).unwrap()])
+        .unwrap();
 
     let a = Rational::try_from(-6.28).unwrap();
     let a_c = runtime.encrypt(a, &public_key).unwrap();
@@ -522,7 +571,7 @@ fn can_mul_cipher_literal() {
     let args: Vec<FheProgramInput> = vec![a_c.into()];
 
     let result = runtime
-        .run(app.get_fhe_program(mul).unwrap(), args, &public_key)
+        .run(app.get_fhe_program(mul).unwrap(), args, &evaluation_keys)
         .unwrap();
 
     let c: Rational = runtime.decrypt(&result[0], &private_key).unwrap();
@@ -547,6 +596,9 @@ fn can_mul_literal_cipher() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(mul).unwrap()])
+        .unwrap();
 
     let a = Rational::try_from(-6.28).unwrap();
     let a_c = runtime.encrypt(a, &public_key).unwrap();
@@ -554,7 +606,7 @@ fn can_mul_literal_cipher() {
     let args: Vec<FheProgramInput> = vec![a_c.into()];
 
     let result = runtime
-        .run(app.get_fhe_program(mul).unwrap(), args, &public_key)
+        .run(app.get_fhe_program(mul).unwrap(), args, &evaluation_keys)
         .unwrap();
 
     let c: Rational = runtime.decrypt(&result[0], &private_key).unwrap();
@@ -586,6 +638,9 @@ fn can_div_cipher_cipher() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(div).unwrap()])
+        .unwrap();
 
     let a = Rational::try_from(-3.14).unwrap();
     let a_c = runtime.encrypt(a, &public_key).unwrap();
@@ -596,7 +651,7 @@ fn can_div_cipher_cipher() {
         .run(
             app.get_fhe_program(div).unwrap(),
             vec![a_c, b_c],
-            &public_key,
+            &evaluation_keys,
         )
         .unwrap();
 
@@ -622,6 +677,9 @@ fn can_div_cipher_plain() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(div).unwrap()])
+        .unwrap();
 
     let a = Rational::try_from(-3.14).unwrap();
     let a_c = runtime.encrypt(a, &public_key).unwrap();
@@ -630,7 +688,7 @@ fn can_div_cipher_plain() {
     let args: Vec<FheProgramInput> = vec![a_c.into(), b.into()];
 
     let result = runtime
-        .run(app.get_fhe_program(div).unwrap(), args, &public_key)
+        .run(app.get_fhe_program(div).unwrap(), args, &evaluation_keys)
         .unwrap();
 
     let c: Rational = runtime.decrypt(&result[0], &private_key).unwrap();
@@ -655,6 +713,9 @@ fn can_div_plain_cipher() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(div).unwrap()])
+        .unwrap();
 
     let a = Rational::try_from(-3.14).unwrap();
 
@@ -664,7 +725,7 @@ fn can_div_plain_cipher() {
     let args: Vec<FheProgramInput> = vec![a.into(), b_c.into()];
 
     let result = runtime
-        .run(app.get_fhe_program(div).unwrap(), args, &public_key)
+        .run(app.get_fhe_program(div).unwrap(), args, &evaluation_keys)
         .unwrap();
 
     let c: Rational = runtime.decrypt(&result[0], &private_key).unwrap();
@@ -689,6 +750,9 @@ fn can_div_cipher_literal() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(div).unwrap()])
+        .unwrap();
 
     let a = Rational::try_from(-6.28).unwrap();
     let a_c = runtime.encrypt(a, &public_key).unwrap();
@@ -696,7 +760,7 @@ fn can_div_cipher_literal() {
     let args: Vec<FheProgramInput> = vec![a_c.into()];
 
     let result = runtime
-        .run(app.get_fhe_program(div).unwrap(), args, &public_key)
+        .run(app.get_fhe_program(div).unwrap(), args, &evaluation_keys)
         .unwrap();
 
     let c: Rational = runtime.decrypt(&result[0], &private_key).unwrap();
@@ -721,6 +785,9 @@ fn can_div_literal_cipher() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(div).unwrap()])
+        .unwrap();
 
     let a = Rational::try_from(-6.28).unwrap();
     let a_c = runtime.encrypt(a, &public_key).unwrap();
@@ -728,7 +795,7 @@ fn can_div_literal_cipher() {
     let args: Vec<FheProgramInput> = vec![a_c.into()];
 
     let result = runtime
-        .run(app.get_fhe_program(div).unwrap(), args, &public_key)
+        .run(app.get_fhe_program(div).unwrap(), args, &evaluation_keys)
         .unwrap();
 
     let c: Rational = runtime.decrypt(&result[0], &private_key).unwrap();
@@ -760,6 +827,9 @@ fn can_neg_cipher() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(neg).unwrap()])
+        .unwrap();
 
     let a = Rational::try_from(-6.28).unwrap();
     let a_c = runtime.encrypt(a, &public_key).unwrap();
@@ -767,7 +837,7 @@ fn can_neg_cipher() {
     let args: Vec<FheProgramInput> = vec![a_c.into()];
 
     let result = runtime
-        .run(app.get_fhe_program(neg).unwrap(), args, &public_key)
+        .run(app.get_fhe_program(neg).unwrap(), args, &evaluation_keys)
         .unwrap();
 
     let c: Rational = runtime.decrypt(&result[0], &private_key).unwrap();
@@ -0,0 +1,123 @@
+use sunscreen::{
+    fhe_program,
+    types::{bfv::Signed, Cipher},
+    Compiler, FheProgramInput, PlainModulusConstraint, Runtime,
+};
+use sunscreen_fhe_program::{FheProgramTrait, Operation};
+
+#[fhe_program(scheme = "bfv")]
+fn product(
+    a: Cipher<Signed>,
+    b: Cipher<Signed>,
+    c: Cipher<Signed>,
+    d: Cipher<Signed>,
+    e: Cipher<Signed>,
+    f: Cipher<Signed>,
+) -> Cipher<Signed> {
+    a * b * c * d * e * f
+}
+
+#[test]
+fn multiplication_chains_get_balanced() {
+    let app = Compiler::new()
+        .fhe_program(product)
+        .plain_modulus_constraint(PlainModulusConstraint::Raw(500))
+        .compile()
+        .unwrap();
+
+    let program = app.get_fhe_program(product).unwrap();
+
+    // Written left-deep, this chain would have depth 5.
+    assert_eq!(program.fhe_program_fn.multiplicative_depth(), 3);
+
+    // The whole chain runs as one operation that relinearizes as it goes.
+    let ops = program
+        .fhe_program_fn
+        .graph
+        .node_weights()
+        .map(|n| n.operation.clone())
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        ops.iter()
+            .filter(|op| matches!(op, Operation::MultiplyMany))
+            .count(),
+        1
+    );
+    assert!(ops
+        .iter()
+        .all(|op| !matches!(op, Operation::Multiply | Operation::Relinearize)));
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [program])
+        .unwrap();
+
+    let args = [2i64, -1, 3, 1, -2, 2]
+        .iter()
+        .map(|x| {
+            runtime
+                .encrypt(Signed::from(*x), &public_key)
+                .unwrap()
+                .into()
+        })
+        .collect::<Vec<FheProgramInput>>();
+
+    let result = runtime.run(program, args, &evaluation_keys).unwrap();
+
+    let c: Signed = runtime.decrypt(&result[0], &private_key).unwrap();
+
+    assert_eq!(c, Signed::from(24));
+}
+
+#[fhe_program(scheme = "bfv")]
+fn sum(
+    a: Cipher<Signed>,
+    b: Cipher<Signed>,
+    c: Cipher<Signed>,
+    d: Cipher<Signed>,
+) -> Cipher<Signed> {
+    a * b + c + d + a
+}
+
+#[test]
+fn addition_chains_get_batched() {
+    let app = Compiler::new()
+        .fhe_program(sum)
+        .plain_modulus_constraint(PlainModulusConstraint::Raw(500))
+        .compile()
+        .unwrap();
+
+    let program = app.get_fhe_program(sum).unwrap();
+
+    assert!(program
+        .fhe_program_fn
+        .graph
+        .node_weights()
+        .all(|n| !matches!(n.operation, Operation::Add)));
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [program])
+        .unwrap();
+
+    let args = [3i64, -4, 5, 7]
+        .iter()
+        .map(|x| {
+            runtime
+                .encrypt(Signed::from(*x), &public_key)
+                .unwrap()
+                .into()
+        })
+        .collect::<Vec<FheProgramInput>>();
+
+    let result = runtime.run(program, args, &evaluation_keys).unwrap();
+
+    let c: Signed = runtime.decrypt(&result[0], &private_key).unwrap();
+
+    assert_eq!(c, Signed::from(3 * -4 + 5 + 7 + 3));
+}
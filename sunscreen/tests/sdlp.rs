@@ -95,8 +95,11 @@ mod sdlp_tests {
             x + x
         }
         let double_compiled = double.compile().unwrap();
+        let evaluation_keys = rt
+            .generate_evaluation_keys(&private_key, [&double_compiled])
+            .unwrap();
         let computed_ct = rt
-            .run(&double_compiled, vec![initial_ct], &public_key)
+            .run(&double_compiled, vec![initial_ct], &evaluation_keys)
             .unwrap()
             .remove(0);
 
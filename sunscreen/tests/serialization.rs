@@ -1,7 +1,14 @@
 use seal_fhe::{CoefficientModulus, SecurityLevel};
-use sunscreen::types::bfv::Signed;
+use sunscreen::{
+    fhe_program,
+    types::{bfv::Signed, Cipher},
+    Compiler,
+};
 use sunscreen_fhe_program::SchemeType;
-use sunscreen_runtime::{Ciphertext, Params, Runtime};
+use sunscreen_runtime::{
+    Ciphertext, CompactOptions, CompactSerialize, Error as RuntimeError, EvaluationKeys, Params,
+    PublicKey, Runtime, WireFormatError,
+};
 
 #[test]
 fn can_roundtrip_ciphertexts_bincode() {
@@ -62,3 +69,175 @@ fn can_roundtrip_ciphertexts_json() {
     let actual: i64 = v.into();
     assert_eq!(actual, expected);
 }
+
+#[test]
+fn can_roundtrip_ciphertexts_compact() {
+    let runtime = Runtime::new_fhe(&Params {
+        lattice_dimension: 8192,
+        plain_modulus: 1024,
+        coeff_modulus: CoefficientModulus::bfv_default(8192, SecurityLevel::TC128)
+            .unwrap()
+            .iter()
+            .map(|c| c.value())
+            .collect(),
+        security_level: SecurityLevel::TC128,
+        scheme_type: SchemeType::Bfv,
+    })
+    .unwrap();
+
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+
+    let expected = [1i64, -2, 3, -4, 5, -6, 7, -8];
+
+    let c = runtime
+        .encrypt(expected.map(Signed::from), &public_key)
+        .unwrap();
+
+    // Params are stored once rather than once per ciphertext.
+    let compact = c.to_compact_bytes(&CompactOptions::default()).unwrap();
+    assert!(compact.len() < bincode::serialize(&c).unwrap().len());
+
+    let embedded = Ciphertext::from_compact_bytes(&compact).unwrap();
+
+    let digest_only = c
+        .to_compact_bytes(&CompactOptions {
+            compress: false,
+            embed_params: false,
+        })
+        .unwrap();
+    let digest_only =
+        Ciphertext::from_compact_bytes_with_params(&digest_only, runtime.params()).unwrap();
+
+    for c in [embedded, digest_only] {
+        let v: [Signed; 8] = runtime.decrypt(&c, &private_key).unwrap();
+        assert_eq!(v.map(i64::from), expected);
+    }
+}
+
+#[test]
+fn compact_ciphertexts_reject_truncation() {
+    let runtime = Runtime::new_fhe(&Params {
+        lattice_dimension: 4096,
+        plain_modulus: 1024,
+        coeff_modulus: CoefficientModulus::bfv_default(4096, SecurityLevel::TC128)
+            .unwrap()
+            .iter()
+            .map(|c| c.value())
+            .collect(),
+        security_level: SecurityLevel::TC128,
+        scheme_type: SchemeType::Bfv,
+    })
+    .unwrap();
+
+    let (public_key, _) = runtime.generate_keys().unwrap();
+
+    let c = runtime.encrypt(Signed::from(42), &public_key).unwrap();
+    let bytes = c.to_compact_bytes(&CompactOptions::default()).unwrap();
+
+    let res = Ciphertext::from_compact_bytes(&bytes[..bytes.len() - 1]);
+    assert!(matches!(
+        res,
+        Err(RuntimeError::WireFormatError(e)) if matches!(*e, WireFormatError::Truncated { .. })
+    ));
+}
+
+#[test]
+fn seeded_ciphertexts_expand_on_deserialization() {
+    let runtime = Runtime::new_fhe(&Params {
+        lattice_dimension: 4096,
+        plain_modulus: 1024,
+        coeff_modulus: CoefficientModulus::bfv_default(4096, SecurityLevel::TC128)
+            .unwrap()
+            .iter()
+            .map(|c| c.value())
+            .collect(),
+        security_level: SecurityLevel::TC128,
+        scheme_type: SchemeType::Bfv,
+    })
+    .unwrap();
+
+    let (_, private_key) = runtime.generate_keys().unwrap();
+
+    let full = runtime
+        .encrypt_symmetric(Signed::from(42), &private_key)
+        .unwrap();
+    let seeded = runtime
+        .encrypt_symmetric_seeded(Signed::from(42), &private_key)
+        .unwrap();
+
+    let full_bytes = bincode::serialize(&full).unwrap();
+    let seeded_bytes = bincode::serialize(&seeded).unwrap();
+    assert!(seeded_bytes.len() * 3 < full_bytes.len() * 2);
+
+    let c: Ciphertext = bincode::deserialize(&seeded_bytes).unwrap();
+    let v: Signed = runtime.decrypt(&c, &private_key).unwrap();
+    assert_eq!(i64::from(v), 42);
+
+    let v: Signed = runtime
+        .decrypt(&seeded.expand().unwrap(), &private_key)
+        .unwrap();
+    assert_eq!(i64::from(v), 42);
+}
+
+#[test]
+fn compact_keys_expand_on_deserialization() {
+    let runtime = Runtime::new_fhe(&Params {
+        lattice_dimension: 4096,
+        plain_modulus: 1024,
+        coeff_modulus: CoefficientModulus::bfv_default(4096, SecurityLevel::TC128)
+            .unwrap()
+            .iter()
+            .map(|c| c.value())
+            .collect(),
+        security_level: SecurityLevel::TC128,
+        scheme_type: SchemeType::Bfv,
+    })
+    .unwrap();
+
+    let (full_key, _) = runtime.generate_keys().unwrap();
+    let (seeded_key, private_key) = runtime.generate_keys_compact().unwrap();
+
+    let full_bytes = serde_json::to_string(&full_key).unwrap();
+    let seeded_bytes = serde_json::to_string(&seeded_key).unwrap();
+    assert!(seeded_bytes.len() * 3 < full_bytes.len() * 2);
+
+    let public_key: PublicKey = serde_json::from_str(&seeded_bytes).unwrap();
+
+    let c = runtime.encrypt(Signed::from(-7), &public_key).unwrap();
+    let v: Signed = runtime.decrypt(&c, &private_key).unwrap();
+    assert_eq!(i64::from(v), -7);
+}
+
+#[test]
+fn compact_evaluation_keys_expand_on_deserialization() {
+    #[fhe_program(scheme = "bfv")]
+    fn square(a: Cipher<Signed>) -> Cipher<Signed> {
+        a * a
+    }
+
+    let app = Compiler::new().fhe_program(square).compile().unwrap();
+    let program = app.get_fhe_program(square).unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let full_keys = runtime
+        .generate_evaluation_keys(&private_key, [program])
+        .unwrap();
+    let seeded_keys = runtime
+        .generate_evaluation_keys_compact(&private_key, [program])
+        .unwrap();
+
+    let full_bytes = bincode::serialize(&full_keys).unwrap();
+    let seeded_bytes = bincode::serialize(&seeded_keys).unwrap();
+    assert!(seeded_bytes.len() * 3 < full_bytes.len() * 2);
+
+    let evaluation_keys: EvaluationKeys = bincode::deserialize(&seeded_bytes).unwrap();
+    assert!(evaluation_keys.relin_key.is_some());
+    assert!(evaluation_keys.galois_key.is_none());
+
+    let a = runtime.encrypt(Signed::from(-7), &public_key).unwrap();
+    let result = runtime.run(program, vec![a], &evaluation_keys).unwrap();
+    let v: Signed = runtime.decrypt(&result[0], &private_key).unwrap();
+    assert_eq!(i64::from(v), 49);
+}
@@ -30,6 +30,9 @@ fn can_add_cipher_cipher() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(add).unwrap()])
+        .unwrap();
 
     let a = Signed::from(15);
     let a_c = runtime.encrypt(a, &public_key).unwrap();
@@ -39,7 +42,7 @@ fn can_add_cipher_cipher() {
     let args: Vec<FheProgramInput> = vec![a_c.into(), b_c.into()];
 
     let result = runtime
-        .run(app.get_fhe_program(add).unwrap(), args, &public_key)
+        .run(app.get_fhe_program(add).unwrap(), args, &evaluation_keys)
         .unwrap();
 
     let c: Signed = runtime.decrypt(&result[0], &private_key).unwrap();
@@ -64,6 +67,9 @@ fn can_add_cipher_plain() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(add).unwrap()])
+        .unwrap();
 
     let a = Signed::from(15);
     let a_c = runtime.encrypt(a, &public_key).unwrap();
@@ -72,7 +78,7 @@ fn can_add_cipher_plain() {
     let args: Vec<FheProgramInput> = vec![a_c.into(), b.into()];
 
     let result = runtime
-        .run(app.get_fhe_program(add).unwrap(), args, &public_key)
+        .run(app.get_fhe_program(add).unwrap(), args, &evaluation_keys)
         .unwrap();
 
     let c: Signed = runtime.decrypt(&result[0], &private_key).unwrap();
@@ -97,6 +103,9 @@ fn can_add_plain_cipher() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(add).unwrap()])
+        .unwrap();
 
     let a = Signed::from(-5);
     let b = Signed::from(15);
@@ -105,7 +114,7 @@ fn can_add_plain_cipher() {
     let args: Vec<FheProgramInput> = vec![a.into(), b_c.into()];
 
     let result = runtime
-        .run(app.get_fhe_program(add).unwrap(), args, &public_key)
+        .run(app.get_fhe_program(add).unwrap(), args, &evaluation_keys)
         .unwrap();
 
     let c: Signed = runtime.decrypt(&result[0], &private_key).unwrap();
@@ -130,13 +139,16 @@ fn can_add_cipher_literal() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(add).unwrap()])
+        .unwrap();
 
     let a = Signed::from(15);
     let a_c = runtime.encrypt(a, &public_key).unwrap();
     let args: Vec<FheProgramInput> = vec![a_c.into()];
 
     let result = runtime
-        .run(app.get_fhe_program(add).unwrap(), args, &public_key)
+        .run(app.get_fhe_program(add).unwrap(), args, &evaluation_keys)
         .unwrap();
 
     let c: Signed = runtime.decrypt(&result[0], &private_key).unwrap();
@@ -161,13 +173,16 @@ fn can_add_literal_cipher() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(add).unwrap()])
+        .unwrap();
 
     let a = Signed::from(15);
     let a_c = runtime.encrypt(a, &public_key).unwrap();
     let args: Vec<FheProgramInput> = vec![a_c.into()];
 
     let result = runtime
-        .run(app.get_fhe_program(add).unwrap(), args, &public_key)
+        .run(app.get_fhe_program(add).unwrap(), args, &evaluation_keys)
         .unwrap();
 
     let c: Signed = runtime.decrypt(&result[0], &private_key).unwrap();
@@ -199,6 +214,9 @@ fn can_sub_cipher_cipher() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(sub).unwrap()])
+        .unwrap();
 
     let a = Signed::from(15);
     let a_c = runtime.encrypt(a, &public_key).unwrap();
@@ -208,7 +226,7 @@ fn can_sub_cipher_cipher() {
     let args: Vec<FheProgramInput> = vec![a_c.into(), b_c.into()];
 
     let result = runtime
-        .run(app.get_fhe_program(sub).unwrap(), args, &public_key)
+        .run(app.get_fhe_program(sub).unwrap(), args, &evaluation_keys)
         .unwrap();
 
     let c: Signed = runtime.decrypt(&result[0], &private_key).unwrap();
@@ -233,6 +251,9 @@ fn can_sub_cipher_plain() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(sub).unwrap()])
+        .unwrap();
 
     let a = Signed::from(15);
     let a_c = runtime.encrypt(a, &public_key).unwrap();
@@ -241,7 +262,7 @@ fn can_sub_cipher_plain() {
     let args: Vec<FheProgramInput> = vec![a_c.into(), b.into()];
 
     let result = runtime
-        .run(app.get_fhe_program(sub).unwrap(), args, &public_key)
+        .run(app.get_fhe_program(sub).unwrap(), args, &evaluation_keys)
         .unwrap();
 
     let c: Signed = runtime.decrypt(&result[0], &private_key).unwrap();
@@ -266,6 +287,9 @@ fn can_sub_plain_cipher() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(sub).unwrap()])
+        .unwrap();
 
     let a = Signed::from(-5);
     let b = Signed::from(15);
@@ -274,7 +298,7 @@ fn can_sub_plain_cipher() {
     let args: Vec<FheProgramInput> = vec![a.into(), b_c.into()];
 
     let result = runtime
-        .run(app.get_fhe_program(sub).unwrap(), args, &public_key)
+        .run(app.get_fhe_program(sub).unwrap(), args, &evaluation_keys)
         .unwrap();
 
     let c: Signed = runtime.decrypt(&result[0], &private_key).unwrap();
@@ -299,13 +323,16 @@ fn can_sub_cipher_literal() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(sub).unwrap()])
+        .unwrap();
 
     let a = Signed::from(15);
     let a_c = runtime.encrypt(a, &public_key).unwrap();
     let args: Vec<FheProgramInput> = vec![a_c.into()];
 
     let result = runtime
-        .run(app.get_fhe_program(sub).unwrap(), args, &public_key)
+        .run(app.get_fhe_program(sub).unwrap(), args, &evaluation_keys)
         .unwrap();
 
     let c: Signed = runtime.decrypt(&result[0], &private_key).unwrap();
@@ -330,13 +357,16 @@ fn can_sub_literal_cipher() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(sub).unwrap()])
+        .unwrap();
 
     let a = Signed::from(15);
     let a_c = runtime.encrypt(a, &public_key).unwrap();
     let args: Vec<FheProgramInput> = vec![a_c.into()];
 
     let result = runtime
-        .run(app.get_fhe_program(sub).unwrap(), args, &public_key)
+        .run(app.get_fhe_program(sub).unwrap(), args, &evaluation_keys)
         .unwrap();
 
     let c: Signed = runtime.decrypt(&result[0], &private_key).unwrap();
@@ -368,6 +398,9 @@ fn can_mul_cipher_cipher() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(mul).unwrap()])
+        .unwrap();
 
     let a = Signed::from(15);
     let a_c = runtime.encrypt(a, &public_key).unwrap();
@@ -377,7 +410,7 @@ fn can_mul_cipher_cipher() {
     let args: Vec<FheProgramInput> = vec![a_c.into(), b_c.into()];
 
     let result = runtime
-        .run(app.get_fhe_program(mul).unwrap(), args, &public_key)
+        .run(app.get_fhe_program(mul).unwrap(), args, &evaluation_keys)
         .unwrap();
 
     let c: Signed = runtime.decrypt(&result[0], &private_key).unwrap();
@@ -402,6 +435,9 @@ fn can_mul_cipher_plain() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(mul).unwrap()])
+        .unwrap();
 
     let a = Signed::from(15);
     let a_c = runtime.encrypt(a, &public_key).unwrap();
@@ -410,7 +446,7 @@ fn can_mul_cipher_plain() {
     let args: Vec<FheProgramInput> = vec![a_c.into(), b.into()];
 
     let result = runtime
-        .run(app.get_fhe_program(mul).unwrap(), args, &public_key)
+        .run(app.get_fhe_program(mul).unwrap(), args, &evaluation_keys)
         .unwrap();
 
     let c: Signed = runtime.decrypt(&result[0], &private_key).unwrap();
@@ -435,6 +471,9 @@ fn can_mul_plain_cipher() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(mul).unwrap()])
+        .unwrap();
 
     let a = Signed::from(-5);
     let b = Signed::from(15);
@@ -443,7 +482,7 @@ fn can_mul_plain_cipher() {
     let args: Vec<FheProgramInput> = vec![a.into(), b_c.into()];
 
     let result = runtime
-        .run(app.get_fhe_program(mul).unwrap(), args, &public_key)
+        .run(app.get_fhe_program(mul).unwrap(), args, &evaluation_keys)
         .unwrap();
 
     let c: Signed = runtime.decrypt(&result[0], &private_key).unwrap();
@@ -468,13 +507,16 @@ fn can_mul_cipher_literal() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(mul).unwrap()])
+        .unwrap();
 
     let a = Signed::from(15);
     let a_c = runtime.encrypt(a, &public_key).unwrap();
     let args: Vec<FheProgramInput> = vec![a_c.into()];
 
     let result = runtime
-        .run(app.get_fhe_program(mul).unwrap(), args, &public_key)
+        .run(app.get_fhe_program(mul).unwrap(), args, &evaluation_keys)
         .unwrap();
 
     let c: Signed = runtime.decrypt(&result[0], &private_key).unwrap();
@@ -499,13 +541,16 @@ fn can_mul_literal_cipher() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(mul).unwrap()])
+        .unwrap();
 
     let a = Signed::from(15);
     let a_c = runtime.encrypt(a, &public_key).unwrap();
     let args: Vec<FheProgramInput> = vec![a_c.into()];
 
     let result = runtime
-        .run(app.get_fhe_program(mul).unwrap(), args, &public_key)
+        .run(app.get_fhe_program(mul).unwrap(), args, &evaluation_keys)
         .unwrap();
 
     let c: Signed = runtime.decrypt(&result[0], &private_key).unwrap();
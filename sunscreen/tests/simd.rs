@@ -30,6 +30,9 @@ fn can_swap_rows_cipher() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(swap_rows).unwrap()])
+        .unwrap();
 
     let data = [vec![1, 2, 3, 4], vec![5, 6, 7, 8]];
 
@@ -39,7 +42,11 @@ fn can_swap_rows_cipher() {
     let args: Vec<FheProgramInput> = vec![a_c.into()];
 
     let result = runtime
-        .run(app.get_fhe_program(swap_rows).unwrap(), args, &public_key)
+        .run(
+            app.get_fhe_program(swap_rows).unwrap(),
+            args,
+            &evaluation_keys,
+        )
         .unwrap();
 
     let c: Batched<4> = runtime.decrypt(&result[0], &private_key).unwrap();
@@ -74,6 +81,9 @@ fn can_rotate_left_cipher() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(add).unwrap()])
+        .unwrap();
 
     let data = [vec![1, 2, 3, 4], vec![5, 6, 7, 8]];
 
@@ -83,7 +93,7 @@ fn can_rotate_left_cipher() {
     let args: Vec<FheProgramInput> = vec![a_c.into()];
 
     let result = runtime
-        .run(app.get_fhe_program(add).unwrap(), args, &public_key)
+        .run(app.get_fhe_program(add).unwrap(), args, &evaluation_keys)
         .unwrap();
 
     let c: Batched<4> = runtime.decrypt(&result[0], &private_key).unwrap();
@@ -115,6 +125,9 @@ fn can_rotate_right_cipher() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(add).unwrap()])
+        .unwrap();
 
     let data = [vec![1, 2, 3, 4], vec![5, 6, 7, 8]];
 
@@ -124,7 +137,7 @@ fn can_rotate_right_cipher() {
     let args: Vec<FheProgramInput> = vec![a_c.into()];
 
     let result = runtime
-        .run(app.get_fhe_program(add).unwrap(), args, &public_key)
+        .run(app.get_fhe_program(add).unwrap(), args, &evaluation_keys)
         .unwrap();
 
     let c: Batched<4> = runtime.decrypt(&result[0], &private_key).unwrap();
@@ -156,6 +169,9 @@ fn can_add_cipher_cipher() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(add).unwrap()])
+        .unwrap();
 
     let data = [vec![1, 2, 3, 4], vec![5, 6, 7, 8]];
 
@@ -167,7 +183,7 @@ fn can_add_cipher_cipher() {
     let args: Vec<FheProgramInput> = vec![a_c.into(), b_c.into()];
 
     let result = runtime
-        .run(app.get_fhe_program(add).unwrap(), args, &public_key)
+        .run(app.get_fhe_program(add).unwrap(), args, &evaluation_keys)
         .unwrap();
 
     let c: Batched<4> = runtime.decrypt(&result[0], &private_key).unwrap();
@@ -199,6 +215,9 @@ fn can_sub_cipher_cipher() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(sub).unwrap()])
+        .unwrap();
 
     let data = [vec![1, 2, 3, 4], vec![5, 6, 7, 8]];
 
@@ -210,7 +229,7 @@ fn can_sub_cipher_cipher() {
     let args: Vec<FheProgramInput> = vec![a_c.into(), b_c.into()];
 
     let result = runtime
-        .run(app.get_fhe_program(sub).unwrap(), args, &public_key)
+        .run(app.get_fhe_program(sub).unwrap(), args, &evaluation_keys)
         .unwrap();
 
     let c: Batched<4> = runtime.decrypt(&result[0], &private_key).unwrap();
@@ -242,6 +261,9 @@ fn can_mul_cipher_cipher() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(mul).unwrap()])
+        .unwrap();
 
     let data = [vec![1, 2, 3, 4], vec![5, 6, 7, 8]];
 
@@ -253,7 +275,7 @@ fn can_mul_cipher_cipher() {
     let args: Vec<FheProgramInput> = vec![a_c.into(), b_c.into()];
 
     let result = runtime
-        .run(app.get_fhe_program(mul).unwrap(), args, &public_key)
+        .run(app.get_fhe_program(mul).unwrap(), args, &evaluation_keys)
         .unwrap();
 
     let c: Batched<4> = runtime.decrypt(&result[0], &private_key).unwrap();
@@ -285,6 +307,9 @@ fn can_neg_cipher_cipher() {
     let runtime = Runtime::new_fhe(app.params()).unwrap();
 
     let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [app.get_fhe_program(mul).unwrap()])
+        .unwrap();
 
     let data = [vec![1, 2, 3, 4], vec![5, 6, 7, 8]];
 
@@ -294,10 +319,55 @@ fn can_neg_cipher_cipher() {
     let args: Vec<FheProgramInput> = vec![a_c.into()];
 
     let result = runtime
-        .run(app.get_fhe_program(mul).unwrap(), args, &public_key)
+        .run(app.get_fhe_program(mul).unwrap(), args, &evaluation_keys)
         .unwrap();
 
     let c: Batched<4> = runtime.decrypt(&result[0], &private_key).unwrap();
 
     assert_eq!(c, neg_impl(a));
 }
+
+#[test]
+fn can_sum_lanes_and_dot_product() {
+    #[fhe_program(scheme = "bfv")]
+    fn reduce(
+        a: Cipher<Batched<4>>,
+        b: Cipher<Batched<4>>,
+    ) -> (Cipher<Batched<4>>, Cipher<Batched<4>>) {
+        (a.sum_lanes(), a.dot_product(b))
+    }
+
+    let app = Compiler::new()
+        .fhe_program(reduce)
+        .plain_modulus_constraint(PlainModulusConstraint::BatchingMinimum(0))
+        .compile()
+        .unwrap();
+
+    let program = app.get_fhe_program(reduce).unwrap();
+
+    assert_eq!(program.metadata.galois_steps, vec![0, 1, 2]);
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+    let evaluation_keys = runtime
+        .generate_evaluation_keys(&private_key, [program])
+        .unwrap();
+    assert!(evaluation_keys.galois_key.is_some());
+
+    let a = Batched::<4>::from([[1, 2, 3, 4], [5, 6, 7, 8]]);
+    let b = Batched::<4>::from([[1, -1, 2, -2], [0, 1, 0, -1]]);
+
+    let args: Vec<FheProgramInput> = vec![
+        runtime.encrypt(a, &public_key).unwrap().into(),
+        runtime.encrypt(b, &public_key).unwrap().into(),
+    ];
+
+    let result = runtime.run(program, args, &evaluation_keys).unwrap();
+
+    let sum: Batched<4> = runtime.decrypt(&result[0], &private_key).unwrap();
+    let dot: Batched<4> = runtime.decrypt(&result[1], &private_key).unwrap();
+
+    assert_eq!(sum, Batched::<4>::from(36));
+    assert_eq!(dot, Batched::<4>::from(-5));
+}
@@ -0,0 +1,198 @@
+use sunscreen::{types::bfv::Signed, types::Cipher, *};
+
+const PARTIES: usize = 3;
+const THRESHOLD: usize = 2;
+
+#[fhe_program(scheme = "bfv")]
+fn multiply(a: Cipher<Signed>, b: Cipher<Signed>) -> Cipher<Signed> {
+    a * b
+}
+
+struct Consortium {
+    app: FheApplication,
+    runtime: FheRuntime,
+    public_key: PublicKey,
+    evaluation_keys: EvaluationKeys,
+    private_keys: Vec<PrivateKey>,
+    threshold_keys: Vec<ThresholdKey>,
+}
+
+/**
+ * Simulates every party of a 2 of 3 consortium generating its collective keys.
+ */
+fn consortium() -> Consortium {
+    let app = Compiler::new()
+        .fhe_program(multiply)
+        .additional_noise_budget(10)
+        .compile()
+        .unwrap();
+
+    let runtime = FheRuntime::new(app.params()).unwrap();
+    let crs = CommonReferenceString::new();
+
+    let private_keys = (0..PARTIES)
+        .map(|_| runtime.generate_keys().unwrap().1)
+        .collect::<Vec<_>>();
+
+    let public_key_shares = private_keys
+        .iter()
+        .map(|k| runtime.generate_public_key_share(k, &crs).unwrap())
+        .collect::<Vec<_>>();
+    let public_key = runtime
+        .combine_public_key_shares(&crs, &public_key_shares)
+        .unwrap();
+
+    let (ephemerals, round_one_shares): (Vec<_>, Vec<_>) = private_keys
+        .iter()
+        .map(|k| runtime.generate_relin_key_round_one(k, &crs).unwrap())
+        .unzip();
+    let round_one = runtime
+        .combine_relin_key_round_one(&round_one_shares)
+        .unwrap();
+    let round_two_shares = private_keys
+        .iter()
+        .zip(&ephemerals)
+        .map(|(k, e)| {
+            runtime
+                .generate_relin_key_round_two(k, e, &round_one)
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+    let evaluation_keys = runtime
+        .combine_relin_key_shares(&round_one, &round_two_shares)
+        .unwrap();
+
+    let fragments = private_keys
+        .iter()
+        .map(|k| runtime.split_private_key(k, THRESHOLD, PARTIES).unwrap())
+        .collect::<Vec<_>>();
+    let threshold_keys = (0..PARTIES)
+        .map(|i| {
+            let received = fragments.iter().map(|f| f[i].clone()).collect::<Vec<_>>();
+            runtime.combine_key_fragments(&received).unwrap()
+        })
+        .collect::<Vec<_>>();
+
+    Consortium {
+        app,
+        runtime,
+        public_key,
+        evaluation_keys,
+        private_keys,
+        threshold_keys,
+    }
+}
+
+impl Consortium {
+    fn multiply(&self, a: i64, b: i64) -> Ciphertext {
+        let a = self
+            .runtime
+            .encrypt(Signed::from(a), &self.public_key)
+            .unwrap();
+        let b = self
+            .runtime
+            .encrypt(Signed::from(b), &self.public_key)
+            .unwrap();
+
+        self.runtime
+            .run(
+                self.app.get_fhe_program(multiply).unwrap(),
+                vec![a, b],
+                &self.evaluation_keys,
+            )
+            .unwrap()
+            .remove(0)
+    }
+
+    fn decryption_shares(&self, c: &Ciphertext, participants: &[usize]) -> Vec<DecryptionShare> {
+        participants
+            .iter()
+            .map(|&p| {
+                self.runtime
+                    .decryption_share(c, &self.threshold_keys[p - 1], participants)
+                    .unwrap()
+            })
+            .collect()
+    }
+}
+
+#[test]
+fn any_threshold_of_parties_can_decrypt() {
+    let consortium = consortium();
+
+    assert!(consortium
+        .threshold_keys
+        .iter()
+        .all(|k| k.threshold() == THRESHOLD));
+
+    let c = consortium.multiply(6, -7);
+
+    for participants in [vec![1, 2], vec![1, 3], vec![2, 3], vec![1, 2, 3]] {
+        let shares = consortium.decryption_shares(&c, &participants);
+
+        let result: Signed = consortium
+            .runtime
+            .combine_decryption_shares(&c, &shares)
+            .unwrap();
+
+        assert_eq!(result, Signed::from(-42));
+    }
+}
+
+#[test]
+fn fewer_than_threshold_parties_cannot_decrypt() {
+    let consortium = consortium();
+    let c = consortium.multiply(6, -7);
+
+    // No single party's key decrypts collective ciphertexts.
+    for private_key in &consortium.private_keys {
+        let result = consortium.runtime.decrypt::<Signed>(&c, private_key);
+
+        assert!(!matches!(result, Ok(v) if v == Signed::from(-42)));
+    }
+
+    let result = consortium
+        .runtime
+        .decryption_share(&c, &consortium.threshold_keys[0], &[1]);
+
+    assert!(matches!(
+        result,
+        Err(RuntimeError::ThresholdError(
+            ThresholdError::InsufficientShares
+        ))
+    ));
+}
+
+#[test]
+fn shares_from_different_participants_are_rejected() {
+    let consortium = consortium();
+    let c = consortium.multiply(2, 3);
+
+    let mut shares = consortium.decryption_shares(&c, &[1, 2]);
+    shares[1] = consortium.decryption_shares(&c, &[2, 3]).remove(0);
+
+    let result = consortium
+        .runtime
+        .combine_decryption_shares::<Signed>(&c, &shares);
+
+    assert!(matches!(
+        result,
+        Err(RuntimeError::ThresholdError(
+            ThresholdError::MismatchedShares
+        ))
+    ));
+
+    // Dropping a participant's share also fails.
+    let shares = consortium.decryption_shares(&c, &[1, 3]);
+
+    let result = consortium
+        .runtime
+        .combine_decryption_shares::<Signed>(&c, &shares[..1]);
+
+    assert!(matches!(
+        result,
+        Err(RuntimeError::ThresholdError(
+            ThresholdError::MismatchedShares
+        ))
+    ));
+}
@@ -8,7 +8,7 @@ use sunscreen::{
         bfv::{Unsigned, Unsigned256},
         Cipher,
     },
-    Compiler, FheApplication, FheProgramInput, FheRuntime, PrivateKey, PublicKey,
+    Compiler, EvaluationKeys, FheApplication, FheProgramInput, FheRuntime, PrivateKey, PublicKey,
 };
 
 macro_rules! fhe_program {
@@ -39,6 +39,7 @@ struct FheApp {
     rt: FheRuntime,
     pk: PublicKey,
     sk: PrivateKey,
+    ek: EvaluationKeys,
 }
 impl FheApp {
     fn new() -> Self {
@@ -53,7 +54,16 @@ impl FheApp {
             .unwrap();
         let rt: FheRuntime = FheRuntime::new(app.params()).unwrap();
         let (pk, sk) = rt.generate_keys().unwrap();
-        Self { app, rt, pk, sk }
+        let ek = rt
+            .generate_evaluation_keys(&sk, app.get_fhe_programs().map(|(_, p)| p))
+            .unwrap();
+        Self {
+            app,
+            rt,
+            pk,
+            sk,
+            ek,
+        }
     }
 }
 
@@ -67,7 +77,7 @@ macro_rules! op_proptest {
             paste! {
                 #[test]
                 fn [<$op _fhe_proptest>]() {
-                    let FheApp { app, rt, pk, sk } = &*FHE_APP;
+                    let FheApp { app, rt, pk, sk, ek } = &*FHE_APP;
 
                     proptest!(ProptestConfig::with_cases(20), |(lhs in [ANY; 4], rhs in [ANY; 4])| {
 
@@ -79,7 +89,7 @@ macro_rules! op_proptest {
                         let args: Vec<FheProgramInput> = vec![a_c.clone().into(), b_c.clone().into()];
 
                         let result = rt
-                            .run(app.get_fhe_program($op).unwrap(), args, pk)
+                            .run(app.get_fhe_program($op).unwrap(), args, ek)
                             .unwrap();
 
                         let c: Unsigned256 = rt.decrypt(&result[0], &sk).unwrap();
@@ -89,7 +99,7 @@ macro_rules! op_proptest {
                         // Test mixed ciphertexts and plaintexts
                         let args_mixed: Vec<FheProgramInput> = vec![a_c.into(), Unsigned256::from(b).into()];
                         let result_mixed = rt
-                            .run(app.get_fhe_program([<$op _plain>]).unwrap(), args_mixed, pk)
+                            .run(app.get_fhe_program([<$op _plain>]).unwrap(), args_mixed, ek)
                             .unwrap();
 
                         let c_mixed: Unsigned256 = rt.decrypt(&result_mixed[0], &sk).unwrap();
@@ -114,7 +124,13 @@ where
     O2: AsRef<str>,
     F: Fn(&Uint<L>, &Uint<L>) -> Uint<L>,
 {
-    let FheApp { app, rt, pk, sk } = &*FHE_APP;
+    let FheApp {
+        app,
+        rt,
+        pk,
+        sk,
+        ek,
+    } = &*FHE_APP;
     let a_u = Unsigned::from(a);
     let b_u = Unsigned::from(b);
 
@@ -123,7 +139,7 @@ where
     let args: Vec<FheProgramInput> = vec![a_c.clone().into(), b_c.into()];
 
     let result = rt
-        .run(app.get_fhe_program(fhe_op).unwrap(), args, pk)
+        .run(app.get_fhe_program(fhe_op).unwrap(), args, ek)
         .unwrap();
 
     let c: Unsigned<L> = rt.decrypt(&result[0], sk).unwrap();
@@ -133,7 +149,7 @@ where
     // Same test but subtracting plaintext
     let args_mixed: Vec<FheProgramInput> = vec![a_c.into(), b_u.into()];
     let result_mixed = rt
-        .run(app.get_fhe_program(fhe_op_plain).unwrap(), args_mixed, pk)
+        .run(app.get_fhe_program(fhe_op_plain).unwrap(), args_mixed, ek)
         .unwrap();
 
     let c_mixed: Unsigned<L> = rt.decrypt(&result_mixed[0], sk).unwrap();
@@ -101,6 +101,64 @@ fn can_use_public_inputs() {
         .unwrap();
 }
 
+#[test]
+fn can_batch_verify_proofs() {
+    #[zkp_program]
+    fn add_mul<F: FieldSpec>(b: Field<F>, c: Field<F>, #[public] a: Field<F>) {
+        let x = a * b + c;
+
+        x.constrain_eq(Field::from(42u32))
+    }
+
+    let app = Compiler::new()
+        .zkp_backend::<BulletproofsBackend>()
+        .zkp_program(add_mul)
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_zkp(BulletproofsBackend::new()).unwrap();
+
+    let program = app.get_zkp_program(add_mul).unwrap();
+
+    // (a, b, c) with a * b + c == 42
+    let inputs = [(10u8, 4u8, 2u8), (5, 8, 2), (1, 40, 2), (20, 2, 2)];
+
+    let proofs = inputs
+        .iter()
+        .map(|(a, b, c)| {
+            runtime
+                .prove(
+                    program,
+                    vec![BPField::from(*b), BPField::from(*c)],
+                    vec![BPField::from(*a)],
+                    vec![],
+                )
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+
+    let batch = proofs
+        .iter()
+        .zip(inputs.iter())
+        .map(|(proof, (a, _, _))| (proof, vec![BPField::from(*a)]))
+        .collect::<Vec<_>>();
+
+    runtime
+        .verify_batch(program, batch.clone(), vec![])
+        .unwrap();
+
+    runtime
+        .verification_builder(program)
+        .verify_many(batch.clone())
+        .unwrap();
+
+    // One proof checked against the wrong public input fails the batch.
+    let mut bad_batch = batch;
+    bad_batch[2].1 = vec![BPField::from(5u8)];
+
+    assert!(runtime.verify_batch(program, bad_batch, vec![]).is_err());
+}
+
 #[test]
 fn can_use_constant_inputs() {
     #[zkp_program]
@@ -218,3 +276,64 @@ fn builder_methods_work() {
         .verify()
         .unwrap();
 }
+
+#[test]
+fn debug_prove_reports_failing_constraints() {
+    use sunscreen::types::zkp::ConstrainCmp;
+    use sunscreen::WitnessFailure;
+    use sunscreen_zkp_backend::{BigInt, Operation};
+
+    const CONSTRAINT_LINE: u32 = line!() + 5;
+    const CMP_LINE: u32 = line!() + 5;
+
+    #[zkp_program]
+    fn checks<F: FieldSpec>(a: Field<F>, b: Field<F>, #[public] c: Field<F>) {
+        (a * b).constrain_eq(c);
+        a.constrain_le_bounded(b, 8);
+    }
+
+    let app = Compiler::new()
+        .zkp_backend::<BulletproofsBackend>()
+        .zkp_program(checks)
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_zkp(BulletproofsBackend::new()).unwrap();
+
+    let program = app.get_zkp_program(checks).unwrap();
+
+    let debug = |a: u8, b: u8, c: u8| {
+        runtime
+            .proof_builder(program)
+            .private_input(BPField::from(a))
+            .private_input(BPField::from(b))
+            .public_input(BPField::from(c))
+            .debug()
+            .unwrap()
+    };
+
+    assert!(debug(3, 4, 12).is_satisfied());
+
+    match debug(3, 4, 13).failure.unwrap() {
+        WitnessFailure::Constraint(c) => {
+            let location = c.location.unwrap();
+
+            assert!(location.file.ends_with("zkp_program_tests.rs"));
+            assert_eq!(location.line, CONSTRAINT_LINE);
+            assert!(matches!(c.operation, Operation::Sub));
+            assert_eq!(c.expected, BigInt::ZERO);
+
+            let operands = c.operands.iter().map(|(_, v)| *v).collect::<Vec<_>>();
+
+            assert_eq!(operands, vec![BigInt::from(12u8), BigInt::from(13u8)]);
+        }
+        x => panic!("Expected a constraint violation, got {x}"),
+    }
+
+    match debug(5, 4, 20).failure.unwrap() {
+        WitnessFailure::Gadget(g) => {
+            assert_eq!(g.location.unwrap().line, CMP_LINE);
+        }
+        x => panic!("Expected a gadget failure, got {x}"),
+    }
+}
@@ -5,6 +5,10 @@
 //! following useful operations:
 //! * [`compile`] takes either an FHE program from the compiler frontend and applies a set
 //! of transformations.
+//! * [`insert_mod_switches`] drops primes a compiled FHE program no longer needs once
+//! parameters are known.
+//! * [`balance_trees`] reports how much rebalancing reduces an FHE program's
+//! multiplicative depth.
 
 mod error;
 /**
@@ -15,9 +19,15 @@ mod transforms;
 
 pub use error::*;
 
+use noise_model::NoiseModel;
 use sunscreen_fhe_program::FheProgram;
+use sunscreen_runtime::Params;
 
-use transforms::transform_intermediate_representation;
+use transforms::{
+    apply_balance_trees, apply_insert_mod_switches, transform_intermediate_representation,
+};
+
+pub use transforms::DepthReport;
 
 /**
  * Clones the given [`FheProgram`] and compiles it.
@@ -38,3 +48,31 @@ pub fn compile_inplace(mut ir: FheProgram) -> FheProgram {
 
     ir
 }
+
+/**
+ * Inserts modulus switches into the given [`FheProgram`], dropping any primes
+ * in the coefficient modulus its outputs don't need to keep their invariant
+ * noise at or below `target_noise` under the given noise model. `ceilings`
+ * bounds how far up the program the switches move; an empty slice only
+ * switches outputs.
+ *
+ * # Remarks
+ * `ceilings[d]` is the most primes an operation with `d` multiplications
+ * remaining before an output (counting itself) may drop. Switching before a
+ * multiplication makes it cheaper but amplifies the rounding noise of the
+ * switch, so the noise model's prediction for the outputs no longer applies;
+ * measure the program's noise before using it.
+ *
+ * Outputs switched down the modulus chain are smaller, but can no longer be
+ * combined with ciphertexts at the top of the chain. As such, you shouldn't
+ * use this on programs whose outputs feed into other programs.
+ */
+pub fn insert_mod_switches(
+    ir: &mut FheProgram,
+    model: &(dyn NoiseModel + Sync),
+    params: &Params,
+    target_noise: f64,
+    ceilings: &[usize],
+) {
+    apply_insert_mod_switches(ir, model, params, target_noise, ceilings);
+}
@@ -0,0 +1,301 @@
+use crate::{noise_model::NoiseModel, Error, Result};
+use num::{BigUint, ToPrimitive};
+use sunscreen_fhe_program::SchemeType;
+use sunscreen_runtime::Params;
+
+#[derive(Clone)]
+/**
+ * A model for tracking noise growth in BGV ciphertexts using the canonical
+ * embedding norm of ciphertexts' randomness as an upper bound.
+ *
+ * # Remarks
+ * Under BGV, a ciphertext decrypts to `m + t * e` modulo `q`. This model
+ * tracks `||m + t * e|| / q`, which is the quantity SEAL uses to compute a
+ * BGV ciphertext's invariant noise budget. The implemented heuristics come
+ * from "Revisiting Homomorphic Encryption Schemes for Finite Fields" by Kim,
+ * Polyakov, and Zucca, section 3:
+ * * `encrypt`: fresh public key encryption bound scaled by `t`.
+ * * `add_ct_ct`: the noise terms add.
+ * * `mul_ct_ct`: the canonical embedding norm is sub-multiplicative, so the
+ *   noise terms multiply. Unlike BFV, this grows with `q`.
+ * * `relinearize`: key switching with a special prime adds a rounding term
+ *   proportional to `t * ||s||`.
+ * * `mod_switch`: dividing by the dropped primes scales `t * e` down with
+ *   `q`, but not `m`, whose norm is now relative to the smaller modulus `q'`.
+ *   Rounding adds the same term as key switching.
+ */
+pub struct BgvCanonicalEmbeddingNormModel {
+    /**
+     * The BGV scheme parameters to use for this model.
+     */
+    pub params: Params,
+}
+
+impl BgvCanonicalEmbeddingNormModel {
+    /**
+     * Create a new noise model with the given parameters.
+     *
+     * # Remarks
+     * Returns [`Error::InvalidParams`] if the given parameters:
+     * * aren't for the BGV scheme
+     * * contain no coefficient modulus chain values
+     * * have a plain modulus < 2
+     */
+    pub fn new(params: &Params) -> Result<Self> {
+        if params.scheme_type != SchemeType::Bgv {
+            return Err(Error::InvalidParams);
+        }
+
+        if params.coeff_modulus.is_empty() {
+            return Err(Error::InvalidParams);
+        }
+
+        if params.plain_modulus < 2 {
+            return Err(Error::InvalidParams);
+        }
+
+        Ok(Self {
+            params: params.clone(),
+        })
+    }
+
+    /**
+     * Compute q from the coefficient modulus chain in the given Params.
+     * This excludes the final "special" modulus that SEAL uses.
+     */
+    pub fn total_q(&self) -> BigUint {
+        self.params
+            .coeff_modulus
+            .iter()
+            .take(usize::max(self.params.coeff_modulus.len() - 1, 1))
+            .fold(BigUint::from(1u64), |sum, x| sum * (*x))
+    }
+
+    /**
+     * Compute the modulus that remains after dropping `dropped_primes` from
+     * the end of [`total_q`](Self::total_q).
+     */
+    pub fn remaining_q(&self, dropped_primes: usize) -> BigUint {
+        let data_primes = usize::max(self.params.coeff_modulus.len() - 1, 1);
+
+        self.params
+            .coeff_modulus
+            .iter()
+            .take(usize::max(data_primes.saturating_sub(dropped_primes), 1))
+            .fold(BigUint::from(1u64), |sum, x| sum * (*x))
+    }
+
+    fn q(&self) -> f64 {
+        self.total_q()
+            .to_f64()
+            .expect("Failed to convert BigUInt to f64")
+    }
+
+    /**
+     * The canonical embedding norm of a plaintext polynomial with
+     * coefficients in `[0, t)`.
+     */
+    fn plaintext_norm(&self) -> f64 {
+        let t = self.params.plain_modulus as f64;
+        let n = self.params.lattice_dimension as f64;
+
+        n * (t - 1.) / 2.
+    }
+
+    /**
+     * The noise introduced by rounding when dividing out a modulus, as
+     * happens at the end of key switching.
+     */
+    fn rounding_noise(&self) -> f64 {
+        let t = self.params.plain_modulus as f64;
+        let n = self.params.lattice_dimension as f64;
+
+        t * f64::sqrt(3. * n + 2. * n * n)
+    }
+}
+
+impl NoiseModel for BgvCanonicalEmbeddingNormModel {
+    fn encrypt(&self) -> f64 {
+        let t = self.params.plain_modulus as f64;
+        let n = self.params.lattice_dimension as f64;
+
+        let noise = self.plaintext_norm()
+            + t * 2f64 * super::NOISE_STD_DEV * f64::sqrt(12f64 * n * n + 9f64 * n);
+
+        noise / self.q()
+    }
+
+    fn add_ct_ct(&self, a_invariant_noise: f64, b_invariant_noise: f64) -> f64 {
+        a_invariant_noise + b_invariant_noise
+    }
+
+    fn add_ct_pt(&self, ct_invariant_noise: f64) -> f64 {
+        ct_invariant_noise + self.plaintext_norm() / self.q()
+    }
+
+    fn mul_ct_ct(&self, a_invariant_noise: f64, b_invariant_noise: f64) -> f64 {
+        a_invariant_noise * b_invariant_noise * self.q()
+    }
+
+    fn mul_ct_pt(&self, a_invariant_noise: f64) -> f64 {
+        a_invariant_noise * self.plaintext_norm()
+    }
+
+    fn relinearize(&self, a_invariant_noise: f64) -> f64 {
+        a_invariant_noise + self.rounding_noise() / self.q()
+    }
+
+    fn mod_switch(&self, a_invariant_noise: f64, dropped_primes: usize) -> f64 {
+        let q = self
+            .remaining_q(dropped_primes)
+            .to_f64()
+            .expect("Failed to convert BigUInt to f64");
+
+        a_invariant_noise + (self.plaintext_norm() + self.rounding_noise()) / q
+    }
+
+    fn output(&self, _output_id: usize, invariant_noise: f64) -> f64 {
+        invariant_noise
+    }
+
+    fn neg(&self, invariant_noise: f64) -> f64 {
+        invariant_noise
+    }
+
+    fn sub_ct_ct(&self, a_invariant_noise: f64, b_invariant_noise: f64) -> f64 {
+        self.add_ct_ct(a_invariant_noise, b_invariant_noise)
+    }
+
+    fn sub_ct_pt(&self, a_invariant_noise: f64) -> f64 {
+        self.add_ct_pt(a_invariant_noise)
+    }
+
+    fn shift_left(&self, a_invariant_noise: f64, _places: i32) -> f64 {
+        // Rotations are key switches.
+        self.relinearize(a_invariant_noise)
+    }
+
+    fn shift_right(&self, a_invariant_noise: f64, _places: i32) -> f64 {
+        self.relinearize(a_invariant_noise)
+    }
+
+    fn swap_rows(&self, a_invariant_noise: f64) -> f64 {
+        self.relinearize(a_invariant_noise)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_model::noise_to_noise_budget;
+    use seal_fhe::*;
+
+    fn setup_scheme(lattice_dimension: u64, plain_modulus: u64) -> (Context, Params) {
+        let params = BgvEncryptionParametersBuilder::new()
+            .set_plain_modulus_u64(plain_modulus)
+            .set_poly_modulus_degree(lattice_dimension)
+            .set_coefficient_modulus(
+                CoefficientModulus::bfv_default(lattice_dimension, SecurityLevel::TC128).unwrap(),
+            )
+            .build()
+            .unwrap();
+
+        let params_ret = Params {
+            lattice_dimension: params.get_poly_modulus_degree(),
+            plain_modulus: params.get_plain_modulus().value(),
+            coeff_modulus: params
+                .get_coefficient_modulus()
+                .iter()
+                .map(|x| x.value())
+                .collect(),
+            scheme_type: SchemeType::Bgv,
+            security_level: SecurityLevel::TC128,
+        };
+
+        let ctx = Context::new(&params, false, SecurityLevel::TC128).unwrap();
+
+        (ctx, params_ret)
+    }
+
+    fn make_plaintext(d: u64, p: u64) -> Plaintext {
+        let mut pt = Plaintext::new().unwrap();
+        pt.resize(d as usize);
+
+        for i in 0..d {
+            pt.set_coefficient(i as usize, p - 1);
+        }
+
+        pt
+    }
+
+    #[test]
+    fn rejects_bfv_params() {
+        let (_, params) = setup_scheme(4096, 1000);
+
+        let params = Params {
+            scheme_type: SchemeType::Bfv,
+            ..params
+        };
+
+        assert!(BgvCanonicalEmbeddingNormModel::new(&params).is_err());
+    }
+
+    #[test]
+    fn fresh_encryption_bound_exceeds_measured() {
+        for d in [2048, 4096, 8192, 16384] {
+            for p in [100, 1000, 10000] {
+                let (ctx, params) = setup_scheme(d, p);
+
+                let keygen = KeyGenerator::new(&ctx).unwrap();
+                let public_key = keygen.create_public_key();
+                let private_key = keygen.secret_key();
+                let encryptor = Encryptor::with_public_key(&ctx, &public_key).unwrap();
+                let decryptor = Decryptor::new(&ctx, &private_key).unwrap();
+
+                let ct = encryptor.encrypt(&make_plaintext(d, p)).unwrap();
+
+                let measured_noise_budget = decryptor.invariant_noise_budget(&ct).unwrap();
+
+                let noise_model = BgvCanonicalEmbeddingNormModel::new(&params).unwrap();
+
+                let modeled_noise_budget = noise_to_noise_budget(noise_model.encrypt()) as u32;
+
+                assert!(modeled_noise_budget < measured_noise_budget);
+            }
+        }
+    }
+
+    #[test]
+    fn multiply_bound_exceeds_measured() {
+        for d in [4096, 8192, 16384] {
+            for p in [100, 1000, 10000] {
+                let (ctx, params) = setup_scheme(d, p);
+
+                let keygen = KeyGenerator::new(&ctx).unwrap();
+                let public_key = keygen.create_public_key();
+                let private_key = keygen.secret_key();
+                let encryptor = Encryptor::with_public_key(&ctx, &public_key).unwrap();
+                let decryptor = Decryptor::new(&ctx, &private_key).unwrap();
+                let evaluator = BGVEvaluator::new(&ctx).unwrap();
+
+                let pt = make_plaintext(d, p);
+                let ct_0 = encryptor.encrypt(&pt).unwrap();
+                let ct_1 = encryptor.encrypt(&pt).unwrap();
+
+                let s = evaluator.multiply(&ct_0, &ct_1).unwrap();
+
+                let measured_noise_budget = decryptor.invariant_noise_budget(&s).unwrap();
+
+                let noise_model = BgvCanonicalEmbeddingNormModel::new(&params).unwrap();
+
+                let s_noise = noise_model.mul_ct_ct(noise_model.encrypt(), noise_model.encrypt());
+
+                // The model may predict the ciphertext no longer decrypts, in
+                // which case the budget saturates at 0.
+                let modeled_noise_budget = f64::max(noise_to_noise_budget(s_noise), 0.) as u32;
+
+                assert!(modeled_noise_budget <= measured_noise_budget);
+            }
+        }
+    }
+}
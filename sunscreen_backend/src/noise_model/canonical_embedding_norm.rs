@@ -17,6 +17,8 @@ use super::noise_budget_to_noise;
  * * `mul_ct_ct`: "Optimizations of Fully Homomorphic Encryption" by Ilia  Iliashenko, page 48.
  * * `mul_ct_pt`: SEAL 2.3.1 manual page 13.
  * * `relinearize`: Empirically measured that relinearization produces no noise. See `relinearization_consumes_no_noise_budget()` in `seal_fhe/tests/assumptions.rs`
+ * * `mod_switch`: Invariant noise carries over unchanged, plus the rounding error
+ * `(t/q') * (r_0 + r_1 * s)` with `|r_i| <= 1/2`, where `q'` is the remaining modulus.
  */
 pub struct CanonicalEmbeddingNormModel {
     /**
@@ -63,6 +65,20 @@ impl CanonicalEmbeddingNormModel {
         val
     }
 
+    /**
+     * Compute the modulus that remains after dropping `dropped_primes` from
+     * the end of [`total_q`](Self::total_q).
+     */
+    pub fn remaining_q(&self, dropped_primes: usize) -> BigUint {
+        let data_primes = usize::max(self.params.coeff_modulus.len() - 1, 1);
+
+        self.params
+            .coeff_modulus
+            .iter()
+            .take(usize::max(data_primes.saturating_sub(dropped_primes), 1))
+            .fold(BigUint::from(1u64), |sum, x| sum * (*x))
+    }
+
     /**
      * Compute `q mod t`, where q is `total_q(params)` and `t` is the plain
      * modulus.
@@ -139,6 +155,17 @@ impl NoiseModel for CanonicalEmbeddingNormModel {
         a_invariant_noise
     }
 
+    fn mod_switch(&self, a_invariant_noise: f64, dropped_primes: usize) -> f64 {
+        let q = self
+            .remaining_q(dropped_primes)
+            .to_f64()
+            .expect("Failed to convert BigUInt to f64");
+        let t = self.params.plain_modulus as f64;
+        let n = self.params.lattice_dimension as f64;
+
+        a_invariant_noise + t * (1. + f64::sqrt(3. * n + 2. * n * n)) / (2. * q)
+    }
+
     fn output(&self, _output_id: usize, invariant_noise: f64) -> f64 {
         invariant_noise
     }
@@ -198,7 +225,7 @@ mod tests {
             security_level: SecurityLevel::TC128,
         };
 
-        let ctx = Context::new(&params, false, SecurityLevel::TC128).unwrap();
+        let ctx = Context::new(&params, true, SecurityLevel::TC128).unwrap();
 
         (ctx, params_ret)
     }
@@ -424,4 +451,44 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn mod_switch_bound_exceeds_measured() {
+        for d in [4096, 8192, 16384] {
+            for p in [100, 1000, 10000] {
+                let (ctx, params) = setup_scheme(d, p);
+
+                let keygen = KeyGenerator::new(&ctx).unwrap();
+                let public_key = keygen.create_public_key();
+                let private_key = keygen.secret_key();
+                let encryptor = Encryptor::with_public_key(&ctx, &public_key).unwrap();
+                let decryptor = Decryptor::new(&ctx, &private_key).unwrap();
+                let evalulator = BFVEvaluator::new(&ctx).unwrap();
+
+                let mut pt = Plaintext::new().unwrap();
+                pt.resize(d as usize);
+
+                for i in 0..d {
+                    pt.set_coefficient(i as usize, p - 1);
+                }
+
+                let mut ct = encryptor.encrypt(&pt).unwrap();
+
+                let noise_model = CanonicalEmbeddingNormModel::new(&params).unwrap();
+                let mut noise = noise_model.encrypt();
+
+                // Drop every data prime but the first.
+                for dropped in 1..params.coeff_modulus.len() - 1 {
+                    ct = evalulator.mod_switch_to_next(&ct).unwrap();
+                    noise = noise_model.mod_switch(noise, dropped);
+
+                    let measured_noise_budget = decryptor.invariant_noise_budget(&ct).unwrap();
+                    let modeled_noise_budget =
+                        crate::noise_model::noise_to_noise_budget(noise) as u32;
+
+                    assert!(modeled_noise_budget <= measured_noise_budget);
+                }
+            }
+        }
+    }
 }
@@ -5,7 +5,9 @@ use sunscreen_fhe_program::{
 };
 use sunscreen_runtime::{run_program_unchecked, Params, SealData};
 
-use super::{noise_budget_to_noise, NoiseModel};
+use super::{
+    noise_budget_to_noise, BgvCanonicalEmbeddingNormModel, CanonicalEmbeddingNormModel, NoiseModel,
+};
 use crate::{Error, Result};
 
 #[derive(Copy, Clone)]
@@ -231,31 +233,34 @@ pub fn create_ciphertext_with_noise_level(
  * # Remarks
  * * This model is non-deterministic because we're running on real ciphertexts.
  * * All other models should bound its results from above
- * * All operations other than `output` return 0.0 noise.
+ * * All operations other than `output` and `mod_switch` return 0.0 noise.
+ * * `mod_switch` predicts the effect of switches the measured program didn't
+ * run, so it falls back to the scheme's canonical embedding norm model.
+ * * CKKS programs aren't supported, as CKKS has no noise budget.
  */
 pub struct MeasuredModel {
     output_noise: Vec<f64>,
+    params: Params,
 }
 
 fn create_seal_params(params: &Params) -> Result<EncryptionParameters> {
-    #[allow(unreachable_patterns)]
+    let plaintext_modulus = PlainModulus::raw(params.plain_modulus)?;
+    let coeff_modulus =
+        CoefficientModulus::bfv_default(params.lattice_dimension, params.security_level).unwrap();
+
     match params.scheme_type {
-        FheProgramSchemeType::Bfv => {
-            let plaintext_modulus = PlainModulus::raw(params.plain_modulus)?;
-
-            Ok(BfvEncryptionParametersBuilder::new()
-                .set_plain_modulus(plaintext_modulus)
-                .set_poly_modulus_degree(params.lattice_dimension)
-                .set_coefficient_modulus(
-                    CoefficientModulus::bfv_default(
-                        params.lattice_dimension,
-                        params.security_level,
-                    )
-                    .unwrap(),
-                )
-                .build()?)
-        }
-        _ => Err(Error::InvalidParams),
+        FheProgramSchemeType::Bfv => Ok(BfvEncryptionParametersBuilder::new()
+            .set_plain_modulus(plaintext_modulus)
+            .set_poly_modulus_degree(params.lattice_dimension)
+            .set_coefficient_modulus(coeff_modulus)
+            .build()?),
+        FheProgramSchemeType::Bgv => Ok(BgvEncryptionParametersBuilder::new()
+            .set_plain_modulus(plaintext_modulus)
+            .set_poly_modulus_degree(params.lattice_dimension)
+            .set_coefficient_modulus(coeff_modulus)
+            .build()?),
+        // CKKS has no noise budget to measure.
+        FheProgramSchemeType::Ckks => Err(Error::InvalidParams),
     }
 }
 
@@ -349,10 +354,6 @@ impl MeasuredModel {
 
         let decryptor = Decryptor::new(&context, &private_key).unwrap();
 
-        let evaluator = match ir.data {
-            FheProgramSchemeType::Bfv => BFVEvaluator::new(&context).unwrap(),
-        };
-
         let (relin_keys, galois_keys) = make_relin_galois_keys(ir, &keygen)?;
 
         let inputs = create_inputs_for_program(
@@ -366,14 +367,34 @@ impl MeasuredModel {
 
         // We validated the fhe_program, so it's safe to call
         // run_program_unchecked
-        let outputs = unsafe {
-            run_program_unchecked(
-                ir,
-                &inputs,
-                &evaluator,
-                &relin_keys.as_ref(),
-                &galois_keys.as_ref(),
-            )
+        let outputs = match ir.data {
+            FheProgramSchemeType::Bfv => {
+                let evaluator = BFVEvaluator::new(&context).unwrap();
+
+                unsafe {
+                    run_program_unchecked(
+                        ir,
+                        &inputs,
+                        &evaluator,
+                        &relin_keys.as_ref(),
+                        &galois_keys.as_ref(),
+                    )
+                }
+            }
+            FheProgramSchemeType::Bgv => {
+                let evaluator = BGVEvaluator::new(&context).unwrap();
+
+                unsafe {
+                    run_program_unchecked(
+                        ir,
+                        &inputs,
+                        &evaluator,
+                        &relin_keys.as_ref(),
+                        &galois_keys.as_ref(),
+                    )
+                }
+            }
+            FheProgramSchemeType::Ckks => return Err(Error::InvalidParams),
         }?;
 
         let mut noise_levels = vec![];
@@ -394,6 +415,7 @@ impl MeasuredModel {
 
         Ok(Self {
             output_noise: noise_levels,
+            params: params.clone(),
         })
     }
 }
@@ -423,6 +445,18 @@ impl NoiseModel for MeasuredModel {
         0.
     }
 
+    fn mod_switch(&self, a_invariant_noise: f64, dropped_primes: usize) -> f64 {
+        let params = self.params.clone();
+
+        match self.params.scheme_type {
+            FheProgramSchemeType::Bgv => BgvCanonicalEmbeddingNormModel { params }
+                .mod_switch(a_invariant_noise, dropped_primes),
+            _ => {
+                CanonicalEmbeddingNormModel { params }.mod_switch(a_invariant_noise, dropped_primes)
+            }
+        }
+    }
+
     fn output(&self, output_id: usize, _invariant_noise: f64) -> f64 {
         self.output_noise[output_id]
     }
@@ -1,12 +1,15 @@
 use crossbeam::atomic::AtomicCell;
+use petgraph::Direction;
 use sunscreen_compiler_common::GraphQuery;
 use sunscreen_fhe_program::{FheProgram, Literal, Operation::*};
 use sunscreen_runtime::traverse;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 
+mod bgv_canonical_embedding_norm;
 mod canonical_embedding_norm;
 mod measured_model;
+pub use bgv_canonical_embedding_norm::*;
 pub use canonical_embedding_norm::*;
 pub use measured_model::*;
 
@@ -38,8 +41,14 @@ pub const NOISE_MAX: f64 = NOISE_STD_DEV * NOISE_NUM_STD_DEVIATIONS;
 pub fn predict_noise(model: &(dyn NoiseModel + Sync), fhe_program: &FheProgram) -> Vec<f64> {
     let mut noise_levels: Vec<AtomicCell<f64>> = Vec::with_capacity(fhe_program.graph.node_count());
 
+    // The number of primes each ciphertext has dropped from the top of the
+    // modulus chain.
+    let mut dropped_primes: Vec<AtomicCell<usize>> =
+        Vec::with_capacity(fhe_program.graph.node_count());
+
     for _ in 0..fhe_program.graph.node_count() {
         noise_levels.push(AtomicCell::new(0.));
+        dropped_primes.push(AtomicCell::new(0));
     }
 
     let node_id_to_output_id = fhe_program
@@ -59,6 +68,20 @@ pub fn predict_noise(model: &(dyn NoiseModel + Sync), fhe_program: &FheProgram)
             let node = &fhe_program.graph[node_id];
             let query = GraphQuery::new(&fhe_program.graph.0);
 
+            let parents_dropped = fhe_program
+                .graph
+                .neighbors_directed(node_id, Direction::Incoming)
+                .map(|p| dropped_primes[p.index()].load())
+                .max()
+                .unwrap_or(0);
+
+            let dropped = match &node.operation {
+                ModSwitch => parents_dropped + 1,
+                _ => parents_dropped,
+            };
+
+            dropped_primes[node_id.index()].store(dropped);
+
             let noise = match &node.operation {
                 InputCiphertext(_) => model.encrypt(),
                 InputPlaintext(_) => 0.0,
@@ -70,6 +93,13 @@ pub fn predict_noise(model: &(dyn NoiseModel + Sync), fhe_program: &FheProgram)
                         noise_levels[right.index()].load(),
                     )
                 }
+                AddMany => query
+                    .get_unordered_operands(node_id)
+                    .unwrap()
+                    .iter()
+                    .map(|x| noise_levels[x.index()].load())
+                    .reduce(|a, b| model.add_ct_ct(a, b))
+                    .unwrap(),
                 AddPlaintext => {
                     let (left, _) = query.get_binary_operands(node_id).unwrap();
 
@@ -83,6 +113,26 @@ pub fn predict_noise(model: &(dyn NoiseModel + Sync), fhe_program: &FheProgram)
                         noise_levels[right.index()].load(),
                     )
                 }
+                MultiplyMany => {
+                    let operands = query.get_unordered_operands(node_id).unwrap();
+
+                    // Follow the runtime, which multiplies the first 2
+                    // values in the queue and appends their relinearized
+                    // product until 1 remains.
+                    let mut queue = operands
+                        .iter()
+                        .map(|x| noise_levels[x.index()].load())
+                        .collect::<VecDeque<_>>();
+
+                    while queue.len() > 1 {
+                        let a = queue.pop_front().unwrap();
+                        let b = queue.pop_front().unwrap();
+
+                        queue.push_back(model.relinearize(model.mul_ct_ct(a, b)));
+                    }
+
+                    queue[0]
+                }
                 MultiplyPlaintext => {
                     let (left, _) = query.get_binary_operands(node_id).unwrap();
 
@@ -93,6 +143,21 @@ pub fn predict_noise(model: &(dyn NoiseModel + Sync), fhe_program: &FheProgram)
 
                     model.relinearize(noise_levels[x.index()].load())
                 }
+                Rescale => {
+                    // Rescaling only appears in CKKS programs, where noise is
+                    // part of the approximation error rather than a budget.
+                    let x = query.get_unary_operand(node_id).unwrap();
+
+                    noise_levels[x.index()].load()
+                }
+                ModSwitch => {
+                    let x = query.get_unary_operand(node_id).unwrap();
+
+                    model.mod_switch(
+                        noise_levels[x.index()].load(),
+                        dropped_primes[node_id.index()].load(),
+                    )
+                }
                 Negate => {
                     let x = query.get_unary_operand(node_id).unwrap();
 
@@ -229,6 +294,14 @@ pub trait NoiseModel {
      */
     fn relinearize(&self, a_invariant_noise: f64) -> f64;
 
+    /**
+     * Predict the amount of noise after switching a ciphertext to the next
+     * modulus in the chain, where `dropped_primes` is the total number of
+     * primes the ciphertext has dropped from the top of the chain (including
+     * this switch).
+     */
+    fn mod_switch(&self, a_invariant_noise: f64, dropped_primes: usize) -> f64;
+
     /**
      * Predict the amount of noise for the given output's index.
      *
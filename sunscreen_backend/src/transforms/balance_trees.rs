@@ -0,0 +1,484 @@
+use std::{
+    cmp::{max, Reverse},
+    collections::{BinaryHeap, HashMap},
+};
+
+use sunscreen_fhe_program::{
+    FheProgram, FheProgramTrait,
+    Operation::{self, *},
+    SchemeType,
+};
+
+use petgraph::{algo::toposort, stable_graph::NodeIndex, visit::EdgeRef, Direction};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/**
+ * The multiplicative depth of an FHE program before and after rebalancing
+ * its addition and multiplication chains.
+ */
+pub struct DepthReport {
+    /**
+     * The multiplicative depth of the program as written.
+     */
+    pub before: usize,
+
+    /**
+     * The multiplicative depth of the rebalanced program.
+     */
+    pub after: usize,
+}
+
+/**
+ * The multiplicative depth and number of chain operations below a node
+ * in a tree we're building. We combine the shallowest subtrees first.
+ */
+type Key = (usize, usize);
+
+fn is_chain_op(op: &Operation) -> bool {
+    matches!(op, Add | Multiply)
+}
+
+/**
+ * Whether the node at `id` feeds exactly one operation of the same kind,
+ * which makes it part of that operation's chain rather than the root of
+ * its own.
+ */
+fn is_interior(ir: &FheProgram, id: NodeIndex) -> bool {
+    let op = &ir.graph[id].operation;
+
+    if !is_chain_op(op) {
+        return false;
+    }
+
+    let mut edges = ir.graph.edges_directed(id, Direction::Outgoing);
+
+    match (edges.next(), edges.next()) {
+        (Some(e), None) => ir.graph[e.target()].operation == *op,
+        _ => false,
+    }
+}
+
+fn combine(op: &Operation, a: Key, b: Key) -> Key {
+    let depth = max(a.0, b.0) + usize::from(matches!(op, Multiply));
+
+    (depth, max(a.1, b.1) + 1)
+}
+
+/**
+ * Reassociates chains of ciphertext additions and multiplications into
+ * balanced trees. For example, `a * b * c * d` becomes
+ * `(a * b) * (c * d)`, reducing the multiplicative depth from 3 to 2.
+ *
+ * # Remarks
+ * A chain is a tree of the same commutative operation where every
+ * intermediate result has exactly one use. Operands are combined
+ * shallowest first, so operands computed with many multiplications join
+ * the tree as late as possible.
+ *
+ * Under BFV and BGV, addition chains become a single
+ * [`AddMany`](Operation::AddMany). Multiplication chains become a single
+ * [`MultiplyMany`](Operation::MultiplyMany), which the runtime evaluates
+ * as a balanced tree, unless their operands' depths differ enough that
+ * combining shallow operands first needs fewer levels.
+ *
+ * This pass must run before relinearizations are inserted, since they
+ * would otherwise split multiplication chains.
+ */
+pub fn apply_balance_trees(ir: &mut FheProgram) -> DepthReport {
+    let before = ir.multiplicative_depth();
+
+    let mut depths = HashMap::<NodeIndex, usize>::new();
+
+    for id in toposort(&ir.graph.0, None).unwrap() {
+        // Rebalancing removes the interior nodes of chains we've already
+        // visited.
+        if !ir.graph.contains_node(id) {
+            continue;
+        }
+
+        let parent_depth = ir
+            .graph
+            .neighbors_directed(id, Direction::Incoming)
+            .map(|p| depths[&p])
+            .max()
+            .unwrap_or(0);
+
+        let depth = match ir.graph[id].operation {
+            Multiply => parent_depth + 1,
+            _ => parent_depth,
+        };
+
+        depths.insert(id, depth);
+
+        if is_chain_op(&ir.graph[id].operation) && !is_interior(ir, id) {
+            rebalance(ir, id, &mut depths);
+        }
+    }
+
+    DepthReport {
+        before,
+        after: ir.multiplicative_depth(),
+    }
+}
+
+/**
+ * Rebuilds the chain ending at `root` as a single n-ary operation, as
+ * described in [`apply_balance_trees`], or as a balanced tree if doing so
+ * reduces its depth.
+ */
+fn rebalance(ir: &mut FheProgram, root: NodeIndex, depths: &mut HashMap<NodeIndex, usize>) {
+    let op = ir.graph[root].operation.clone();
+
+    let mut leaves = vec![];
+    let mut interior = vec![];
+    let mut heights = HashMap::new();
+
+    // Walk the chain from the root, recording each node's height so we can
+    // tell whether rebalancing helps. Operands appear once per edge, so
+    // `x * x` contributes `x` twice.
+    let mut stack = vec![root];
+
+    while let Some(n) = stack.pop() {
+        for p in ir.graph.neighbors_directed(n, Direction::Incoming) {
+            if is_interior(ir, p) {
+                interior.push(p);
+                stack.push(p);
+            } else {
+                leaves.push(p);
+            }
+        }
+    }
+
+    if interior.is_empty() {
+        return;
+    }
+
+    for n in interior.iter().rev().chain(std::iter::once(&root)) {
+        let height = ir
+            .graph
+            .neighbors_directed(*n, Direction::Incoming)
+            .map(|p| heights.get(&p).copied().unwrap_or(0))
+            .max()
+            .unwrap_or(0)
+            + 1;
+
+        heights.insert(*n, height);
+    }
+
+    let current: Key = (depths[&root], heights[&root]);
+
+    // BFV and BGV can add or multiply many ciphertexts in one operation.
+    // The sum of the leaves doesn't change the depth, so a single AddMany
+    // always works.
+    let batch = ir.data != SchemeType::Ckks;
+
+    if batch && op == Add {
+        let top = ir.add_add_many(&leaves);
+
+        depths.insert(top, current.0);
+        replace_chain(ir, root, &interior, top);
+
+        return;
+    }
+
+    // Simulate the rebuild to see if it's worth doing.
+    let mut heap = leaves
+        .iter()
+        .map(|l| Reverse((depths[l], 0)))
+        .collect::<BinaryHeap<Reverse<Key>>>();
+
+    while heap.len() > 1 {
+        let Reverse(a) = heap.pop().unwrap();
+        let Reverse(b) = heap.pop().unwrap();
+
+        heap.push(Reverse(combine(&op, a, b)));
+    }
+
+    let Reverse(balanced) = heap.pop().unwrap();
+
+    // MultiplyMany multiplies its operands in a balanced tree without
+    // regard for their depths, so it's only as good as the tree we'd build
+    // when the leaves have similar depths.
+    if batch && op == Multiply {
+        let depth = leaves.iter().map(|l| depths[l]).max().unwrap()
+            + leaves.len().next_power_of_two().trailing_zeros() as usize;
+
+        if depth <= balanced.0 {
+            let top = ir.add_multiply_many(&leaves);
+
+            depths.insert(top, depth);
+            replace_chain(ir, root, &interior, top);
+
+            return;
+        }
+    }
+
+    if balanced >= current {
+        return;
+    }
+
+    let mut heap = leaves
+        .iter()
+        .map(|l| Reverse((depths[l], 0, *l)))
+        .collect::<BinaryHeap<Reverse<(usize, usize, NodeIndex)>>>();
+
+    while heap.len() > 1 {
+        let Reverse((a_depth, a_height, a)) = heap.pop().unwrap();
+        let Reverse((b_depth, b_height, b)) = heap.pop().unwrap();
+
+        let (depth, height) = combine(&op, (a_depth, a_height), (b_depth, b_height));
+        let n = ir.add_binary_operation(op.clone(), a, b);
+
+        depths.insert(n, depth);
+        heap.push(Reverse((depth, height, n)));
+    }
+
+    let Reverse((_, _, top)) = heap.pop().unwrap();
+
+    replace_chain(ir, root, &interior, top);
+}
+
+/**
+ * Moves the uses of the chain ending at `root` to `top` and removes the
+ * chain's nodes.
+ */
+fn replace_chain(ir: &mut FheProgram, root: NodeIndex, interior: &[NodeIndex], top: NodeIndex) {
+    let uses = ir
+        .graph
+        .edges_directed(root, Direction::Outgoing)
+        .map(|e| (e.target(), *e.weight()))
+        .collect::<Vec<_>>();
+
+    for (target, edge) in uses {
+        ir.graph.add_edge(top, target, edge);
+    }
+
+    ir.graph.remove_node(root);
+
+    for n in interior {
+        ir.graph.remove_node(*n);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sunscreen_compiler_common::GraphQuery;
+
+    fn count_ops(ir: &FheProgram, op: Operation) -> usize {
+        ir.graph
+            .node_weights()
+            .filter(|n| n.operation == op)
+            .count()
+    }
+
+    #[test]
+    fn balances_multiplication_chain() {
+        let mut ir = FheProgram::new(SchemeType::Ckks);
+
+        let inputs = (0..6)
+            .map(|i| ir.add_input_ciphertext(i))
+            .collect::<Vec<_>>();
+
+        let product = inputs[1..]
+            .iter()
+            .fold(inputs[0], |acc, x| ir.add_multiply(acc, *x));
+
+        ir.add_output_ciphertext(product);
+
+        let report = apply_balance_trees(&mut ir);
+
+        ir.validate().unwrap();
+
+        assert_eq!(
+            report,
+            DepthReport {
+                before: 5,
+                after: 3
+            }
+        );
+        assert_eq!(count_ops(&ir, Multiply), 5);
+    }
+
+    #[test]
+    fn batches_multiplication_chain() {
+        let mut ir = FheProgram::new(SchemeType::Bfv);
+
+        let inputs = (0..6)
+            .map(|i| ir.add_input_ciphertext(i))
+            .collect::<Vec<_>>();
+
+        let product = inputs[1..]
+            .iter()
+            .fold(inputs[0], |acc, x| ir.add_multiply(acc, *x));
+
+        let out = ir.add_output_ciphertext(product);
+
+        let report = apply_balance_trees(&mut ir);
+
+        ir.validate().unwrap();
+
+        assert_eq!(
+            report,
+            DepthReport {
+                before: 5,
+                after: 3
+            }
+        );
+        assert_eq!(count_ops(&ir, Multiply), 0);
+        assert_eq!(count_ops(&ir, MultiplyMany), 1);
+
+        let query = GraphQuery::new(&ir.graph.0);
+        let product = query.get_unary_operand(out).unwrap();
+
+        let mut operands = query.get_unordered_operands(product).unwrap();
+        operands.sort();
+
+        assert_eq!(operands, inputs);
+    }
+
+    #[test]
+    fn joins_deep_operands_last() {
+        let mut ir = FheProgram::new(SchemeType::Bfv);
+
+        let a = ir.add_input_ciphertext(0);
+        let b = ir.add_input_ciphertext(1);
+        let c = ir.add_input_ciphertext(2);
+        let d = ir.add_input_ciphertext(3);
+
+        // deep has depth 3, and is also an output so it isn't part of the
+        // chain below.
+        let deep = ir.add_multiply(a, a);
+        let deep = ir.add_multiply(deep, deep);
+        let deep = ir.add_multiply(deep, deep);
+        ir.add_output_ciphertext(deep);
+
+        // ((deep * b) * c) * d needs depth 6, while deep * ((b * c) * d)
+        // needs only 4. Multiplying all 4 at once would need 5.
+        let mul = ir.add_multiply(deep, b);
+        let mul = ir.add_multiply(mul, c);
+        let mul = ir.add_multiply(mul, d);
+        ir.add_output_ciphertext(mul);
+
+        let report = apply_balance_trees(&mut ir);
+
+        ir.validate().unwrap();
+
+        assert_eq!(
+            report,
+            DepthReport {
+                before: 6,
+                after: 4
+            }
+        );
+        assert_eq!(count_ops(&ir, MultiplyMany), 0);
+    }
+
+    #[test]
+    fn does_not_flatten_shared_intermediates() {
+        let mut ir = FheProgram::new(SchemeType::Bfv);
+
+        let a = ir.add_input_ciphertext(0);
+        let b = ir.add_input_ciphertext(1);
+        let c = ir.add_input_ciphertext(2);
+
+        // ab is used twice, so (ab * c) can't absorb it.
+        let ab = ir.add_multiply(a, b);
+        let abc = ir.add_multiply(ab, c);
+        let out = ir.add_add(abc, ab);
+        ir.add_output_ciphertext(out);
+
+        let report = apply_balance_trees(&mut ir);
+
+        assert_eq!(
+            report,
+            DepthReport {
+                before: 2,
+                after: 2
+            }
+        );
+        assert!(ir.graph.contains_node(ab));
+        assert!(ir.graph.contains_node(abc));
+    }
+
+    #[test]
+    fn balances_addition_chain() {
+        let mut ir = FheProgram::new(SchemeType::Ckks);
+
+        let inputs = (0..8)
+            .map(|i| ir.add_input_ciphertext(i))
+            .collect::<Vec<_>>();
+
+        let sum = inputs[1..]
+            .iter()
+            .fold(inputs[0], |acc, x| ir.add_add(acc, *x));
+
+        let out = ir.add_output_ciphertext(sum);
+
+        apply_balance_trees(&mut ir);
+
+        ir.validate().unwrap();
+
+        assert_eq!(count_ops(&ir, Add), 7);
+
+        // The output's operand is the root of a tree of height 3.
+        let mut height = 0;
+        let mut frontier = vec![out];
+
+        while !frontier.is_empty() {
+            frontier = frontier
+                .iter()
+                .flat_map(|n| ir.graph.neighbors_directed(*n, Direction::Incoming))
+                .filter(|n| ir.graph[*n].operation == Add)
+                .collect();
+
+            if !frontier.is_empty() {
+                height += 1;
+            }
+        }
+
+        assert_eq!(height, 3);
+    }
+
+    #[test]
+    fn batches_addition_chain() {
+        let mut ir = FheProgram::new(SchemeType::Bgv);
+
+        let inputs = (0..4)
+            .map(|i| ir.add_input_ciphertext(i))
+            .collect::<Vec<_>>();
+
+        let product = ir.add_multiply(inputs[0], inputs[1]);
+
+        let sum = ir.add_add(product, inputs[2]);
+        let sum = ir.add_add(sum, inputs[3]);
+        let sum = ir.add_add(sum, inputs[3]);
+        ir.add_output_ciphertext(sum);
+
+        let report = apply_balance_trees(&mut ir);
+
+        ir.validate().unwrap();
+
+        assert_eq!(
+            report,
+            DepthReport {
+                before: 1,
+                after: 1
+            }
+        );
+        assert_eq!(count_ops(&ir, Add), 0);
+        assert_eq!(count_ops(&ir, AddMany), 1);
+
+        let query = GraphQuery::new(&ir.graph.0);
+        let sum = ir
+            .graph
+            .node_indices()
+            .find(|id| ir.graph[*id].operation == AddMany)
+            .unwrap();
+
+        let mut operands = query.get_unordered_operands(sum).unwrap();
+        operands.sort();
+
+        assert_eq!(operands, vec![inputs[2], inputs[3], inputs[3], product]);
+    }
+}
@@ -0,0 +1,329 @@
+use std::collections::HashMap;
+
+use sunscreen_fhe_program::{
+    FheProgram, FheProgramTrait,
+    Operation::{self, *},
+    SchemeType,
+};
+use sunscreen_runtime::Params;
+
+use petgraph::{algo::toposort, visit::EdgeRef, Direction};
+
+use crate::noise_model::{predict_noise, NoiseModel};
+
+/**
+ * Inserts modulus switches into the FHE program, dropping as many primes from
+ * the coefficient modulus as the given noise model allows while keeping each
+ * output's invariant noise at or below `target_noise`. Smaller moduli mean
+ * smaller output ciphertexts and cheaper operations after the switch.
+ *
+ * # Remarks
+ * Each output's level (the number of primes it drops) comes from the noise
+ * model. Switches then move up the graph as far as `ceilings` allows:
+ * `ceilings[d]` is the most primes an operation may drop when `d`
+ * multiplications remain on its way to an output, counting the operation
+ * itself. A [`MultiplyMany`](Operation::MultiplyMany) counts as the depth of
+ * its multiplication tree. Relinearizations count as part of their multiplication, so they run
+ * at the same level. Operations with `ceilings.len()` or more multiplications
+ * remaining keep the full modulus, so an empty `ceilings` only switches
+ * outputs. A ciphertext never drops more primes than any of its consumers,
+ * and all operands of an operation are switched to the same level.
+ *
+ * The noise model only predicts switches before outputs, so callers should
+ * verify the program's noise after moving switches further up (e.g. by
+ * measuring it) and retry with lower ceilings if needed.
+ *
+ * This pass never drops the last data prime and does nothing under CKKS, where
+ * rescaling already moves ciphertexts down the modulus chain.
+ */
+pub fn apply_insert_mod_switches(
+    ir: &mut FheProgram,
+    model: &(dyn NoiseModel + Sync),
+    params: &Params,
+    target_noise: f64,
+    ceilings: &[usize],
+) {
+    if ir.data == SchemeType::Ckks {
+        return;
+    }
+
+    // SEAL reserves the last prime for key switching, and ciphertexts need at
+    // least one prime.
+    let max_dropped = params.coeff_modulus.len().saturating_sub(2);
+
+    let output_noises = predict_noise(model, ir);
+
+    let mut levels = ir
+        .graph
+        .node_indices()
+        .filter(|id| matches!(ir.graph[*id].operation, OutputCiphertext))
+        .zip(output_noises)
+        .map(|(id, noise)| {
+            let level = (1..=max_dropped)
+                .take_while(|dropped| model.mod_switch(noise, *dropped) <= target_noise)
+                .last()
+                .unwrap_or(0);
+
+            (id, level)
+        })
+        .collect::<HashMap<_, _>>();
+
+    let order = toposort(&ir.graph.0, None).unwrap();
+
+    // The most multiplications after each node on its way to an output.
+    let mut multiplications = HashMap::new();
+
+    for &id in order.iter().rev() {
+        let consumers = ir
+            .graph
+            .neighbors_directed(id, Direction::Outgoing)
+            .collect::<Vec<_>>();
+
+        let depth = consumers
+            .iter()
+            .map(|c| multiplications[c] + ir.multiplications(*c))
+            .max()
+            .unwrap_or(0);
+
+        multiplications.insert(id, depth);
+
+        let op = &ir.graph[id].operation;
+
+        if !is_switchable(op) {
+            levels.entry(id).or_insert(0);
+            continue;
+        }
+
+        let remaining = depth + ir.multiplications(id) + usize::from(matches!(op, Relinearize));
+        let ceiling = ceilings.get(remaining).copied().unwrap_or(0);
+
+        let level = consumers
+            .iter()
+            .map(|c| levels[c])
+            .min()
+            .unwrap_or(0)
+            .min(ceiling);
+
+        levels.insert(id, level);
+    }
+
+    for id in order {
+        if !is_ciphertext(&ir.graph[id].operation) {
+            continue;
+        }
+
+        let level = levels[&id];
+
+        let edges = ir
+            .graph
+            .edges_directed(id, Direction::Outgoing)
+            .map(|e| (e.id(), e.target(), *e.weight()))
+            .collect::<Vec<_>>();
+
+        // switched[k] holds this node's value switched down k more primes.
+        let mut switched = vec![id];
+
+        for (edge, target, info) in edges {
+            let switches = levels[&target].saturating_sub(level);
+
+            if switches == 0 {
+                continue;
+            }
+
+            while switched.len() <= switches {
+                let next = ir.add_mod_switch(*switched.last().unwrap());
+                switched.push(next);
+            }
+
+            ir.graph.remove_edge(edge);
+            ir.graph.add_edge(switched[switches], target, info);
+        }
+    }
+}
+
+/**
+ * Whether the operation produces a ciphertext.
+ */
+fn is_ciphertext(op: &Operation) -> bool {
+    !matches!(op, InputPlaintext(_) | Literal(_) | OutputCiphertext)
+}
+
+/**
+ * Whether the operation produces a ciphertext that can run below the top of
+ * the modulus chain. Inputs always arrive with the full modulus.
+ */
+fn is_switchable(op: &Operation) -> bool {
+    is_ciphertext(op) && !matches!(op, InputCiphertext(_))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_model::{noise_budget_to_noise, CanonicalEmbeddingNormModel};
+    use seal_fhe::{CoefficientModulus, SecurityLevel};
+    use sunscreen_compiler_common::GraphQuery;
+    use sunscreen_fhe_program::FheProgramTrait;
+
+    fn params(scheme_type: SchemeType) -> Params {
+        Params {
+            lattice_dimension: 8192,
+            plain_modulus: 1024,
+            coeff_modulus: CoefficientModulus::bfv_default(8192, SecurityLevel::TC128)
+                .unwrap()
+                .iter()
+                .map(|x| x.value())
+                .collect(),
+            scheme_type,
+            security_level: SecurityLevel::TC128,
+        }
+    }
+
+    fn create_test_dag(scheme_type: SchemeType) -> FheProgram {
+        let mut ir = FheProgram::new(scheme_type);
+
+        let a = ir.add_input_ciphertext(0);
+        let b = ir.add_input_ciphertext(1);
+        let add = ir.add_add(a, b);
+        ir.add_output_ciphertext(add);
+
+        ir
+    }
+
+    fn count_mod_switches(ir: &FheProgram) -> usize {
+        ir.graph
+            .node_weights()
+            .filter(|n| matches!(n.operation, ModSwitch))
+            .count()
+    }
+
+    #[test]
+    fn switches_low_noise_outputs_down() {
+        let params = params(SchemeType::Bfv);
+        let model = CanonicalEmbeddingNormModel::new(&params).unwrap();
+        let mut ir = create_test_dag(SchemeType::Bfv);
+
+        apply_insert_mod_switches(&mut ir, &model, &params, noise_budget_to_noise(10.), &[]);
+
+        ir.validate().unwrap();
+
+        // The 8192 default chain has 4 data primes and a sum of 2 fresh
+        // ciphertexts needs only one of them.
+        assert_eq!(count_mod_switches(&ir), params.coeff_modulus.len() - 2);
+
+        let query = GraphQuery::new(&ir.graph.0);
+        let output = ir.get_outputs().next().unwrap();
+        let operand = query.get_unary_operand(output).unwrap();
+
+        assert!(matches!(ir.graph[operand].operation, ModSwitch));
+    }
+
+    #[test]
+    fn does_not_switch_when_noise_budget_is_exhausted() {
+        let params = params(SchemeType::Bfv);
+        let model = CanonicalEmbeddingNormModel::new(&params).unwrap();
+        let mut ir = create_test_dag(SchemeType::Bfv);
+
+        // Require more noise budget than a smaller modulus can provide.
+        apply_insert_mod_switches(&mut ir, &model, &params, noise_budget_to_noise(150.), &[]);
+
+        assert_eq!(count_mod_switches(&ir), 0);
+    }
+
+    #[test]
+    fn does_not_switch_under_ckks() {
+        let params = params(SchemeType::Bfv);
+        let model = CanonicalEmbeddingNormModel::new(&params).unwrap();
+        let mut ir = create_test_dag(SchemeType::Ckks);
+
+        apply_insert_mod_switches(&mut ir, &model, &params, noise_budget_to_noise(20.), &[]);
+
+        assert_eq!(count_mod_switches(&ir), 0);
+    }
+
+    fn create_multiply_dag() -> FheProgram {
+        let mut ir = FheProgram::new(SchemeType::Bfv);
+
+        let a = ir.add_input_ciphertext(0);
+        let b = ir.add_input_ciphertext(1);
+        let c = ir.add_input_ciphertext(2);
+        let mul = ir.add_multiply(a, b);
+        let relin = ir.add_relinearize(mul);
+        let add = ir.add_add(relin, c);
+        ir.add_output_ciphertext(add);
+
+        ir
+    }
+
+    fn consumers_of_mod_switches(ir: &FheProgram) -> Vec<Operation> {
+        ir.graph
+            .node_indices()
+            .filter(|id| matches!(ir.graph[*id].operation, ModSwitch))
+            .flat_map(|id| ir.graph.neighbors_directed(id, Direction::Outgoing))
+            .map(|id| ir.graph[id].operation.clone())
+            .filter(|op| !matches!(op, ModSwitch))
+            .collect()
+    }
+
+    #[test]
+    fn can_switch_before_multiply() {
+        let params = params(SchemeType::Bfv);
+        let model = CanonicalEmbeddingNormModel::new(&params).unwrap();
+        let mut ir = create_multiply_dag();
+        let max_dropped = params.coeff_modulus.len() - 2;
+
+        apply_insert_mod_switches(
+            &mut ir,
+            &model,
+            &params,
+            noise_budget_to_noise(10.),
+            &[max_dropped, 1],
+        );
+
+        ir.validate().unwrap();
+
+        let consumers = consumers_of_mod_switches(&ir);
+
+        // Both multiplicands drop 1 prime, while c drops as many as the output.
+        assert_eq!(
+            consumers.iter().filter(|op| matches!(op, Multiply)).count(),
+            2
+        );
+        assert!(consumers.iter().any(|op| matches!(op, Relinearize | Add)));
+
+        // Relinearization runs at the multiplication's level.
+        let query = GraphQuery::new(&ir.graph.0);
+        let relin = ir
+            .graph
+            .node_indices()
+            .find(|id| matches!(ir.graph[*id].operation, Relinearize))
+            .unwrap();
+
+        assert!(matches!(
+            ir.graph[query.get_unary_operand(relin).unwrap()].operation,
+            Multiply
+        ));
+    }
+
+    #[test]
+    fn ceilings_keep_multiplications_at_full_modulus() {
+        let params = params(SchemeType::Bfv);
+        let model = CanonicalEmbeddingNormModel::new(&params).unwrap();
+        let mut ir = create_multiply_dag();
+        let max_dropped = params.coeff_modulus.len() - 2;
+
+        apply_insert_mod_switches(
+            &mut ir,
+            &model,
+            &params,
+            noise_budget_to_noise(10.),
+            &[max_dropped],
+        );
+
+        ir.validate().unwrap();
+
+        let consumers = consumers_of_mod_switches(&ir);
+
+        assert!(!consumers.is_empty());
+        assert!(consumers.iter().all(|op| !matches!(op, Multiply)));
+    }
+}
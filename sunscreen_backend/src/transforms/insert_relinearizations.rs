@@ -50,7 +50,7 @@ pub fn apply_insert_relinearizations(ir: &mut FheProgram) {
             // multiplications. Plaintext multiplications don't increase
             // the number of polynomials (see
             // multiply_plaintext_does_not_increase_polynomials) test in
-            // assumptions.rs
+            // assumptions.rs. MultiplyMany relinearizes its own products.
             Multiply => insert_relin(id, query),
             _ => GraphTransforms::default(),
         };
@@ -0,0 +1,133 @@
+use std::convert::Infallible;
+
+use sunscreen_compiler_common::{
+    forward_traverse_mut,
+    transforms::{GraphTransforms, Transform},
+    EdgeInfo, GraphQuery, NodeInfo,
+};
+use sunscreen_fhe_program::{
+    FheProgram,
+    Operation::{self, *},
+    SchemeType,
+};
+
+use petgraph::{stable_graph::NodeIndex, visit::EdgeRef, Direction};
+
+type FheGraphQuery<'a> = GraphQuery<'a, NodeInfo<Operation>, EdgeInfo>;
+
+/**
+ * Under CKKS, multiplication multiplies the scales of the operands. This
+ * pass inserts a rescale after every ciphertext-ciphertext multiplication
+ * (after its relinearization) and every ciphertext-plaintext multiplication
+ * so each ciphertext's scale returns to roughly the nominal scale.
+ *
+ * This pass does nothing for other schemes and must run after
+ * relinearizations have been inserted.
+ */
+pub fn apply_insert_rescales(ir: &mut FheProgram) {
+    if ir.data != SchemeType::Ckks {
+        return;
+    }
+
+    let insert_rescale = |id: NodeIndex, query: FheGraphQuery| {
+        let mut transforms = GraphTransforms::new();
+
+        let rescale_node = transforms.push(Transform::AddNode(NodeInfo {
+            operation: Operation::Rescale,
+        }));
+
+        transforms.push(Transform::AddEdge(
+            id.into(),
+            rescale_node.into(),
+            EdgeInfo::Unary,
+        ));
+
+        for e in query.edges_directed(id, Direction::Outgoing) {
+            let operand_type = e.weight();
+
+            transforms.push(Transform::RemoveEdge(id.into(), e.target().into()));
+            transforms.push(Transform::AddEdge(
+                rescale_node.into(),
+                e.target().into(),
+                *operand_type,
+            ));
+        }
+
+        transforms
+    };
+
+    forward_traverse_mut(&mut ir.graph.0, |query, id| {
+        let transforms = match query.get_node(id).unwrap().operation {
+            // Ciphertext multiplications are always followed by a
+            // relinearization, so rescale the relinearized result.
+            Relinearize | MultiplyPlaintext => insert_rescale(id, query),
+            _ => GraphTransforms::default(),
+        };
+
+        Ok::<_, Infallible>(transforms)
+    })
+    .unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transforms::insert_relinearizations::apply_insert_relinearizations;
+    use sunscreen_fhe_program::{FheProgramTrait, Literal as FheProgramLiteral};
+
+    fn create_test_dag(scheme: SchemeType) -> FheProgram {
+        let mut ir = FheProgram::new(scheme);
+
+        let a = ir.add_input_ciphertext(0);
+        let b = ir.add_input_ciphertext(1);
+        let l = ir.add_input_literal(FheProgramLiteral::Plaintext(vec![]));
+        let mul = ir.add_multiply(a, b);
+        let mul_pt = ir.add_multiply_plaintext(mul, l);
+        let add = ir.add_add(mul_pt, a);
+        ir.add_output_ciphertext(add);
+
+        ir
+    }
+
+    #[test]
+    fn inserts_rescales_under_ckks() {
+        let mut ir = create_test_dag(SchemeType::Ckks);
+
+        apply_insert_relinearizations(&mut ir);
+        apply_insert_rescales(&mut ir);
+
+        let query = GraphQuery::new(&ir.graph.0);
+
+        let rescale_nodes = ir
+            .graph
+            .node_indices()
+            .filter(|i| matches!(query.get_node(*i).unwrap().operation, Rescale))
+            .collect::<Vec<NodeIndex>>();
+
+        assert_eq!(rescale_nodes.len(), 2);
+
+        // Every rescale should follow a relinearization or plaintext multiply.
+        assert!(rescale_nodes.iter().all(|id| {
+            query
+                .neighbors_directed(*id, Direction::Incoming)
+                .map(|id| query.get_node(id).unwrap())
+                .all(|node| matches!(node.operation, Relinearize | MultiplyPlaintext))
+        }));
+
+        assert_eq!(ir.rescale_depth(), 2);
+    }
+
+    #[test]
+    fn does_not_rescale_under_bfv() {
+        let mut ir = create_test_dag(SchemeType::Bfv);
+
+        apply_insert_relinearizations(&mut ir);
+        apply_insert_rescales(&mut ir);
+
+        assert!(ir
+            .graph
+            .node_weights()
+            .all(|n| !matches!(n.operation, Rescale)));
+        assert_eq!(ir.rescale_depth(), 0);
+    }
+}
@@ -1,12 +1,27 @@
+mod balance_trees;
+mod insert_mod_switches;
 mod insert_relinearizations;
+mod insert_rescales;
 
+use log::debug;
 use petgraph::stable_graph::NodeIndex;
 use sunscreen_fhe_program::{FheProgram, FheProgramTrait};
 
+pub use balance_trees::{apply_balance_trees, DepthReport};
+pub use insert_mod_switches::apply_insert_mod_switches;
 use insert_relinearizations::apply_insert_relinearizations;
+use insert_rescales::apply_insert_rescales;
 
 pub fn transform_intermediate_representation(ir: &mut FheProgram) {
+    let report = apply_balance_trees(ir);
+
+    debug!(
+        "Rebalancing changed multiplicative depth from {} to {}",
+        report.before, report.after
+    );
+
     apply_insert_relinearizations(ir);
+    apply_insert_rescales(ir);
 
     // Dead code elimination.
     *ir = ir.prune(&ir.get_outputs().collect::<Vec<NodeIndex>>());
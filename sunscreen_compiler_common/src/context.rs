@@ -272,6 +272,20 @@ where
         node
     }
 
+    /**
+     * Add an operation node with any number of unordered operands to the
+     * parse graph and an edge for each operand.
+     */
+    pub fn add_unordered_operation(&mut self, operation: O, operands: &[NodeIndex]) -> NodeIndex {
+        let node = self.add_node(operation);
+
+        for operand in operands {
+            self.graph.add_edge(*operand, node, EdgeInfo::Unordered);
+        }
+
+        node
+    }
+
     /**
      * Add an edge between `from` and `to`.
      */
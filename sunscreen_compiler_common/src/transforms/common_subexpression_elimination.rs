@@ -108,6 +108,10 @@ pub fn common_subexpression_elimination<O: Operation>(
                 let (left, right) = get_binary_operands(&query, e);
 
                 match visited_nodes.get(&(left, Some(right), child_op)) {
+                    // When both operands are the same node (e.g. `a + a`),
+                    // we visit the child once per edge and must not merge
+                    // it with itself.
+                    Some(equiv_node) if *equiv_node == e => {}
                     Some(equiv_node) => {
                         move_edges(*equiv_node, e);
                     }
@@ -325,6 +329,7 @@ mod tests {
         let out_1 = fe.add_node(make_node(Operation::Add));
         let out_3 = fe.add_node(make_node(Operation::Add));
         let out_4 = fe.add_node(make_node(Operation::Add));
+        let out_5 = fe.add_node(make_node(Operation::Add));
         let out_6 = fe.add_node(make_node(Operation::Add));
 
         fe.add_edge(sub_1, out_1, EdgeInfo::Left);
@@ -333,6 +338,9 @@ mod tests {
         fe.add_edge(add_1, out_3, EdgeInfo::Right);
         fe.add_edge(sub_4, out_4, EdgeInfo::Left);
         fe.add_edge(add_4, out_4, EdgeInfo::Right);
+        // out_5 now adds mul_1 to itself.
+        fe.add_edge(mul_1, out_5, EdgeInfo::Left);
+        fe.add_edge(mul_1, out_5, EdgeInfo::Right);
         fe.add_edge(mul_1, out_6, EdgeInfo::Left);
         fe.add_edge(mul_4, out_6, EdgeInfo::Right);
 
@@ -1,6 +1,6 @@
-use petgraph::stable_graph::NodeIndex;
+use petgraph::{stable_graph::NodeIndex, visit::EdgeRef, Direction};
 
-use crate::graph::TransformList;
+use crate::graph::{GraphQuery, TransformList};
 
 /**
  * The index type for referring to nodes in the current transform list
@@ -117,6 +117,29 @@ impl<N, E> GraphTransforms<N, E> {
 
         self.transforms.len() - 1
     }
+
+    /**
+     * Pushes transforms that make every consumer of `node` use
+     * `replacement` instead, then remove `node`.
+     */
+    pub fn replace_node(
+        &mut self,
+        query: &GraphQuery<N, E>,
+        node: NodeIndex,
+        replacement: TransformNodeIndex,
+    ) where
+        E: Clone,
+    {
+        for e in query.edges_directed(node, Direction::Outgoing) {
+            self.push(Transform::AddEdge(
+                replacement,
+                e.target().into(),
+                e.weight().clone(),
+            ));
+        }
+
+        self.push(Transform::RemoveNode(node.into()));
+    }
 }
 
 impl<N, E> Default for GraphTransforms<N, E>
@@ -172,6 +172,16 @@ impl<'a> FheProgram<'a> {
                     sunscreen::SchemeType::Bfv
                 }
             }
+            Scheme::Bgv => {
+                quote! {
+                    sunscreen::SchemeType::Bgv
+                }
+            }
+            Scheme::Ckks => {
+                quote! {
+                    sunscreen::SchemeType::Ckks
+                }
+            }
         };
 
         let fhe_program_args = self.fhe_program_args();
@@ -210,7 +220,7 @@ impl<'a> FheProgram<'a> {
                     use std::mem::transmute;
                     use sunscreen::{fhe::{CURRENT_FHE_CTX, FheContext}, Error, INDEX_ARENA, Result, Params, SchemeType, Value, types::{intern::{FheProgramNode, Input, Output, Coerce}, NumCiphertexts, Type, TypeName, SwapRows, LaneCount, TypeNameInstance}};
 
-                    if SchemeType::Bfv != params.scheme_type {
+                    if #scheme_type != params.scheme_type {
                         return Err(Error::IncorrectScheme)
                     }
 
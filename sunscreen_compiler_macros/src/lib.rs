@@ -31,7 +31,7 @@ pub fn derive_typename(input: proc_macro::TokenStream) -> proc_macro::TokenStrea
  * directly or eagerly perform homomorphic operations.
  *
  * # Parameters
- * * `scheme` (required): Designates the scheme this [`fhe_program`](macro@fhe_program) uses. Today, this must be `"bfv"`.
+ * * `scheme` (required): Designates the scheme this [`fhe_program`](macro@fhe_program) uses. This must be `"bfv"`, `"bgv"`, or `"ckks"`.
  *
  * # Examples
  * ```rust,ignore
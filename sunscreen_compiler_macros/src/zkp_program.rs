@@ -271,7 +271,7 @@ fn parse_inner(_attr_params: ZkpProgramAttrs, input_fn: ItemFn) -> Result<TokenS
                     ctx.swap(&RefCell::new(None));
                 });
 
-                Ok(context.graph)
+                Ok(context.into())
             }
 
             fn name(&self) -> &str {
@@ -16,6 +16,7 @@ use petgraph::{
     graph::{Graph, NodeIndex},
     stable_graph::StableGraph,
     visit::IntoNeighbors,
+    Direction,
 };
 use serde::{Deserialize, Serialize};
 
@@ -24,13 +25,13 @@ pub use literal::*;
 pub use operation::*;
 pub use seal_fhe::SecurityLevel;
 
-use sunscreen_compiler_common::{CompilationResult, Context, EdgeInfo, NodeInfo};
+use sunscreen_compiler_common::{CompilationResult, Context, EdgeInfo, GraphQuery, NodeInfo};
 
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashMap, HashSet};
 
 #[derive(Debug, Clone, Copy, Serialize, Hash, Deserialize, PartialEq, Eq)]
 /**
- * Sunscreen supports the BFV scheme.
+ * Sunscreen supports the BFV, BGV, and CKKS schemes.
  */
 pub enum SchemeType {
     /**
@@ -89,6 +90,60 @@ pub enum SchemeType {
      * will be approximate and/or particular to the scheme parameters.
      */
     Bfv,
+
+    /**
+     *
+     * # Remarks
+     * [BGV](https://eprint.iacr.org/2011/277.pdf) is a leveled scheme over the same
+     * cyclotomic ring and plaintext space as BFV, so every encoding described under
+     * [`SchemeType::Bfv`] (and every BFV type in Sunscreen) works unchanged.
+     *
+     * The schemes differ in where they place the message. BFV scales the message
+     * into the upper bits of the ciphertext modulus, while BGV stores it in the low
+     * bits and multiplies the noise by the plaintext modulus. As a consequence, BGV
+     * noise grows with the ciphertext modulus under multiplication and one must
+     * switch to smaller moduli as the computation progresses to keep noise in check.
+     * Each switch shrinks the ciphertext and makes later operations cheaper.
+     *
+     * Pros:
+     * * Cheaper multiplication than BFV for large plaintext moduli.
+     * * Ciphertexts shrink as the computation proceeds when using modulus switching.
+     *
+     * Cons:
+     * * Without modulus switching, noise grows quadratically per multiplication, so
+     * deep programs support fewer multiplications than under BFV.
+     */
+    Bgv,
+
+    /**
+     *
+     * # Remarks
+     * [CKKS](https://eprint.iacr.org/2016/421.pdf) is a leveled scheme for approximate
+     * arithmetic on vectors of real numbers. A plaintext holds N/2 lanes (where N is the
+     * polynomial degree), each of which is a real number multiplied by a scale and rounded.
+     * Homomorphic operations execute lane-wise, as with BFV's Batched vectors, and rotations
+     * rotate the whole vector.
+     *
+     * Rather than treating noise as something that must stay below a threshold, CKKS treats it
+     * as part of the approximation error. Multiplication multiplies scales, so after each
+     * multiplication Sunscreen automatically inserts a rescale operation that divides the
+     * ciphertext (and its scale) by a prime in the coefficient modulus. Each rescale consumes
+     * a prime, so the number of primes determines the FHE program's multiplicative depth.
+     * Sunscreen chooses the modulus chain based on the deepest chain of multiplications
+     * in the FHE program.
+     *
+     * CKKS has no plaintext modulus. Under this scheme, the `plain_modulus` scheme
+     * parameter holds the base-2 logarithm of the scale instead.
+     *
+     * Pros:
+     * * Efficient arithmetic on real numbers with thousands of lanes per ciphertext.
+     * * Noise management reduces to choosing the multiplicative depth.
+     *
+     * Cons:
+     * * Results are approximate; precision depends on the scale.
+     * * No exact integer arithmetic.
+     */
+    Ckks,
 }
 
 impl From<SchemeType> for u8 {
@@ -98,6 +153,8 @@ impl From<SchemeType> for u8 {
     fn from(val: SchemeType) -> Self {
         match val {
             SchemeType::Bfv => 0,
+            SchemeType::Bgv => 1,
+            SchemeType::Ckks => 2,
         }
     }
 }
@@ -111,6 +168,8 @@ impl TryFrom<u8> for SchemeType {
     fn try_from(val: u8) -> Result<Self> {
         Ok(match val {
             0 => Self::Bfv,
+            1 => Self::Bgv,
+            2 => Self::Ckks,
             _ => Err(Error::InvalidSchemeType)?,
         })
     }
@@ -182,6 +241,16 @@ pub trait FheProgramTrait {
      */
     fn add_add(&mut self, x: NodeIndex, y: NodeIndex) -> NodeIndex;
 
+    /**
+     * Appends a multiply operation that depends on all the given operands.
+     */
+    fn add_multiply_many(&mut self, operands: &[NodeIndex]) -> NodeIndex;
+
+    /**
+     * Appends an add operation that depends on all the given operands.
+     */
+    fn add_add_many(&mut self, operands: &[NodeIndex]) -> NodeIndex;
+
     /**
      * Appends a subtract operation that depends on the operands `x` and `y`.
      */
@@ -214,6 +283,18 @@ pub trait FheProgramTrait {
      */
     fn add_relinearize(&mut self, x: NodeIndex) -> NodeIndex;
 
+    /**
+     * Appends an operation that rescales `x` to the next level of the
+     * modulus chain. Only valid under the CKKS scheme.
+     */
+    fn add_rescale(&mut self, x: NodeIndex) -> NodeIndex;
+
+    /**
+     * Appends an operation that switches `x` to the next level of the
+     * modulus chain.
+     */
+    fn add_mod_switch(&mut self, x: NodeIndex) -> NodeIndex;
+
     /**
      * Appends an operation that rotates ciphertext `x` left by the literal node at `y` places.
      *
@@ -267,6 +348,37 @@ pub trait FheProgramTrait {
      * operations.
      */
     fn requires_galois_keys(&self) -> bool;
+
+    /**
+     * The largest number of rescale operations on any path from an input to an
+     * output. Under CKKS, each rescale consumes a prime from the coefficient modulus,
+     * so the modulus chain needs at least this many primes between the first and
+     * special primes.
+     */
+    fn rescale_depth(&self) -> usize;
+
+    /**
+     * The largest number of ciphertext-ciphertext multiplications on any
+     * path from an input to an output. This largely determines how much
+     * noise budget (or, under CKKS, how many primes) a program needs.
+     */
+    fn multiplicative_depth(&self) -> usize;
+
+    /**
+     * The distinct rotation steps this FHE program performs, in ascending
+     * order. Positive steps rotate left, negative steps rotate right, and
+     * 0 denotes a row swap. Shifts by 0 don't rotate, so they don't
+     * appear. Runtimes need a Galois key for each of these and no others.
+     */
+    fn galois_steps(&self) -> Vec<i32>;
+
+    /**
+     * The number of ciphertext-ciphertext multiplications on a path
+     * through the node at `id`: 1 for [`Operation::Multiply`],
+     * `ceil(log2(k))` for an [`Operation::MultiplyMany`] with `k` operands
+     * and 0 otherwise.
+     */
+    fn multiplications(&self, id: NodeIndex) -> usize;
 }
 
 impl FheProgramTrait for FheProgram {
@@ -286,6 +398,14 @@ impl FheProgramTrait for FheProgram {
         self.add_binary_operation(Operation::Add, x, y)
     }
 
+    fn add_multiply_many(&mut self, operands: &[NodeIndex]) -> NodeIndex {
+        self.add_unordered_operation(Operation::MultiplyMany, operands)
+    }
+
+    fn add_add_many(&mut self, operands: &[NodeIndex]) -> NodeIndex {
+        self.add_unordered_operation(Operation::AddMany, operands)
+    }
+
     fn add_sub(&mut self, x: NodeIndex, y: NodeIndex) -> NodeIndex {
         self.add_binary_operation(Operation::Sub, x, y)
     }
@@ -310,6 +430,14 @@ impl FheProgramTrait for FheProgram {
         self.add_unary_operation(Operation::Relinearize, x)
     }
 
+    fn add_rescale(&mut self, x: NodeIndex) -> NodeIndex {
+        self.add_unary_operation(Operation::Rescale, x)
+    }
+
+    fn add_mod_switch(&mut self, x: NodeIndex) -> NodeIndex {
+        self.add_unary_operation(Operation::ModSwitch, x)
+    }
+
     fn add_rotate_left(&mut self, x: NodeIndex, y: NodeIndex) -> NodeIndex {
         self.add_binary_operation(Operation::ShiftLeft, x, y)
     }
@@ -396,19 +524,92 @@ impl FheProgramTrait for FheProgram {
     }
 
     fn requires_relin_keys(&self) -> bool {
-        self.graph
-            .node_weights()
-            .any(|n| matches!(n.operation, Operation::Relinearize))
-    }
-
-    fn requires_galois_keys(&self) -> bool {
         self.graph.node_weights().any(|n| {
             matches!(
                 n.operation,
-                Operation::ShiftRight | Operation::ShiftLeft | Operation::SwapRows
+                Operation::Relinearize | Operation::MultiplyMany
             )
         })
     }
+
+    fn requires_galois_keys(&self) -> bool {
+        !self.galois_steps().is_empty()
+    }
+
+    fn rescale_depth(&self) -> usize {
+        longest_path(self, |id| {
+            usize::from(matches!(self.graph[id].operation, Operation::Rescale))
+        })
+    }
+
+    fn multiplicative_depth(&self) -> usize {
+        longest_path(self, |id| self.multiplications(id))
+    }
+
+    fn galois_steps(&self) -> Vec<i32> {
+        let query = GraphQuery::new(&self.graph.0);
+
+        self.graph
+            .node_indices()
+            .filter_map(|id| {
+                let sign = match self.graph[id].operation {
+                    Operation::SwapRows => return Some(0),
+                    Operation::ShiftLeft => 1,
+                    Operation::ShiftRight => -1,
+                    _ => return None,
+                };
+
+                let (_, amount) = query.get_binary_operands(id).ok()?;
+
+                // Shifting by 0 doesn't rotate, so it needs no key. Step 0
+                // would instead request the row swap key.
+                match self.graph[amount].operation {
+                    Operation::Literal(Literal::U64(v)) if v != 0 => Some(sign * v as i32),
+                    _ => None,
+                }
+            })
+            .collect::<BTreeSet<i32>>()
+            .into_iter()
+            .collect()
+    }
+
+    fn multiplications(&self, id: NodeIndex) -> usize {
+        match self.graph[id].operation {
+            Operation::Multiply => 1,
+            // The runtime multiplies the operands in a balanced tree.
+            Operation::MultiplyMany => self
+                .graph
+                .neighbors_directed(id, Direction::Incoming)
+                .count()
+                .next_power_of_two()
+                .trailing_zeros() as usize,
+            _ => 0,
+        }
+    }
+}
+
+/**
+ * Returns the largest sum of `weight` over the nodes on any path through
+ * the given FHE program.
+ */
+fn longest_path<F>(ir: &FheProgram, weight: F) -> usize
+where
+    F: Fn(NodeIndex) -> usize,
+{
+    let mut depths = HashMap::new();
+
+    for id in toposort(&ir.graph.0, None).unwrap() {
+        let parent_depth = ir
+            .graph
+            .neighbors_directed(id, Direction::Incoming)
+            .map(|p| depths[&p])
+            .max()
+            .unwrap_or(0);
+
+        depths.insert(id, parent_depth + weight(id));
+    }
+
+    depths.values().copied().max().unwrap_or(0)
 }
 
 #[cfg(test)]
@@ -525,9 +726,72 @@ mod tests {
         assert!(eq(&pruned, &expected_ir));
     }
 
+    #[test]
+    fn multiplicative_depth_counts_only_ciphertext_multiplies() {
+        let mut ir = FheProgram::new(SchemeType::Bfv);
+
+        let ct1 = ir.add_input_ciphertext(0);
+        let ct2 = ir.add_input_ciphertext(1);
+        let pt = ir.add_input_plaintext(2);
+        let mul1 = ir.add_multiply(ct1, ct2);
+        let mul2 = ir.add_multiply(mul1, ct2);
+        let mul3 = ir.add_multiply_plaintext(mul2, pt);
+        let add = ir.add_add(mul3, ct1);
+        ir.add_output_ciphertext(add);
+
+        assert_eq!(ir.multiplicative_depth(), 2);
+    }
+
+    #[test]
+    fn multiplicative_depth_counts_multiply_many_as_a_tree() {
+        let mut ir = FheProgram::new(SchemeType::Bfv);
+
+        let inputs = (0..5)
+            .map(|i| ir.add_input_ciphertext(i))
+            .collect::<Vec<_>>();
+        let product = ir.add_multiply_many(&inputs);
+        let sum = ir.add_add_many(&[product, inputs[0], inputs[1]]);
+        ir.add_output_ciphertext(sum);
+
+        ir.validate().unwrap();
+
+        assert_eq!(ir.multiplicative_depth(), 3);
+        assert!(ir.requires_relin_keys());
+    }
+
+    #[test]
+    fn galois_steps_are_distinct_and_sorted() {
+        let mut ir = FheProgram::new(SchemeType::Bfv);
+
+        let ct = ir.add_input_ciphertext(0);
+        let two = ir.add_input_literal(Literal::U64(2));
+        let one = ir.add_input_literal(Literal::U64(1));
+        let left_1 = ir.add_rotate_left(ct, two);
+        let left_2 = ir.add_rotate_left(left_1, two);
+        let right = ir.append_rotate_right(left_2, one);
+        let swap = ir.add_unary_operation(Operation::SwapRows, right);
+        ir.add_output_ciphertext(swap);
+
+        assert_eq!(ir.galois_steps(), vec![-1, 0, 2]);
+    }
+
+    #[test]
+    fn zero_shifts_need_no_galois_keys() {
+        let mut ir = FheProgram::new(SchemeType::Bfv);
+
+        let ct = ir.add_input_ciphertext(0);
+        let zero = ir.add_input_literal(Literal::U64(0));
+        let left = ir.add_rotate_left(ct, zero);
+        let right = ir.append_rotate_right(left, zero);
+        ir.add_output_ciphertext(right);
+
+        assert_eq!(ir.galois_steps(), Vec::<i32>::new());
+        assert!(!ir.requires_galois_keys());
+    }
+
     #[test]
     fn can_roundtrip_scheme_type() {
-        let schemes = [SchemeType::Bfv];
+        let schemes = [SchemeType::Bfv, SchemeType::Bgv, SchemeType::Ckks];
         for s in schemes {
             let s_2: u8 = s.into();
             let s_2 = SchemeType::try_from(s_2).unwrap();
@@ -35,11 +35,34 @@ pub enum Operation {
      */
     Relinearize,
 
+    /**
+     * In the CKKS scheme, this operation divides a ciphertext and its scale by the
+     * last prime in its coefficient modulus. The compiler inserts these after
+     * multiplications to keep the scale from growing without bound.
+     */
+    Rescale,
+
+    /**
+     * Switches a ciphertext to the next level of the modulus chain, dropping its
+     * last prime. Under BFV and BGV, the compiler inserts these once a
+     * ciphertext's remaining operations no longer need the full coefficient
+     * modulus, shrinking outputs and speeding up later operations.
+     */
+    ModSwitch,
+
     /**
      * Multiply two ciphertext values.
      */
     Multiply,
 
+    /**
+     * Multiply any number of ciphertext values, given as unordered operands.
+     * The runtime multiplies them pairwise in a balanced tree and
+     * relinearizes each product, so the result never needs a separate
+     * [`Relinearize`](Self::Relinearize). Only BFV and BGV support this.
+     */
+    MultiplyMany,
+
     /**
      * Multiply a ciphertext and a plaintext
      */
@@ -50,6 +73,11 @@ pub enum Operation {
      */
     Add,
 
+    /**
+     * Add any number of ciphertext values, given as unordered operands.
+     */
+    AddMany,
+
     /**
      * Add a plaintext to a ciphertext.
      */
@@ -122,12 +150,17 @@ impl OperationTrait for Operation {
     fn is_unary(&self) -> bool {
         matches!(
             self,
-            Self::Negate | Self::Relinearize | Self::SwapRows | Self::OutputCiphertext
+            Self::Negate
+                | Self::Relinearize
+                | Self::Rescale
+                | Self::ModSwitch
+                | Self::SwapRows
+                | Self::OutputCiphertext
         )
     }
 
     fn is_unordered(&self) -> bool {
-        false
+        matches!(self, Self::AddMany | Self::MultiplyMany)
     }
 
     fn is_ordered(&self) -> bool {
@@ -64,6 +64,8 @@ pub(crate) fn validate_nodes(ir: &FheProgram) -> Vec<IRError> {
                 OutputType::Ciphertext,
                 OutputType::Plaintext,
             )),
+            AddMany => Some(validate_unordered_op_has_correct_operands(ir, i)),
+            MultiplyMany => Some(validate_unordered_op_has_correct_operands(ir, i)),
             ShiftLeft => None,
             ShiftRight => None,
             Negate => Some(validate_unary_op_has_correct_operands(ir, i)),
@@ -71,6 +73,8 @@ pub(crate) fn validate_nodes(ir: &FheProgram) -> Vec<IRError> {
             InputPlaintext(_) => None,
             OutputCiphertext => Some(validate_unary_op_has_correct_operands(ir, i)),
             Relinearize => Some(validate_unary_op_has_correct_operands(ir, i)),
+            Rescale => Some(validate_unary_op_has_correct_operands(ir, i)),
+            ModSwitch => Some(validate_unary_op_has_correct_operands(ir, i)),
             Literal(_) => None,
             SwapRows => None,
         };
@@ -159,6 +163,32 @@ fn validate_unary_op_has_correct_operands(ir: &FheProgram, index: NodeIndex) ->
     errors
 }
 
+fn validate_unordered_op_has_correct_operands(ir: &FheProgram, index: NodeIndex) -> Vec<NodeError> {
+    let operand_count = ir.graph.edges_directed(index, Direction::Incoming).count();
+
+    if operand_count < 2 {
+        return vec![NodeError::wrong_operand_count(2, operand_count)];
+    }
+
+    let mut errors = vec![];
+
+    for e in ir.graph.edges_directed(index, Direction::Incoming) {
+        if !matches!(e.weight(), EdgeInfo::Unordered) {
+            errors.push(NodeError::MissingOperand(EdgeInfo::Unordered));
+        } else if !ir.graph.contains_node(e.source()) {
+            errors.push(NodeError::MissingParent(e.source()));
+        } else if ir.graph[e.source()].output_type() != OutputType::Ciphertext {
+            errors.push(NodeError::parent_has_incorrect_output_type(
+                EdgeInfo::Unordered,
+                ir.graph[e.source()].output_type(),
+                OutputType::Ciphertext,
+            ));
+        }
+    }
+
+    errors
+}
+
 fn get_left_right_operands(
     ir: &FheProgram,
     index: NodeIndex,
@@ -411,4 +441,48 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn multiply_many_wrong_operands() {
+        let mut ir = FheProgram::new(SchemeType::Bfv);
+        let a = ir.add_input_ciphertext(0);
+        let b = ir.add_input_plaintext(1);
+        let c = ir.add_input_ciphertext(2);
+        let mul = ir.add_multiply_many(&[a, b, c]);
+
+        let errors = validate_ir(&ir);
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0],
+            IRError::node_error(
+                mul,
+                "MultiplyMany".to_owned(),
+                NodeError::parent_has_incorrect_output_type(
+                    EdgeInfo::Unordered,
+                    OutputType::Plaintext,
+                    OutputType::Ciphertext
+                )
+            )
+        );
+    }
+
+    #[test]
+    fn add_many_too_few_operands() {
+        let mut ir = FheProgram::new(SchemeType::Bfv);
+        let a = ir.add_input_ciphertext(0);
+        let add = ir.add_add_many(&[a]);
+
+        let errors = validate_ir(&ir);
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0],
+            IRError::node_error(
+                add,
+                "AddMany".to_owned(),
+                NodeError::wrong_operand_count(2, 1)
+            )
+        );
+    }
 }
@@ -1,6 +1,6 @@
 //! This module contains various builders for ZKPs, SDLPs, and linked proofs.
 
-use sunscreen_zkp_backend::{Proof, ZkpBackend};
+use sunscreen_zkp_backend::{Proof, WitnessReport, ZkpBackend};
 
 use crate::{marker, CompiledZkpProgram, GenericRuntime, Params, Result, ZkpProgramInput};
 
@@ -111,6 +111,17 @@ impl<'r, 'p, T: marker::Zkp, B: ZkpBackend> ProofBuilder<'r, 'p, T, B> {
             self.constant_inputs,
         )
     }
+
+    /// Run the program natively to find out why proving fails; see
+    /// [`runtime.debug_prove()`][GenericRuntime::debug_prove].
+    pub fn debug(self) -> Result<WitnessReport> {
+        self.runtime.debug_prove(
+            self.program,
+            self.private_inputs,
+            self.public_inputs,
+            self.constant_inputs,
+        )
+    }
 }
 
 /// A builder for verifying a proof.
@@ -203,6 +214,40 @@ impl<'r, 'p, 'a, T: marker::Zkp, B: ZkpBackend> VerificationBuilder<'r, 'p, 'a,
             self.constant_inputs,
         )
     }
+
+    /// Verify that each of `proofs` satisfies `self.program` with the public inputs
+    /// accompanying it; see [`runtime.verify_batch()`][GenericRuntime::verify_batch].
+    ///
+    /// # Remarks
+    /// The builder's constant inputs apply to every proof. Will error if the underlying
+    /// `verify_batch` call errors, or if a proof or public inputs were supplied to the
+    /// builder, as each proof brings its own.
+    pub fn verify_many<'b, I, P>(self, proofs: I) -> Result<()>
+    where
+        I: IntoIterator<Item = (&'b Proof, Vec<P>)>,
+        P: Into<ZkpProgramInput>,
+    {
+        if self.proof.is_some() || !self.public_inputs.is_empty() {
+            return Err(BuilderError::user_error(
+                "Pass each proof with its public inputs to `verify_many` rather than to the builder",
+            ));
+        }
+
+        let proofs = proofs
+            .into_iter()
+            .map(|(proof, public_inputs)| {
+                let public_inputs = public_inputs
+                    .into_iter()
+                    .map(P::into)
+                    .collect::<Vec<ZkpProgramInput>>();
+
+                (proof, public_inputs)
+            })
+            .collect();
+
+        self.runtime
+            .verify_batch(self.program, proofs, self.constant_inputs)
+    }
 }
 
 #[cfg(feature = "linkedproofs")]
@@ -221,7 +266,10 @@ mod linked {
     use logproof::{
         bfv_statement::{self, BfvMessage, BfvProofStatement, BfvWitness, StatementParams},
         math::Log2,
-        rings::{SealQ128_1024, SealQ128_2048, SealQ128_4096, SealQ128_8192},
+        rings::{
+            SealQ128_1024, SealQ128_16384, SealQ128_2048, SealQ128_32768, SealQ128_4096,
+            SealQ128_8192,
+        },
         Bounds, LogProofProverKnowledge, LogProofVerifierKnowledge,
     };
     use sunscreen_compiler_common::{Type, TypeName};
@@ -257,6 +305,17 @@ mod linked {
         /// elements is only a 256-bit integer, and will overflow! So, set this value carefully
         /// depending on the plaintext encoding and field element decoding logic.
         const DEGREE_BOUND: usize;
+
+        /// The number of highest-degree coefficients to link, in addition to the `DEGREE_BOUND`
+        /// lowest. Encodings that rely on the negacyclic wraparound `x^n = -1`, such as
+        /// fractional values, store nonzero coefficients here.
+        const HIGH_DEGREE_BOUND: usize = 0;
+
+        /// Returns an error if values of this type can't be linked under the given FHE
+        /// parameters.
+        fn validate_link_params(_params: &Params) -> Result<()> {
+            Ok(())
+        }
     }
 
     /// We pass this around for both plain and linked messages. This type exists because we need
@@ -770,6 +829,8 @@ mod linked {
                 SealQ128_2048::Q => Ok(self.build_sdlp_pk_generic::<1, SealQ128_2048>()?.into()),
                 SealQ128_4096::Q => Ok(self.build_sdlp_pk_generic::<2, SealQ128_4096>()?.into()),
                 SealQ128_8192::Q => Ok(self.build_sdlp_pk_generic::<3, SealQ128_8192>()?.into()),
+                SealQ128_16384::Q => Ok(self.build_sdlp_pk_generic::<7, SealQ128_16384>()?.into()),
+                SealQ128_32768::Q => Ok(self.build_sdlp_pk_generic::<13, SealQ128_32768>()?.into()),
                 _ => Err(BuilderError::UnsupportedParameters(Box::new(
                     params.clone(),
                 ))),
@@ -813,6 +874,8 @@ mod linked {
         where
             P: LinkWithZkp + TryIntoPlaintext + TypeName,
         {
+            P::validate_link_params(self.runtime.params())?;
+
             // The user intends to link this message, so add a more conservative bound
             let bounds = mk_bounds::<P>(self.runtime.params());
             let (ct, msg) = self.encrypt_returning_msg_internal(
@@ -837,6 +900,8 @@ mod linked {
         where
             P: LinkWithZkp + TryIntoPlaintext + TypeName,
         {
+            P::validate_link_params(self.runtime.params())?;
+
             // The user intends to link this message, so add a more conservative bound
             let bounds = mk_bounds::<P>(self.runtime.params());
             let (ct, msg) = self.encrypt_returning_msg_internal(
@@ -864,6 +929,8 @@ mod linked {
         where
             P: LinkWithZkp + TryIntoPlaintext + TryFromPlaintext + TypeName,
         {
+            P::validate_link_params(self.runtime.params())?;
+
             let bounds = mk_bounds::<P>(self.runtime.params());
             let (pt, msg) = self.decrypt_internal::<P>(ciphertext, private_key, Some(bounds))?;
             let zkp_type = P::ZkpType::<BulletproofsFieldSpec>::type_name();
@@ -945,8 +1012,17 @@ mod linked {
     }
 
     fn mk_bounds<P: LinkWithZkp>(params: &Params) -> Bounds {
-        let mut bounds = vec![params.plain_modulus.ceil_log2(); P::DEGREE_BOUND];
-        bounds.resize(params.lattice_dimension as usize, 0);
+        let bound = params.plain_modulus.ceil_log2();
+        let mut bounds = vec![0; params.lattice_dimension as usize];
+
+        for b in bounds.iter_mut().take(P::DEGREE_BOUND) {
+            *b = bound;
+        }
+
+        for b in bounds.iter_mut().rev().take(P::HIGH_DEGREE_BOUND) {
+            *b = bound;
+        }
+
         Bounds(bounds)
     }
 
@@ -1246,6 +1322,8 @@ mod linked {
                 SealQ128_2048::Q => Ok(self.build_sdlp_vk_generic::<1, SealQ128_2048>()?.into()),
                 SealQ128_4096::Q => Ok(self.build_sdlp_vk_generic::<2, SealQ128_4096>()?.into()),
                 SealQ128_8192::Q => Ok(self.build_sdlp_vk_generic::<3, SealQ128_8192>()?.into()),
+                SealQ128_16384::Q => Ok(self.build_sdlp_vk_generic::<7, SealQ128_16384>()?.into()),
+                SealQ128_32768::Q => Ok(self.build_sdlp_vk_generic::<13, SealQ128_32768>()?.into()),
                 _ => Err(BuilderError::UnsupportedParameters(Box::new(
                     params.clone(),
                 ))),
@@ -1284,6 +1362,8 @@ mod linked {
         where
             P: LinkWithZkp,
         {
+            P::validate_link_params(self.runtime.params())?;
+
             self.encrypt_internal(
                 ciphertext,
                 public_key,
@@ -1299,6 +1379,8 @@ mod linked {
         where
             P: LinkWithZkp,
         {
+            P::validate_link_params(self.runtime.params())?;
+
             self.encrypt_symmetric_internal(ciphertext, Some(mk_bounds::<P>(self.runtime.params())))
         }
 
@@ -1307,6 +1389,8 @@ mod linked {
         where
             P: LinkWithZkp,
         {
+            P::validate_link_params(self.runtime.params())?;
+
             self.decrypt_internal(ciphertext, Some(mk_bounds::<P>(self.runtime.params())))
         }
 
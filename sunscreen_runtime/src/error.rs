@@ -109,6 +109,12 @@ pub enum Error {
     #[error("Bincode serialization failed: {0}")]
     BincodeError(Box<String>),
 
+    /**
+     * Failed to encode or decode a compact container.
+     */
+    #[error("Wire format error: {0}")]
+    WireFormatError(Box<crate::WireFormatError>),
+
     /**
      * Called [`inner_as_seal_plaintext`](crate::InnerPlaintext.inner_as_seal_plaintext)
      * on non-Seal plaintext.
@@ -128,6 +134,18 @@ pub enum Error {
     #[error("Builder error: {0}")]
     BuilderError(#[from] crate::builder::BuilderError),
 
+    /**
+     * An error in a multiparty key generation or threshold decryption protocol.
+     */
+    #[error("Threshold error: {0}")]
+    ThresholdError(#[from] crate::threshold::ThresholdError),
+
+    /**
+     * An error when creating or using a key switching key.
+     */
+    #[error("Key switch error: {0}")]
+    KeySwitchError(#[from] crate::key_switch::KeySwitchError),
+
     /// Error when proving or verifying a linked proof.
     #[cfg(feature = "linkedproofs")]
     #[error("Linked proof error: {0}")]
@@ -0,0 +1,328 @@
+use seal_fhe::{BFVEvaluator, Evaluator, KeyGenerator, RelinearizationKeys};
+use serde::{Deserialize, Serialize};
+use sunscreen_fhe_program::SchemeType;
+
+use crate::{
+    marker,
+    threshold::{relin_keys_from_polys, Basis},
+    Ciphertext, Error, GenericRuntime, InnerCiphertext, PrivateKey, PublicKey, Result, WithContext,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+/**
+ * An error when creating or using a [`KeySwitchKey`].
+ */
+pub enum KeySwitchError {
+    /**
+     * Key switching is only supported under the BFV scheme.
+     */
+    #[error("Key switching requires the BFV scheme")]
+    UnsupportedScheme,
+
+    /**
+     * The parameters have a single coefficient modulus, so they don't support
+     * key switching.
+     */
+    #[error("The parameters don't support key switching")]
+    KeySwitchingUnsupported,
+
+    /**
+     * Key switching requires ciphertexts with 2 polynomials. Relinearize the
+     * ciphertext first.
+     */
+    #[error("Key switching requires relinearized ciphertexts")]
+    UnrelinearizedCiphertext,
+}
+
+#[derive(Clone, Deserialize, PartialEq, Serialize)]
+/**
+ * A key that moves ciphertexts encrypted under one party's key to another
+ * party's key without decrypting them. Create one with
+ * [`generate_key_switch_key`](GenericRuntime::generate_key_switch_key) and use
+ * it with [`key_switch`](GenericRuntime::key_switch).
+ *
+ * # Remarks
+ * This key may be given to an untrusted party (e.g. a proxy) without risk of
+ * compromising either party's data; it only lets its holder switch
+ * ciphertexts. Anyone holding it can however switch every ciphertext
+ * encrypted under the source key, so only create one when the target party
+ * may read all of them.
+ */
+pub struct KeySwitchKey {
+    key: WithContext<RelinearizationKeys>,
+}
+
+impl<T, B> GenericRuntime<T, B>
+where
+    T: marker::Fhe,
+{
+    /**
+     * Creates a key that switches ciphertexts encrypted under `source`'s public
+     * key to ciphertexts encrypted under `target`.
+     *
+     * # Remarks
+     * Only the source party's [`PrivateKey`] is needed, so the target party
+     * needn't take part beyond sharing its [`PublicKey`].
+     */
+    pub fn generate_key_switch_key(
+        &self,
+        source: &PrivateKey,
+        target: &PublicKey,
+    ) -> Result<KeySwitchKey> {
+        let basis = self.key_switch_basis()?;
+        let decomposition = basis.decomposition_count()?;
+        let context = self.context();
+
+        if source.0.params != *self.params() || target.public_key.params != *self.params() {
+            return Err(Error::ParameterMismatch);
+        }
+
+        let s = source.0.data.rns_data()?;
+        let mut p0 = target.public_key.data.rns_data()?;
+
+        if s.len() != basis.len() || p0.len() != 2 * basis.len() {
+            return Err(Error::ParameterMismatch);
+        }
+
+        let p1 = p0.split_off(basis.len());
+
+        let mut k0 = Vec::with_capacity(decomposition);
+        let mut k1 = Vec::with_capacity(decomposition);
+
+        // Encrypts w_j * s under the target's public key. The result decrypts
+        // under the target's secret key s' as k0 + k1 * s' = w_j * s + noise, so
+        // SEAL's relinearization switches the s component of (c0, 0, c1) to s'.
+        for j in 0..decomposition {
+            let u = KeyGenerator::new(context)?.secret_key().rns_data()?;
+
+            // p0 * u + e + w_j * s
+            let mut x = basis.mul(&p0, &u);
+            basis.add_assign(&mut x, &context.sample_error()?);
+            basis.add_gadget(&mut x, &s, j);
+
+            // p1 * u + e
+            let mut y = basis.mul(&p1, &u);
+            basis.add_assign(&mut y, &context.sample_error()?);
+
+            k0.push(x);
+            k1.push(y);
+        }
+
+        Ok(KeySwitchKey {
+            key: WithContext {
+                params: self.params().clone(),
+                data: relin_keys_from_polys(context, &basis, &k0, &k1)?,
+            },
+        })
+    }
+
+    /**
+     * Switches `ciphertext` to the target key of `key`. The result has the same
+     * type as `ciphertext` and decrypts to the same value under the target's
+     * [`PrivateKey`].
+     *
+     * # Remarks
+     * Key switching adds a fixed amount of noise, independent of the noise
+     * already in `ciphertext`. Because the key is encrypted under the target's
+     * public key, this is more than a relinearization adds: it costs a fresh
+     * ciphertext several bits of noise budget, but is negligible for
+     * ciphertexts that went through a multiplication. Reserve that budget with
+     * the compiler's `additional_noise_budget` option when compiling FHE
+     * programs whose outputs you'll switch.
+     */
+    pub fn key_switch(&self, ciphertext: &Ciphertext, key: &KeySwitchKey) -> Result<Ciphertext> {
+        let basis = self.key_switch_basis()?;
+        let context = self.context();
+
+        if key.key.params != *self.params() {
+            return Err(Error::ParameterMismatch);
+        }
+
+        let evaluator = BFVEvaluator::new(context)?;
+
+        let ciphertexts = match &ciphertext.inner {
+            InnerCiphertext::Seal(ciphertexts) => ciphertexts
+                .iter()
+                .map(|c| {
+                    if c.params != *self.params() {
+                        return Err(Error::ParameterMismatch);
+                    }
+
+                    if c.data.num_polynomials() != 2 {
+                        return Err(KeySwitchError::UnrelinearizedCiphertext.into());
+                    }
+
+                    let limbs = c.data.coeff_modulus_size() as usize;
+
+                    if limbs == 0 || limbs > basis.moduli.len() {
+                        return Err(Error::ParameterMismatch);
+                    }
+
+                    let len = basis.degree * limbs;
+                    let mut c0 = c.data.rns_data()?;
+
+                    if c0.len() != 2 * len {
+                        return Err(Error::ParameterMismatch);
+                    }
+
+                    let c1 = c0.split_off(len);
+
+                    // Relinearizing (c0, 0, c1) switches its c1 * s term to the
+                    // target key.
+                    let mut extended = c.data.clone();
+                    extended.set_rns_data(context, 3, &[c0, vec![0; len], c1].concat())?;
+
+                    Ok(WithContext {
+                        params: self.params().clone(),
+                        data: evaluator.relinearize(&extended, &key.key.data)?,
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?,
+        };
+
+        Ok(Ciphertext {
+            data_type: ciphertext.data_type.clone(),
+            inner: InnerCiphertext::Seal(ciphertexts),
+        })
+    }
+
+    fn key_switch_basis(&self) -> Result<Basis> {
+        let params = self.params();
+
+        if params.scheme_type != SchemeType::Bfv {
+            return Err(KeySwitchError::UnsupportedScheme.into());
+        }
+
+        if params.coeff_modulus.len() < 2 {
+            return Err(KeySwitchError::KeySwitchingUnsupported.into());
+        }
+
+        Ok(Basis {
+            degree: params.lattice_dimension as usize,
+            moduli: params.coeff_modulus.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+    use seal_fhe::{
+        BFVScalarEncoder, Ciphertext as SealCiphertext, CoefficientModulus, Decryptor, Encryptor,
+        SecurityLevel,
+    };
+    use semver::Version;
+
+    /**
+     * Encrypts 7 under a new key, applies `compute` and switches the result
+     * to another key. Returns the decrypted result and the noise budget
+     * before and after switching.
+     */
+    fn switch_budgets<F>(compute: F) -> (u64, u32, u32)
+    where
+        F: FnOnce(&BFVEvaluator, SealCiphertext, &RelinearizationKeys) -> SealCiphertext,
+    {
+        let runtime = Runtime::new_fhe(&Params {
+            lattice_dimension: 8192,
+            plain_modulus: 1024,
+            coeff_modulus: CoefficientModulus::bfv_default(8192, SecurityLevel::TC128)
+                .unwrap()
+                .iter()
+                .map(|c| c.value())
+                .collect(),
+            security_level: SecurityLevel::TC128,
+            scheme_type: SchemeType::Bfv,
+        })
+        .unwrap();
+        let context = runtime.context();
+
+        let (alice_public, alice_private) = runtime.generate_keys().unwrap();
+        let (bob_public, bob_private) = runtime.generate_keys().unwrap();
+        let key = runtime
+            .generate_key_switch_key(&alice_private, &bob_public)
+            .unwrap();
+
+        let encoder = BFVScalarEncoder::new();
+        let x = Encryptor::with_public_key(context, &alice_public.public_key.data)
+            .unwrap()
+            .encrypt(&encoder.encode_unsigned(7).unwrap())
+            .unwrap();
+        let relin_keys = KeyGenerator::new_from_secret_key(context, &alice_private.0.data)
+            .unwrap()
+            .create_relinearization_keys()
+            .unwrap();
+
+        let c = Ciphertext {
+            data_type: Type {
+                name: "Test".to_owned(),
+                version: Version::new(1, 0, 0),
+                is_encrypted: true,
+            },
+            inner: InnerCiphertext::Seal(vec![WithContext {
+                params: runtime.params().clone(),
+                data: compute(&BFVEvaluator::new(context).unwrap(), x, &relin_keys),
+            }]),
+        };
+
+        let switched = runtime.key_switch(&c, &key).unwrap();
+
+        let before = runtime.measure_noise_budget(&c, &alice_private).unwrap();
+        let after = runtime
+            .measure_noise_budget(&switched, &bob_private)
+            .unwrap();
+
+        let result = match &switched.inner {
+            InnerCiphertext::Seal(c) => Decryptor::new(context, &bob_private.0.data)
+                .unwrap()
+                .decrypt(&c[0].data)
+                .unwrap(),
+        };
+
+        (encoder.decode_unsigned(&result).unwrap(), before, after)
+    }
+
+    #[test]
+    fn key_switching_fresh_ciphertext_costs_a_few_bits() {
+        let (result, before, after) = switch_budgets(|_, x, _| x);
+
+        assert_eq!(result, 7);
+        assert!(after > 0);
+        assert!(before.saturating_sub(after) <= 10);
+    }
+
+    #[test]
+    fn key_switching_noise_is_negligible_after_multiplication() {
+        let (result, before, after) = switch_budgets(|evaluator, x, relin_keys| {
+            let square = evaluator.multiply(&x, &x).unwrap();
+
+            evaluator.relinearize(&square, relin_keys).unwrap()
+        });
+
+        assert_eq!(result, 49);
+        assert!(before.saturating_sub(after) <= 1);
+    }
+
+    #[test]
+    fn key_switching_requires_bfv() {
+        let runtime = Runtime::new_fhe(&Params {
+            lattice_dimension: 4096,
+            plain_modulus: 0,
+            coeff_modulus: CoefficientModulus::create(4096, &[36, 36, 37])
+                .unwrap()
+                .iter()
+                .map(|c| c.value())
+                .collect(),
+            security_level: SecurityLevel::TC128,
+            scheme_type: SchemeType::Ckks,
+        })
+        .unwrap();
+
+        let (public_key, private_key) = runtime.generate_keys().unwrap();
+
+        assert!(matches!(
+            runtime.generate_key_switch_key(&private_key, &public_key),
+            Err(Error::KeySwitchError(KeySwitchError::UnsupportedScheme))
+        ));
+    }
+}
@@ -7,8 +7,25 @@ use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Deserialize, PartialEq, Serialize)]
 /**
- * A bundle of public keys. These may be freely shared with other parties without
- * risk of compromising data security.
+ * The public key used to encrypt data. This may be freely shared with other
+ * parties without risk of compromising data security.
+ *
+ * # Remarks
+ * Encrypting data only requires this key. The additional keys needed to run
+ * FHE programs live in [`EvaluationKeys`], which only the party running
+ * FHE programs needs.
+ */
+pub struct PublicKey {
+    /**
+     * The public key used for encryption operations.
+     */
+    pub public_key: WithContext<SealPublicKey>,
+}
+
+#[derive(Clone, Deserialize, PartialEq, Serialize)]
+/**
+ * The keys needed to run FHE programs. These may be freely shared with other
+ * parties without risk of compromising data security.
  *
  * # Remarks
  * In traditional asymmetric cryptography (e.g. RSA, ECC), schemes contain only public
@@ -21,13 +38,12 @@ use serde::{Deserialize, Serialize};
  * they are generally used for operations other than encryption. For example,
  * [`RelinearizationKeys`] are used in the BFV and CKKS schemes to reduce noise growth
  * and prevent ciphertext size growth after multiplication.
+ *
+ * Unlike the [`PublicKey`], which everyone who encrypts needs, these keys can be large.
+ * [`generate_evaluation_keys`](crate::GenericRuntime::generate_evaluation_keys) only
+ * creates the keys the given FHE programs require.
  */
-pub struct PublicKey {
-    /**
-     * The public key used for encryption operations.
-     */
-    pub public_key: WithContext<SealPublicKey>,
-
+pub struct EvaluationKeys {
     /**
      * Galois keys are used in BFV and CKKS schemes to rotate Batched vectors.
      *
@@ -57,7 +73,54 @@ mod tests {
     use super::*;
     use crate::*;
     use seal_fhe::{CoefficientModulus, PlainModulus, SecurityLevel, ToBytes};
-    use sunscreen_fhe_program::SchemeType;
+    use sunscreen_fhe_program::{FheProgram, SchemeType};
+
+    fn batching_runtime() -> FheRuntime {
+        Runtime::new_fhe(&Params {
+            lattice_dimension: 8192,
+            security_level: SecurityLevel::TC128,
+            plain_modulus: PlainModulus::batching(8192, 20).unwrap().value(),
+            scheme_type: SchemeType::Bfv,
+            coeff_modulus: CoefficientModulus::bfv_default(8192, SecurityLevel::TC128)
+                .unwrap()
+                .iter()
+                .map(|x| x.value())
+                .collect(),
+        })
+        .unwrap()
+    }
+
+    fn program_requiring(
+        params: &Params,
+        required_keys: Vec<RequiredKeys>,
+        galois_steps: Vec<i32>,
+    ) -> CompiledFheProgram {
+        CompiledFheProgram {
+            fhe_program_fn: FheProgram::new(SchemeType::Bfv),
+            metadata: FheProgramMetadata {
+                params: params.clone(),
+                signature: CallSignature {
+                    arguments: vec![],
+                    returns: vec![],
+                    num_ciphertexts: vec![],
+                },
+                required_keys,
+                galois_steps,
+            },
+        }
+    }
+
+    fn all_evaluation_keys(runtime: &FheRuntime, private_key: &PrivateKey) -> EvaluationKeys {
+        let program = program_requiring(
+            runtime.params(),
+            vec![RequiredKeys::Galois, RequiredKeys::Relin],
+            vec![1],
+        );
+
+        runtime
+            .generate_evaluation_keys(private_key, [&program])
+            .unwrap()
+    }
 
     #[test]
     fn can_roundtrip_seal_public_key() {
@@ -81,7 +144,6 @@ mod tests {
 
         let public_2 = PublicKey {
             public_key: enc_key,
-            ..public_key
         };
 
         assert_eq!(
@@ -92,95 +154,67 @@ mod tests {
 
     #[test]
     fn can_roundtrip_seal_galois_keys() {
-        let runtime = Runtime::new_fhe(&Params {
-            lattice_dimension: 8192,
-            security_level: SecurityLevel::TC128,
-            plain_modulus: PlainModulus::batching(8192, 20).unwrap().value(),
-            scheme_type: SchemeType::Bfv,
-            coeff_modulus: CoefficientModulus::bfv_default(8192, SecurityLevel::TC128)
-                .unwrap()
-                .iter()
-                .map(|x| x.value())
-                .collect(),
-        })
-        .unwrap();
+        let runtime = batching_runtime();
 
-        let (public_key, _) = runtime.generate_keys().unwrap();
+        let (_, private_key) = runtime.generate_keys().unwrap();
+        let eval_keys = all_evaluation_keys(&runtime, &private_key);
 
-        let data = bincode::serialize(&public_key.galois_key.as_ref().unwrap()).unwrap();
+        let data = bincode::serialize(&eval_keys.galois_key.as_ref().unwrap()).unwrap();
         let galois_key: WithContext<GaloisKeys> = bincode::deserialize(&data).unwrap();
 
-        let public_2 = PublicKey {
+        let eval_keys_2 = EvaluationKeys {
             galois_key: Some(galois_key),
-            ..public_key
+            ..eval_keys.clone()
         };
 
         assert_eq!(
-            public_key.galois_key.unwrap().data.as_bytes(),
-            public_2.galois_key.unwrap().data.as_bytes()
+            eval_keys.galois_key.unwrap().data.as_bytes(),
+            eval_keys_2.galois_key.unwrap().data.as_bytes()
         );
     }
 
     #[test]
     fn can_roundtrip_seal_relin_keys() {
-        let runtime = Runtime::new_fhe(&Params {
-            lattice_dimension: 8192,
-            security_level: SecurityLevel::TC128,
-            plain_modulus: PlainModulus::batching(8192, 20).unwrap().value(),
-            scheme_type: SchemeType::Bfv,
-            coeff_modulus: CoefficientModulus::bfv_default(8192, SecurityLevel::TC128)
-                .unwrap()
-                .iter()
-                .map(|x| x.value())
-                .collect(),
-        })
-        .unwrap();
+        let runtime = batching_runtime();
 
-        let (public_key, _) = runtime.generate_keys().unwrap();
+        let (_, private_key) = runtime.generate_keys().unwrap();
+        let eval_keys = all_evaluation_keys(&runtime, &private_key);
 
-        let data = serde_json::to_string(&public_key.relin_key.as_ref().unwrap()).unwrap();
+        let data = serde_json::to_string(&eval_keys.relin_key.as_ref().unwrap()).unwrap();
         let relin_keys: WithContext<RelinearizationKeys> = serde_json::from_str(&data).unwrap();
 
-        let public_2 = PublicKey {
+        let eval_keys_2 = EvaluationKeys {
             relin_key: Some(relin_keys),
-            ..public_key
+            ..eval_keys.clone()
         };
 
         assert_eq!(
-            public_key.relin_key.unwrap().data.as_bytes(),
-            public_2.relin_key.unwrap().data.as_bytes()
+            eval_keys.relin_key.unwrap().data.as_bytes(),
+            eval_keys_2.relin_key.unwrap().data.as_bytes()
         );
     }
 
     #[test]
     fn can_roundtrip_all_keys() {
-        let runtime = Runtime::new_fhe(&Params {
-            lattice_dimension: 8192,
-            security_level: SecurityLevel::TC128,
-            plain_modulus: PlainModulus::batching(8192, 20).unwrap().value(),
-            scheme_type: SchemeType::Bfv,
-            coeff_modulus: CoefficientModulus::bfv_default(8192, SecurityLevel::TC128)
-                .unwrap()
-                .iter()
-                .map(|x| x.value())
-                .collect(),
-        })
-        .unwrap();
+        let runtime = batching_runtime();
 
         let (public_key, private_key) = runtime.generate_keys().unwrap();
+        let eval_keys = all_evaluation_keys(&runtime, &private_key);
 
         let data = serde_json::to_string(&public_key).unwrap();
         let sk_data = serde_json::to_string(&private_key).unwrap();
+        let eval_data = serde_json::to_string(&eval_keys).unwrap();
         let public_2: PublicKey = serde_json::from_str(&data).unwrap();
         let private_2: PrivateKey = serde_json::from_str(&sk_data).unwrap();
+        let eval_keys_2: EvaluationKeys = serde_json::from_str(&eval_data).unwrap();
 
         assert_eq!(
-            public_key.relin_key.unwrap().data.as_bytes(),
-            public_2.relin_key.unwrap().data.as_bytes()
+            eval_keys.relin_key.unwrap().data.as_bytes(),
+            eval_keys_2.relin_key.unwrap().data.as_bytes()
         );
         assert_eq!(
-            public_key.galois_key.unwrap().data.as_bytes(),
-            public_2.galois_key.unwrap().data.as_bytes()
+            eval_keys.galois_key.unwrap().data.as_bytes(),
+            eval_keys_2.galois_key.unwrap().data.as_bytes()
         );
         assert_eq!(
             public_key.public_key.data.as_bytes(),
@@ -188,4 +222,27 @@ mod tests {
         );
         assert_eq!(private_key.0.as_bytes(), private_2.0.as_bytes());
     }
+
+    #[test]
+    fn evaluation_keys_only_include_required_keys() {
+        let runtime = batching_runtime();
+        let (_, private_key) = runtime.generate_keys().unwrap();
+
+        let no_keys = program_requiring(runtime.params(), vec![], vec![]);
+        let relin = program_requiring(runtime.params(), vec![RequiredKeys::Relin], vec![]);
+
+        let eval_keys = runtime
+            .generate_evaluation_keys(&private_key, [&no_keys])
+            .unwrap();
+
+        assert!(eval_keys.galois_key.is_none());
+        assert!(eval_keys.relin_key.is_none());
+
+        let eval_keys = runtime
+            .generate_evaluation_keys(&private_key, [&no_keys, &relin])
+            .unwrap();
+
+        assert!(eval_keys.galois_key.is_none());
+        assert!(eval_keys.relin_key.is_some());
+    }
 }
@@ -6,6 +6,7 @@
 mod array;
 mod builder;
 mod error;
+mod key_switch;
 mod keys;
 #[cfg(feature = "linkedproofs")]
 mod linked;
@@ -13,6 +14,8 @@ mod metadata;
 mod run;
 mod runtime;
 mod serialization;
+mod threshold;
+mod wire;
 
 use std::sync::Arc;
 
@@ -22,13 +25,16 @@ use sunscreen_zkp_backend::BigInt;
 
 pub use builder::*;
 pub use error::*;
+pub use key_switch::*;
 pub use keys::*;
 #[cfg(feature = "linkedproofs")]
 pub use linked::*;
 pub use metadata::*;
 pub use run::*;
 pub use runtime::*;
-pub use serialization::WithContext;
+pub use serialization::{Serializable, WithContext};
+pub use threshold::*;
+pub use wire::*;
 
 #[derive(Debug, Clone, PartialEq, Hash, Serialize, Deserialize, Eq)]
 /**
@@ -354,6 +360,11 @@ pub trait FheType:
  */
 pub trait BfvType: FheType {}
 
+/**
+ * Denotes the given type is valid under the CKKS scheme.
+ */
+pub trait CkksType: FheType {}
+
 /**
  * A trait the gives a name an version to a given type
  */
@@ -9,7 +9,10 @@ use log::trace;
 use logproof::{
     linear_algebra::Matrix,
     math::rand256,
-    rings::{ZqSeal128_1024, ZqSeal128_2048, ZqSeal128_4096, ZqSeal128_8192},
+    rings::{
+        ZqSeal128_1024, ZqSeal128_16384, ZqSeal128_2048, ZqSeal128_32768, ZqSeal128_4096,
+        ZqSeal128_8192,
+    },
     Bounds, InnerProductVerifierKnowledge, LogProof, LogProofGenerators, LogProofProverKnowledge,
     LogProofVerifierKnowledge, ProofError,
 };
@@ -334,6 +337,8 @@ pub(crate) enum SdlpProverKnowledgeInternal {
     LP2(LogProofProverKnowledge<ZqSeal128_2048>),
     LP3(LogProofProverKnowledge<ZqSeal128_4096>),
     LP4(LogProofProverKnowledge<ZqSeal128_8192>),
+    LP5(LogProofProverKnowledge<ZqSeal128_16384>),
+    LP6(LogProofProverKnowledge<ZqSeal128_32768>),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -342,6 +347,8 @@ pub(crate) enum SdlpVerifierKnowledgeInternal {
     LP2(LogProofVerifierKnowledge<ZqSeal128_2048>),
     LP3(LogProofVerifierKnowledge<ZqSeal128_4096>),
     LP4(LogProofVerifierKnowledge<ZqSeal128_8192>),
+    LP5(LogProofVerifierKnowledge<ZqSeal128_16384>),
+    LP6(LogProofVerifierKnowledge<ZqSeal128_32768>),
 }
 
 macro_rules! impl_from {
@@ -365,10 +372,12 @@ impl_from!(ZqSeal128_1024, LP1);
 impl_from!(ZqSeal128_2048, LP2);
 impl_from!(ZqSeal128_4096, LP3);
 impl_from!(ZqSeal128_8192, LP4);
+impl_from!(ZqSeal128_16384, LP5);
+impl_from!(ZqSeal128_32768, LP6);
 
 macro_rules! seq_zq {
     ($block:tt) => (
-        seq!(N in 1..=4 {
+        seq!(N in 1..=6 {
             #[allow(unused_braces)]
             $block
         })
@@ -1,10 +1,13 @@
+use std::collections::HashMap;
+
+use petgraph::stable_graph::NodeIndex;
 use rlp::encode_list;
 use seal_fhe::SecurityLevel;
 pub use semver::Version;
 use serde::{Deserialize, Serialize};
 use sunscreen_compiler_common::Type;
 use sunscreen_fhe_program::{FheProgram, SchemeType};
-use sunscreen_zkp_backend::CompiledZkpProgram as ZkpProgram;
+use sunscreen_zkp_backend::{CompiledZkpProgram as ZkpProgram, SourceLocation};
 
 use crate::{Error, Result};
 
@@ -193,6 +196,13 @@ pub struct FheProgramMetadata {
      * The set of keys required to run the FHE program.
      */
     pub required_keys: Vec<RequiredKeys>,
+
+    /**
+     * The distinct rotation steps the FHE program performs. A runtime
+     * only needs Galois keys for these steps to run the program.
+     */
+    #[serde(default)]
+    pub galois_steps: Vec<i32>,
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -233,6 +243,10 @@ pub struct CompiledZkpProgram {
     /// Information about the FHE program, including its call signature and the scheme
     /// parameters needed for ZKP programs with SDLP-linked inputs.
     pub metadata: ZkpProgramMetadata,
+
+    /// Where in the `#[zkp_program]` each node in `zkp_program_fn` came from. Used to
+    /// report failing constraints when debugging proofs.
+    pub source_locations: HashMap<NodeIndex, SourceLocation>,
 }
 
 #[cfg(test)]
@@ -1,7 +1,7 @@
 use crate::{InnerPlaintext, SealData};
 use static_assertions::const_assert;
 use sunscreen_compiler_common::{GraphQuery, GraphQueryError};
-use sunscreen_fhe_program::{FheProgram, FheProgramTrait, Literal, Operation::*};
+use sunscreen_fhe_program::{FheProgram, FheProgramTrait, Literal, Operation::*, SchemeType};
 
 use crossbeam::atomic::AtomicCell;
 use petgraph::{stable_graph::NodeIndex, Direction};
@@ -9,6 +9,7 @@ use petgraph::{stable_graph::NodeIndex, Direction};
 use std::borrow::Cow;
 #[cfg(target_arch = "wasm32")]
 use std::collections::VecDeque;
+use std::collections::{HashMap, HashSet};
 #[cfg(not(target_arch = "wasm32"))]
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
@@ -157,6 +158,23 @@ pub unsafe fn run_program_unchecked<E: Evaluator + Sync + Send>(
         data.push(AtomicCell::new(None));
     }
 
+    let is_ckks = ir.data == SchemeType::Ckks;
+
+    // Under BFV, rotations sharing a source run together when the source
+    // completes, so the evaluator decomposes the source once for all of them.
+    // Other evaluators don't hoist, so their rotations stay separate tasks
+    // and run in parallel.
+    let rotation_groups = if ir.data == SchemeType::Bfv {
+        rotation_groups(ir)?
+    } else {
+        HashMap::new()
+    };
+    let hoisted = rotation_groups
+        .values()
+        .flatten()
+        .map(|(id, _)| *id)
+        .collect::<HashSet<NodeIndex>>();
+
     traverse(
         ir,
         |index| {
@@ -164,6 +182,7 @@ pub unsafe fn run_program_unchecked<E: Evaluator + Sync + Send>(
             let query = GraphQuery::new(&ir.graph.0);
 
             match &node.operation {
+                ShiftLeft | ShiftRight if hoisted.contains(&index) => {}
                 InputCiphertext(id) => {
                     data[index.index()].store(Some(inputs[*id].clone()));
                 }
@@ -182,13 +201,19 @@ pub unsafe fn run_program_unchecked<E: Evaluator + Sync + Send>(
                         ),
                     };
 
-                    let c = evaluator.rotate_rows(
-                        a,
-                        b,
-                        galois_keys
-                            .as_ref()
-                            .ok_or(FheProgramRunFailure::MissingGaloisKeys)?,
-                    )?;
+                    // Shifting by 0 doesn't rotate, so programs that only
+                    // do so have no Galois keys.
+                    let c = if b == 0 {
+                        a.clone()
+                    } else {
+                        evaluator.rotate_rows(
+                            a,
+                            b,
+                            galois_keys
+                                .as_ref()
+                                .ok_or(FheProgramRunFailure::MissingGaloisKeys)?,
+                        )?
+                    };
 
                     data[index.index()].store(Some(Arc::new(c.into())));
                 }
@@ -204,13 +229,19 @@ pub unsafe fn run_program_unchecked<E: Evaluator + Sync + Send>(
                         ),
                     };
 
-                    let c = evaluator.rotate_rows(
-                        a,
-                        -b,
-                        galois_keys
-                            .as_ref()
-                            .ok_or(FheProgramRunFailure::MissingGaloisKeys)?,
-                    )?;
+                    // Shifting by 0 doesn't rotate, so programs that only
+                    // do so have no Galois keys.
+                    let c = if b == 0 {
+                        a.clone()
+                    } else {
+                        evaluator.rotate_rows(
+                            a,
+                            -b,
+                            galois_keys
+                                .as_ref()
+                                .ok_or(FheProgramRunFailure::MissingGaloisKeys)?,
+                        )?
+                    };
 
                     data[index.index()].store(Some(Arc::new(c.into())));
                 }
@@ -220,7 +251,40 @@ pub unsafe fn run_program_unchecked<E: Evaluator + Sync + Send>(
                     let a = get_ciphertext(&data, left.index())?;
                     let b = get_ciphertext(&data, right.index())?;
 
-                    let c = evaluator.add(a, b)?;
+                    let c = if is_ckks {
+                        let (a, b) = align_ckks_ciphertexts(evaluator, a, b, true)?;
+
+                        evaluator.add(&a, &b)?
+                    } else {
+                        evaluator.add(a, b)?
+                    };
+
+                    data[index.index()].store(Some(Arc::new(c.into())));
+                }
+                AddMany => {
+                    let operands = query
+                        .get_unordered_operands(index)?
+                        .iter()
+                        .map(|x| get_ciphertext(&data, x.index()))
+                        .collect::<Result<Vec<_>, _>>()?;
+
+                    // CKKS operands may sit at different levels and scales,
+                    // so add them pairwise.
+                    let c = if is_ckks {
+                        let (first, rest) = operands
+                            .split_first()
+                            .ok_or(FheProgramRunFailure::MissingData)?;
+
+                        rest.iter().try_fold((*first).clone(), |acc, x| {
+                            let (a, b) = align_ckks_ciphertexts(evaluator, &acc, x, true)?;
+
+                            Ok::<_, FheProgramRunFailure>(evaluator.add(&a, &b)?)
+                        })?
+                    } else {
+                        let operands = operands.into_iter().cloned().collect::<Vec<_>>();
+
+                        evaluator.add_many(&operands)?
+                    };
 
                     data[index.index()].store(Some(Arc::new(c.into())));
                 }
@@ -230,7 +294,13 @@ pub unsafe fn run_program_unchecked<E: Evaluator + Sync + Send>(
                     let a = get_ciphertext(&data, left.index())?;
                     let b = get_plaintext(&data, right.index())?;
 
-                    let c = evaluator.add_plain(a, b)?;
+                    let c = if is_ckks {
+                        let (a, b) = align_ckks_plaintext(evaluator, a, b, true)?;
+
+                        evaluator.add_plain(&a, &b)?
+                    } else {
+                        evaluator.add_plain(a, b)?
+                    };
 
                     data[index.index()].store(Some(Arc::new(c.into())));
                 }
@@ -240,7 +310,30 @@ pub unsafe fn run_program_unchecked<E: Evaluator + Sync + Send>(
                     let a = get_ciphertext(&data, left.index())?;
                     let b = get_ciphertext(&data, right.index())?;
 
-                    let c = evaluator.multiply(a, b)?;
+                    let c = if is_ckks {
+                        let (a, b) = align_ckks_ciphertexts(evaluator, a, b, false)?;
+
+                        evaluator.multiply(&a, &b)?
+                    } else {
+                        evaluator.multiply(a, b)?
+                    };
+
+                    data[index.index()].store(Some(Arc::new(c.into())));
+                }
+                MultiplyMany => {
+                    let relin_keys = relin_keys
+                        .as_ref()
+                        .ok_or(FheProgramRunFailure::MissingRelinearizationKeys)?;
+
+                    let operands = query
+                        .get_unordered_operands(index)?
+                        .iter()
+                        .map(|x| get_ciphertext(&data, x.index()).cloned())
+                        .collect::<Result<Vec<_>, _>>()?;
+
+                    // The evaluator multiplies in a balanced tree and
+                    // relinearizes each product.
+                    let c = evaluator.multiply_many(&operands, relin_keys)?;
 
                     data[index.index()].store(Some(Arc::new(c.into())));
                 }
@@ -250,7 +343,13 @@ pub unsafe fn run_program_unchecked<E: Evaluator + Sync + Send>(
                     let a = get_ciphertext(&data, left.index())?;
                     let b = get_plaintext(&data, right.index())?;
 
-                    let c = evaluator.multiply_plain(a, b)?;
+                    let c = if is_ckks {
+                        let (a, b) = align_ckks_plaintext(evaluator, a, b, false)?;
+
+                        evaluator.multiply_plain(&a, &b)?
+                    } else {
+                        evaluator.multiply_plain(a, b)?
+                    };
 
                     data[index.index()].store(Some(Arc::new(c.into())));
                 }
@@ -280,6 +379,24 @@ pub unsafe fn run_program_unchecked<E: Evaluator + Sync + Send>(
 
                     data[index.index()].store(Some(Arc::new(c.into())));
                 }
+                Rescale => {
+                    let input = query.get_unary_operand(index)?;
+
+                    let a = get_ciphertext(&data, input.index())?;
+
+                    let c = evaluator.rescale_to_next(a)?;
+
+                    data[index.index()].store(Some(Arc::new(c.into())));
+                }
+                ModSwitch => {
+                    let input = query.get_unary_operand(index)?;
+
+                    let a = get_ciphertext(&data, input.index())?;
+
+                    let c = evaluator.mod_switch_to_next(a)?;
+
+                    data[index.index()].store(Some(Arc::new(c.into())));
+                }
                 Negate => {
                     let x_id = query.get_unary_operand(index)?;
 
@@ -295,7 +412,13 @@ pub unsafe fn run_program_unchecked<E: Evaluator + Sync + Send>(
                     let a = get_ciphertext(&data, left.index())?;
                     let b = get_ciphertext(&data, right.index())?;
 
-                    let c = evaluator.sub(a, b)?;
+                    let c = if is_ckks {
+                        let (a, b) = align_ckks_ciphertexts(evaluator, a, b, true)?;
+
+                        evaluator.sub(&a, &b)?
+                    } else {
+                        evaluator.sub(a, b)?
+                    };
 
                     data[index.index()].store(Some(Arc::new(c.into())));
                 }
@@ -305,7 +428,13 @@ pub unsafe fn run_program_unchecked<E: Evaluator + Sync + Send>(
                     let a = get_ciphertext(&data, left.index())?;
                     let b = get_plaintext(&data, right.index())?;
 
-                    let c = evaluator.sub_plain(a, b)?;
+                    let c = if is_ckks {
+                        let (a, b) = align_ckks_plaintext(evaluator, a, b, true)?;
+
+                        evaluator.sub_plain(&a, &b)?
+                    } else {
+                        evaluator.sub_plain(a, b)?
+                    };
 
                     data[index.index()].store(Some(Arc::new(c.into())));
                 }
@@ -335,6 +464,21 @@ pub unsafe fn run_program_unchecked<E: Evaluator + Sync + Send>(
                 }
             };
 
+            if let Some(group) = rotation_groups.get(&index) {
+                let galois_keys = galois_keys
+                    .as_ref()
+                    .ok_or(FheProgramRunFailure::MissingGaloisKeys)?;
+
+                let x = get_ciphertext(&data, index.index())?;
+                let steps = group.iter().map(|(_, s)| *s).collect::<Vec<i32>>();
+
+                let rotated = evaluator.rotate_rows_many(x, &steps, galois_keys)?;
+
+                for ((id, _), c) in group.iter().zip(rotated) {
+                    data[id.index()].store(Some(Arc::new(c.into())));
+                }
+            }
+
             Ok(())
         },
         None,
@@ -356,6 +500,102 @@ pub unsafe fn run_program_unchecked<E: Evaluator + Sync + Send>(
     Ok(output)
 }
 
+/**
+ * Finds the nodes rotated by more than one `ShiftLeft` or `ShiftRight`
+ * node, mapping each to its rotation nodes and their signed step counts.
+ */
+fn rotation_groups(
+    ir: &FheProgram,
+) -> Result<HashMap<NodeIndex, Vec<(NodeIndex, i32)>>, FheProgramRunFailure> {
+    let query = GraphQuery::new(&ir.graph.0);
+    let mut groups: HashMap<NodeIndex, Vec<(NodeIndex, i32)>> = HashMap::new();
+
+    for index in ir.graph.node_indices() {
+        let sign = match ir.graph[index].operation {
+            ShiftLeft => 1,
+            ShiftRight => -1,
+            _ => continue,
+        };
+
+        let (left, right) = query.get_binary_operands(index)?;
+
+        let steps = match ir.graph[right].operation {
+            Literal(Literal::U64(v)) if v != 0 => sign * v as i32,
+            _ => continue,
+        };
+
+        groups.entry(left).or_default().push((index, steps));
+    }
+
+    groups.retain(|_, g| g.len() > 1);
+
+    Ok(groups)
+}
+
+/**
+ * Under CKKS, binary operations require both operands be at the same level
+ * of the modulus chain. Since ciphertexts move down the chain as they get
+ * rescaled, this switches whichever operand is higher down to the other's
+ * level.
+ *
+ * When `match_scales` is set, this additionally overwrites the right operand's
+ * scale with the left's, as addition and subtraction require equal scales.
+ * Rescaling divides by a prime close to, but not exactly, the nominal scale,
+ * so scales that should be equal differ by a factor very close to 1 and
+ * treating them as equal introduces negligible error.
+ */
+fn align_ckks_ciphertexts<'a, E: Evaluator>(
+    evaluator: &E,
+    a: &'a Ciphertext,
+    b: &'a Ciphertext,
+    match_scales: bool,
+) -> Result<(Cow<'a, Ciphertext>, Cow<'a, Ciphertext>), FheProgramRunFailure> {
+    let mut a = Cow::Borrowed(a);
+    let mut b = Cow::Borrowed(b);
+
+    if a.coeff_modulus_size() > b.coeff_modulus_size() {
+        a = Cow::Owned(evaluator.mod_switch_to(&a, &b.get_parms_id())?);
+    } else if b.coeff_modulus_size() > a.coeff_modulus_size() {
+        b = Cow::Owned(evaluator.mod_switch_to(&b, &a.get_parms_id())?);
+    }
+
+    if match_scales && a.get_scale() != b.get_scale() {
+        let mut b_owned = b.into_owned();
+        b_owned.set_scale(a.get_scale())?;
+        b = Cow::Owned(b_owned);
+    }
+
+    Ok((a, b))
+}
+
+/**
+ * The ciphertext-plaintext analogue of [`align_ckks_ciphertexts`]. Plaintexts
+ * are encoded at the top of the modulus chain, so this switches the plaintext
+ * down to the ciphertext's level. When `match_scales` is set, the
+ * ciphertext takes on the plaintext's scale.
+ */
+fn align_ckks_plaintext<'a, E: Evaluator>(
+    evaluator: &E,
+    a: &'a Ciphertext,
+    b: &'a Plaintext,
+    match_scales: bool,
+) -> Result<(Cow<'a, Ciphertext>, Cow<'a, Plaintext>), FheProgramRunFailure> {
+    let mut a = Cow::Borrowed(a);
+    let mut b = Cow::Borrowed(b);
+
+    if a.get_parms_id() != b.get_parms_id() {
+        b = Cow::Owned(evaluator.mod_switch_to_plaintext(&b, &a.get_parms_id())?);
+    }
+
+    if match_scales && a.get_scale() != b.get_scale() {
+        let mut a_owned = a.into_owned();
+        a_owned.set_scale(b.get_scale())?;
+        a = Cow::Owned(a_owned);
+    }
+
+    Ok((a, b))
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 /**
  * Traverses the FheProgram's nodes in topological order, executing
@@ -727,6 +967,59 @@ mod tests {
         );
     }
 
+    #[test]
+    fn can_add_many_and_multiply_many() {
+        let mut ir = FheProgram::new(SchemeType::Bfv);
+
+        let inputs = (0..5)
+            .map(|i| ir.add_input_ciphertext(i))
+            .collect::<Vec<_>>();
+        let product = ir.add_multiply_many(&inputs);
+        let sum = ir.add_add_many(&inputs);
+        ir.add_output_ciphertext(product);
+        ir.add_output_ciphertext(sum);
+
+        let degree = 8192;
+
+        let (keygen, context, _public_key, _private_key, encryptor, decryptor, evaluator) =
+            setup_scheme(degree);
+
+        let encoder = BFVEncoder::new(&context).unwrap();
+        let relin_keys = keygen.create_relinearization_keys().unwrap();
+
+        let values = [2, -3, 4, 1, -5];
+
+        let cts = values
+            .iter()
+            .map(|x| {
+                let pt = encoder.encode_signed(&vec![*x; degree as usize]).unwrap();
+
+                encryptor.encrypt(&pt).unwrap().into()
+            })
+            .collect::<Vec<SealData>>();
+
+        let output = unsafe {
+            run_program_unchecked(&ir, &cts, &evaluator, &Some(&relin_keys), &None).unwrap()
+        };
+
+        assert_eq!(output.len(), 2);
+
+        // The product comes back relinearized.
+        assert_eq!(output[0].num_polynomials(), 2);
+
+        let product = decryptor.decrypt(&output[0]).unwrap();
+        let sum = decryptor.decrypt(&output[1]).unwrap();
+
+        assert_eq!(
+            encoder.decode_signed(&product).unwrap(),
+            vec![values.iter().product::<i64>(); degree as usize]
+        );
+        assert_eq!(
+            encoder.decode_signed(&sum).unwrap(),
+            vec![values.iter().sum::<i64>(); degree as usize]
+        );
+    }
+
     #[test]
     fn add_reduction() {
         let mut ir = FheProgram::new(SchemeType::Bfv);
@@ -879,4 +1172,101 @@ mod tests {
 
         assert_eq!(encoder.decode_unsigned(&o_p).unwrap(), expected);
     }
+
+    #[test]
+    fn zero_shifts_run_without_galois_keys() {
+        let mut ir = FheProgram::new(SchemeType::Bfv);
+
+        let a = ir.add_input_ciphertext(0);
+        let zero = ir.add_input_literal(Literal::U64(0));
+
+        let left = ir.add_rotate_left(a, zero);
+        let right = ir.append_rotate_right(a, zero);
+
+        ir.add_output_ciphertext(left);
+        ir.add_output_ciphertext(right);
+
+        assert!(rotation_groups(&ir).unwrap().is_empty());
+
+        let degree = 4096;
+
+        let (_keygen, context, _public_key, _private_key, encryptor, decryptor, evaluator) =
+            setup_scheme(degree);
+
+        let encoder = BFVEncoder::new(&context).unwrap();
+
+        let a: Vec<u64> = (0..degree).collect();
+
+        let pt_0 = encoder.encode_unsigned(&a).unwrap();
+
+        let ct_0 = encryptor.encrypt(&pt_0).unwrap();
+
+        let output = unsafe {
+            run_program_unchecked(&ir, &[ct_0.into()], &evaluator, &None, &None).unwrap()
+        };
+
+        assert_eq!(output.len(), 2);
+
+        for o in output {
+            let o_p = decryptor.decrypt(&o).unwrap();
+
+            assert_eq!(encoder.decode_unsigned(&o_p).unwrap(), a);
+        }
+    }
+
+    #[test]
+    fn can_hoist_rotations_of_same_ciphertext() {
+        let mut ir = FheProgram::new(SchemeType::Bfv);
+
+        let a = ir.add_input_ciphertext(0);
+        let one = ir.add_input_literal(Literal::U64(1));
+        let two = ir.add_input_literal(Literal::U64(2));
+        let three = ir.add_input_literal(Literal::U64(3));
+
+        let left_1 = ir.add_rotate_left(a, one);
+        let left_2 = ir.add_rotate_left(a, two);
+        let right_3 = ir.append_rotate_right(a, three);
+
+        ir.add_output_ciphertext(left_1);
+        ir.add_output_ciphertext(left_2);
+        ir.add_output_ciphertext(right_3);
+
+        let groups = rotation_groups(&ir).unwrap();
+
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[&a], vec![(left_1, 1), (left_2, 2), (right_3, -3)]);
+
+        let degree = 4096;
+
+        let (keygen, context, _public_key, _private_key, encryptor, decryptor, evaluator) =
+            setup_scheme(degree);
+
+        let encoder = BFVEncoder::new(&context).unwrap();
+        let galois_keys = keygen.create_galois_keys().unwrap();
+
+        let a: Vec<u64> = (0..degree).collect();
+
+        let pt_0 = encoder.encode_unsigned(&a).unwrap();
+
+        let ct_0 = encryptor.encrypt(&pt_0).unwrap();
+
+        let output = unsafe {
+            run_program_unchecked(&ir, &[ct_0.into()], &evaluator, &None, &Some(&galois_keys))
+                .unwrap()
+        };
+
+        assert_eq!(output.len(), 3);
+
+        let row = degree / 2;
+
+        for (o, steps) in output.iter().zip([1, 2, row - 3]) {
+            let o_p = decryptor.decrypt(o).unwrap();
+
+            let expected = (0..degree)
+                .map(|i| (i / row) * row + (i + steps) % row)
+                .collect::<Vec<u64>>();
+
+            assert_eq!(encoder.decode_unsigned(&o_p).unwrap(), expected);
+        }
+    }
 }
@@ -1,3 +1,4 @@
+use std::collections::BTreeSet;
 use std::marker::PhantomData;
 use std::time::Instant;
 
@@ -9,9 +10,11 @@ use crate::ProofBuilder;
 use crate::VerificationBuilder;
 use crate::ZkpProgramInput;
 use crate::{
-    run_program_unchecked, serialization::WithContext, Ciphertext, FheProgramInput,
-    InnerCiphertext, InnerPlaintext, Plaintext, PrivateKey, PublicKey, SealCiphertext, SealData,
-    SealPlaintext, TryFromPlaintext, TryIntoPlaintext, TypeNameInstance,
+    run_program_unchecked,
+    serialization::{SeededWithContext, WithContext},
+    Ciphertext, EvaluationKeys, FheProgramInput, InnerCiphertext, InnerPlaintext, Plaintext,
+    PrivateKey, PublicKey, SealCiphertext, SealData, SealPlaintext, Serializable, TryFromPlaintext,
+    TryIntoPlaintext, TypeNameInstance,
 };
 
 use log::trace;
@@ -19,13 +22,15 @@ use sunscreen_fhe_program::FheProgramTrait;
 use sunscreen_fhe_program::SchemeType;
 
 use seal_fhe::{
-    AsymmetricComponents, BFVEvaluator, BfvEncryptionParametersBuilder, Context as SealContext,
-    Decryptor, Encryptor, KeyGenerator, Modulus, SymmetricComponents,
+    AsymmetricComponents, BFVEvaluator, BGVEvaluator, BfvEncryptionParametersBuilder,
+    BgvEncryptionParametersBuilder, CKKSEvaluator, CkksEncryptionParametersBuilder,
+    Context as SealContext, Decryptor, Encryptor, KeyGenerator, Modulus, SymmetricComponents,
 };
 
 pub use sunscreen_compiler_common::{Type, TypeName};
 use sunscreen_zkp_backend::BigInt;
 use sunscreen_zkp_backend::Proof;
+use sunscreen_zkp_backend::WitnessReport;
 use sunscreen_zkp_backend::ZkpBackend;
 
 enum Context {
@@ -135,6 +140,69 @@ impl<T, B> GenericRuntime<T, B> {
     }
 }
 
+/**
+ * The Galois keys a set of FHE programs needs.
+ */
+enum GaloisRequirement {
+    /**
+     * No program performs rotations.
+     */
+    None,
+
+    /**
+     * Keys for these rotation steps.
+     */
+    Steps(Vec<i32>),
+
+    /**
+     * Keys for every rotation. Programs compiled before their rotation steps were
+     * recorded in their metadata require this.
+     */
+    All,
+}
+
+/**
+ * The evaluation keys a set of FHE programs needs.
+ */
+struct RequiredEvaluationKeys {
+    relin: bool,
+    galois: GaloisRequirement,
+}
+
+impl RequiredEvaluationKeys {
+    fn new<'a, I>(fhe_programs: I) -> Self
+    where
+        I: IntoIterator<Item = &'a CompiledFheProgram>,
+    {
+        let mut relin = false;
+        let mut galois = false;
+        let mut all_steps = false;
+        let mut steps = BTreeSet::new();
+
+        for program in fhe_programs {
+            let required_keys = &program.metadata.required_keys;
+
+            relin |= required_keys.contains(&RequiredKeys::Relin);
+
+            if required_keys.contains(&RequiredKeys::Galois) {
+                galois = true;
+                all_steps |= program.metadata.galois_steps.is_empty();
+                steps.extend(program.metadata.galois_steps.iter().copied());
+            }
+        }
+
+        let galois = if !galois {
+            GaloisRequirement::None
+        } else if all_steps {
+            GaloisRequirement::All
+        } else {
+            GaloisRequirement::Steps(steps.into_iter().collect())
+        };
+
+        Self { relin, galois }
+    }
+}
+
 impl<T, B> GenericRuntime<T, B>
 where
     T: self::marker::Fhe,
@@ -179,10 +247,13 @@ where
                 let plaintexts = ciphertexts
                     .iter()
                     .map(|c| {
-                        if decryptor
-                            .invariant_noise_budget(c)
-                            .map_err(Error::SealError)?
-                            == 0
+                        // CKKS has no noise budget; noise is part of the
+                        // approximation error.
+                        if fhe_data.params.scheme_type != SchemeType::Ckks
+                            && decryptor
+                                .invariant_noise_budget(c)
+                                .map_err(Error::SealError)?
+                                == 0
                         {
                             return Err(Error::TooMuchNoise);
                         }
@@ -217,6 +288,9 @@ where
      * than one ciphertext. This function returns the
      * *minimum* noise budget remaining of all the enclosed
      * ciphertexts.
+     *
+     * CKKS ciphertexts have no noise budget, so this returns an error
+     * under the CKKS scheme.
      */
     pub fn measure_noise_budget(&self, c: &Ciphertext, private_key: &PrivateKey) -> Result<u32> {
         let fhe_data = self.runtime_data.unwrap_fhe();
@@ -234,13 +308,12 @@ where
     }
 
     /**
-     * Generates a tuple of public/private keys for the encapsulated scheme and parameters.
+     * Generates a public/private key pair for the encapsulated scheme and parameters.
      *
      * # Remarks
-     * For some parameters, generating some public key types may fail. For example, Galois
-     * keys tend to fail creation for small parameter values. FhePrograms with small parameters
-     * can't require these associated keys and so long as the FHE program was compiled using the
-     * search algorithm, it won't.
+     * The public key can only encrypt data. Running FHE programs additionally requires
+     * [`EvaluationKeys`], which you can create with
+     * [`generate_evaluation_keys`](Self::generate_evaluation_keys).
      *
      * See [`PublicKey`] for more information.
      */
@@ -251,33 +324,168 @@ where
             Context::Seal(context) => {
                 let keygen = KeyGenerator::new(context)?;
 
-                let galois_keys = keygen.create_galois_keys().ok().map(|v| WithContext {
-                    params: fhe_data.params.clone(),
-                    data: v,
-                });
-
-                let relin_keys = keygen
-                    .create_relinearization_keys()
-                    .ok()
-                    .map(|v| WithContext {
-                        params: fhe_data.params.clone(),
-                        data: v,
-                    });
-
-                let public_keys = PublicKey {
+                let public_key = PublicKey {
                     public_key: WithContext {
                         params: fhe_data.params.clone(),
                         data: keygen.create_public_key(),
                     },
-                    galois_key: galois_keys,
-                    relin_key: relin_keys,
                 };
                 let private_key = PrivateKey(WithContext {
                     params: fhe_data.params.clone(),
                     data: keygen.secret_key(),
                 });
 
-                (public_keys, private_key)
+                (public_key, private_key)
+            }
+        };
+
+        Ok(keys)
+    }
+
+    /**
+     * Generates a public/private key pair like [`generate_keys`](Self::generate_keys),
+     * but returns the public key in SEAL's seeded form.
+     *
+     * # Remarks
+     * Seeded public keys serialize to roughly half the size of those from
+     * [`generate_keys`](Self::generate_keys), which reduces upload bandwidth when a client
+     * sends its keys to a server. They can't be used until they're deserialized as a
+     * [`PublicKey`] or expanded with [`Serializable::expand`].
+     */
+    pub fn generate_keys_compact(&self) -> Result<(Serializable<PublicKey>, PrivateKey)> {
+        let fhe_data = self.runtime_data.unwrap_fhe();
+
+        let keys = match &fhe_data.context {
+            Context::Seal(context) => {
+                let keygen = KeyGenerator::new(context)?;
+
+                let public_key = Serializable::new_public_key(SeededWithContext {
+                    params: fhe_data.params.clone(),
+                    data: keygen.create_compact_public_key().as_bytes()?,
+                });
+                let private_key = PrivateKey(WithContext {
+                    params: fhe_data.params.clone(),
+                    data: keygen.secret_key(),
+                });
+
+                (public_key, private_key)
+            }
+        };
+
+        Ok(keys)
+    }
+
+    /**
+     * Generates the evaluation keys needed to run the given FHE programs on data
+     * encrypted under `private_key`'s public key.
+     *
+     * # Remarks
+     * This only creates the keys listed in the programs'
+     * [`required_keys`](FheProgramMetadata::required_keys), and Galois keys only for the
+     * rotations they perform. If none of the programs need evaluation keys, this returns
+     * an empty [`EvaluationKeys`]. The returned keys can't run FHE programs that require
+     * other keys or rotations.
+     *
+     * See [`EvaluationKeys`] for more information.
+     */
+    pub fn generate_evaluation_keys<'a, I>(
+        &self,
+        private_key: &PrivateKey,
+        fhe_programs: I,
+    ) -> Result<EvaluationKeys>
+    where
+        I: IntoIterator<Item = &'a CompiledFheProgram>,
+    {
+        let fhe_data = self.runtime_data.unwrap_fhe();
+        let required = RequiredEvaluationKeys::new(fhe_programs);
+
+        let keys = match &fhe_data.context {
+            Context::Seal(context) => {
+                let keygen = KeyGenerator::new_from_secret_key(context, &private_key.0.data)?;
+
+                let galois_key = match &required.galois {
+                    GaloisRequirement::None => None,
+                    GaloisRequirement::All => Some(keygen.create_galois_keys()?),
+                    GaloisRequirement::Steps(steps) => {
+                        Some(keygen.create_galois_keys_from_steps(steps)?)
+                    }
+                };
+
+                let relin_key = if required.relin {
+                    Some(keygen.create_relinearization_keys()?)
+                } else {
+                    None
+                };
+
+                EvaluationKeys {
+                    galois_key: galois_key.map(|v| WithContext {
+                        params: fhe_data.params.clone(),
+                        data: v,
+                    }),
+                    relin_key: relin_key.map(|v| WithContext {
+                        params: fhe_data.params.clone(),
+                        data: v,
+                    }),
+                }
+            }
+        };
+
+        Ok(keys)
+    }
+
+    /**
+     * Generates evaluation keys like
+     * [`generate_evaluation_keys`](Self::generate_evaluation_keys), but returns them in
+     * SEAL's seeded form.
+     *
+     * # Remarks
+     * Seeded evaluation keys serialize to roughly half the size. They can't be used
+     * until they're deserialized as [`EvaluationKeys`] or expanded with
+     * [`Serializable::expand`].
+     */
+    pub fn generate_evaluation_keys_compact<'a, I>(
+        &self,
+        private_key: &PrivateKey,
+        fhe_programs: I,
+    ) -> Result<Serializable<EvaluationKeys>>
+    where
+        I: IntoIterator<Item = &'a CompiledFheProgram>,
+    {
+        let fhe_data = self.runtime_data.unwrap_fhe();
+        let required = RequiredEvaluationKeys::new(fhe_programs);
+
+        let keys = match &fhe_data.context {
+            Context::Seal(context) => {
+                let keygen = KeyGenerator::new_from_secret_key(context, &private_key.0.data)?;
+
+                let seeded = |data: seal_fhe::Result<Vec<u8>>| -> Result<SeededWithContext> {
+                    Ok(SeededWithContext {
+                        params: fhe_data.params.clone(),
+                        data: data?,
+                    })
+                };
+
+                let galois_key = match &required.galois {
+                    GaloisRequirement::None => None,
+                    GaloisRequirement::All => {
+                        Some(seeded(keygen.create_compact_galois_keys()?.as_bytes())?)
+                    }
+                    GaloisRequirement::Steps(steps) => Some(seeded(
+                        keygen
+                            .create_compact_galois_keys_from_steps(steps)?
+                            .as_bytes(),
+                    )?),
+                };
+
+                let relin_key = if required.relin {
+                    Some(seeded(
+                        keygen.create_compact_relinearization_keys()?.as_bytes(),
+                    )?)
+                } else {
+                    None
+                };
+
+                Serializable::new_evaluation_keys(galois_key, relin_key)
             }
         };
 
@@ -306,12 +514,15 @@ where
     /**
      * Validates and runs the given FHE program. Unless you can guarantee your FHE program is valid,
      * you should use this method rather than [`run_program_unchecked`].
+     *
+     * `evaluation_keys` must contain the keys `fhe_program` requires; see
+     * [`generate_evaluation_keys`](Self::generate_evaluation_keys).
      */
     pub fn run<I>(
         &self,
         fhe_program: &CompiledFheProgram,
         mut arguments: Vec<I>,
-        public_key: &PublicKey,
+        evaluation_keys: &EvaluationKeys,
     ) -> Result<Vec<Ciphertext>>
     where
         I: Into<FheProgramInput>,
@@ -324,11 +535,12 @@ where
         fhe_program.fhe_program_fn.validate()?;
 
         // Aside from FHE program correctness, check that the required keys are given.
-        if public_key.relin_key.is_none() && fhe_program.fhe_program_fn.requires_relin_keys() {
+        if evaluation_keys.relin_key.is_none() && fhe_program.fhe_program_fn.requires_relin_keys() {
             return Err(Error::MissingRelinearizationKeys);
         }
 
-        if public_key.galois_key.is_none() && fhe_program.fhe_program_fn.requires_galois_keys() {
+        if evaluation_keys.galois_key.is_none() && fhe_program.fhe_program_fn.requires_galois_keys()
+        {
             return Err(Error::MissingGaloisKeys);
         }
 
@@ -347,8 +559,6 @@ where
 
         match &fhe_data.context {
             Context::Seal(context) => {
-                let evaluator = BFVEvaluator::new(context)?;
-
                 let mut inputs: Vec<SealData> = vec![];
 
                 for i in arguments.drain(0..) {
@@ -374,17 +584,49 @@ where
                     }
                 }
 
-                let relin_key = public_key.relin_key.as_ref().map(|p| &p.data);
-                let galois_key = public_key.galois_key.as_ref().map(|p| &p.data);
-
-                let mut raw_ciphertexts = unsafe {
-                    run_program_unchecked(
-                        &fhe_program.fhe_program_fn,
-                        &inputs,
-                        &evaluator,
-                        &relin_key,
-                        &galois_key,
-                    )
+                let relin_key = evaluation_keys.relin_key.as_ref().map(|p| &p.data);
+                let galois_key = evaluation_keys.galois_key.as_ref().map(|p| &p.data);
+
+                let mut raw_ciphertexts = match fhe_data.params.scheme_type {
+                    SchemeType::Bfv => {
+                        let evaluator = BFVEvaluator::new(context)?;
+
+                        unsafe {
+                            run_program_unchecked(
+                                &fhe_program.fhe_program_fn,
+                                &inputs,
+                                &evaluator,
+                                &relin_key,
+                                &galois_key,
+                            )
+                        }
+                    }
+                    SchemeType::Bgv => {
+                        let evaluator = BGVEvaluator::new(context)?;
+
+                        unsafe {
+                            run_program_unchecked(
+                                &fhe_program.fhe_program_fn,
+                                &inputs,
+                                &evaluator,
+                                &relin_key,
+                                &galois_key,
+                            )
+                        }
+                    }
+                    SchemeType::Ckks => {
+                        let evaluator = CKKSEvaluator::new(context)?;
+
+                        unsafe {
+                            run_program_unchecked(
+                                &fhe_program.fhe_program_fn,
+                                &inputs,
+                                &evaluator,
+                                &relin_key,
+                                &galois_key,
+                            )
+                        }
+                    }
                 }?;
 
                 let mut packed_ciphertexts = vec![];
@@ -460,6 +702,52 @@ where
         }
     }
 
+    /// Encrypts the given [`FheType`](crate::FheType) symmetrically using the given secret
+    /// key, returning the ciphertext in SEAL's seeded form.
+    ///
+    /// Seeded ciphertexts serialize to roughly half the size of those from
+    /// [`encrypt_symmetric`](Self::encrypt_symmetric), but can't be used until they're
+    /// deserialized as a [`Ciphertext`] or expanded with [`Serializable::expand`].
+    ///
+    /// Returns [`Error::ParameterMismatch`] if the plaintext is incompatible with this runtime's
+    /// scheme.
+    pub fn encrypt_symmetric_seeded<P>(
+        &self,
+        val: P,
+        private_key: &PrivateKey,
+    ) -> Result<Serializable<Ciphertext>>
+    where
+        P: TryIntoPlaintext + TypeName,
+    {
+        let fhe_data = self.runtime_data.unwrap_fhe();
+        match (
+            &fhe_data.context,
+            &val.try_into_plaintext(&fhe_data.params)?.inner,
+        ) {
+            (Context::Seal(context), InnerPlaintext::Seal(inner_plain)) => {
+                let encryptor = Encryptor::with_secret_key(context, &private_key.0.data)?;
+
+                let cts = inner_plain
+                    .iter()
+                    .map(|pt| {
+                        Ok(SeededWithContext {
+                            params: pt.params.clone(),
+                            data: encryptor.encrypt_symmetric_compact(&pt.data)?.as_bytes()?,
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+
+                Ok(Serializable::new_ciphertext(
+                    Type {
+                        is_encrypted: true,
+                        ..P::type_name()
+                    },
+                    cts,
+                ))
+            }
+        }
+    }
+
     /**
      * DO NOT USE THIS FUNCTION IN PRODUCTION: IT PRODUCES DETERMINISTIC
      * ENCRYPTIONS. IT IS INHERENTLY INSECURE, AND ONLY MEANT FOR TESTING OR
@@ -715,6 +1003,40 @@ where
         Ok(backend.prove(&prog, &inputs)?)
     }
 
+    /**
+     * Runs `program` natively with the given inputs without creating a
+     * proof. The returned report contains the first unsatisfied
+     * constraint or failing gadget, if any, with the values involved and
+     * where in the `#[zkp_program]` it came from, along with the witness.
+     *
+     * # Remarks
+     * Use this to find out why [`prove`](Self::prove) fails. Errors are
+     * only returned when the arguments don't match the program.
+     */
+    pub fn debug_prove<I>(
+        &self,
+        program: &CompiledZkpProgram,
+        private_inputs: Vec<I>,
+        public_inputs: Vec<I>,
+        constant_inputs: Vec<I>,
+    ) -> Result<WitnessReport>
+    where
+        I: Into<ZkpProgramInput>,
+    {
+        let [private_inputs, public_inputs, constant_inputs] = Self::collect_and_validate_zkp_args(
+            [private_inputs, public_inputs, constant_inputs],
+            program,
+        )?;
+
+        Ok(self.zkp_backend.debug_prover(
+            &program.zkp_program_fn,
+            &private_inputs,
+            &public_inputs,
+            &constant_inputs,
+            &program.source_locations,
+        )?)
+    }
+
     /// Create a proof builder.
     ///
     /// This provides a wrapper around calling [`Self::prove`], and can be convenient when you
@@ -768,6 +1090,58 @@ where
         Ok(backend.verify(&prog, proof)?)
     }
 
+    /**
+     * Verify that each of the given `proofs` satisfies the given
+     * `program` with the public inputs accompanying it.
+     *
+     * # Remarks
+     * Fails if any proof doesn't verify. When the backend supports batch
+     * verification (e.g. Bulletproofs), this costs far less than
+     * verifying each proof separately.
+     */
+    pub fn verify_batch<I>(
+        &self,
+        program: &CompiledZkpProgram,
+        proofs: Vec<(&Proof, Vec<I>)>,
+        constant_inputs: Vec<I>,
+    ) -> Result<()>
+    where
+        I: Into<ZkpProgramInput>,
+    {
+        let [constant_inputs] = Self::collect_zkp_args([constant_inputs])?;
+
+        let backend = &self.zkp_backend;
+
+        trace!("Starting JIT (verifier)");
+
+        let now = Instant::now();
+
+        let progs = proofs
+            .into_iter()
+            .map(|(proof, public_inputs)| {
+                let [public_inputs] = Self::collect_zkp_args([public_inputs])?;
+
+                let prog = backend.jit_verifier(
+                    &program.zkp_program_fn,
+                    &constant_inputs,
+                    &public_inputs,
+                )?;
+
+                Ok((prog, proof))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        trace!("Verifier JIT time {}s", now.elapsed().as_secs_f64());
+        trace!("Starting backend batch verify...");
+
+        let instances = progs
+            .iter()
+            .map(|(prog, proof)| (prog, *proof))
+            .collect::<Vec<_>>();
+
+        Ok(backend.verify_batch(&instances)?)
+    }
+
     /**
      * Verify that the given `proof` satisfies the given `program`.
      */
@@ -839,32 +1213,39 @@ impl GenericRuntime<(), ()> {
     }
 
     fn make_fhe_runtime_data(params: &Params) -> Result<FheRuntimeData> {
-        match params.scheme_type {
-            SchemeType::Bfv => {
-                let bfv_params = BfvEncryptionParametersBuilder::new()
-                    .set_plain_modulus_u64(params.plain_modulus)
-                    .set_poly_modulus_degree(params.lattice_dimension)
-                    .set_coefficient_modulus(
-                        params
-                            .coeff_modulus
-                            .iter()
-                            .map(|v| Modulus::new(*v).unwrap())
-                            .collect::<Vec<Modulus>>(),
-                    )
-                    .build()?;
-
-                #[cfg(feature = "insecure-params")]
-                let context = SealContext::new_insecure(&bfv_params, true)?;
-
-                #[cfg(not(feature = "insecure-params"))]
-                let context = SealContext::new(&bfv_params, true, params.security_level)?;
-
-                Ok(FheRuntimeData {
-                    params: params.clone(),
-                    context: Context::Seal(context),
-                })
-            }
-        }
+        let coeff_modulus = params
+            .coeff_modulus
+            .iter()
+            .map(|v| Modulus::new(*v).unwrap())
+            .collect::<Vec<Modulus>>();
+
+        let seal_params = match params.scheme_type {
+            SchemeType::Bfv => BfvEncryptionParametersBuilder::new()
+                .set_plain_modulus_u64(params.plain_modulus)
+                .set_poly_modulus_degree(params.lattice_dimension)
+                .set_coefficient_modulus(coeff_modulus)
+                .build()?,
+            SchemeType::Bgv => BgvEncryptionParametersBuilder::new()
+                .set_plain_modulus_u64(params.plain_modulus)
+                .set_poly_modulus_degree(params.lattice_dimension)
+                .set_coefficient_modulus(coeff_modulus)
+                .build()?,
+            SchemeType::Ckks => CkksEncryptionParametersBuilder::new()
+                .set_poly_modulus_degree(params.lattice_dimension)
+                .set_coefficient_modulus(coeff_modulus)
+                .build()?,
+        };
+
+        #[cfg(feature = "insecure-params")]
+        let context = SealContext::new_insecure(&seal_params, true)?;
+
+        #[cfg(not(feature = "insecure-params"))]
+        let context = SealContext::new(&seal_params, true, params.security_level)?;
+
+        Ok(FheRuntimeData {
+            params: params.clone(),
+            context: Context::Seal(context),
+        })
     }
 
     fn make_zkp_runtime_data() -> ZkpRuntimeData {
@@ -1,12 +1,17 @@
 use std::hash::Hash;
+use std::marker::PhantomData;
 
-use crate::Params;
-use seal_fhe::{BfvEncryptionParametersBuilder, Context, FromBytes, Modulus, ToBytes};
+use crate::{Ciphertext, EvaluationKeys, Params, PublicKey, Type};
+use seal_fhe::{
+    BfvEncryptionParametersBuilder, BgvEncryptionParametersBuilder,
+    CkksEncryptionParametersBuilder, Context, FromBytes, Modulus, ToBytes,
+};
 use serde::{
-    de::{Deserializer, MapAccess, SeqAccess, Visitor},
+    de::{DeserializeOwned, Deserializer, MapAccess, SeqAccess, Visitor},
     ser::{Error, SerializeStruct, Serializer},
     Deserialize, Serialize,
 };
+use sunscreen_fhe_program::SchemeType;
 
 #[derive(Debug, PartialEq, Hash, Eq, Clone)]
 /**
@@ -161,25 +166,161 @@ where
     }
 }
 
+#[derive(Clone, Serialize)]
+#[serde(rename = "WithContext")]
+/**
+ * A SEAL object in seeded form, already serialized. This serializes exactly like a
+ * [`WithContext`] holding the expanded object.
+ */
+pub(crate) struct SeededWithContext {
+    pub params: Params,
+    pub data: Vec<u8>,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename = "InnerCiphertext")]
+enum SeededInnerCiphertext {
+    Seal(Vec<SeededWithContext>),
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename = "Ciphertext")]
+struct SeededCiphertext {
+    data_type: Type,
+    inner: SeededInnerCiphertext,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename = "PublicKey")]
+struct SeededPublicKey {
+    public_key: SeededWithContext,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename = "EvaluationKeys")]
+struct SeededEvaluationKeys {
+    galois_key: Option<SeededWithContext>,
+    relin_key: Option<SeededWithContext>,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(untagged)]
+enum SeededRepr {
+    Ciphertext(SeededCiphertext),
+    PublicKey(SeededPublicKey),
+    EvaluationKeys(SeededEvaluationKeys),
+}
+
+#[derive(Clone)]
+/**
+ * A value in SEAL's seeded form, where half of each underlying object is replaced with
+ * the random seed that generates it.
+ *
+ * # Remarks
+ * Seeded values can't be used directly. Instead, serialize them and deserialize the result
+ * as a `T` (e.g. on a server), which expands the seeds. A `Serializable<T>` serializes to the
+ * same format as `T`, but is roughly half the size.
+ */
+pub struct Serializable<T> {
+    repr: SeededRepr,
+    _phantom: PhantomData<T>,
+}
+
+impl Serializable<Ciphertext> {
+    pub(crate) fn new_ciphertext(data_type: Type, data: Vec<SeededWithContext>) -> Self {
+        Self {
+            repr: SeededRepr::Ciphertext(SeededCiphertext {
+                data_type,
+                inner: SeededInnerCiphertext::Seal(data),
+            }),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl Serializable<PublicKey> {
+    pub(crate) fn new_public_key(public_key: SeededWithContext) -> Self {
+        Self {
+            repr: SeededRepr::PublicKey(SeededPublicKey { public_key }),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl Serializable<EvaluationKeys> {
+    pub(crate) fn new_evaluation_keys(
+        galois_key: Option<SeededWithContext>,
+        relin_key: Option<SeededWithContext>,
+    ) -> Self {
+        Self {
+            repr: SeededRepr::EvaluationKeys(SeededEvaluationKeys {
+                galois_key,
+                relin_key,
+            }),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T> Serializable<T>
+where
+    T: DeserializeOwned,
+{
+    /**
+     * Expands the seeds, producing a usable `T`. This is equivalent to serializing and
+     * deserializing this value.
+     */
+    pub fn expand(&self) -> crate::Result<T> {
+        Ok(bincode::deserialize(&bincode::serialize(self)?)?)
+    }
+}
+
+impl<T> Serialize for Serializable<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.repr.serialize(serializer)
+    }
+}
+
 fn deserialize_with_params<T>(params: &Params, data: &[u8]) -> Result<T, seal_fhe::Error>
 where
     T: FromBytes,
 {
+    let seal_context = seal_context(params)?;
+
+    T::from_bytes(&seal_context, data)
+}
+
+/**
+ * Creates a SEAL context able to load objects encrypted under the given parameters.
+ */
+pub(crate) fn seal_context(params: &Params) -> Result<Context, seal_fhe::Error> {
     let coeffs = params
         .coeff_modulus
         .iter()
         .map(|x| Modulus::new(*x))
         .collect::<std::result::Result<Vec<Modulus>, seal_fhe::Error>>()?;
 
-    let encryption_params = BfvEncryptionParametersBuilder::new()
-        .set_coefficient_modulus(coeffs)
-        .set_plain_modulus_u64(params.plain_modulus)
-        .set_poly_modulus_degree(params.lattice_dimension)
-        .build()?;
-
-    let seal_context = Context::new(&encryption_params, false, params.security_level)?;
-
-    let data = T::from_bytes(&seal_context, data)?;
+    let encryption_params = match params.scheme_type {
+        SchemeType::Bfv => BfvEncryptionParametersBuilder::new()
+            .set_coefficient_modulus(coeffs)
+            .set_plain_modulus_u64(params.plain_modulus)
+            .set_poly_modulus_degree(params.lattice_dimension)
+            .build()?,
+        SchemeType::Bgv => BgvEncryptionParametersBuilder::new()
+            .set_coefficient_modulus(coeffs)
+            .set_plain_modulus_u64(params.plain_modulus)
+            .set_poly_modulus_degree(params.lattice_dimension)
+            .build()?,
+        SchemeType::Ckks => CkksEncryptionParametersBuilder::new()
+            .set_coefficient_modulus(coeffs)
+            .set_poly_modulus_degree(params.lattice_dimension)
+            .build()?,
+    };
 
-    Ok(data)
+    // Ciphertexts move down the modulus chain as they get rescaled or
+    // modulus switched, so SEAL needs the full chain to load them.
+    Context::new(&encryption_params, true, params.security_level)
 }
@@ -0,0 +1,1395 @@
+use merlin::Transcript;
+use rand::{rngs::OsRng, Rng, RngCore};
+use seal_fhe::{
+    Ciphertext as SealCiphertext, Context as SealContext, Decryptor, KeyGenerator, PolynomialArray,
+    PublicKey as SealPublicKey, RelinearizationKeys, SecretKey as SealSecretKey,
+};
+use serde::{Deserialize, Serialize};
+use sunscreen_fhe_program::SchemeType;
+
+use crate::{
+    marker, Ciphertext, Error, EvaluationKeys, GenericRuntime, InnerCiphertext, InnerPlaintext,
+    Plaintext, PrivateKey, PublicKey, Result, TryFromPlaintext, Type, TypeName, WithContext,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+/**
+ * An error in a multiparty key generation or threshold decryption protocol.
+ */
+pub enum ThresholdError {
+    /**
+     * Threshold operations are only supported under the BFV scheme.
+     */
+    #[error("Threshold operations require the BFV scheme")]
+    UnsupportedScheme,
+
+    /**
+     * The parameters have a single coefficient modulus, so they don't support
+     * relinearization.
+     */
+    #[error("The parameters don't support key switching")]
+    KeySwitchingUnsupported,
+
+    /**
+     * The threshold was 0 or exceeded the number of parties.
+     */
+    #[error("The threshold must be between 1 and the number of parties")]
+    InvalidThreshold,
+
+    /**
+     * The given party index is 0, repeated, or not among the participants.
+     */
+    #[error("Party {0} is not a valid participant")]
+    InvalidParty(usize),
+
+    /**
+     * No shares were given to combine.
+     */
+    #[error("No shares were given")]
+    NoShares,
+
+    /**
+     * Fewer parties than the threshold took part in decryption.
+     */
+    #[error("Fewer parties than the threshold took part")]
+    InsufficientShares,
+
+    /**
+     * The shares came from different protocol runs, parameters or ciphertexts.
+     */
+    #[error("The shares don't belong together")]
+    MismatchedShares,
+
+    /**
+     * Threshold decryption requires ciphertexts with 2 polynomials. Relinearize
+     * the ciphertext first.
+     */
+    #[error("Threshold decryption requires relinearized ciphertexts")]
+    UnrelinearizedCiphertext,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+/**
+ * A random seed all parties agree on before generating collective keys.
+ *
+ * # Remarks
+ * Every party must use the same value during a key generation protocol. The
+ * seed needn't be secret, but no single party should be able to choose it
+ * (e.g. derive it from a coin flip among the parties or a public beacon).
+ */
+pub struct CommonReferenceString {
+    seed: [u8; 32],
+}
+
+impl CommonReferenceString {
+    /**
+     * Creates a common reference string from a random seed.
+     */
+    pub fn new() -> Self {
+        let mut seed = [0u8; 32];
+        OsRng.fill_bytes(&mut seed);
+
+        Self { seed }
+    }
+
+    /**
+     * Creates a common reference string from the given seed.
+     */
+    pub fn from_seed(seed: [u8; 32]) -> Self {
+        Self { seed }
+    }
+
+    /**
+     * The seed from which the common polynomials are derived.
+     */
+    pub fn seed(&self) -> &[u8; 32] {
+        &self.seed
+    }
+}
+
+impl Default for CommonReferenceString {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+/**
+ * A party's contribution to a collective public key. Combine every party's
+ * share with
+ * [`combine_public_key_shares`](GenericRuntime::combine_public_key_shares).
+ */
+pub struct PublicKeyShare {
+    data: Vec<u64>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+/**
+ * The ephemeral secret a party creates in the first round of relinearization key
+ * generation and needs again in the second round.
+ *
+ * # Remarks
+ * Treat this like a [`PrivateKey`]. Discard it once the second round completes.
+ */
+pub struct RelinKeyEphemeral {
+    u: Vec<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+/**
+ * A party's contribution to the first round of relinearization key generation.
+ */
+pub struct RelinKeyRoundOneShare {
+    h0: Vec<Vec<u64>>,
+    h1: Vec<Vec<u64>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+/**
+ * The aggregate of every party's [`RelinKeyRoundOneShare`], which each party
+ * needs to compute its second round share.
+ */
+pub struct RelinKeyRoundOne {
+    h0: Vec<Vec<u64>>,
+    h1: Vec<Vec<u64>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+/**
+ * A party's contribution to the second round of relinearization key generation.
+ */
+pub struct RelinKeyRoundTwoShare {
+    data: Vec<Vec<u64>>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+/**
+ * A Shamir share of one party's private key, destined for the party
+ * [`party`](Self::party).
+ *
+ * # Remarks
+ * Fragments are secret. Send each one only to the party it's destined for over
+ * a private channel.
+ */
+pub struct SecretKeyFragment {
+    party: usize,
+    threshold: usize,
+    data: Vec<u64>,
+}
+
+impl SecretKeyFragment {
+    /**
+     * The (1-based) index of the party this fragment belongs to.
+     */
+    pub fn party(&self) -> usize {
+        self.party
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+/**
+ * A party's share of the collective private key. Any `threshold` parties can
+ * jointly decrypt with their shares, while fewer learn nothing.
+ */
+pub struct ThresholdKey {
+    party: usize,
+    threshold: usize,
+    share: Vec<u64>,
+}
+
+impl ThresholdKey {
+    /**
+     * The (1-based) index of the party holding this key.
+     */
+    pub fn party(&self) -> usize {
+        self.party
+    }
+
+    /**
+     * How many parties must cooperate to decrypt.
+     */
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+/**
+ * One party's partial decryption of a [`Ciphertext`]. Combine the shares of
+ * every participant with
+ * [`combine_decryption_shares`](GenericRuntime::combine_decryption_shares).
+ */
+pub struct DecryptionShare {
+    party: usize,
+    participants: Vec<usize>,
+    data: Vec<Vec<u64>>,
+}
+
+impl DecryptionShare {
+    /**
+     * The (1-based) index of the party that created this share.
+     */
+    pub fn party(&self) -> usize {
+        self.party
+    }
+}
+
+/**
+ * Multiparty key generation and threshold decryption for BFV.
+ *
+ * # Remarks
+ * The protocols follow Mouchet et al., "Multiparty Homomorphic Encryption from
+ * Ring-Learning-with-Errors". Each of the N parties generates an ordinary key pair
+ * and uses its [`PrivateKey`] as an additive share of the collective secret.
+ * Key generation requires all N parties:
+ * 1. Every party calls [`generate_public_key_share`](Self::generate_public_key_share)
+ *    and anyone combines the shares into the collective [`PublicKey`].
+ * 2. Relinearization keys take two rounds:
+ *    [`generate_relin_key_round_one`](Self::generate_relin_key_round_one), then
+ *    [`generate_relin_key_round_two`](Self::generate_relin_key_round_two) with the
+ *    aggregated first round.
+ *
+ * To decrypt with any t of the N parties, every party splits its private key with
+ * [`split_private_key`](Self::split_private_key) and sends fragment `i` to party `i`,
+ * who combines the fragments it receives into a [`ThresholdKey`]. Afterwards, the
+ * private keys should be discarded.
+ */
+impl<T, B> GenericRuntime<T, B>
+where
+    T: marker::Fhe,
+{
+    /**
+     * Creates this party's share of the collective public key.
+     */
+    pub fn generate_public_key_share(
+        &self,
+        private_key: &PrivateKey,
+        crs: &CommonReferenceString,
+    ) -> Result<PublicKeyShare> {
+        let basis = self.threshold_basis()?;
+        let s = self.party_secret(private_key, &basis)?;
+
+        let a = CrsStream::new(crs, &basis, b"public key").sample(&basis);
+
+        // -(a * s + e)
+        let mut data = basis.mul(&a, &s);
+        basis.add_assign(&mut data, &self.context().sample_error()?);
+        basis.neg_assign(&mut data);
+
+        Ok(PublicKeyShare { data })
+    }
+
+    /**
+     * Combines every party's [`PublicKeyShare`] into the collective public key.
+     *
+     * # Remarks
+     * Ciphertexts encrypted under the collective key can only be decrypted by the
+     * parties together.
+     */
+    pub fn combine_public_key_shares(
+        &self,
+        crs: &CommonReferenceString,
+        shares: &[PublicKeyShare],
+    ) -> Result<PublicKey> {
+        let basis = self.threshold_basis()?;
+        let context = self.context();
+
+        let p0 = basis.sum(shares.iter().map(|s| &s.data))?;
+        let p1 = CrsStream::new(crs, &basis, b"public key").sample(&basis);
+
+        Ok(PublicKey {
+            public_key: WithContext {
+                params: self.params().clone(),
+                data: SealPublicKey::from_rns_data(context, &[p0, p1].concat())?,
+            },
+        })
+    }
+
+    /**
+     * Runs the first round of relinearization key generation for this party.
+     *
+     * # Remarks
+     * Keep the returned [`RelinKeyEphemeral`] secret and pass it to
+     * [`generate_relin_key_round_two`](Self::generate_relin_key_round_two). Send the
+     * [`RelinKeyRoundOneShare`] to whoever aggregates the shares.
+     */
+    pub fn generate_relin_key_round_one(
+        &self,
+        private_key: &PrivateKey,
+        crs: &CommonReferenceString,
+    ) -> Result<(RelinKeyEphemeral, RelinKeyRoundOneShare)> {
+        let basis = self.threshold_basis()?;
+        let decomposition = basis.decomposition_count()?;
+        let s = self.party_secret(private_key, &basis)?;
+
+        let u = KeyGenerator::new(self.context())?.secret_key().rns_data()?;
+        let mut crs = CrsStream::new(crs, &basis, b"relinearization key");
+
+        let mut h0 = Vec::with_capacity(decomposition);
+        let mut h1 = Vec::with_capacity(decomposition);
+
+        for j in 0..decomposition {
+            let a = crs.sample(&basis);
+
+            // -u * a + w_j * s + e
+            let mut x = basis.mul(&u, &a);
+            basis.neg_assign(&mut x);
+            basis.add_gadget(&mut x, &s, j);
+            basis.add_assign(&mut x, &self.context().sample_error()?);
+
+            // s * a + e
+            let mut y = basis.mul(&s, &a);
+            basis.add_assign(&mut y, &self.context().sample_error()?);
+
+            h0.push(x);
+            h1.push(y);
+        }
+
+        Ok((RelinKeyEphemeral { u }, RelinKeyRoundOneShare { h0, h1 }))
+    }
+
+    /**
+     * Aggregates every party's first round relinearization key share.
+     */
+    pub fn combine_relin_key_round_one(
+        &self,
+        shares: &[RelinKeyRoundOneShare],
+    ) -> Result<RelinKeyRoundOne> {
+        let basis = self.threshold_basis()?;
+
+        Ok(RelinKeyRoundOne {
+            h0: basis.sum_decompositions(shares.iter().map(|s| &s.h0))?,
+            h1: basis.sum_decompositions(shares.iter().map(|s| &s.h1))?,
+        })
+    }
+
+    /**
+     * Runs the second round of relinearization key generation for this party.
+     */
+    pub fn generate_relin_key_round_two(
+        &self,
+        private_key: &PrivateKey,
+        ephemeral: &RelinKeyEphemeral,
+        round_one: &RelinKeyRoundOne,
+    ) -> Result<RelinKeyRoundTwoShare> {
+        let basis = self.threshold_basis()?;
+        let s = self.party_secret(private_key, &basis)?;
+        basis.check_round_one(round_one)?;
+
+        if ephemeral.u.len() != basis.len() {
+            return Err(Error::ParameterMismatch);
+        }
+
+        let mut u_minus_s = ephemeral.u.clone();
+        basis.sub_assign(&mut u_minus_s, &s);
+
+        // s * h0 + (u - s) * h1 + e
+        let data = round_one
+            .h0
+            .iter()
+            .zip(&round_one.h1)
+            .map(|(h0, h1)| {
+                let mut x = basis.mul(&s, h0);
+                basis.add_assign(&mut x, &basis.mul(&u_minus_s, h1));
+                basis.add_assign(&mut x, &self.context().sample_error()?);
+
+                Ok(x)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(RelinKeyRoundTwoShare { data })
+    }
+
+    /**
+     * Combines the aggregated first round and every party's second round share
+     * into collective relinearization keys.
+     *
+     * # Remarks
+     * The returned [`EvaluationKeys`] contain no Galois keys, so FHE programs that
+     * rotate batched values can't run under a collective key.
+     */
+    pub fn combine_relin_key_shares(
+        &self,
+        round_one: &RelinKeyRoundOne,
+        shares: &[RelinKeyRoundTwoShare],
+    ) -> Result<EvaluationKeys> {
+        let basis = self.threshold_basis()?;
+        basis.check_round_one(round_one)?;
+
+        let k0 = basis.sum_decompositions(shares.iter().map(|s| &s.data))?;
+
+        Ok(EvaluationKeys {
+            galois_key: None,
+            relin_key: Some(WithContext {
+                params: self.params().clone(),
+                data: relin_keys_from_polys(self.context(), &basis, &k0, &round_one.h1)?,
+            }),
+        })
+    }
+
+    /**
+     * Splits this party's private key into `parties` Shamir shares, any
+     * `threshold` of which reconstruct it. The returned fragments are ordered by
+     * party, starting with party 1.
+     */
+    pub fn split_private_key(
+        &self,
+        private_key: &PrivateKey,
+        threshold: usize,
+        parties: usize,
+    ) -> Result<Vec<SecretKeyFragment>> {
+        let basis = self.threshold_basis()?;
+
+        if threshold == 0 || threshold > parties {
+            return Err(ThresholdError::InvalidThreshold.into());
+        }
+
+        if private_key.0.params != *self.params() {
+            return Err(Error::ParameterMismatch);
+        }
+
+        let s = basis.lift(&secret_coefficients(
+            self.context(),
+            &private_key.0.data,
+            &basis,
+        )?);
+
+        // f(x) = s + c_1 x + ... + c_{t-1} x^{t-1}, independently for each
+        // coefficient and modulus.
+        let coefficients = (1..threshold)
+            .map(|_| basis.uniform(&mut OsRng))
+            .collect::<Vec<_>>();
+
+        let fragments = (1..=parties)
+            .map(|party| {
+                let mut data = vec![0; basis.len()];
+
+                for c in coefficients.iter().rev().chain(std::iter::once(&s)) {
+                    basis.mul_scalar_add(&mut data, party as u64, c);
+                }
+
+                SecretKeyFragment {
+                    party,
+                    threshold,
+                    data,
+                }
+            })
+            .collect();
+
+        Ok(fragments)
+    }
+
+    /**
+     * Combines the fragments a party received (one from every party, including
+     * itself) into its [`ThresholdKey`].
+     */
+    pub fn combine_key_fragments(&self, fragments: &[SecretKeyFragment]) -> Result<ThresholdKey> {
+        let basis = self.threshold_basis()?;
+
+        let first = fragments.first().ok_or(ThresholdError::NoShares)?;
+
+        if fragments
+            .iter()
+            .any(|f| f.party != first.party || f.threshold != first.threshold)
+        {
+            return Err(ThresholdError::MismatchedShares.into());
+        }
+
+        Ok(ThresholdKey {
+            party: first.party,
+            threshold: first.threshold,
+            share: basis.sum(fragments.iter().map(|f| &f.data))?,
+        })
+    }
+
+    /**
+     * Creates this party's decryption share of `ciphertext`.
+     *
+     * # Remarks
+     * `participants` lists the (1-based) indices of the parties taking part in this
+     * decryption, including this one. It must contain at least
+     * [`threshold`](ThresholdKey::threshold) parties and every participant must use
+     * the same list.
+     *
+     * The share includes smudging noise that hides this party's key share. The
+     * noise is as large as decryption allows, which consumes all but a few bits of
+     * the ciphertext's noise budget. It only hides the key share statistically when
+     * the ciphertext's own noise is much smaller (e.g. 40 bits of remaining noise
+     * budget for a statistical distance of 2^-40).
+     */
+    pub fn decryption_share(
+        &self,
+        ciphertext: &Ciphertext,
+        key: &ThresholdKey,
+        participants: &[usize],
+    ) -> Result<DecryptionShare> {
+        let basis = self.threshold_basis()?;
+
+        if key.share.len() != basis.len() {
+            return Err(Error::ParameterMismatch);
+        }
+
+        let participants = validate_participants(participants)?;
+
+        if participants.len() < key.threshold {
+            return Err(ThresholdError::InsufficientShares.into());
+        }
+
+        if !participants.contains(&key.party) {
+            return Err(ThresholdError::InvalidParty(key.party).into());
+        }
+
+        let data = match &ciphertext.inner {
+            InnerCiphertext::Seal(ciphertexts) => ciphertexts
+                .iter()
+                .map(|c| {
+                    if c.params != *self.params() {
+                        return Err(Error::ParameterMismatch);
+                    }
+
+                    let (level, _, c1) = ciphertext_polys(&basis, &c.data)?;
+
+                    let mut secret = key.share[..level.len()].to_vec();
+                    level.mul_limb_scalars(&mut secret, |q| {
+                        lagrange_coefficient(&participants, key.party, q)
+                    });
+
+                    let mut share = level.mul_negacyclic(&c1, &secret);
+                    level.add_assign(
+                        &mut share,
+                        &level.smudging_noise(
+                            self.params().plain_modulus,
+                            participants.len(),
+                            &mut OsRng,
+                        ),
+                    );
+
+                    Ok(share)
+                })
+                .collect::<Result<Vec<_>>>()?,
+        };
+
+        Ok(DecryptionShare {
+            party: key.party,
+            participants,
+            data,
+        })
+    }
+
+    /**
+     * Combines the decryption shares of every participant into the decrypted
+     * value.
+     */
+    pub fn combine_decryption_shares<P>(
+        &self,
+        ciphertext: &Ciphertext,
+        shares: &[DecryptionShare],
+    ) -> Result<P>
+    where
+        P: TryFromPlaintext + TypeName,
+    {
+        let expected_type = Type {
+            is_encrypted: true,
+            ..P::type_name()
+        };
+        if expected_type != ciphertext.data_type {
+            return Err(Error::type_mismatch(&expected_type, &ciphertext.data_type));
+        }
+
+        let basis = self.threshold_basis()?;
+        let context = self.context();
+
+        let participants = &shares.first().ok_or(ThresholdError::NoShares)?.participants;
+        let mut parties = shares.iter().map(|s| s.party).collect::<Vec<_>>();
+        parties.sort_unstable();
+
+        if shares.iter().any(|s| s.participants != *participants) || parties != *participants {
+            return Err(ThresholdError::MismatchedShares.into());
+        }
+
+        // The shares reveal c0 + c1 * s for the collective secret s. SEAL needs a
+        // key to decode that, so we re-express it under a throwaway key s' as the
+        // ciphertext (c0 + c1 * s - c1 * s', c1).
+        let throwaway = KeyGenerator::new(context)?.secret_key();
+        let throwaway_coefficients = secret_coefficients(context, &throwaway, &basis)?;
+        let decryptor = Decryptor::new(context, &throwaway)?;
+
+        let plaintexts = match &ciphertext.inner {
+            InnerCiphertext::Seal(ciphertexts) => ciphertexts
+                .iter()
+                .enumerate()
+                .map(|(i, c)| {
+                    if c.params != *self.params() {
+                        return Err(Error::ParameterMismatch);
+                    }
+
+                    let (level, mut c0, c1) = ciphertext_polys(&basis, &c.data)?;
+
+                    for share in shares {
+                        match share.data.get(i) {
+                            Some(d) if d.len() == level.len() => level.add_assign(&mut c0, d),
+                            _ => return Err(ThresholdError::MismatchedShares.into()),
+                        }
+                    }
+
+                    level.sub_assign(
+                        &mut c0,
+                        &level.mul_negacyclic(&c1, &level.lift(&throwaway_coefficients)),
+                    );
+
+                    let mut c = c.data.clone();
+                    c.set_rns_data(context, 2, &[c0, c1].concat())?;
+
+                    if decryptor.invariant_noise_budget(&c)? == 0 {
+                        return Err(Error::TooMuchNoise);
+                    }
+
+                    Ok(WithContext {
+                        params: self.params().clone(),
+                        data: decryptor.decrypt(&c)?,
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?,
+        };
+
+        let pt = Plaintext {
+            data_type: P::type_name(),
+            inner: InnerPlaintext::Seal(plaintexts),
+        };
+
+        P::try_from_plaintext(&pt, self.params())
+    }
+
+    fn threshold_basis(&self) -> Result<Basis> {
+        let params = self.params();
+
+        if params.scheme_type != SchemeType::Bfv {
+            return Err(ThresholdError::UnsupportedScheme.into());
+        }
+
+        Ok(Basis {
+            degree: params.lattice_dimension as usize,
+            moduli: params.coeff_modulus.clone(),
+        })
+    }
+
+    /**
+     * Returns the private key in NTT form.
+     */
+    fn party_secret(&self, private_key: &PrivateKey, basis: &Basis) -> Result<Vec<u64>> {
+        if private_key.0.params != *self.params() {
+            return Err(Error::ParameterMismatch);
+        }
+
+        let s = private_key.0.data.rns_data()?;
+
+        if s.len() != basis.len() {
+            return Err(Error::ParameterMismatch);
+        }
+
+        Ok(s)
+    }
+}
+
+/**
+ * The RNS representation of polynomials under a prefix of the coefficient
+ * modulus. Polynomials are stored modulus-major, i.e. the coefficients modulo
+ * the first modulus followed by those modulo the second and so on, matching
+ * SEAL's memory layout.
+ */
+pub(crate) struct Basis {
+    pub(crate) degree: usize,
+    pub(crate) moduli: Vec<u64>,
+}
+
+impl Basis {
+    pub(crate) fn len(&self) -> usize {
+        self.degree * self.moduli.len()
+    }
+
+    /**
+     * SEAL ciphertexts lose moduli from the end of the chain when modulus
+     * switching, so a ciphertext with `limbs` moduli uses the first `limbs`.
+     */
+    fn truncate(&self, limbs: usize) -> Self {
+        Self {
+            degree: self.degree,
+            moduli: self.moduli[..limbs].to_vec(),
+        }
+    }
+
+    /**
+     * The number of key switching keys in relinearization keys, i.e. the number of
+     * moduli excluding SEAL's special modulus.
+     */
+    pub(crate) fn decomposition_count(&self) -> Result<usize> {
+        match self.moduli.len() {
+            0 | 1 => Err(ThresholdError::KeySwitchingUnsupported.into()),
+            n => Ok(n - 1),
+        }
+    }
+
+    fn check_round_one(&self, round_one: &RelinKeyRoundOne) -> Result<()> {
+        let decomposition = self.decomposition_count()?;
+
+        if round_one.h0.len() != decomposition
+            || round_one.h1.len() != decomposition
+            || round_one
+                .h0
+                .iter()
+                .chain(&round_one.h1)
+                .any(|p| p.len() != self.len())
+        {
+            return Err(ThresholdError::MismatchedShares.into());
+        }
+
+        Ok(())
+    }
+
+    fn limbs_mut<'a>(&'a self, a: &'a mut [u64]) -> impl Iterator<Item = (u64, &'a mut [u64])> {
+        self.moduli.iter().copied().zip(a.chunks_mut(self.degree))
+    }
+
+    fn zip_with(&self, a: &mut [u64], b: &[u64], f: impl Fn(u64, u64, u64) -> u64) {
+        for ((q, a), b) in self.limbs_mut(a).zip(b.chunks(self.degree)) {
+            for (x, y) in a.iter_mut().zip(b) {
+                *x = f(*x, *y, q);
+            }
+        }
+    }
+
+    pub(crate) fn add_assign(&self, a: &mut [u64], b: &[u64]) {
+        self.zip_with(a, b, add_mod);
+    }
+
+    fn sub_assign(&self, a: &mut [u64], b: &[u64]) {
+        self.zip_with(a, b, sub_mod);
+    }
+
+    fn neg_assign(&self, a: &mut [u64]) {
+        for (q, a) in self.limbs_mut(a) {
+            for x in a {
+                *x = sub_mod(0, *x, q);
+            }
+        }
+    }
+
+    /**
+     * Multiplies polynomials in NTT form, i.e. coefficient-wise.
+     */
+    pub(crate) fn mul(&self, a: &[u64], b: &[u64]) -> Vec<u64> {
+        let mut c = a.to_vec();
+        self.zip_with(&mut c, b, mul_mod);
+        c
+    }
+
+    /**
+     * Multiplies polynomials in coefficient form modulo X^N + 1.
+     */
+    fn mul_negacyclic(&self, a: &[u64], b: &[u64]) -> Vec<u64> {
+        let mut c = a.to_vec();
+
+        for ((q, c), b) in self.limbs_mut(&mut c).zip(b.chunks(self.degree)) {
+            let table = NttTable::new(q, self.degree);
+            let mut b = b.to_vec();
+
+            table.forward(c);
+            table.forward(&mut b);
+
+            for (x, y) in c.iter_mut().zip(&b) {
+                *x = mul_mod(*x, *y, q);
+            }
+
+            table.inverse(c);
+        }
+
+        c
+    }
+
+    /**
+     * Multiplies each modulus' coefficients by the scalar `f` returns for that
+     * modulus.
+     */
+    fn mul_limb_scalars(&self, a: &mut [u64], f: impl Fn(u64) -> u64) {
+        for (q, a) in self.limbs_mut(a) {
+            let scalar = f(q);
+
+            for x in a {
+                *x = mul_mod(*x, scalar, q);
+            }
+        }
+    }
+
+    /**
+     * Computes `a = a * x + b`.
+     */
+    fn mul_scalar_add(&self, a: &mut [u64], x: u64, b: &[u64]) {
+        self.zip_with(a, b, |a, b, q| add_mod(mul_mod(a, x % q, q), b, q));
+    }
+
+    /**
+     * Adds `w_j * s`, where the gadget vector `w_j` is SEAL's special modulus
+     * under modulus `j` and 0 under every other modulus.
+     */
+    pub(crate) fn add_gadget(&self, a: &mut [u64], s: &[u64], j: usize) {
+        let q = self.moduli[j];
+        let factor = self.moduli[self.moduli.len() - 1] % q;
+        let range = j * self.degree..(j + 1) * self.degree;
+
+        for (x, y) in a[range.clone()].iter_mut().zip(&s[range]) {
+            *x = add_mod(*x, mul_mod(*y, factor, q), q);
+        }
+    }
+
+    fn sum<'a>(&self, polys: impl Iterator<Item = &'a Vec<u64>>) -> Result<Vec<u64>> {
+        let mut sum: Option<Vec<u64>> = None;
+
+        for p in polys {
+            if p.len() != self.len() {
+                return Err(ThresholdError::MismatchedShares.into());
+            }
+
+            match &mut sum {
+                Some(s) => self.add_assign(s, p),
+                None => sum = Some(p.clone()),
+            }
+        }
+
+        sum.ok_or_else(|| ThresholdError::NoShares.into())
+    }
+
+    /**
+     * Sums each party's per-decomposition-modulus polynomials.
+     */
+    fn sum_decompositions<'a>(
+        &self,
+        polys: impl Iterator<Item = &'a Vec<Vec<u64>>>,
+    ) -> Result<Vec<Vec<u64>>> {
+        let decomposition = self.decomposition_count()?;
+        let polys = polys.collect::<Vec<_>>();
+
+        if polys.iter().any(|p| p.len() != decomposition) {
+            return Err(ThresholdError::MismatchedShares.into());
+        }
+
+        (0..decomposition)
+            .map(|j| self.sum(polys.iter().map(|p| &p[j])))
+            .collect()
+    }
+
+    fn lift(&self, coefficients: &[i64]) -> Vec<u64> {
+        self.moduli
+            .iter()
+            .flat_map(|&q| {
+                coefficients.iter().map(move |&c| {
+                    let r = c.unsigned_abs() % q;
+
+                    if c < 0 {
+                        sub_mod(0, r, q)
+                    } else {
+                        r
+                    }
+                })
+            })
+            .collect()
+    }
+
+    fn uniform<R: Rng>(&self, rng: &mut R) -> Vec<u64> {
+        self.moduli
+            .iter()
+            .flat_map(|&q| {
+                (0..self.degree)
+                    .map(|_| rng.gen_range(0..q))
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    /**
+     * Samples coefficients uniformly from [-2^b, 2^b), where b is as large as
+     * possible while keeping the combined noise of `participants` shares below
+     * q / (8t).
+     */
+    fn smudging_noise<R: RngCore>(
+        &self,
+        plain_modulus: u64,
+        participants: usize,
+        rng: &mut R,
+    ) -> Vec<u64> {
+        let log_q: u32 = self.moduli.iter().map(|q| 63 - q.leading_zeros()).sum();
+        let log_t = 64 - plain_modulus.leading_zeros();
+        let log_participants = (participants as u64).next_power_of_two().trailing_zeros();
+
+        let bits = log_q.saturating_sub(log_t + 3 + log_participants) as usize;
+        let words = bits / 64 + 1;
+        let excess = words * 64 - (bits + 1);
+
+        let offsets = self
+            .moduli
+            .iter()
+            .map(|&q| pow_mod(2, bits as u64, q))
+            .collect::<Vec<_>>();
+
+        let mut noise = vec![0; self.len()];
+        let mut r = vec![0u64; words];
+
+        for i in 0..self.degree {
+            r.iter_mut().for_each(|w| *w = rng.next_u64());
+            r[words - 1] &= u64::MAX >> excess;
+
+            for (j, &q) in self.moduli.iter().enumerate() {
+                let x = r.iter().rev().fold(0u64, |acc, &w| {
+                    ((((acc as u128) << 64) | w as u128) % q as u128) as u64
+                });
+
+                noise[j * self.degree + i] = sub_mod(x, offsets[j], q);
+            }
+        }
+
+        noise
+    }
+}
+
+/**
+ * Deterministically expands a [`CommonReferenceString`] into uniform
+ * polynomials, so every party derives the same ones.
+ */
+struct CrsStream {
+    transcript: Transcript,
+    buffer: Vec<u64>,
+}
+
+impl CrsStream {
+    fn new(crs: &CommonReferenceString, basis: &Basis, domain: &'static [u8]) -> Self {
+        let mut transcript = Transcript::new(b"sunscreen-threshold-crs");
+        transcript.append_message(b"seed", &crs.seed);
+        transcript.append_u64(b"degree", basis.degree as u64);
+
+        for q in &basis.moduli {
+            transcript.append_u64(b"modulus", *q);
+        }
+
+        transcript.append_message(b"domain", domain);
+
+        Self {
+            transcript,
+            buffer: vec![],
+        }
+    }
+
+    fn below(&mut self, q: u64) -> u64 {
+        let mask = u64::MAX >> q.leading_zeros();
+
+        loop {
+            if self.buffer.is_empty() {
+                let mut bytes = [0u8; 4096];
+                self.transcript.challenge_bytes(b"coefficients", &mut bytes);
+
+                self.buffer = bytes
+                    .chunks_exact(8)
+                    .map(|c| u64::from_le_bytes(c.try_into().unwrap()))
+                    .collect();
+            }
+
+            let x = self.buffer.pop().unwrap() & mask;
+
+            if x < q {
+                return x;
+            }
+        }
+    }
+
+    fn sample(&mut self, basis: &Basis) -> Vec<u64> {
+        basis
+            .moduli
+            .iter()
+            .flat_map(|&q| (0..basis.degree).map(|_| self.below(q)).collect::<Vec<_>>())
+            .collect()
+    }
+}
+
+/**
+ * Creates relinearization keys from the polynomials `(k0[j], k1[j])` of each
+ * key switching key in NTT form.
+ */
+pub(crate) fn relin_keys_from_polys(
+    context: &SealContext,
+    basis: &Basis,
+    k0: &[Vec<u64>],
+    k1: &[Vec<u64>],
+) -> Result<RelinearizationKeys> {
+    let decomposition = basis.decomposition_count()?;
+
+    if k0.len() != decomposition || k1.len() != decomposition {
+        return Err(Error::ParameterMismatch);
+    }
+
+    let keys = k0
+        .iter()
+        .zip(k1)
+        .map(|(k0, k1)| [k0.as_slice(), k1].concat())
+        .collect::<Vec<_>>();
+
+    Ok(RelinearizationKeys::from_rns_data(context, &keys)?)
+}
+
+/**
+ * Returns a 2 polynomial ciphertext's modulus and its polynomials in
+ * coefficient form.
+ */
+fn ciphertext_polys(
+    basis: &Basis,
+    ciphertext: &SealCiphertext,
+) -> Result<(Basis, Vec<u64>, Vec<u64>)> {
+    if ciphertext.num_polynomials() != 2 {
+        return Err(ThresholdError::UnrelinearizedCiphertext.into());
+    }
+
+    let limbs = ciphertext.coeff_modulus_size() as usize;
+
+    if limbs == 0 || limbs > basis.moduli.len() {
+        return Err(Error::ParameterMismatch);
+    }
+
+    let level = basis.truncate(limbs);
+    let mut c0 = ciphertext.rns_data()?;
+
+    if c0.len() != 2 * level.len() {
+        return Err(Error::ParameterMismatch);
+    }
+
+    let c1 = c0.split_off(level.len());
+
+    Ok((level, c0, c1))
+}
+
+/**
+ * Returns the secret key's (small) coefficients.
+ */
+fn secret_coefficients(
+    context: &SealContext,
+    secret_key: &SealSecretKey,
+    basis: &Basis,
+) -> Result<Vec<i64>> {
+    let data = PolynomialArray::new_from_secret_key(context, secret_key)?.as_rns_u64s()?;
+    let q = basis.moduli[0];
+
+    if data.len() < basis.degree {
+        return Err(Error::ParameterMismatch);
+    }
+
+    Ok(data[..basis.degree]
+        .iter()
+        .map(|&c| {
+            if c > q / 2 {
+                -((q - c) as i64)
+            } else {
+                c as i64
+            }
+        })
+        .collect())
+}
+
+/**
+ * Sorts the participants and checks they're distinct, nonzero indices.
+ */
+fn validate_participants(participants: &[usize]) -> Result<Vec<usize>> {
+    let mut sorted = participants.to_vec();
+    sorted.sort_unstable();
+
+    if let Some(&0) = sorted.first() {
+        return Err(ThresholdError::InvalidParty(0).into());
+    }
+
+    if let Some(w) = sorted.windows(2).find(|w| w[0] == w[1]) {
+        return Err(ThresholdError::InvalidParty(w[0]).into());
+    }
+
+    Ok(sorted)
+}
+
+/**
+ * The Lagrange coefficient of `party` for interpolating at 0 from the given
+ * participants' points, modulo the prime `q`.
+ */
+fn lagrange_coefficient(participants: &[usize], party: usize, q: u64) -> u64 {
+    let party = party as u64 % q;
+
+    participants
+        .iter()
+        .map(|&p| p as u64 % q)
+        .filter(|&p| p != party)
+        .fold(1, |acc, p| {
+            mul_mod(acc, mul_mod(p, inv_mod(sub_mod(p, party, q), q), q), q)
+        })
+}
+
+/**
+ * Precomputed powers for the negacyclic number theoretic transform modulo a
+ * prime `q = 1 mod 2N`.
+ */
+struct NttTable {
+    q: u64,
+    psi: Vec<u64>,
+    psi_inv: Vec<u64>,
+    degree_inv: u64,
+}
+
+impl NttTable {
+    fn new(q: u64, degree: usize) -> Self {
+        let log_degree = degree.trailing_zeros();
+        let psi = primitive_root(q, 2 * degree as u64);
+
+        let bit_reversed_powers = |root: u64| {
+            let mut powers = vec![0; degree];
+            let mut x = 1;
+
+            for i in 0..degree {
+                powers[bit_reverse(i, log_degree)] = x;
+                x = mul_mod(x, root, q);
+            }
+
+            powers
+        };
+
+        Self {
+            q,
+            psi: bit_reversed_powers(psi),
+            psi_inv: bit_reversed_powers(inv_mod(psi, q)),
+            degree_inv: inv_mod(degree as u64 % q, q),
+        }
+    }
+
+    /**
+     * Transforms coefficients in natural order into evaluations in bit-reversed
+     * order.
+     */
+    fn forward(&self, a: &mut [u64]) {
+        let q = self.q;
+        let mut t = a.len();
+        let mut m = 1;
+
+        while m < a.len() {
+            t /= 2;
+
+            for i in 0..m {
+                let s = self.psi[m + i];
+
+                for j in 2 * i * t..(2 * i + 1) * t {
+                    let u = a[j];
+                    let v = mul_mod(a[j + t], s, q);
+                    a[j] = add_mod(u, v, q);
+                    a[j + t] = sub_mod(u, v, q);
+                }
+            }
+
+            m *= 2;
+        }
+    }
+
+    /**
+     * Inverts [`forward`](Self::forward).
+     */
+    fn inverse(&self, a: &mut [u64]) {
+        let q = self.q;
+        let mut t = 1;
+        let mut m = a.len();
+
+        while m > 1 {
+            let h = m / 2;
+
+            for i in 0..h {
+                let s = self.psi_inv[h + i];
+
+                for j in 2 * i * t..(2 * i + 1) * t {
+                    let u = a[j];
+                    let v = a[j + t];
+                    a[j] = add_mod(u, v, q);
+                    a[j + t] = mul_mod(sub_mod(u, v, q), s, q);
+                }
+            }
+
+            t *= 2;
+            m = h;
+        }
+
+        for x in a {
+            *x = mul_mod(*x, self.degree_inv, q);
+        }
+    }
+}
+
+fn bit_reverse(i: usize, bits: u32) -> usize {
+    if bits == 0 {
+        0
+    } else {
+        i.reverse_bits() >> (usize::BITS - bits)
+    }
+}
+
+/**
+ * Finds a primitive `order`-th root of unity modulo the prime `q`, where
+ * `order` is a power of 2 dividing `q - 1`.
+ */
+fn primitive_root(q: u64, order: u64) -> u64 {
+    (2..q)
+        .map(|g| pow_mod(g, (q - 1) / order, q))
+        .find(|&x| pow_mod(x, order / 2, q) == q - 1)
+        .expect("SEAL coefficient moduli support the NTT")
+}
+
+fn add_mod(a: u64, b: u64, q: u64) -> u64 {
+    let s = a + b;
+
+    if s >= q {
+        s - q
+    } else {
+        s
+    }
+}
+
+fn sub_mod(a: u64, b: u64, q: u64) -> u64 {
+    if a >= b {
+        a - b
+    } else {
+        a + q - b
+    }
+}
+
+fn mul_mod(a: u64, b: u64, q: u64) -> u64 {
+    ((a as u128 * b as u128) % q as u128) as u64
+}
+
+fn pow_mod(mut base: u64, mut exp: u64, q: u64) -> u64 {
+    let mut result = 1 % q;
+    base %= q;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_mod(result, base, q);
+        }
+
+        base = mul_mod(base, base, q);
+        exp >>= 1;
+    }
+
+    result
+}
+
+fn inv_mod(a: u64, q: u64) -> u64 {
+    pow_mod(a, q - 2, q)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+    use seal_fhe::{CoefficientModulus, SecurityLevel};
+
+    fn schoolbook_negacyclic(a: &[u64], b: &[u64], q: u64) -> Vec<u64> {
+        let n = a.len();
+        let mut c = vec![0; n];
+
+        for (i, &a) in a.iter().enumerate() {
+            for (j, &b) in b.iter().enumerate() {
+                let x = mul_mod(a, b, q);
+
+                if i + j < n {
+                    c[i + j] = add_mod(c[i + j], x, q);
+                } else {
+                    c[i + j - n] = sub_mod(c[i + j - n], x, q);
+                }
+            }
+        }
+
+        c
+    }
+
+    #[test]
+    fn ntt_multiplication_matches_schoolbook() {
+        let basis = Basis {
+            degree: 16,
+            moduli: vec![97, 193],
+        };
+
+        let a = basis.uniform(&mut OsRng);
+        let b = basis.uniform(&mut OsRng);
+
+        let expected = basis
+            .moduli
+            .iter()
+            .enumerate()
+            .flat_map(|(j, &q)| {
+                let limb = j * basis.degree..(j + 1) * basis.degree;
+                schoolbook_negacyclic(&a[limb.clone()], &b[limb], q)
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(basis.mul_negacyclic(&a, &b), expected);
+    }
+
+    #[test]
+    fn lagrange_coefficients_reconstruct_secret() {
+        let q = 193;
+        let secret = 42;
+        let f = |x: u64| {
+            add_mod(
+                secret,
+                add_mod(mul_mod(17, x, q), mul_mod(5, x * x, q), q),
+                q,
+            )
+        };
+
+        for participants in [vec![1, 2, 3], vec![2, 4, 5], vec![1, 3, 5, 6]] {
+            let reconstructed = participants.iter().fold(0, |acc, &p| {
+                add_mod(
+                    acc,
+                    mul_mod(lagrange_coefficient(&participants, p, q), f(p as u64), q),
+                    q,
+                )
+            });
+
+            assert_eq!(reconstructed, secret);
+        }
+    }
+
+    #[test]
+    fn smudging_noise_is_bounded() {
+        let basis = Basis {
+            degree: 64,
+            moduli: vec![(1 << 60) - 93],
+        };
+
+        // q > 2^59 and t = 3 < 2^2, so 3 shares stay below q / (8t) with
+        // |e| <= 2^(59 - 2 - 3 - 2) = 2^52.
+        let noise = basis.smudging_noise(3, 3, &mut OsRng);
+        let q = basis.moduli[0];
+        let magnitudes = noise.iter().map(|&x| x.min(q - x)).collect::<Vec<_>>();
+
+        assert!(magnitudes.iter().all(|&x| x <= 1 << 52));
+        assert!(magnitudes.iter().any(|&x| x > 1 << 48));
+    }
+
+    #[test]
+    fn threshold_operations_require_bfv() {
+        let runtime = Runtime::new_fhe(&Params {
+            lattice_dimension: 4096,
+            plain_modulus: 0,
+            coeff_modulus: CoefficientModulus::create(4096, &[36, 36, 37])
+                .unwrap()
+                .iter()
+                .map(|c| c.value())
+                .collect(),
+            security_level: SecurityLevel::TC128,
+            scheme_type: SchemeType::Ckks,
+        })
+        .unwrap();
+
+        let (_, private_key) = runtime.generate_keys().unwrap();
+
+        assert!(matches!(
+            runtime.generate_public_key_share(&private_key, &CommonReferenceString::new()),
+            Err(Error::ThresholdError(ThresholdError::UnsupportedScheme))
+        ));
+    }
+}
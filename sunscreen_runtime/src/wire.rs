@@ -0,0 +1,957 @@
+use std::fmt::Display;
+
+use merlin::Transcript;
+use seal_fhe::{CompressionType, Context as SealContext, FromBytes, SecurityLevel, ToBytes};
+use semver::Version;
+use sunscreen_fhe_program::SchemeType;
+
+use crate::{
+    serialization::seal_context, Ciphertext, Error, EvaluationKeys, InnerCiphertext,
+    InnerPlaintext, Params, Plaintext, PrivateKey, PublicKey, Result, Type, WithContext,
+};
+
+/**
+ * The bytes that begin every compact container.
+ */
+pub const WIRE_MAGIC: [u8; 4] = *b"SUNS";
+
+/**
+ * The newest compact container version this crate reads and the version it writes.
+ */
+pub const WIRE_FORMAT_VERSION: u16 = 1;
+
+const FLAG_COMPRESSED: u8 = 0x1;
+const FLAG_PARAMS_DIGEST: u8 = 0x2;
+const KNOWN_FLAGS: u8 = FLAG_COMPRESSED | FLAG_PARAMS_DIGEST;
+
+const DIGEST_LEN: usize = 32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+/**
+ * The kind of value stored in a compact container.
+ */
+pub enum WireKind {
+    /**
+     * A [`Ciphertext`].
+     */
+    Ciphertext = 1,
+
+    /**
+     * A [`Plaintext`].
+     */
+    Plaintext = 2,
+
+    /**
+     * A [`PublicKey`].
+     */
+    PublicKey = 3,
+
+    /**
+     * A [`PrivateKey`].
+     */
+    PrivateKey = 4,
+
+    /**
+     * An [`EvaluationKeys`].
+     */
+    EvaluationKeys = 5,
+}
+
+impl Display for WireKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Ciphertext => "Ciphertext",
+            Self::Plaintext => "Plaintext",
+            Self::PublicKey => "PublicKey",
+            Self::PrivateKey => "PrivateKey",
+            Self::EvaluationKeys => "EvaluationKeys",
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+/**
+ * Describes why a compact container couldn't be encoded or decoded.
+ */
+pub enum WireFormatError {
+    /**
+     * The input doesn't begin with [`WIRE_MAGIC`].
+     */
+    #[error("Input is not a Sunscreen compact container")]
+    BadMagic,
+
+    /**
+     * The container was written by a newer (or invalid) format version.
+     */
+    #[error("Unsupported format version {0}")]
+    UnsupportedVersion(u16),
+
+    /**
+     * The container holds a different kind of value than requested.
+     */
+    #[error("Expected a {expected} container, found kind tag {found}")]
+    KindMismatch {
+        /**
+         * The kind the caller asked to decode.
+         */
+        expected: WireKind,
+
+        /**
+         * The kind tag stored in the container.
+         */
+        found: u8,
+    },
+
+    /**
+     * The container sets flags this version doesn't understand.
+     */
+    #[error("Unknown flags {0:#04x}")]
+    UnknownFlags(u8),
+
+    /**
+     * The input ended before a field could be read.
+     */
+    #[error("Input truncated reading {field} at offset {offset}: needed {needed} bytes, {remaining} remain")]
+    Truncated {
+        /**
+         * The field being read.
+         */
+        field: &'static str,
+
+        /**
+         * The offset of the field in the input.
+         */
+        offset: usize,
+
+        /**
+         * The length of the field.
+         */
+        needed: usize,
+
+        /**
+         * The number of bytes left in the input.
+         */
+        remaining: usize,
+    },
+
+    /**
+     * Bytes remain after the container's last field.
+     */
+    #[error("{0} unexpected bytes after the end of the container")]
+    TrailingBytes(usize),
+
+    /**
+     * A field holds a value that isn't valid.
+     */
+    #[error("Invalid {0}")]
+    InvalidField(&'static str),
+
+    /**
+     * The container only stores a digest of its parameters, and none were given to
+     * [`CompactSerialize::from_compact_bytes_with_params`].
+     */
+    #[error("Container stores a parameter digest; the parameters must be supplied to decode it")]
+    MissingParams,
+
+    /**
+     * The container was encoded under different parameters than those supplied.
+     */
+    #[error("Container was encoded under different parameters")]
+    ParamsMismatch,
+
+    /**
+     * The value contains no SEAL objects, so there are no parameters to encode.
+     */
+    #[error("Cannot encode an empty value")]
+    Empty,
+}
+
+impl From<WireFormatError> for Error {
+    fn from(err: WireFormatError) -> Self {
+        Self::WireFormatError(Box::new(err))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/**
+ * Controls how [`CompactSerialize::to_compact_bytes`] encodes a value.
+ */
+pub struct CompactOptions {
+    /**
+     * Compress SEAL objects with Zstandard. Smaller, but slower to encode and decode.
+     */
+    pub compress: bool,
+
+    /**
+     * Store the full scheme parameters in the container. When `false`, only a digest of the
+     * parameters is stored and readers must supply them to
+     * [`CompactSerialize::from_compact_bytes_with_params`].
+     */
+    pub embed_params: bool,
+}
+
+impl Default for CompactOptions {
+    fn default() -> Self {
+        Self {
+            compress: true,
+            embed_params: true,
+        }
+    }
+}
+
+/**
+ * A versioned binary container format for ciphertexts, plaintexts and keys.
+ *
+ * # Remarks
+ * Unlike the serde representations, which store a copy of the [`Params`] next to every
+ * SEAL object, a compact container stores the parameters once (or just their digest).
+ *
+ * Every container starts with a header:
+ * * 4 bytes: [`WIRE_MAGIC`].
+ * * 2 bytes: the format version, big endian.
+ * * 1 byte: the [`WireKind`] tag.
+ * * 1 byte: flags. Bit 0 marks compressed SEAL objects, bit 1 marks a parameter digest.
+ * * The encoded parameters or their 32 byte digest.
+ *
+ * followed by the value's SEAL objects, each prefixed with its big endian `u32` length.
+ */
+pub trait CompactSerialize: encoding::WireObject {
+    /**
+     * Encodes this value as a compact container.
+     */
+    fn to_compact_bytes(&self, options: &CompactOptions) -> Result<Vec<u8>> {
+        let params = self.wire_params()?;
+        let compression = if options.compress {
+            CompressionType::ZStd
+        } else {
+            CompressionType::None
+        };
+
+        let mut writer = encoding::Writer::new(compression);
+        writer.write_header(Self::KIND, params, options);
+        self.write_body(&mut writer)?;
+
+        Ok(writer.finish())
+    }
+
+    /**
+     * Decodes a compact container that embeds its parameters.
+     */
+    fn from_compact_bytes(bytes: &[u8]) -> Result<Self> {
+        encoding::decode(bytes, None)
+    }
+
+    /**
+     * Decodes a compact container, checking it was encoded under `params`. This works for
+     * containers that store either the full parameters or their digest.
+     */
+    fn from_compact_bytes_with_params(bytes: &[u8], params: &Params) -> Result<Self> {
+        encoding::decode(bytes, Some(params))
+    }
+}
+
+impl CompactSerialize for Ciphertext {}
+impl CompactSerialize for Plaintext {}
+impl CompactSerialize for PublicKey {}
+impl CompactSerialize for PrivateKey {}
+impl CompactSerialize for EvaluationKeys {}
+
+/**
+ * Returns the single set of parameters shared by all the given SEAL objects.
+ */
+fn shared_params<'a, T>(items: &'a [WithContext<T>]) -> Result<&'a Params>
+where
+    T: ToBytes + FromBytes + PartialEq,
+{
+    let params = &items.first().ok_or(WireFormatError::Empty)?.params;
+
+    if items.iter().any(|x| &x.params != params) {
+        return Err(Error::ParameterMismatch);
+    }
+
+    Ok(params)
+}
+
+fn params_digest(encoded: &[u8]) -> [u8; DIGEST_LEN] {
+    let mut transcript = Transcript::new(b"sunscreen-params");
+    transcript.append_message(b"params", encoded);
+
+    let mut digest = [0u8; DIGEST_LEN];
+    transcript.challenge_bytes(b"digest", &mut digest);
+
+    digest
+}
+
+mod encoding {
+    use super::*;
+
+    /**
+     * The per-type encoding of a compact container's body.
+     */
+    pub trait WireObject: Sized {
+        /**
+         * The kind tag for this type.
+         */
+        const KIND: WireKind;
+
+        /**
+         * The parameters every SEAL object in this value was created under.
+         */
+        fn wire_params(&self) -> Result<&Params>;
+
+        /**
+         * Writes everything following the header.
+         */
+        fn write_body(&self, writer: &mut Writer) -> Result<()>;
+
+        /**
+         * Reads everything following the header.
+         */
+        fn read_body(reader: &mut Reader, params: &Params, context: &SealContext) -> Result<Self>;
+    }
+
+    pub fn decode<T: WireObject>(bytes: &[u8], params: Option<&Params>) -> Result<T> {
+        let mut reader = Reader::new(bytes);
+        let params = reader.read_header(T::KIND, params)?;
+        let context = seal_context(&params)?;
+
+        let val = T::read_body(&mut reader, &params, &context)?;
+        reader.finish()?;
+
+        Ok(val)
+    }
+
+    fn encode_params(params: &Params) -> Vec<u8> {
+        let mut writer = Writer::new(CompressionType::None);
+
+        writer.write_u64(params.lattice_dimension);
+        writer.write_u64(params.plain_modulus);
+        writer.write_u8(params.scheme_type.into());
+        writer.write_i32(params.security_level.into());
+        writer.write_u32(params.coeff_modulus.len() as u32);
+
+        for q in &params.coeff_modulus {
+            writer.write_u64(*q);
+        }
+
+        writer.finish()
+    }
+
+    pub struct Writer {
+        buf: Vec<u8>,
+        compression: CompressionType,
+    }
+
+    impl Writer {
+        pub fn new(compression: CompressionType) -> Self {
+            Self {
+                buf: vec![],
+                compression,
+            }
+        }
+
+        pub fn write_header(&mut self, kind: WireKind, params: &Params, options: &CompactOptions) {
+            let mut flags = 0;
+
+            if options.compress {
+                flags |= FLAG_COMPRESSED;
+            }
+
+            if !options.embed_params {
+                flags |= FLAG_PARAMS_DIGEST;
+            }
+
+            self.buf.extend_from_slice(&WIRE_MAGIC);
+            self.buf
+                .extend_from_slice(&WIRE_FORMAT_VERSION.to_be_bytes());
+            self.write_u8(kind as u8);
+            self.write_u8(flags);
+
+            let params = encode_params(params);
+
+            if options.embed_params {
+                self.buf.extend_from_slice(&params);
+            } else {
+                self.buf.extend_from_slice(&params_digest(&params));
+            }
+        }
+
+        pub fn write_u8(&mut self, val: u8) {
+            self.buf.push(val);
+        }
+
+        pub fn write_u32(&mut self, val: u32) {
+            self.buf.extend_from_slice(&val.to_be_bytes());
+        }
+
+        pub fn write_u64(&mut self, val: u64) {
+            self.buf.extend_from_slice(&val.to_be_bytes());
+        }
+
+        pub fn write_i32(&mut self, val: i32) {
+            self.buf.extend_from_slice(&val.to_be_bytes());
+        }
+
+        pub fn write_bytes(&mut self, bytes: &[u8]) {
+            self.write_u32(bytes.len() as u32);
+            self.buf.extend_from_slice(bytes);
+        }
+
+        pub fn write_type(&mut self, data_type: &Type) {
+            self.write_bytes(data_type.name.as_bytes());
+            self.write_bytes(data_type.version.to_string().as_bytes());
+            self.write_u8(data_type.is_encrypted as u8);
+        }
+
+        pub fn write_seal<T: ToBytes>(&mut self, val: &T) -> Result<()> {
+            let bytes = val.as_bytes_with_compression(self.compression)?;
+            self.write_bytes(&bytes);
+
+            Ok(())
+        }
+
+        pub fn write_seal_list<T>(&mut self, items: &[WithContext<T>]) -> Result<()>
+        where
+            T: ToBytes + FromBytes + PartialEq,
+        {
+            self.write_u32(items.len() as u32);
+
+            for x in items {
+                self.write_seal(&x.data)?;
+            }
+
+            Ok(())
+        }
+
+        pub fn write_optional_seal<T>(&mut self, val: &Option<WithContext<T>>) -> Result<()>
+        where
+            T: ToBytes + FromBytes + PartialEq,
+        {
+            match val {
+                Some(x) => {
+                    self.write_u8(1);
+                    self.write_seal(&x.data)
+                }
+                None => {
+                    self.write_u8(0);
+                    Ok(())
+                }
+            }
+        }
+
+        pub fn finish(self) -> Vec<u8> {
+            self.buf
+        }
+    }
+
+    pub struct Reader<'a> {
+        bytes: &'a [u8],
+        offset: usize,
+    }
+
+    impl<'a> Reader<'a> {
+        pub fn new(bytes: &'a [u8]) -> Self {
+            Self { bytes, offset: 0 }
+        }
+
+        fn take(&mut self, len: usize, field: &'static str) -> Result<&'a [u8]> {
+            let remaining = self.bytes.len() - self.offset;
+
+            if len > remaining {
+                return Err(WireFormatError::Truncated {
+                    field,
+                    offset: self.offset,
+                    needed: len,
+                    remaining,
+                }
+                .into());
+            }
+
+            let data = &self.bytes[self.offset..self.offset + len];
+            self.offset += len;
+
+            Ok(data)
+        }
+
+        fn read_array<const N: usize>(&mut self, field: &'static str) -> Result<[u8; N]> {
+            let mut data = [0u8; N];
+            data.copy_from_slice(self.take(N, field)?);
+
+            Ok(data)
+        }
+
+        pub fn read_u8(&mut self, field: &'static str) -> Result<u8> {
+            Ok(self.read_array::<1>(field)?[0])
+        }
+
+        pub fn read_u16(&mut self, field: &'static str) -> Result<u16> {
+            Ok(u16::from_be_bytes(self.read_array(field)?))
+        }
+
+        pub fn read_u32(&mut self, field: &'static str) -> Result<u32> {
+            Ok(u32::from_be_bytes(self.read_array(field)?))
+        }
+
+        pub fn read_u64(&mut self, field: &'static str) -> Result<u64> {
+            Ok(u64::from_be_bytes(self.read_array(field)?))
+        }
+
+        pub fn read_i32(&mut self, field: &'static str) -> Result<i32> {
+            Ok(i32::from_be_bytes(self.read_array(field)?))
+        }
+
+        pub fn read_bytes(&mut self, field: &'static str) -> Result<&'a [u8]> {
+            let len = self.read_u32(field)? as usize;
+
+            self.take(len, field)
+        }
+
+        pub fn read_bool(&mut self, field: &'static str) -> Result<bool> {
+            match self.read_u8(field)? {
+                0 => Ok(false),
+                1 => Ok(true),
+                _ => Err(WireFormatError::InvalidField(field).into()),
+            }
+        }
+
+        fn read_str(&mut self, field: &'static str) -> Result<&'a str> {
+            std::str::from_utf8(self.read_bytes(field)?)
+                .map_err(|_| WireFormatError::InvalidField(field).into())
+        }
+
+        pub fn read_type(&mut self) -> Result<Type> {
+            let name = self.read_str("type name")?.to_owned();
+            let version = Version::parse(self.read_str("type version")?)
+                .map_err(|_| WireFormatError::InvalidField("type version"))?;
+            let is_encrypted = self.read_bool("type encryption flag")?;
+
+            Ok(Type {
+                name,
+                version,
+                is_encrypted,
+            })
+        }
+
+        pub fn read_seal<T: FromBytes>(
+            &mut self,
+            context: &SealContext,
+            field: &'static str,
+        ) -> Result<T> {
+            let bytes = self.read_bytes(field)?;
+
+            Ok(T::from_bytes(context, bytes)?)
+        }
+
+        pub fn read_seal_list<T>(
+            &mut self,
+            params: &Params,
+            context: &SealContext,
+            field: &'static str,
+        ) -> Result<Vec<WithContext<T>>>
+        where
+            T: ToBytes + FromBytes + PartialEq,
+        {
+            let count = self.read_u32("object count")?;
+
+            if count == 0 {
+                return Err(WireFormatError::Empty.into());
+            }
+
+            (0..count)
+                .map(|_| {
+                    Ok(WithContext {
+                        params: params.clone(),
+                        data: self.read_seal(context, field)?,
+                    })
+                })
+                .collect()
+        }
+
+        pub fn read_optional_seal<T>(
+            &mut self,
+            params: &Params,
+            context: &SealContext,
+            field: &'static str,
+        ) -> Result<Option<WithContext<T>>>
+        where
+            T: ToBytes + FromBytes + PartialEq,
+        {
+            if !self.read_bool(field)? {
+                return Ok(None);
+            }
+
+            Ok(Some(WithContext {
+                params: params.clone(),
+                data: self.read_seal(context, field)?,
+            }))
+        }
+
+        fn read_params(&mut self) -> Result<Params> {
+            let lattice_dimension = self.read_u64("lattice dimension")?;
+            let plain_modulus = self.read_u64("plain modulus")?;
+            let scheme_type = SchemeType::try_from(self.read_u8("scheme type")?)
+                .map_err(|_| WireFormatError::InvalidField("scheme type"))?;
+            let security_level = SecurityLevel::try_from(self.read_i32("security level")?)
+                .map_err(|_| WireFormatError::InvalidField("security level"))?;
+
+            let count = self.read_u32("coefficient modulus count")?;
+            let coeff_modulus = (0..count)
+                .map(|_| self.read_u64("coefficient modulus"))
+                .collect::<Result<Vec<_>>>()?;
+
+            Ok(Params {
+                lattice_dimension,
+                coeff_modulus,
+                plain_modulus,
+                scheme_type,
+                security_level,
+            })
+        }
+
+        /**
+         * Validates the header and returns the container's parameters.
+         */
+        pub fn read_header(&mut self, kind: WireKind, params: Option<&Params>) -> Result<Params> {
+            if self.read_array::<4>("magic")? != WIRE_MAGIC {
+                return Err(WireFormatError::BadMagic.into());
+            }
+
+            let version = self.read_u16("version")?;
+
+            if version == 0 || version > WIRE_FORMAT_VERSION {
+                return Err(WireFormatError::UnsupportedVersion(version).into());
+            }
+
+            let found = self.read_u8("kind")?;
+
+            if found != kind as u8 {
+                return Err(WireFormatError::KindMismatch {
+                    expected: kind,
+                    found,
+                }
+                .into());
+            }
+
+            let flags = self.read_u8("flags")?;
+
+            if flags & !KNOWN_FLAGS != 0 {
+                return Err(WireFormatError::UnknownFlags(flags).into());
+            }
+
+            // SEAL records whether each object is compressed, so the compression flag is
+            // purely informational when reading.
+            if flags & FLAG_PARAMS_DIGEST != 0 {
+                let digest = self.read_array::<DIGEST_LEN>("parameter digest")?;
+                let params = params.ok_or(WireFormatError::MissingParams)?;
+
+                if params_digest(&encode_params(params)) != digest {
+                    return Err(WireFormatError::ParamsMismatch.into());
+                }
+
+                Ok(params.clone())
+            } else {
+                let embedded = self.read_params()?;
+
+                match params {
+                    Some(p) if *p != embedded => Err(WireFormatError::ParamsMismatch.into()),
+                    _ => Ok(embedded),
+                }
+            }
+        }
+
+        pub fn finish(self) -> Result<()> {
+            let remaining = self.bytes.len() - self.offset;
+
+            if remaining > 0 {
+                return Err(WireFormatError::TrailingBytes(remaining).into());
+            }
+
+            Ok(())
+        }
+    }
+
+    impl WireObject for Ciphertext {
+        const KIND: WireKind = WireKind::Ciphertext;
+
+        fn wire_params(&self) -> Result<&Params> {
+            match &self.inner {
+                InnerCiphertext::Seal(x) => shared_params(x),
+            }
+        }
+
+        fn write_body(&self, writer: &mut Writer) -> Result<()> {
+            writer.write_type(&self.data_type);
+
+            match &self.inner {
+                InnerCiphertext::Seal(x) => writer.write_seal_list(x),
+            }
+        }
+
+        fn read_body(reader: &mut Reader, params: &Params, context: &SealContext) -> Result<Self> {
+            let data_type = reader.read_type()?;
+            let inner =
+                InnerCiphertext::Seal(reader.read_seal_list(params, context, "ciphertext")?);
+
+            Ok(Self { data_type, inner })
+        }
+    }
+
+    impl WireObject for Plaintext {
+        const KIND: WireKind = WireKind::Plaintext;
+
+        fn wire_params(&self) -> Result<&Params> {
+            match &self.inner {
+                InnerPlaintext::Seal(x) => shared_params(x),
+            }
+        }
+
+        fn write_body(&self, writer: &mut Writer) -> Result<()> {
+            writer.write_type(&self.data_type);
+
+            match &self.inner {
+                InnerPlaintext::Seal(x) => writer.write_seal_list(x),
+            }
+        }
+
+        fn read_body(reader: &mut Reader, params: &Params, context: &SealContext) -> Result<Self> {
+            let data_type = reader.read_type()?;
+            let inner =
+                InnerPlaintext::Seal(reader.read_seal_list(params, context, "plaintext")?);
+
+            Ok(Self { data_type, inner })
+        }
+    }
+
+    impl WireObject for PublicKey {
+        const KIND: WireKind = WireKind::PublicKey;
+
+        fn wire_params(&self) -> Result<&Params> {
+            Ok(&self.public_key.params)
+        }
+
+        fn write_body(&self, writer: &mut Writer) -> Result<()> {
+            writer.write_seal(&self.public_key.data)
+        }
+
+        fn read_body(reader: &mut Reader, params: &Params, context: &SealContext) -> Result<Self> {
+            Ok(Self {
+                public_key: WithContext {
+                    params: params.clone(),
+                    data: reader.read_seal(context, "public key")?,
+                },
+            })
+        }
+    }
+
+    impl WireObject for EvaluationKeys {
+        const KIND: WireKind = WireKind::EvaluationKeys;
+
+        fn wire_params(&self) -> Result<&Params> {
+            let mut params = [
+                self.galois_key.as_ref().map(|x| &x.params),
+                self.relin_key.as_ref().map(|x| &x.params),
+            ]
+            .into_iter()
+            .flatten();
+
+            let first = params.next().ok_or(WireFormatError::Empty)?;
+
+            if params.any(|x| x != first) {
+                return Err(Error::ParameterMismatch);
+            }
+
+            Ok(first)
+        }
+
+        fn write_body(&self, writer: &mut Writer) -> Result<()> {
+            writer.write_optional_seal(&self.galois_key)?;
+            writer.write_optional_seal(&self.relin_key)
+        }
+
+        fn read_body(reader: &mut Reader, params: &Params, context: &SealContext) -> Result<Self> {
+            let galois_key = reader.read_optional_seal(params, context, "Galois keys")?;
+            let relin_key = reader.read_optional_seal(params, context, "relinearization keys")?;
+
+            Ok(Self {
+                galois_key,
+                relin_key,
+            })
+        }
+    }
+
+    impl WireObject for PrivateKey {
+        const KIND: WireKind = WireKind::PrivateKey;
+
+        fn wire_params(&self) -> Result<&Params> {
+            Ok(&self.0.params)
+        }
+
+        fn write_body(&self, writer: &mut Writer) -> Result<()> {
+            writer.write_seal(&self.0.data)
+        }
+
+        fn read_body(reader: &mut Reader, params: &Params, context: &SealContext) -> Result<Self> {
+            Ok(Self(WithContext {
+                params: params.clone(),
+                data: reader.read_seal(context, "secret key")?,
+            }))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+    use seal_fhe::{CoefficientModulus, PlainModulus, SecurityLevel};
+
+    fn runtime() -> FheRuntime {
+        Runtime::new_fhe(&Params {
+            lattice_dimension: 4096,
+            security_level: SecurityLevel::TC128,
+            plain_modulus: PlainModulus::batching(4096, 20).unwrap().value(),
+            scheme_type: SchemeType::Bfv,
+            coeff_modulus: CoefficientModulus::bfv_default(4096, SecurityLevel::TC128)
+                .unwrap()
+                .iter()
+                .map(|x| x.value())
+                .collect(),
+        })
+        .unwrap()
+    }
+
+    fn wire_error(err: Error) -> WireFormatError {
+        match err {
+            Error::WireFormatError(e) => *e,
+            e => panic!("Expected a wire format error, got {:?}", e),
+        }
+    }
+
+    #[test]
+    fn can_roundtrip_keys() {
+        let runtime = runtime();
+        let (public_key, private_key) = runtime.generate_keys().unwrap();
+
+        for options in [
+            CompactOptions::default(),
+            CompactOptions {
+                compress: false,
+                embed_params: false,
+            },
+        ] {
+            let pk_bytes = public_key.to_compact_bytes(&options).unwrap();
+            let sk_bytes = private_key.to_compact_bytes(&options).unwrap();
+
+            let public_2 =
+                PublicKey::from_compact_bytes_with_params(&pk_bytes, runtime.params()).unwrap();
+            let private_2 =
+                PrivateKey::from_compact_bytes_with_params(&sk_bytes, runtime.params()).unwrap();
+
+            assert!(public_key == public_2);
+            assert!(private_key == private_2);
+        }
+    }
+
+    #[test]
+    fn digest_containers_require_params() {
+        let runtime = runtime();
+        let (_, private_key) = runtime.generate_keys().unwrap();
+
+        let bytes = private_key
+            .to_compact_bytes(&CompactOptions {
+                embed_params: false,
+                ..CompactOptions::default()
+            })
+            .unwrap();
+
+        let err = PrivateKey::from_compact_bytes(&bytes).err().unwrap();
+        assert_eq!(wire_error(err), WireFormatError::MissingParams);
+
+        let other_params = Params {
+            plain_modulus: 1024,
+            ..runtime.params().clone()
+        };
+
+        let err = PrivateKey::from_compact_bytes_with_params(&bytes, &other_params)
+            .err()
+            .unwrap();
+        assert_eq!(wire_error(err), WireFormatError::ParamsMismatch);
+    }
+
+    #[test]
+    fn rejects_malformed_headers() {
+        let runtime = runtime();
+        let (_, private_key) = runtime.generate_keys().unwrap();
+        let bytes = private_key
+            .to_compact_bytes(&CompactOptions::default())
+            .unwrap();
+
+        let mut bad_magic = bytes.clone();
+        bad_magic[0] = b'X';
+        let err = PrivateKey::from_compact_bytes(&bad_magic).err().unwrap();
+        assert_eq!(wire_error(err), WireFormatError::BadMagic);
+
+        let mut bad_version = bytes.clone();
+        bad_version[4..6].copy_from_slice(&(WIRE_FORMAT_VERSION + 1).to_be_bytes());
+        let err = PrivateKey::from_compact_bytes(&bad_version).err().unwrap();
+        assert_eq!(
+            wire_error(err),
+            WireFormatError::UnsupportedVersion(WIRE_FORMAT_VERSION + 1)
+        );
+
+        let err = PublicKey::from_compact_bytes(&bytes).err().unwrap();
+        assert_eq!(
+            wire_error(err),
+            WireFormatError::KindMismatch {
+                expected: WireKind::PublicKey,
+                found: WireKind::PrivateKey as u8
+            }
+        );
+
+        let mut bad_flags = bytes.clone();
+        bad_flags[7] |= 0x80;
+        let err = PrivateKey::from_compact_bytes(&bad_flags).err().unwrap();
+        assert_eq!(wire_error(err), WireFormatError::UnknownFlags(0x81));
+
+        let mut trailing = bytes;
+        trailing.push(0);
+        let err = PrivateKey::from_compact_bytes(&trailing).err().unwrap();
+        assert_eq!(wire_error(err), WireFormatError::TrailingBytes(1));
+    }
+
+    #[test]
+    fn rejects_truncated_input() {
+        let runtime = runtime();
+        let (_, private_key) = runtime.generate_keys().unwrap();
+        let bytes = private_key
+            .to_compact_bytes(&CompactOptions::default())
+            .unwrap();
+
+        for len in [0, 3, 7, 12, 40, bytes.len() / 2, bytes.len() - 1] {
+            let err = PrivateKey::from_compact_bytes(&bytes[..len]).err().unwrap();
+
+            assert!(
+                matches!(wire_error(err), WireFormatError::Truncated { .. }),
+                "Truncating to {} bytes wasn't detected",
+                len
+            );
+        }
+    }
+
+    #[test]
+    fn cannot_encode_empty_evaluation_keys() {
+        let eval_keys = EvaluationKeys {
+            galois_key: None,
+            relin_key: None,
+        };
+
+        let err = eval_keys
+            .to_compact_bytes(&CompactOptions::default())
+            .err()
+            .unwrap();
+        assert_eq!(wire_error(err), WireFormatError::Empty);
+    }
+}
@@ -1,24 +1,28 @@
 use std::{
+    collections::{BTreeMap, HashMap},
     ops::{Add, Deref, Mul, Neg, Sub},
     time::Instant,
 };
 
 use bulletproofs::{
-    r1cs::{ConstraintSystem, LinearCombination, Metrics, Prover, R1CSError, R1CSProof, Verifier},
+    r1cs::{
+        batch_verify, ConstraintSystem, LinearCombination, Metrics, Prover, R1CSError, R1CSProof,
+        Verifier,
+    },
     BulletproofGens, PedersenGens,
 };
 use crypto_bigint::{Limb, Uint};
 use curve25519_dalek::scalar::Scalar;
 use log::trace;
 use merlin::Transcript;
-use petgraph::stable_graph::NodeIndex;
+use petgraph::{algo::toposort, stable_graph::NodeIndex, Direction};
 use rand::thread_rng;
 use serde::{Deserialize, Serialize};
 use sunscreen_compiler_common::{forward_traverse, GraphQuery};
 
 use crate::{
-    exec::Operation, jit::jit_verifier, jit_prover, BigInt, CompiledZkpProgram, Error,
-    ExecutableZkpProgram, FieldSpec, Proof, Result, ZkpBackend,
+    exec::Operation, jit::jit_verifier, jit::Operation as JitOperation, jit_prover, BigInt,
+    CompiledZkpProgram, Error, ExecutableZkpProgram, FieldSpec, Proof, Result, ZkpBackend,
 };
 
 #[derive(Clone)]
@@ -314,6 +318,87 @@ impl BulletproofsCircuit {
     }
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+/**
+ * The cost of a portion of a circuit in the Bulletproofs backend.
+ */
+pub struct CircuitMetrics {
+    /**
+     * The number of multiplication gates, excluding multiplications by a
+     * constant.
+     */
+    pub multipliers: usize,
+
+    /**
+     * The number of allocated variables (inputs and gadget hidden inputs).
+     * Bulletproofs packs two allocations into each multiplier.
+     */
+    pub allocations: usize,
+
+    /**
+     * The number of linear constraints.
+     */
+    pub constraints: usize,
+}
+
+impl Add for CircuitMetrics {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            multipliers: self.multipliers + rhs.multipliers,
+            allocations: self.allocations + rhs.allocations,
+            constraints: self.constraints + rhs.constraints,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+/**
+ * The cost attributed to a type of gadget.
+ */
+pub struct GadgetMetrics {
+    /**
+     * The number of times the program invokes this gadget.
+     */
+    pub invocations: usize,
+
+    /**
+     * The combined cost of every invocation.
+     */
+    pub metrics: CircuitMetrics,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+/**
+ * A breakdown of where a program's constraints come from. See
+ * [`BulletproofsBackend::gadget_metrics`].
+ */
+pub struct MetricsBreakdown {
+    /**
+     * The cost of the program's own operations outside any gadget.
+     */
+    pub program: CircuitMetrics,
+
+    /**
+     * The cost of each type of gadget, keyed by [`Gadget::debug_name`](crate::Gadget::debug_name).
+     * The cost of a gadget invoked by another gadget goes to the inner
+     * gadget.
+     */
+    pub gadgets: BTreeMap<&'static str, GadgetMetrics>,
+}
+
+impl MetricsBreakdown {
+    /**
+     * The cost of the whole program.
+     */
+    pub fn total(&self) -> CircuitMetrics {
+        self.gadgets
+            .values()
+            .fold(self.program, |acc, g| acc + g.metrics)
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 /**
  * A Bulletproofs backend.
@@ -395,6 +480,113 @@ impl BulletproofsBackend {
 
         Ok(constraint_count)
     }
+    /// Returns a breakdown of the given program's cost by gadget, so you
+    /// can see where constraints go.
+    ///
+    /// An operation belongs to a gadget invocation when all its
+    /// non-constant operands belong to that invocation or are the
+    /// invocation's inputs. Everything else belongs to the program. This
+    /// doesn't require inputs, but as it runs before JIT compilation the
+    /// counts may slightly exceed [`Self::metrics`].
+    pub fn gadget_metrics(&self, program: &CompiledZkpProgram) -> Result<MetricsBreakdown> {
+        let order = toposort(&program.0, None)
+            .map_err(|_| Error::malformed_zkp_program("cycle detected"))?;
+
+        let mut is_constant = vec![false; program.node_bound()];
+        let mut owner: Vec<Option<NodeIndex>> = vec![None; program.node_bound()];
+        let mut costs: HashMap<Option<NodeIndex>, CircuitMetrics> = HashMap::new();
+
+        for idx in order {
+            let parents = program
+                .neighbors_directed(idx, Direction::Incoming)
+                .collect::<Vec<_>>();
+            let variables = parents
+                .iter()
+                .filter(|p| !is_constant[p.index()])
+                .copied()
+                .collect::<Vec<_>>();
+
+            // The invocations enclosing `p`, innermost first.
+            let invocations = |owner: &[Option<NodeIndex>], p: NodeIndex| {
+                std::iter::successors(owner[p.index()], |g| owner[g.index()]).collect::<Vec<_>>()
+            };
+
+            // The innermost invocation every variable operand is computed
+            // in (possibly by a nested gadget) or feeds into.
+            let enclosing = variables
+                .iter()
+                .flat_map(|p| invocations(&owner, *p))
+                .filter(|g| {
+                    let inputs = program
+                        .neighbors_directed(*g, Direction::Incoming)
+                        .collect::<Vec<_>>();
+
+                    variables
+                        .iter()
+                        .all(|p| invocations(&owner, *p).contains(g) || inputs.contains(p))
+                })
+                .max();
+
+            let mut cost = CircuitMetrics::default();
+
+            match &program[idx].operation {
+                JitOperation::Constant(_) | JitOperation::ConstantInput(_) => {
+                    is_constant[idx.index()] = true;
+                }
+                JitOperation::PrivateInput(_) => {
+                    cost.allocations = 1;
+                }
+                JitOperation::PublicInput(_) => {
+                    // The JIT constrains public inputs to their values.
+                    cost.allocations = 1;
+                    cost.constraints = 1;
+                }
+                JitOperation::HiddenInput(_) => {
+                    owner[idx.index()] = parents.first().copied();
+                    cost.allocations = 1;
+                }
+                JitOperation::InvokeGadget(_) => {
+                    // Gadget invocations themselves don't cost anything.
+                    owner[idx.index()] = enclosing;
+                }
+                JitOperation::Add | JitOperation::Sub | JitOperation::Neg => {
+                    is_constant[idx.index()] = variables.is_empty();
+                    owner[idx.index()] = enclosing;
+                }
+                JitOperation::Mul => {
+                    is_constant[idx.index()] = variables.is_empty();
+                    owner[idx.index()] = enclosing;
+
+                    if variables.len() == 2 {
+                        cost.multipliers = 1;
+                    }
+                }
+                JitOperation::Constraint(_) => {
+                    owner[idx.index()] = enclosing;
+                    cost.constraints = variables.len();
+                }
+            }
+
+            let entry = costs.entry(owner[idx.index()]).or_default();
+            *entry = *entry + cost;
+        }
+
+        let mut breakdown = MetricsBreakdown {
+            program: costs.remove(&None).unwrap_or_default(),
+            gadgets: BTreeMap::new(),
+        };
+
+        for idx in program.node_indices() {
+            if let JitOperation::InvokeGadget(g) = &program[idx].operation {
+                let entry = breakdown.gadgets.entry(g.debug_name()).or_default();
+
+                entry.invocations += 1;
+                entry.metrics = entry.metrics + costs.get(&Some(idx)).copied().unwrap_or_default();
+            }
+        }
+
+        Ok(breakdown)
+    }
 }
 
 impl Default for BulletproofsBackend {
@@ -650,6 +842,81 @@ impl ZkpBackend for BulletproofsBackend {
         Ok(())
     }
 
+    /**
+     * Verify many proofs at once.
+     *
+     * # Remarks
+     * Verifying a Bulletproof ends with a multiscalar multiplication
+     * that must equal the identity. We scale each proof's scalars by a
+     * random weight and check the sum of all the multiplications in one
+     * go. Most of the points are generators the proofs share, so this
+     * costs far less than verifying each proof on its own.
+     */
+    fn verify_batch(&self, instances: &[(&ExecutableZkpProgram, &Proof)]) -> Result<()> {
+        let mut constraint_counts = vec![];
+
+        for (graph, _) in instances {
+            constraint_counts.push(constraint_count(graph)?);
+        }
+
+        let max_constraint_count = constraint_counts.iter().copied().max().unwrap_or(0);
+
+        let (pedersen_gens, bulletproof_gens) =
+            BulletproofsCircuit::make_gens(2 * max_constraint_count);
+
+        let mut transcripts = instances
+            .iter()
+            .map(|_| BulletproofsCircuit::make_base_transcript())
+            .collect::<Vec<Transcript>>();
+
+        let now = Instant::now();
+
+        let mut verifiers = vec![];
+
+        for (((graph, proof), transcript), constraint_count) in instances
+            .iter()
+            .zip(transcripts.iter_mut())
+            .zip(constraint_counts)
+        {
+            let proof = match proof {
+                Proof::Bulletproofs(x) => x,
+                _ => {
+                    return Err(Error::IncorrectProofType);
+                }
+            };
+
+            transcript.append_message(b"dom-sep", b"R1CS proof");
+            transcript.append_u64(b"gen-len", constraint_count as u64);
+
+            let mut circuit = BulletproofsCircuit::new(graph.node_count());
+
+            let mut verifier = Verifier::new(transcript);
+
+            circuit.gen_circuit(graph, &mut verifier, |_| None)?;
+
+            verifiers.push((verifier, &proof.0));
+        }
+
+        trace!("Bulletproofs encode time {}s", now.elapsed().as_secs_f64());
+
+        let now = Instant::now();
+
+        batch_verify(
+            &mut thread_rng(),
+            verifiers,
+            &pedersen_gens,
+            &bulletproof_gens,
+        )?;
+
+        trace!(
+            "Bulletproofs batch verify time {}s ({} proofs)",
+            now.elapsed().as_secs_f64(),
+            instances.len()
+        );
+
+        Ok(())
+    }
+
     fn jit_prover(
         &self,
         prog: &crate::CompiledZkpProgram,
@@ -0,0 +1,355 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Display, Formatter};
+
+use crypto_bigint::Encoding;
+use petgraph::stable_graph::NodeIndex;
+use serde::{Deserialize, Serialize};
+use sunscreen_compiler_common::GraphQuery;
+
+use crate::{
+    jit::{prepare_prover, run_prover},
+    BigInt, CompiledZkpProgram, Error, FieldSpec, Operation, Result, ZkpInto,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+/**
+ * A location in a Rust source file, e.g. the expression in a
+ * `#[zkp_program]` that created a node.
+ */
+pub struct SourceLocation {
+    /**
+     * The source file's path.
+     */
+    pub file: String,
+
+    /**
+     * The line number, starting at 1.
+     */
+    pub line: u32,
+
+    /**
+     * The column number, starting at 1.
+     */
+    pub column: u32,
+}
+
+impl<'a> From<&std::panic::Location<'a>> for SourceLocation {
+    fn from(x: &std::panic::Location<'a>) -> Self {
+        Self {
+            file: x.file().to_owned(),
+            line: x.line(),
+            column: x.column(),
+        }
+    }
+}
+
+impl Display for SourceLocation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}:{}", self.file, self.line, self.column)
+    }
+}
+
+#[derive(Clone, Debug)]
+/**
+ * A constraint the prover's inputs don't satisfy.
+ */
+pub struct ConstraintViolation {
+    /**
+     * The [`Operation::Constraint`] node.
+     */
+    pub constraint: NodeIndex,
+
+    /**
+     * The node whose value should equal [`expected`](Self::expected).
+     */
+    pub node: NodeIndex,
+
+    /**
+     * The operation that computed [`node`](Self::node).
+     */
+    pub operation: Operation,
+
+    /**
+     * The operands of [`operation`](Self::operation) and their values.
+     */
+    pub operands: Vec<(NodeIndex, BigInt)>,
+
+    /**
+     * The value the constraint requires.
+     */
+    pub expected: BigInt,
+
+    /**
+     * The value [`node`](Self::node) actually has.
+     */
+    pub actual: BigInt,
+
+    /**
+     * Where in the `#[zkp_program]` we created the constraint, if known.
+     */
+    pub location: Option<SourceLocation>,
+}
+
+#[derive(Clone, Debug)]
+/**
+ * A gadget that failed to compute its hidden inputs, usually because its
+ * inputs don't meet the gadget's requirements (e.g. a value out of range).
+ */
+pub struct GadgetFailure {
+    /**
+     * The [`Operation::InvokeGadget`] node.
+     */
+    pub node: NodeIndex,
+
+    /**
+     * The gadget's [`debug_name`](crate::Gadget::debug_name).
+     */
+    pub gadget: &'static str,
+
+    /**
+     * The gadget's inputs and their values.
+     */
+    pub inputs: Vec<(NodeIndex, BigInt)>,
+
+    /**
+     * The error the gadget returned.
+     */
+    pub error: Error,
+
+    /**
+     * Where in the `#[zkp_program]` we invoked the gadget, if known.
+     */
+    pub location: Option<SourceLocation>,
+}
+
+#[derive(Clone, Debug)]
+/**
+ * The reason the prover's inputs don't produce a valid witness.
+ */
+pub enum WitnessFailure {
+    /**
+     * A constraint isn't satisfied.
+     */
+    Constraint(ConstraintViolation),
+
+    /**
+     * A gadget couldn't compute its hidden inputs.
+     */
+    Gadget(GadgetFailure),
+}
+
+#[derive(Clone, Debug)]
+/**
+ * The result of running a ZKP program natively with [`debug_prover`].
+ */
+pub struct WitnessReport {
+    /**
+     * The value of each node we evaluated before stopping. This is the
+     * full witness when [`failure`](Self::failure) is `None`.
+     */
+    pub assignment: BTreeMap<NodeIndex, BigInt>,
+
+    /**
+     * The first failure we encountered, if any.
+     */
+    pub failure: Option<WitnessFailure>,
+}
+
+impl WitnessReport {
+    /**
+     * Whether the inputs satisfy every constraint, in which case proving
+     * should succeed.
+     */
+    pub fn is_satisfied(&self) -> bool {
+        self.failure.is_none()
+    }
+}
+
+/**
+ * Formats small values in decimal and larger ones in hex.
+ */
+fn fmt_value(x: &BigInt) -> String {
+    if x.bits_vartime() <= 64 {
+        let bytes = x.to_le_bytes();
+        let mut low = [0u8; 8];
+        low.copy_from_slice(&bytes[..8]);
+
+        format!("{}", u64::from_le_bytes(low))
+    } else {
+        let hex = format!("{}", x.0);
+
+        format!("0x{}", hex.trim_start_matches('0'))
+    }
+}
+
+fn fmt_location(location: &Option<SourceLocation>) -> String {
+    match location {
+        Some(l) => format!(" at {l}"),
+        None => String::new(),
+    }
+}
+
+fn fmt_values(values: &[(NodeIndex, BigInt)]) -> String {
+    values
+        .iter()
+        .map(|(id, v)| format!("{:?} = {}", id, fmt_value(v)))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl Display for WitnessFailure {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Constraint(c) => {
+                writeln!(
+                    f,
+                    "Constraint {:?}{} is unsatisfied: expected {:?} to equal {}, found {}",
+                    c.constraint,
+                    fmt_location(&c.location),
+                    c.node,
+                    fmt_value(&c.expected),
+                    fmt_value(&c.actual)
+                )?;
+                write!(
+                    f,
+                    "  {:?} = {:?}({})",
+                    c.node,
+                    c.operation,
+                    fmt_values(&c.operands)
+                )
+            }
+            Self::Gadget(g) => {
+                writeln!(
+                    f,
+                    "Gadget {} at {:?}{} failed: {}",
+                    g.gadget,
+                    g.node,
+                    fmt_location(&g.location),
+                    g.error
+                )?;
+                write!(f, "  inputs: {}", fmt_values(&g.inputs))
+            }
+        }
+    }
+}
+
+impl Display for WitnessReport {
+    /**
+     * Writes the failure, if any, followed by the witness assignment.
+     */
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.failure {
+            Some(failure) => writeln!(f, "{failure}")?,
+            None => writeln!(f, "All constraints satisfied.")?,
+        };
+
+        writeln!(f, "Witness:")?;
+
+        for (id, v) in &self.assignment {
+            writeln!(f, "  {:?} = {}", id, fmt_value(v))?;
+        }
+
+        Ok(())
+    }
+}
+
+/**
+ * Runs `prog` natively as the prover would, but rather than failing on
+ * the first unsatisfied constraint or gadget error, reports what failed
+ * along with the values involved and the witness computed so far.
+ *
+ * # Remarks
+ * `source_locations` maps nodes in `prog` to where the frontend created
+ * them. Nodes without a location are reported without one.
+ *
+ * Returns an error if the arguments don't match the program.
+ */
+pub fn debug_prover<U>(
+    prog: &CompiledZkpProgram,
+    private_inputs: &[U::BackendField],
+    public_inputs: &[U::BackendField],
+    constant_inputs: &[U::BackendField],
+    source_locations: &HashMap<NodeIndex, SourceLocation>,
+) -> Result<WitnessReport>
+where
+    U: FieldSpec,
+{
+    let prog = prepare_prover::<U>(prog, private_inputs, public_inputs, constant_inputs)?;
+
+    let mut node_outputs = HashMap::new();
+
+    let result = run_prover::<U>(
+        &prog,
+        private_inputs,
+        public_inputs,
+        constant_inputs,
+        &mut node_outputs,
+    );
+
+    let assignment = node_outputs
+        .into_iter()
+        .map(|(id, v)| (id, v.zkp_into()))
+        .collect::<BTreeMap<NodeIndex, BigInt>>();
+
+    let values = |ids: &[NodeIndex]| {
+        ids.iter()
+            .filter_map(|id| assignment.get(id).map(|v| (*id, *v)))
+            .collect::<Vec<_>>()
+    };
+
+    let query = GraphQuery::new(&prog.0);
+
+    let failure = match result {
+        Ok(()) => None,
+        Err((id, Error::UnsatisfiableConstraint(_))) => {
+            let expected = match prog[id].operation {
+                Operation::Constraint(x) => x,
+                _ => unreachable!("Only constraints can be unsatisfied"),
+            };
+
+            // The first operand that doesn't match
+            let (node, actual) = values(&query.get_unordered_operands(id)?)
+                .into_iter()
+                .find(|(_, v)| *v != expected)
+                .expect("Unsatisfied constraint has no mismatched operand");
+
+            let operands = match prog[node].operation {
+                Operation::Add | Operation::Sub | Operation::Mul => {
+                    let (left, right) = query.get_binary_operands(node)?;
+
+                    values(&[left, right])
+                }
+                Operation::Neg => values(&[query.get_unary_operand(node)?]),
+                _ => vec![],
+            };
+
+            Some(WitnessFailure::Constraint(ConstraintViolation {
+                constraint: id,
+                node,
+                operation: prog[node].operation.clone(),
+                operands,
+                expected,
+                actual,
+                location: source_locations
+                    .get(&id)
+                    .or_else(|| source_locations.get(&node))
+                    .cloned(),
+            }))
+        }
+        Err((id, e)) => match &prog[id].operation {
+            Operation::InvokeGadget(g) => Some(WitnessFailure::Gadget(GadgetFailure {
+                node: id,
+                gadget: g.debug_name(),
+                inputs: values(&query.get_ordered_operands(id)?),
+                error: e,
+                location: source_locations.get(&id).cloned(),
+            })),
+            _ => return Err(e),
+        },
+    };
+
+    Ok(WitnessReport {
+        assignment,
+        failure,
+    })
+}
@@ -15,6 +15,13 @@ pub enum Error {
      */
     BulletproofsR1CSError(Box<bulletproofs::r1cs::R1CSError>),
 
+    #[cfg(feature = "groth16")]
+    #[error("Groth16 error: {0}")]
+    /**
+     * Encountered an error when creating or verifying a Groth16 proof.
+     */
+    Groth16Error(Box<String>),
+
     #[error("Value {0} is out of range for the chosen backend")]
     /**
      * Encountered a value out of range for the field type in the chosen backend.
@@ -86,6 +93,14 @@ impl Error {
     pub fn inputs_mismatch(msg: &str) -> Self {
         Self::InputsMismatch(Box::new(msg.to_owned()))
     }
+
+    #[cfg(feature = "groth16")]
+    /**
+     * Create an [`Error::Groth16Error`].
+     */
+    pub fn groth16_error(msg: &str) -> Self {
+        Self::Groth16Error(Box::new(msg.to_owned()))
+    }
 }
 
 impl From<bulletproofs::r1cs::R1CSError> for Error {
@@ -94,6 +109,13 @@ impl From<bulletproofs::r1cs::R1CSError> for Error {
     }
 }
 
+#[cfg(feature = "groth16")]
+impl From<ark_relations::r1cs::SynthesisError> for Error {
+    fn from(e: ark_relations::r1cs::SynthesisError) -> Self {
+        Self::Groth16Error(Box::new(e.to_string()))
+    }
+}
+
 const_assert!(std::mem::size_of::<Error>() <= 16);
 
 /**
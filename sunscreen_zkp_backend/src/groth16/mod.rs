@@ -0,0 +1,770 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+use ark_bn254::{Bn254, Fr};
+use ark_ff::{BigInt as ArkBigInt, PrimeField};
+use ark_groth16::{Groth16, ProvingKey, VerifyingKey};
+use ark_relations::r1cs::{
+    ConstraintSynthesizer, ConstraintSystem, ConstraintSystemRef, LinearCombination,
+    SynthesisError, Variable,
+};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_snark::SNARK;
+use crypto_bigint::Encoding;
+use log::trace;
+use merlin::Transcript;
+use rand::thread_rng;
+use serde::{de::Error as DeError, ser::Error as SerError, Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+use sunscreen_compiler_common::forward_traverse;
+
+use crate::{
+    exec::Operation, jit::jit_verifier, jit_prover, BigInt, CompiledZkpProgram, Error,
+    ExecutableZkpProgram, FieldSpec, Proof, Result, ZkpBackend,
+};
+
+/**
+ * Generates Solidity contracts that verify Groth16 proofs on the EVM.
+ *
+ * # Remarks
+ * The EVM exposes the BN254 curve through the `ecAdd` (`0x06`), `ecMul`
+ * (`0x07`), and `ecPairing` (`0x08`) precompiles, so a Groth16 proof
+ * verifies on chain at a fixed cost. Bulletproofs proofs live on
+ * Ristretto, which the EVM doesn't support, and verifying them costs
+ * time linear in the circuit size, so we only generate verifiers for the
+ * [`Groth16Backend`](crate::groth16::Groth16Backend).
+ */
+pub mod solidity;
+
+/**
+ * (De)serializes arkworks types as their compressed canonical encoding.
+ */
+mod ark_bytes {
+    use super::*;
+
+    pub fn serialize<T, S>(x: &T, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        T: CanonicalSerialize,
+        S: serde::Serializer,
+    {
+        let mut bytes = vec![];
+
+        x.serialize_compressed(&mut bytes)
+            .map_err(S::Error::custom)?;
+
+        bytes.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> std::result::Result<T, D::Error>
+    where
+        T: CanonicalDeserialize,
+        D: serde::Deserializer<'de>,
+    {
+        let bytes = Vec::<u8>::deserialize(deserializer)?;
+
+        T::deserialize_compressed(&bytes[..]).map_err(D::Error::custom)
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+/**
+ * A verifiable proof in the Groth16 proof system over BN254.
+ */
+pub struct Groth16Proof(#[serde(with = "ark_bytes")] pub ark_groth16::Proof<Bn254>);
+
+#[derive(Clone, Debug)]
+enum Instruction {
+    Input(usize),
+    HiddenInput(Option<Fr>),
+    Add(usize, usize),
+    Sub(usize, usize),
+    Mul(usize, usize),
+    Neg(usize),
+    Constraint(Fr, Vec<usize>),
+    Constant(Fr),
+}
+
+#[derive(Clone)]
+enum Node {
+    LinearCombination(LinearCombination<Fr>, Option<Fr>),
+    Constant(Fr),
+}
+
+impl Node {
+    fn into_lc(self) -> (LinearCombination<Fr>, Option<Fr>) {
+        match self {
+            Self::LinearCombination(x, v) => (x, v),
+            Self::Constant(c) => (LinearCombination::zero() + (c, Variable::One), Some(c)),
+        }
+    }
+}
+
+/**
+ * An [`ExecutableZkpProgram`] lowered to a list of R1CS instructions in
+ * topological order.
+ *
+ * # Remarks
+ * Each `Constraint` node's value becomes a public input to the circuit
+ * rather than a circuit constant. Public inputs and program constraints
+ * thus don't affect the circuit's shape, so one setup serves every
+ * assignment of a program's public inputs.
+ */
+#[derive(Clone)]
+struct Groth16Circuit {
+    instructions: Vec<Instruction>,
+    inputs: Option<Vec<Fr>>,
+}
+
+impl Groth16Circuit {
+    fn new(graph: &ExecutableZkpProgram, inputs: Option<&[BigInt]>) -> Result<Self> {
+        let mut instructions = vec![];
+        let mut positions = HashMap::new();
+
+        let inputs = inputs
+            .map(|x| x.iter().map(Fr::try_from).collect::<Result<Vec<Fr>>>())
+            .transpose()?;
+
+        forward_traverse(&graph.0, |query, idx| {
+            let node = query.get_node(idx).unwrap();
+
+            let instruction = match node.operation {
+                Operation::Input(x) => Instruction::Input(x),
+                Operation::HiddenInput(x) => {
+                    Instruction::HiddenInput(x.map(Fr::try_from).transpose()?)
+                }
+                Operation::Add => {
+                    let (left, right) = query.get_binary_operands(idx)?;
+
+                    Instruction::Add(positions[&left], positions[&right])
+                }
+                Operation::Sub => {
+                    let (left, right) = query.get_binary_operands(idx)?;
+
+                    Instruction::Sub(positions[&left], positions[&right])
+                }
+                Operation::Mul => {
+                    let (left, right) = query.get_binary_operands(idx)?;
+
+                    Instruction::Mul(positions[&left], positions[&right])
+                }
+                Operation::Neg => {
+                    let left = query.get_unary_operand(idx)?;
+
+                    Instruction::Neg(positions[&left])
+                }
+                Operation::Constraint(x) => {
+                    let operands = query
+                        .get_unordered_operands(idx)?
+                        .iter()
+                        .map(|o| positions[o])
+                        .collect();
+
+                    Instruction::Constraint(Fr::try_from(x)?, operands)
+                }
+                Operation::Constant(x) => Instruction::Constant(Fr::try_from(x)?),
+            };
+
+            positions.insert(idx, instructions.len());
+            instructions.push(instruction);
+
+            Ok::<(), Error>(())
+        })?;
+
+        Ok(Self {
+            instructions,
+            inputs,
+        })
+    }
+
+    /**
+     * The circuit's public inputs, which are the values of its
+     * constraints.
+     */
+    fn public_inputs(&self) -> Vec<Fr> {
+        self.instructions
+            .iter()
+            .filter_map(|i| match i {
+                Instruction::Constraint(x, _) => Some(*x),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /**
+     * A digest of the circuit's shape. Circuits with the same digest can
+     * share a setup.
+     */
+    fn digest(&self) -> [u8; 32] {
+        let mut hasher = Sha3_256::new();
+
+        let mut write = |tag: u8, words: &[u64]| {
+            hasher.update([tag]);
+
+            for w in words {
+                hasher.update(w.to_le_bytes());
+            }
+        };
+
+        for i in &self.instructions {
+            match i {
+                Instruction::Input(x) => write(0, &[*x as u64]),
+                Instruction::HiddenInput(_) => write(1, &[]),
+                Instruction::Add(a, b) => write(2, &[*a as u64, *b as u64]),
+                Instruction::Sub(a, b) => write(3, &[*a as u64, *b as u64]),
+                Instruction::Mul(a, b) => write(4, &[*a as u64, *b as u64]),
+                Instruction::Neg(a) => write(5, &[*a as u64]),
+                Instruction::Constraint(_, operands) => {
+                    let mut words = vec![operands.len() as u64];
+                    words.extend(operands.iter().map(|o| *o as u64));
+
+                    write(6, &words)
+                }
+                Instruction::Constant(x) => write(7, &x.into_bigint().0),
+            }
+        }
+
+        hasher.finalize().into()
+    }
+}
+
+impl ConstraintSynthesizer<Fr> for Groth16Circuit {
+    fn generate_constraints(
+        self,
+        cs: ConstraintSystemRef<Fr>,
+    ) -> std::result::Result<(), SynthesisError> {
+        let one = || LinearCombination::zero() + Variable::One;
+        let mut nodes: Vec<Node> = Vec::with_capacity(self.instructions.len());
+
+        for i in &self.instructions {
+            let node = match i {
+                Instruction::Input(x) => {
+                    let value = self.inputs.as_ref().map(|inputs| inputs[*x]);
+                    let v =
+                        cs.new_witness_variable(|| value.ok_or(SynthesisError::AssignmentMissing))?;
+
+                    Node::LinearCombination(LinearCombination::zero() + v, value)
+                }
+                Instruction::HiddenInput(value) => {
+                    let value = *value;
+                    let v =
+                        cs.new_witness_variable(|| value.ok_or(SynthesisError::AssignmentMissing))?;
+
+                    Node::LinearCombination(LinearCombination::zero() + v, value)
+                }
+                Instruction::Add(a, b) => match (nodes[*a].clone(), nodes[*b].clone()) {
+                    (Node::Constant(a), Node::Constant(b)) => Node::Constant(a + b),
+                    (a, b) => {
+                        let (a, a_val) = a.into_lc();
+                        let (b, b_val) = b.into_lc();
+
+                        Node::LinearCombination(a + b, a_val.zip(b_val).map(|(a, b)| a + b))
+                    }
+                },
+                Instruction::Sub(a, b) => match (nodes[*a].clone(), nodes[*b].clone()) {
+                    (Node::Constant(a), Node::Constant(b)) => Node::Constant(a - b),
+                    (a, b) => {
+                        let (a, a_val) = a.into_lc();
+                        let (b, b_val) = b.into_lc();
+
+                        Node::LinearCombination(a - b, a_val.zip(b_val).map(|(a, b)| a - b))
+                    }
+                },
+                Instruction::Mul(a, b) => match (nodes[*a].clone(), nodes[*b].clone()) {
+                    (Node::Constant(a), Node::Constant(b)) => Node::Constant(a * b),
+                    (Node::LinearCombination(x, v), Node::Constant(c))
+                    | (Node::Constant(c), Node::LinearCombination(x, v)) => {
+                        Node::LinearCombination(x * c, v.map(|v| v * c))
+                    }
+                    (Node::LinearCombination(a, a_val), Node::LinearCombination(b, b_val)) => {
+                        let value = a_val.zip(b_val).map(|(a, b)| a * b);
+                        let o = cs.new_witness_variable(|| {
+                            value.ok_or(SynthesisError::AssignmentMissing)
+                        })?;
+
+                        cs.enforce_constraint(a, b, LinearCombination::zero() + o)?;
+
+                        Node::LinearCombination(LinearCombination::zero() + o, value)
+                    }
+                },
+                Instruction::Neg(a) => match nodes[*a].clone() {
+                    Node::Constant(a) => Node::Constant(-a),
+                    Node::LinearCombination(x, v) => Node::LinearCombination(-x, v.map(|v| -v)),
+                },
+                Instruction::Constraint(x, operands) => {
+                    let x = *x;
+                    let expected = cs.new_input_variable(|| Ok(x))?;
+
+                    for o in operands {
+                        let (o, _) = nodes[*o].clone().into_lc();
+
+                        cs.enforce_constraint(o - expected, one(), LinearCombination::zero())?;
+                    }
+
+                    // Constraints produce no value.
+                    Node::Constant(Fr::from(0u8))
+                }
+                Instruction::Constant(x) => Node::Constant(*x),
+            };
+
+            nodes.push(node);
+        }
+
+        Ok(())
+    }
+}
+
+/// Parameters for proving a Groth16 circuit.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Groth16ProverParameters {
+    #[serde(with = "ark_bytes")]
+    proving_key: ProvingKey<Bn254>,
+}
+
+/// Parameters for verifying a Groth16 circuit.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Groth16VerifierParameters {
+    #[serde(with = "ark_bytes")]
+    verifying_key: VerifyingKey<Bn254>,
+}
+
+impl Groth16ProverParameters {
+    /// Return the proving key.
+    pub fn proving_key(&self) -> &ProvingKey<Bn254> {
+        &self.proving_key
+    }
+
+    /// Return the parameters for verifying proofs made with these parameters.
+    pub fn verifier_parameters(&self) -> Groth16VerifierParameters {
+        Groth16VerifierParameters {
+            verifying_key: self.proving_key.vk.clone(),
+        }
+    }
+}
+
+impl Groth16VerifierParameters {
+    /// Return the verifying key.
+    pub fn verifying_key(&self) -> &VerifyingKey<Bn254> {
+        &self.verifying_key
+    }
+}
+
+#[derive(Clone, Default)]
+/**
+ * A Groth16 backend over the BN254 curve. Groth16 proofs have constant
+ * size and verify in constant time, regardless of circuit size.
+ *
+ * # Remarks
+ * Groth16 requires a setup for each circuit, which produces the prover
+ * and verifier parameters. Anyone who knows the randomness used during
+ * setup can forge proofs.
+ *
+ * [`prove`](ZkpBackend::prove) performs the setup the first time it sees
+ * a circuit and caches the parameters in this backend (and its clones),
+ * where [`verify`](ZkpBackend::verify) finds them. This suits a prover
+ * and verifier sharing a runtime. Otherwise, call
+ * [`setup`](Self::setup) once (e.g. in a trusted ceremony), distribute
+ * the verifier parameters, and use
+ * [`prove_with_parameters`](ZkpBackend::prove_with_parameters) and
+ * [`verify_with_parameters`](ZkpBackend::verify_with_parameters).
+ */
+pub struct Groth16Backend {
+    parameters: Arc<Mutex<HashMap<[u8; 32], Groth16ProverParameters>>>,
+}
+
+impl Groth16Backend {
+    /**
+     * Create a [`Groth16Backend`].
+     */
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /**
+     * Runs the setup for the given executable program's circuit and
+     * returns the parameters for proving it.
+     *
+     * # Remarks
+     * The circuit depends on the program, its constant inputs, and any
+     * gadgets it invokes, but not on its private or public inputs.
+     */
+    pub fn setup(&self, graph: &ExecutableZkpProgram) -> Result<Groth16ProverParameters> {
+        let circuit = Groth16Circuit::new(graph, None)?;
+
+        let now = Instant::now();
+
+        let (proving_key, _) =
+            Groth16::<Bn254>::circuit_specific_setup(circuit, &mut thread_rng())?;
+
+        trace!("Groth16 setup time {}s", now.elapsed().as_secs_f64());
+
+        Ok(Groth16ProverParameters { proving_key })
+    }
+
+    /**
+     * Returns the parameters for proving the given executable program's
+     * circuit, running the setup if this backend (or one of its clones)
+     * hasn't yet seen the circuit.
+     *
+     * # Remarks
+     * These are the parameters [`prove`](ZkpBackend::prove) and
+     * [`verify`](ZkpBackend::verify) use.
+     */
+    pub fn parameters(&self, graph: &ExecutableZkpProgram) -> Result<Groth16ProverParameters> {
+        let digest = Groth16Circuit::new(graph, None)?.digest();
+
+        let mut parameters = self.parameters.lock().unwrap();
+
+        if let Some(p) = parameters.get(&digest) {
+            return Ok(p.clone());
+        }
+
+        let p = self.setup(graph)?;
+        parameters.insert(digest, p.clone());
+
+        Ok(p)
+    }
+}
+
+impl ZkpBackend for Groth16Backend {
+    type Field = Groth16FieldSpec;
+
+    type ProverParameters = Groth16ProverParameters;
+    type VerifierParameters = Groth16VerifierParameters;
+
+    fn prove(&self, graph: &ExecutableZkpProgram, inputs: &[BigInt]) -> Result<Proof> {
+        let parameters = self.parameters(graph)?;
+
+        self.prove_with_parameters(graph, inputs, &parameters, &mut Transcript::new(b"Groth16"))
+    }
+
+    fn prove_with_parameters(
+        &self,
+        graph: &ExecutableZkpProgram,
+        inputs: &[BigInt],
+        parameters: &Self::ProverParameters,
+        _transcript: &mut Transcript,
+    ) -> Result<Proof> {
+        let expected_input_count = graph
+            .node_weights()
+            .filter(|x| matches!(x.operation, Operation::Input(_)))
+            .count();
+
+        if expected_input_count != inputs.len() {
+            return Err(Error::inputs_mismatch(&format!(
+                "Internal error: Groth16 runtime arguments mismatch. Expected {}, got {}.",
+                expected_input_count,
+                inputs.len()
+            )));
+        }
+
+        let circuit = Groth16Circuit::new(graph, Some(inputs))?;
+
+        // An unsatisfied circuit yields a proof that won't verify. Fail
+        // early instead.
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        circuit.clone().generate_constraints(cs.clone())?;
+
+        if !cs.is_satisfied()? {
+            return Err(Error::groth16_error(
+                "The circuit's constraints aren't satisfied",
+            ));
+        }
+
+        let now = Instant::now();
+
+        let proof = Groth16::<Bn254>::prove(&parameters.proving_key, circuit, &mut thread_rng())?;
+
+        trace!("Groth16 prover time {}s", now.elapsed().as_secs_f64());
+
+        Ok(Proof::Groth16(Box::new(Groth16Proof(proof))))
+    }
+
+    fn verify(&self, graph: &ExecutableZkpProgram, proof: &Proof) -> Result<()> {
+        let digest = Groth16Circuit::new(graph, None)?.digest();
+
+        let parameters = self
+            .parameters
+            .lock()
+            .unwrap()
+            .get(&digest)
+            .map(|p| p.verifier_parameters())
+            .ok_or_else(|| {
+                Error::groth16_error("No setup found for this circuit. Use verify_with_parameters.")
+            })?;
+
+        self.verify_with_parameters(graph, proof, &parameters, &mut Transcript::new(b"Groth16"))
+    }
+
+    fn verify_with_parameters(
+        &self,
+        graph: &ExecutableZkpProgram,
+        proof: &Proof,
+        parameters: &Self::VerifierParameters,
+        _transcript: &mut Transcript,
+    ) -> Result<()> {
+        let proof = match proof {
+            Proof::Groth16(x) => x,
+            _ => {
+                return Err(Error::IncorrectProofType);
+            }
+        };
+
+        let public_inputs = Groth16Circuit::new(graph, None)?.public_inputs();
+
+        let now = Instant::now();
+
+        let valid = Groth16::<Bn254>::verify(&parameters.verifying_key, &public_inputs, &proof.0)?;
+
+        trace!("Groth16 verify time {}s", now.elapsed().as_secs_f64());
+
+        if valid {
+            Ok(())
+        } else {
+            Err(Error::groth16_error("Proof failed to verify"))
+        }
+    }
+
+    fn jit_prover(
+        &self,
+        prog: &CompiledZkpProgram,
+        private_inputs: &[BigInt],
+        public_inputs: &[BigInt],
+        constant_inputs: &[BigInt],
+    ) -> Result<ExecutableZkpProgram> {
+        let private_inputs = private_inputs
+            .iter()
+            .map(Fr::try_from)
+            .collect::<Result<Vec<Fr>>>()?;
+        let public_inputs = public_inputs
+            .iter()
+            .map(Fr::try_from)
+            .collect::<Result<Vec<Fr>>>()?;
+        let constant_inputs = constant_inputs
+            .iter()
+            .map(Fr::try_from)
+            .collect::<Result<Vec<Fr>>>()?;
+
+        jit_prover::<Groth16FieldSpec>(prog, &private_inputs, &public_inputs, &constant_inputs)
+    }
+
+    fn jit_verifier(
+        &self,
+        prog: &CompiledZkpProgram,
+        constant_inputs: &[BigInt],
+        public_inputs: &[BigInt],
+    ) -> Result<ExecutableZkpProgram> {
+        let constant_inputs = constant_inputs
+            .iter()
+            .map(Fr::try_from)
+            .collect::<Result<Vec<Fr>>>()?;
+
+        let public_inputs = public_inputs
+            .iter()
+            .map(Fr::try_from)
+            .collect::<Result<Vec<Fr>>>()?;
+
+        jit_verifier::<Groth16FieldSpec>(prog, &constant_inputs, &public_inputs)
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+/// The specification for the BN254 scalar field used in the Groth16 proof system.
+pub struct Groth16FieldSpec {}
+
+impl FieldSpec for Groth16FieldSpec {
+    type BackendField = Fr;
+
+    // 21888242871839275222246405745257275088548364400416034343698204186575808495617
+    const FIELD_MODULUS: BigInt = BigInt::from_words([
+        0x43e1f593f0000001,
+        0x2833e84879b97091,
+        0xb85045b68181585d,
+        0x30644e72e131a029,
+        0x0,
+        0x0,
+        0x0,
+        0x0,
+    ]);
+}
+
+fn try_bigint_to_fr(x: &BigInt) -> Result<Fr> {
+    let bytes = x.0.to_le_bytes();
+
+    // We attempt to convert the lower 256 bits to a field element and
+    // assert the upper bytes are zero.
+    let (lower, upper) = bytes.split_at(32);
+
+    if upper.iter().any(|b| *b != 0) {
+        return Err(Error::out_of_range(&x.to_string()));
+    }
+
+    let mut limbs = [0u64; 4];
+
+    for (l, chunk) in limbs.iter_mut().zip(lower.chunks(8)) {
+        *l = u64::from_le_bytes(chunk.try_into().unwrap());
+    }
+
+    Fr::from_bigint(ArkBigInt(limbs)).ok_or_else(|| Error::out_of_range(&x.to_string()))
+}
+
+impl TryFrom<BigInt> for Fr {
+    type Error = Error;
+
+    fn try_from(value: BigInt) -> Result<Self> {
+        try_bigint_to_fr(&value)
+    }
+}
+
+impl TryFrom<&BigInt> for Fr {
+    type Error = Error;
+
+    fn try_from(value: &BigInt) -> Result<Self> {
+        try_bigint_to_fr(value)
+    }
+}
+
+impl crate::ZkpFrom<Fr> for BigInt {
+    fn zkp_from(val: Fr) -> BigInt {
+        let limbs = val.into_bigint().0;
+
+        BigInt::from_words([limbs[0], limbs[1], limbs[2], limbs[3], 0, 0, 0, 0])
+    }
+}
+
+impl crate::ZkpFrom<&Fr> for BigInt {
+    fn zkp_from(val: &Fr) -> BigInt {
+        BigInt::zkp_from(*val)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crypto_bigint::U512;
+    use petgraph::stable_graph::NodeIndex;
+    use sunscreen_compiler_common::{EdgeInfo, NodeInfo};
+
+    use super::*;
+    use crate::exec::Operation as BackendOperation;
+    use crate::ZkpInto;
+
+    #[test]
+    fn can_roundtrip_fr() {
+        let a = BigInt::from_words([0x1234567890abcdef, 0x42, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0]);
+
+        let fr = Fr::try_from(a).unwrap();
+        let b: BigInt = fr.zkp_into();
+
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn field_modulus_is_out_of_range() {
+        let p = Groth16FieldSpec::FIELD_MODULUS;
+
+        assert!(Fr::try_from(p).is_err());
+
+        let p_min_1 = BigInt(p.0.wrapping_sub(&U512::ONE));
+
+        let fr = Fr::try_from(p_min_1).unwrap();
+        let roundtrip: BigInt = fr.zkp_into();
+
+        assert_eq!(p_min_1, roundtrip);
+        assert_eq!(fr, -Fr::from(1u8));
+    }
+
+    #[test]
+    fn can_run_simple_proof() {
+        let mut graph = ExecutableZkpProgram::new();
+
+        let mut add_node = |op: BackendOperation, edges: &[(NodeIndex, EdgeInfo)]| {
+            let n = graph.add_node(NodeInfo { operation: op });
+
+            for (source, edge) in edges {
+                graph.add_edge(*source, n, *edge);
+            }
+
+            n
+        };
+
+        let in_0 = add_node(BackendOperation::Input(0), &[]);
+        let in_1 = add_node(BackendOperation::Input(1), &[]);
+        let in_2 = add_node(BackendOperation::Input(2), &[]);
+
+        let mul_1 = add_node(
+            BackendOperation::Mul,
+            &[(in_0, EdgeInfo::Left), (in_1, EdgeInfo::Right)],
+        );
+        let add_1 = add_node(
+            BackendOperation::Add,
+            &[(in_2, EdgeInfo::Left), (mul_1, EdgeInfo::Right)],
+        );
+
+        let _ = add_node(
+            BackendOperation::Constraint(BigInt(U512::from_u32(42))),
+            &[(add_1, EdgeInfo::Unordered)],
+        );
+
+        let backend = Groth16Backend::new();
+
+        // 10 * 4 + 2 == 42
+        let proof = backend
+            .prove(
+                &graph,
+                &[
+                    BigInt::from_u32(10),
+                    BigInt::from_u32(4),
+                    BigInt::from_u32(2),
+                ],
+            )
+            .unwrap();
+
+        backend.verify(&graph, &proof).unwrap();
+
+        // 8 * 5 + 2 == 42, reusing the setup.
+        let proof = backend
+            .prove(
+                &graph,
+                &[
+                    BigInt::from_u32(8),
+                    BigInt::from_u32(5),
+                    BigInt::from_u32(2),
+                ],
+            )
+            .unwrap();
+
+        backend.verify(&graph, &proof).unwrap();
+
+        assert_eq!(backend.parameters.lock().unwrap().len(), 1);
+
+        // 8 * 5 + 3 == 42.
+        // Proving should fail.
+        let proof = backend.prove(
+            &graph,
+            &[
+                BigInt::from_u32(8),
+                BigInt::from_u32(5),
+                BigInt::from_u32(3),
+            ],
+        );
+
+        assert!(proof.is_err());
+
+        // A backend that didn't perform the setup can't verify without
+        // the verifier parameters.
+        let proof = backend
+            .prove(
+                &graph,
+                &[
+                    BigInt::from_u32(10),
+                    BigInt::from_u32(4),
+                    BigInt::from_u32(2),
+                ],
+            )
+            .unwrap();
+
+        assert!(Groth16Backend::new().verify(&graph, &proof).is_err());
+    }
+}
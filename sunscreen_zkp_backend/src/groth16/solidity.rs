@@ -0,0 +1,450 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use ark_bn254::{Fq, Fr, G1Affine, G2Affine};
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::{BigInteger, PrimeField};
+use sha3::{Digest, Keccak256};
+
+use super::{Groth16Circuit, Groth16FieldSpec, Groth16VerifierParameters};
+use crate::{jit_verifier, BigInt, CompiledZkpProgram, Error, Operation, Proof, Result};
+
+/**
+ * A G1 point encoded as the EVM words `x` and `y`.
+ */
+pub type G1Words = [u8; 64];
+
+/**
+ * A G2 point encoded as EVM words. Its coordinates `x` and `y` are
+ * elements `a * i + b` of Fq^2, each encoded as the words `a` and `b`, as
+ * `ecPairing` expects.
+ */
+pub type G2Words = [u8; 128];
+
+fn fq_word(x: &Fq) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word.copy_from_slice(&x.into_bigint().to_bytes_be());
+
+    word
+}
+
+fn fr_word(x: &Fr) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word.copy_from_slice(&x.into_bigint().to_bytes_be());
+
+    word
+}
+
+fn g1_words(p: &G1Affine) -> G1Words {
+    let mut words = [0u8; 64];
+
+    // The EVM encodes the point at infinity as (0, 0).
+    if let Some((x, y)) = p.xy() {
+        words[..32].copy_from_slice(&fq_word(x));
+        words[32..].copy_from_slice(&fq_word(y));
+    }
+
+    words
+}
+
+fn g2_words(p: &G2Affine) -> G2Words {
+    let mut words = [0u8; 128];
+
+    if let Some((x, y)) = p.xy() {
+        words[..32].copy_from_slice(&fq_word(&x.c1));
+        words[32..64].copy_from_slice(&fq_word(&x.c0));
+        words[64..96].copy_from_slice(&fq_word(&y.c1));
+        words[96..].copy_from_slice(&fq_word(&y.c0));
+    }
+
+    words
+}
+
+fn hex(word: &[u8]) -> String {
+    word.iter().fold("0x".to_owned(), |mut s, b| {
+        write!(s, "{:02x}", b).unwrap();
+        s
+    })
+}
+
+/**
+ * A Groth16 verifier for one [`CompiledZkpProgram`], specialized for the
+ * EVM.
+ *
+ * # Remarks
+ * The verifier takes the program's public inputs as arguments. Program
+ * constraints (e.g. `x.constrain_eq(Field::from(42u32))`) and constant
+ * inputs are fixed when generating the verifier, so we fold them into
+ * the verifying key ahead of time.
+ *
+ * [`contract_source`](Self::contract_source) emits a self-contained
+ * Solidity contract with a
+ * `verifyProof(uint256[2] a, uint256[2][2] b, uint256[2] c, uint256[n] publicInputs)`
+ * function that returns whether the proof is valid.
+ * [`encode_calldata`](Self::encode_calldata) ABI-encodes a call to it.
+ */
+#[derive(Clone)]
+pub struct SolidityVerifier {
+    alpha: G1Words,
+    beta: G2Words,
+    gamma: G2Words,
+    delta: G2Words,
+    ic: Vec<G1Words>,
+}
+
+impl SolidityVerifier {
+    /**
+     * Creates a verifier for `program` with the given constant inputs,
+     * whose proofs were made with parameters matching `parameters`.
+     *
+     * # Remarks
+     * Fails if `parameters` don't belong to the program's circuit.
+     */
+    pub fn new(
+        program: &CompiledZkpProgram,
+        constant_inputs: &[BigInt],
+        parameters: &Groth16VerifierParameters,
+    ) -> Result<Self> {
+        let public_input_count = program
+            .node_weights()
+            .filter(|x| matches!(x.operation, Operation::PublicInput(_)))
+            .count();
+
+        let constant_inputs = constant_inputs
+            .iter()
+            .map(Fr::try_from)
+            .collect::<Result<Vec<Fr>>>()?;
+
+        // Find which of the circuit's public inputs hold the program's
+        // public inputs. We JIT the program with all zeros and again with
+        // 1..=n. The circuit's public inputs that change hold the
+        // program's; the rest are fixed.
+        let instance = |public_inputs: &[Fr]| -> Result<Vec<Fr>> {
+            let graph = jit_verifier::<Groth16FieldSpec>(program, &constant_inputs, public_inputs)?;
+
+            Ok(Groth16Circuit::new(&graph, None)?.public_inputs())
+        };
+
+        let zeros = instance(&vec![Fr::from(0u8); public_input_count])?;
+        let markers = (1..=public_input_count as u64)
+            .map(Fr::from)
+            .collect::<Vec<Fr>>();
+        let marked = instance(&markers)?;
+
+        let vk = parameters.verifying_key();
+
+        if vk.gamma_abc_g1.len() != zeros.len() + 1 {
+            return Err(Error::groth16_error(
+                "The verifier parameters don't match the program",
+            ));
+        }
+
+        let markers = markers
+            .iter()
+            .enumerate()
+            .map(|(i, m)| (*m, i))
+            .collect::<HashMap<Fr, usize>>();
+
+        let mut base = vk.gamma_abc_g1[0].into_group();
+        let mut ic = vec![G1Affine::zero().into_group(); public_input_count];
+
+        for (k, (zero, marked)) in zeros.iter().zip(marked.iter()).enumerate() {
+            let point = vk.gamma_abc_g1[k + 1];
+
+            if zero == marked {
+                base += point * zero;
+            } else {
+                ic[markers[marked]] += point;
+            }
+        }
+
+        Ok(Self {
+            alpha: g1_words(&vk.alpha_g1),
+            beta: g2_words(&vk.beta_g2),
+            gamma: g2_words(&vk.gamma_g2),
+            delta: g2_words(&vk.delta_g2),
+            ic: std::iter::once(base)
+                .chain(ic)
+                .map(|p| g1_words(&p.into_affine()))
+                .collect(),
+        })
+    }
+
+    /**
+     * The number of public inputs `verifyProof` takes.
+     */
+    pub fn public_input_count(&self) -> usize {
+        self.ic.len() - 1
+    }
+
+    /**
+     * The verifying key's `alpha` point, EVM encoded.
+     */
+    pub fn alpha(&self) -> &G1Words {
+        &self.alpha
+    }
+
+    /**
+     * The verifying key's `beta` point, EVM encoded.
+     */
+    pub fn beta(&self) -> &G2Words {
+        &self.beta
+    }
+
+    /**
+     * The verifying key's `gamma` point, EVM encoded.
+     */
+    pub fn gamma(&self) -> &G2Words {
+        &self.gamma
+    }
+
+    /**
+     * The verifying key's `delta` point, EVM encoded.
+     */
+    pub fn delta(&self) -> &G2Words {
+        &self.delta
+    }
+
+    /**
+     * The points the verifier combines with the public inputs, EVM
+     * encoded. The first point has the fixed inputs folded in and the
+     * `i + 1`th point multiplies the `i`th public input.
+     */
+    pub fn ic(&self) -> &[G1Words] {
+        &self.ic
+    }
+
+    fn signature(&self) -> String {
+        if self.public_input_count() == 0 {
+            "verifyProof(uint256[2],uint256[2][2],uint256[2])".to_owned()
+        } else {
+            format!(
+                "verifyProof(uint256[2],uint256[2][2],uint256[2],uint256[{}])",
+                self.public_input_count()
+            )
+        }
+    }
+
+    /**
+     * The 4 byte selector of the contract's `verifyProof` function.
+     */
+    pub fn selector(&self) -> [u8; 4] {
+        let hash = Keccak256::digest(self.signature().as_bytes());
+
+        [hash[0], hash[1], hash[2], hash[3]]
+    }
+
+    /**
+     * ABI-encodes a call to `verifyProof` with the given proof and
+     * public inputs.
+     *
+     * # Remarks
+     * Fails if `proof` isn't a Groth16 proof, the number of public
+     * inputs is wrong, or a public input doesn't lie in the scalar field.
+     */
+    pub fn encode_calldata(&self, proof: &Proof, public_inputs: &[BigInt]) -> Result<Vec<u8>> {
+        let proof = match proof {
+            Proof::Groth16(x) => &x.0,
+            _ => {
+                return Err(Error::IncorrectProofType);
+            }
+        };
+
+        if public_inputs.len() != self.public_input_count() {
+            return Err(Error::inputs_mismatch(&format!(
+                "Expected {} public inputs, found {}",
+                self.public_input_count(),
+                public_inputs.len()
+            )));
+        }
+
+        let mut calldata = self.selector().to_vec();
+
+        calldata.extend(g1_words(&proof.a));
+        calldata.extend(g2_words(&proof.b));
+        calldata.extend(g1_words(&proof.c));
+
+        for x in public_inputs {
+            calldata.extend(fr_word(&Fr::try_from(x)?));
+        }
+
+        Ok(calldata)
+    }
+
+    /**
+     * Emits the source of a self-contained Solidity contract named
+     * `name` that verifies proofs for this verifier's program.
+     */
+    pub fn contract_source(&self, name: &str) -> String {
+        let mut s = String::new();
+
+        fn constant(s: &mut String, name: &str, word: &[u8]) {
+            writeln!(s, "    uint256 constant {} = {};", name, hex(word)).unwrap();
+        }
+
+        writeln!(s, "// SPDX-License-Identifier: AGPL-3.0-only").unwrap();
+        writeln!(s, "// Generated by Sunscreen. Do not edit.").unwrap();
+        writeln!(s, "pragma solidity ^0.8.0;").unwrap();
+        writeln!(s).unwrap();
+        writeln!(s, "contract {} {{", name).unwrap();
+        writeln!(s, "    // The BN254 base field modulus.").unwrap();
+        constant(&mut s, "Q", &Fq::MODULUS.to_bytes_be());
+        writeln!(s, "    // The BN254 scalar field modulus.").unwrap();
+        constant(&mut s, "R", &Fr::MODULUS.to_bytes_be());
+        writeln!(s).unwrap();
+
+        constant(&mut s, "ALPHA_X", &self.alpha[..32]);
+        constant(&mut s, "ALPHA_Y", &self.alpha[32..]);
+
+        for (name, p) in [
+            ("BETA", &self.beta),
+            ("GAMMA", &self.gamma),
+            ("DELTA", &self.delta),
+        ] {
+            constant(&mut s, &format!("{}_X_IM", name), &p[..32]);
+            constant(&mut s, &format!("{}_X_RE", name), &p[32..64]);
+            constant(&mut s, &format!("{}_Y_IM", name), &p[64..96]);
+            constant(&mut s, &format!("{}_Y_RE", name), &p[96..]);
+        }
+
+        for (i, p) in self.ic.iter().enumerate() {
+            constant(&mut s, &format!("IC_{}_X", i), &p[..32]);
+            constant(&mut s, &format!("IC_{}_Y", i), &p[32..]);
+        }
+
+        writeln!(s).unwrap();
+        writeln!(s, "    function verifyProof(").unwrap();
+        writeln!(s, "        uint256[2] calldata a,").unwrap();
+        writeln!(s, "        uint256[2][2] calldata b,").unwrap();
+
+        if self.public_input_count() == 0 {
+            writeln!(s, "        uint256[2] calldata c").unwrap();
+        } else {
+            writeln!(s, "        uint256[2] calldata c,").unwrap();
+            writeln!(
+                s,
+                "        uint256[{}] calldata publicInputs",
+                self.public_input_count()
+            )
+            .unwrap();
+        }
+
+        writeln!(s, "    ) external view returns (bool) {{").unwrap();
+        writeln!(s, "        uint256[2] memory x;").unwrap();
+        writeln!(s, "        x[0] = IC_0_X;").unwrap();
+        writeln!(s, "        x[1] = IC_0_Y;").unwrap();
+
+        for i in 0..self.public_input_count() {
+            writeln!(s).unwrap();
+            writeln!(
+                s,
+                "        require(publicInputs[{}] < R, \"Public input out of range\");",
+                i
+            )
+            .unwrap();
+            writeln!(
+                s,
+                "        x = ecAdd(x, ecMul(IC_{}_X, IC_{}_Y, publicInputs[{}]));",
+                i + 1,
+                i + 1,
+                i
+            )
+            .unwrap();
+        }
+
+        writeln!(s).unwrap();
+        writeln!(
+            s,
+            "        // e(-a, b) * e(alpha, beta) * e(x, gamma) * e(c, delta) == 1"
+        )
+        .unwrap();
+        writeln!(s, "        uint256[24] memory input;").unwrap();
+
+        let words = [
+            "a[0]",
+            "(Q - (a[1] % Q)) % Q",
+            "b[0][0]",
+            "b[0][1]",
+            "b[1][0]",
+            "b[1][1]",
+            "ALPHA_X",
+            "ALPHA_Y",
+            "BETA_X_IM",
+            "BETA_X_RE",
+            "BETA_Y_IM",
+            "BETA_Y_RE",
+            "x[0]",
+            "x[1]",
+            "GAMMA_X_IM",
+            "GAMMA_X_RE",
+            "GAMMA_Y_IM",
+            "GAMMA_Y_RE",
+            "c[0]",
+            "c[1]",
+            "DELTA_X_IM",
+            "DELTA_X_RE",
+            "DELTA_Y_IM",
+            "DELTA_Y_RE",
+        ];
+
+        for (i, w) in words.iter().enumerate() {
+            writeln!(s, "        input[{}] = {};", i, w).unwrap();
+        }
+
+        writeln!(s).unwrap();
+        s.push_str(CONTRACT_TAIL);
+
+        s
+    }
+}
+
+const CONTRACT_TAIL: &str = r#"        uint256[1] memory out;
+        bool success;
+
+        assembly {
+            success := staticcall(gas(), 0x08, input, 0x300, out, 0x20)
+        }
+
+        return success && out[0] == 1;
+    }
+
+    function ecAdd(uint256[2] memory p, uint256[2] memory q)
+        internal
+        view
+        returns (uint256[2] memory r)
+    {
+        uint256[4] memory input;
+        input[0] = p[0];
+        input[1] = p[1];
+        input[2] = q[0];
+        input[3] = q[1];
+
+        bool success;
+
+        assembly {
+            success := staticcall(gas(), 0x06, input, 0x80, r, 0x40)
+        }
+
+        require(success, "ecAdd failed");
+    }
+
+    function ecMul(uint256 px, uint256 py, uint256 s)
+        internal
+        view
+        returns (uint256[2] memory r)
+    {
+        uint256[3] memory input;
+        input[0] = px;
+        input[1] = py;
+        input[2] = s;
+
+        bool success;
+
+        assembly {
+            success := staticcall(gas(), 0x07, input, 0x60, r, 0x40)
+        }
+
+        require(success, "ecMul failed");
+    }
+}
+"#;
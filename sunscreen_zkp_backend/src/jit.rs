@@ -245,6 +245,35 @@ pub fn jit_prover<U>(
     public_inputs: &[U::BackendField],
     constant_inputs: &[U::BackendField],
 ) -> Result<ExecutableZkpProgram>
+where
+    U: FieldSpec,
+{
+    let prog = prepare_prover::<U>(prog, private_inputs, public_inputs, constant_inputs)?;
+
+    let mut node_outputs: HashMap<NodeIndex, U::BackendField> = HashMap::new();
+
+    run_prover::<U>(
+        &prog,
+        private_inputs,
+        public_inputs,
+        constant_inputs,
+        &mut node_outputs,
+    )
+    .map_err(|(_, e)| e)?;
+
+    jit_common::<U>(prog, constant_inputs, public_inputs, Some(node_outputs))
+}
+
+/**
+ * Checks the prover's arguments match the program and constrains the
+ * public inputs to their values.
+ */
+pub(crate) fn prepare_prover<U>(
+    prog: &CompiledZkpProgram,
+    private_inputs: &[U::BackendField],
+    public_inputs: &[U::BackendField],
+    constant_inputs: &[U::BackendField],
+) -> Result<CompiledZkpProgram>
 where
     U: FieldSpec,
 {
@@ -268,163 +297,183 @@ where
 
     validate_zkp_program(&prog)?;
 
-    let mut node_outputs: HashMap<NodeIndex, U::BackendField> = HashMap::new();
-
-    // Run the graph as a computation (not a ZKP) to compute all the
-    // gadget hidden input values.
-    forward_traverse(&prog, |query, id| {
-        let node = query.get_node(id).unwrap();
+    Ok(prog)
+}
 
-        match node.operation {
-            Operation::PublicInput(x) => {
-                if x >= public_inputs.len() {
-                    return Err(Error::malformed_zkp_program(&format!("JIT error: Node {:#?}: load public input {} out of bounds. (There are {} public inputs)", id, x, public_inputs.len())));
-                }
+/**
+ * Runs the graph as a computation (not a ZKP) to compute all the
+ * gadget hidden input values, storing each node's output in
+ * `node_outputs`.
+ *
+ * # Remarks
+ * On failure, returns the node we were evaluating along with the error.
+ * `node_outputs` then holds the outputs computed so far.
+ */
+pub(crate) fn run_prover<U>(
+    prog: &CompiledZkpProgram,
+    private_inputs: &[U::BackendField],
+    public_inputs: &[U::BackendField],
+    constant_inputs: &[U::BackendField],
+    node_outputs: &mut HashMap<NodeIndex, U::BackendField>,
+) -> std::result::Result<(), (NodeIndex, Error)>
+where
+    U: FieldSpec,
+{
+    forward_traverse(prog, |query, id| {
+        // Evaluate the node in a closure so we can tag errors with its id.
+        (|| {
+            let node = query.get_node(id).unwrap();
+
+            match node.operation {
+                Operation::PublicInput(x) => {
+                    if x >= public_inputs.len() {
+                        return Err(Error::malformed_zkp_program(&format!("JIT error: Node {:#?}: load public input {} out of bounds. (There are {} public inputs)", id, x, public_inputs.len())));
+                    }
 
-                let val = &public_inputs[x];
+                    let val = &public_inputs[x];
 
-                node_outputs.insert(id, val.clone());
-            }
-            Operation::PrivateInput(x) => {
-                if x >= private_inputs.len() {
-                    return Err(Error::malformed_zkp_program(&format!("JIT error: Node {:#?}: load private input {} out of bounds. (There are {} public inputs)", id, x, private_inputs.len())));
+                    node_outputs.insert(id, val.clone());
                 }
+                Operation::PrivateInput(x) => {
+                    if x >= private_inputs.len() {
+                        return Err(Error::malformed_zkp_program(&format!("JIT error: Node {:#?}: load private input {} out of bounds. (There are {} public inputs)", id, x, private_inputs.len())));
+                    }
 
-                node_outputs.insert(id, private_inputs[x].clone());
-            }
-            Operation::ConstantInput(x) => {
-                node_outputs.insert(id, constant_inputs[x].clone());
-            }
-            Operation::HiddenInput(_) => {} // Gadgets populate these outputs.
-            Operation::Add => {
-                let (left, right) = query.get_binary_operands(id)?;
+                    node_outputs.insert(id, private_inputs[x].clone());
+                }
+                Operation::ConstantInput(x) => {
+                    node_outputs.insert(id, constant_inputs[x].clone());
+                }
+                Operation::HiddenInput(_) => {} // Gadgets populate these outputs.
+                Operation::Add => {
+                    let (left, right) = query.get_binary_operands(id)?;
 
-                let output = node_outputs[&left].clone() + node_outputs[&right].clone();
+                    let output = node_outputs[&left].clone() + node_outputs[&right].clone();
 
-                node_outputs.insert(id, output);
-            }
-            Operation::Mul => {
-                let (left, right) = query.get_binary_operands(id)?;
+                    node_outputs.insert(id, output);
+                }
+                Operation::Mul => {
+                    let (left, right) = query.get_binary_operands(id)?;
 
-                let output = node_outputs[&left].clone() * node_outputs[&right].clone();
+                    let output = node_outputs[&left].clone() * node_outputs[&right].clone();
 
-                node_outputs.insert(id, output);
-            }
-            Operation::Sub => {
-                let (left, right) = query.get_binary_operands(id)?;
+                    node_outputs.insert(id, output);
+                }
+                Operation::Sub => {
+                    let (left, right) = query.get_binary_operands(id)?;
 
-                let output = node_outputs[&left].clone() - node_outputs[&right].clone();
+                    let output = node_outputs[&left].clone() - node_outputs[&right].clone();
 
-                node_outputs.insert(id, output);
-            }
-            Operation::Neg => {
-                let left = query.get_unary_operand(id)?;
+                    node_outputs.insert(id, output);
+                }
+                Operation::Neg => {
+                    let left = query.get_unary_operand(id)?;
 
-                let output = -node_outputs[&left].clone();
+                    let output = -node_outputs[&left].clone();
 
-                node_outputs.insert(id, output);
-            }
-            Operation::Constraint(x) => {
-                // Constraints produce no outputs, but verify it's met.
-                let parents = query.get_unordered_operands(id)?;
-
-                for parent in parents {
-                    let actual = node_outputs[&parent].clone().zkp_into();
-                    if actual != x {
-                        return Err(Error::UnsatisfiableConstraint(id));
-                    }
+                    node_outputs.insert(id, output);
                 }
-            }
-            Operation::Constant(x) => {
-                node_outputs.insert(id, U::BackendField::try_from(x)?);
-            }
-            Operation::InvokeGadget(ref g) => {
-                // Have the gadget tell us what the values are for the
-                // hidden inputs and assign their value.
-                let arg_indices = query.get_ordered_operands(id)?;
-
-                let args = arg_indices
-                    .iter()
-                    .map(|x| node_outputs[x].clone().zkp_into())
-                    .collect::<Vec<BigInt>>();
-
-                let hidden_inputs = g.compute_hidden_inputs(&args)?;
-
-                let mut next_nodes = query
-                    .edges_directed(id, Direction::Outgoing)
-                    .map(|x| {
-                        if !matches!(x.weight(), EdgeInfo::Unary) {
-                            Err(GraphQueryError::NotUnaryOperation)?;
+                Operation::Constraint(x) => {
+                    // Constraints produce no outputs, but verify it's met.
+                    let parents = query.get_unordered_operands(id)?;
+
+                    for parent in parents {
+                        let actual = node_outputs[&parent].clone().zkp_into();
+                        if actual != x {
+                            return Err(Error::UnsatisfiableConstraint(id));
                         }
+                    }
+                }
+                Operation::Constant(x) => {
+                    node_outputs.insert(id, U::BackendField::try_from(x)?);
+                }
+                Operation::InvokeGadget(ref g) => {
+                    // Have the gadget tell us what the values are for the
+                    // hidden inputs and assign their value.
+                    let arg_indices = query.get_ordered_operands(id)?;
+
+                    let args = arg_indices
+                        .iter()
+                        .map(|x| node_outputs[x].clone().zkp_into())
+                        .collect::<Vec<BigInt>>();
+
+                    let hidden_inputs = g.compute_hidden_inputs(&args)?;
+
+                    let mut next_nodes = query
+                        .edges_directed(id, Direction::Outgoing)
+                        .map(|x| {
+                            if !matches!(x.weight(), EdgeInfo::Unary) {
+                                Err(GraphQueryError::NotUnaryOperation)?;
+                            }
 
-                        match prog[x.target()].operation {
-                            Operation::HiddenInput(arg_idx) => {
-                                Ok(SortableEdge(x.target(), arg_idx))
+                            match prog[x.target()].operation {
+                                Operation::HiddenInput(arg_idx) => {
+                                    Ok(SortableEdge(x.target(), arg_idx))
+                                }
+                                _ => Err(Error::malformed_zkp_program(&format!(
+                                    "Node {:#?} is not a Operation::HiddenInput",
+                                    x.target()
+                                ))),
                             }
-                            _ => Err(Error::malformed_zkp_program(&format!(
-                                "Node {:#?} is not a Operation::HiddenInput",
-                                x.target()
-                            ))),
-                        }
-                    })
-                    .collect::<Result<Vec<SortableEdge>>>()?;
+                        })
+                        .collect::<Result<Vec<SortableEdge>>>()?;
 
-                #[derive(Eq)]
-                struct SortableEdge(NodeIndex, usize);
+                    #[derive(Eq)]
+                    struct SortableEdge(NodeIndex, usize);
 
-                impl PartialEq for SortableEdge {
-                    fn eq(&self, other: &Self) -> bool {
-                        self.1 == other.1
+                    impl PartialEq for SortableEdge {
+                        fn eq(&self, other: &Self) -> bool {
+                            self.1 == other.1
+                        }
                     }
-                }
 
-                impl PartialOrd for SortableEdge {
-                    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-                        Some(self.cmp(other))
+                    impl PartialOrd for SortableEdge {
+                        fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                            Some(self.cmp(other))
+                        }
                     }
-                }
 
-                impl Ord for SortableEdge {
-                    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-                        self.1.cmp(&other.1)
+                    impl Ord for SortableEdge {
+                        fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+                            self.1.cmp(&other.1)
+                        }
                     }
-                }
 
-                next_nodes.sort();
-
-                // Assert the HiddenInputs produce a range
-                // 0..hidden_inputs.len()
-                if hidden_inputs.len() != next_nodes.len() {
-                    return Err(Error::malformed_zkp_program(&format!(
-                        "Gadget {} at node id {:#?} has incorrect number of hidden inputs. Expected {}: actual: {}",
-                        g.debug_name(),
-                        id,
-                        args.len(),
-                        next_nodes.len()
-                    )));
-                }
+                    next_nodes.sort();
 
-                // For each hidden node's index, assign the computed
-                // argument
-                for (i, e) in next_nodes.iter().enumerate() {
-                    // Continuing to assert the argument indices form
-                    // a range...
-                    if i != e.1 {
+                    // Assert the HiddenInputs produce a range
+                    // 0..hidden_inputs.len()
+                    if hidden_inputs.len() != next_nodes.len() {
                         return Err(Error::malformed_zkp_program(&format!(
-                            "Invalid hidden argument index. Expected: {} actual: {}",
-                            i, e.1
+                            "Gadget {} at node id {:#?} has incorrect number of hidden inputs. Expected {}: actual: {}",
+                            g.debug_name(),
+                            id,
+                            args.len(),
+                            next_nodes.len()
                         )));
                     }
 
-                    node_outputs.insert(e.0, hidden_inputs[i].try_into()?);
-                }
-            }
-        };
+                    // For each hidden node's index, assign the computed
+                    // argument
+                    for (i, e) in next_nodes.iter().enumerate() {
+                        // Continuing to assert the argument indices form
+                        // a range...
+                        if i != e.1 {
+                            return Err(Error::malformed_zkp_program(&format!(
+                                "Invalid hidden argument index. Expected: {} actual: {}",
+                                i, e.1
+                            )));
+                        }
 
-        Ok::<_, Error>(())
-    })?;
+                        node_outputs.insert(e.0, hidden_inputs[i].try_into()?);
+                    }
+                }
+            };
 
-    jit_common::<U>(prog, constant_inputs, public_inputs, Some(node_outputs))
+            Ok::<_, Error>(())
+        })()
+        .map_err(|e| (id, e))
+    })
 }
 
 /**
@@ -10,12 +10,20 @@
  */
 pub mod bulletproofs;
 
+#[cfg(feature = "groth16")]
+/**
+ * Types for working with Groth16 over BN254 as the ZKP backend.
+ */
+pub mod groth16;
+
+mod debug;
 mod error;
 mod exec;
 mod jit;
 
 use std::{
     any::Any,
+    collections::HashMap,
     ops::{Add, Deref, Mul, Neg, Shl, Sub},
 };
 
@@ -24,6 +32,7 @@ use crypto_bigint::{
     subtle::{Choice, ConditionallySelectable},
     Limb, NonZero, U512,
 };
+pub use debug::*;
 pub use error::*;
 pub use exec::ExecutableZkpProgram;
 pub use jit::{jit_prover, jit_verifier, CompiledZkpProgram, Operation};
@@ -138,6 +147,12 @@ pub enum Proof {
      */
     Bulletproofs(Box<bulletproofs::BulletproofsR1CSProof>),
 
+    #[cfg(feature = "groth16")]
+    /**
+     * A Groth16 proof.
+     */
+    Groth16(Box<groth16::Groth16Proof>),
+
     /**
      * A custom proof type provided by an external crate.
      */
@@ -350,6 +365,36 @@ impl BigInt {
         BigInt::from(result)
     }
 
+    /**
+     * Compute self + x in F_p.
+     *
+     * # Remarks
+     * `self` and `x` should be less than `p`.
+     */
+    pub fn add_fp(&self, x: &Self, p: &Self) -> Self {
+        BigInt::from(self.0.add_mod(&x.0, &p.0))
+    }
+
+    /**
+     * Compute self * x in F_p.
+     *
+     * # Remarks
+     * `self` and `x` should be less than `p`, which should be less than
+     * 2^256.
+     *
+     * # Panics
+     * * If p is zero.
+     */
+    pub fn mul_fp(&self, x: &Self, p: &Self) -> Self {
+        if *p == BigInt::ZERO {
+            panic!("Cannot have a finite field of zero size.");
+        }
+
+        let p = NonZero::from_uint(p.0);
+
+        BigInt::from(self.0.wrapping_mul(&x.0).rem(&p))
+    }
+
     /**
      * The value 0.
      */
@@ -425,6 +470,26 @@ pub trait ZkpBackend {
         transcript: &mut Transcript,
     ) -> Result<()>;
 
+    /**
+     * Verify many proofs at once. Each proof comes with the executable
+     * Sunscreen program it proves, which the verifier JIT compiled with
+     * the proof's public inputs.
+     *
+     * # Remarks
+     * Fails if any proof doesn't verify, without saying which.
+     *
+     * The default implementation verifies each proof in turn. Backends
+     * override this when they can check many proofs for less than the
+     * cost of checking each.
+     */
+    fn verify_batch(&self, instances: &[(&ExecutableZkpProgram, &Proof)]) -> Result<()> {
+        for (graph, proof) in instances {
+            self.verify(graph, proof)?;
+        }
+
+        Ok(())
+    }
+
     /**
      * JIT the given frontend-compiled ZKP program
      * to an executable Sunscreen program for use by
@@ -458,6 +523,37 @@ pub trait ZkpBackend {
         public_inputs: &[BigInt],
         constant_inputs: &[BigInt],
     ) -> Result<ExecutableZkpProgram>;
+
+    /**
+     * Runs the given frontend-compiled ZKP program natively with the
+     * prover's inputs and reports the first unsatisfied constraint or
+     * failing gadget, along with the witness.
+     *
+     * # Remarks
+     * See [`debug_prover<U>`](debug_prover).
+     */
+    fn debug_prover(
+        &self,
+        prog: &CompiledZkpProgram,
+        private_inputs: &[BigInt],
+        public_inputs: &[BigInt],
+        constant_inputs: &[BigInt],
+        source_locations: &HashMap<NodeIndex, SourceLocation>,
+    ) -> Result<WitnessReport> {
+        let convert = |x: &[BigInt]| {
+            x.iter()
+                .map(|x| <Self::Field as FieldSpec>::BackendField::try_from(*x))
+                .collect::<Result<Vec<_>>>()
+        };
+
+        debug_prover::<Self::Field>(
+            prog,
+            &convert(private_inputs)?,
+            &convert(public_inputs)?,
+            &convert(constant_inputs)?,
+            source_locations,
+        )
+    }
 }
 
 /**